@@ -6,6 +6,7 @@
 // Combine them in blueprints to create unique actions.
 
 use super::Element;
+use crate::weapons::FalloffConfig;
 
 // ============================================================================
 // Targeting - WHERE does the action affect?
@@ -51,6 +52,18 @@ pub enum ActionTarget {
         piercing: bool,
     },
 
+    /// Pillar that rises through a column one row at a time (like FireTowr)
+    Tower {
+        /// Which column relative to user (positive = toward enemy)
+        x_offset: i32,
+    },
+
+    /// Missile that flies forward and can turn once toward an enemy's row (like Ratton)
+    TurningProjectile {
+        /// Starting offset from user
+        x_offset: i32,
+    },
+
     /// Projectile spread (like Shotgun)
     ProjectileSpread {
         x_offset: i32,
@@ -58,6 +71,22 @@ pub enum ActionTarget {
         spread_rows: Vec<i32>,
     },
 
+    /// Projectile that bursts into `pattern` around the first enemy it hits (Spreader, Bubbler)
+    SplashProjectile {
+        x_offset: i32,
+        /// Pattern of tiles relative to the hit tile
+        pattern: Vec<(i32, i32)>,
+    },
+
+    /// Mine left on an enemy panel that bursts into `pattern` once an enemy
+    /// steps on it (Mine)
+    Mine {
+        /// Panel the mine is laid on, relative to the user
+        x_offset: i32,
+        /// Pattern of tiles relative to the mine
+        pattern: Vec<(i32, i32)>,
+    },
+
     /// Area around the user
     AreaAroundSelf {
         /// Radius in tiles
@@ -122,6 +151,17 @@ pub enum ActionEffect {
     /// Makes user invisible/invincible
     Invisibility { duration: f32 },
 
+    /// Guard stance: blocks hits from the enemy straight ahead, not from other rows
+    Guard { duration: f32 },
+
+    /// Temporary extra HP that takes hits before `Health`
+    OverHealth {
+        /// HP granted
+        amount: i32,
+        /// Seconds for the pool to fade away
+        duration: f32,
+    },
+
     /// Steals enemy panel(s)
     StealPanel {
         /// Number of columns to steal
@@ -226,6 +266,15 @@ impl ActionEffect {
             threshold: Some(threshold),
         }
     }
+
+    /// Tiles a hit pushes its target back (0 without a `Knockback` effect)
+    pub fn knockback(&self) -> i32 {
+        match self {
+            ActionEffect::Knockback { distance } => *distance,
+            ActionEffect::Combo { effects } => effects.iter().map(Self::knockback).sum(),
+            _ => 0,
+        }
+    }
 }
 
 // ============================================================================
@@ -261,4 +310,13 @@ pub struct ActionModifiers {
 
     /// Random chance to instant-delete (like MagicMan)
     pub instant_delete_chance: Option<f32>,
+
+    /// Fraction of the cooldown refunded when the action connects with an enemy
+    pub recharge_on_hit: Option<f32>,
+
+    /// Seconds the hit panels keep burning (see `PanelStatus::Burning`)
+    pub burns_panels: Option<f32>,
+
+    /// Damage lost over the tiles a projectile travels (same curve as weapons)
+    pub falloff: Option<FalloffConfig>,
 }
@@ -13,6 +13,7 @@
 use super::{
     ActionEffect, ActionId, ActionModifiers, ActionTarget, ActionVisuals, Element, Rarity, colors,
 };
+use crate::weapons::FalloffConfig;
 use bevy::prelude::*;
 
 /// Complete blueprint for an action/chip
@@ -40,6 +41,8 @@ pub struct ActionBlueprint {
     pub target: ActionTarget,
     /// What the action does
     pub effect: ActionEffect,
+    /// Stronger effect used when the slot is still held as the charge completes
+    pub charged_effect: Option<ActionEffect>,
     /// Optional modifiers
     pub modifiers: ActionModifiers,
 
@@ -48,6 +51,14 @@ pub struct ActionBlueprint {
 }
 
 impl ActionBlueprint {
+    /// Effect to apply: the charged variant when fully charged and one exists
+    pub fn effect_for(&self, charged: bool) -> &ActionEffect {
+        match &self.charged_effect {
+            Some(effect) if charged => effect,
+            _ => &self.effect,
+        }
+    }
+
     /// Get the blueprint for a given action ID
     pub fn get(id: ActionId) -> Self {
         match id {
@@ -69,6 +80,7 @@ impl ActionBlueprint {
             ActionId::Invis2 => invis(2),
             ActionId::Invis3 => invis(3),
             ActionId::LifeAura => life_aura(),
+            ActionId::OverHP => over_hp(),
 
             // Sword chips
             ActionId::Sword => sword(80, Rarity::Common, "Sword", 1),
@@ -124,12 +136,17 @@ impl ActionBlueprint {
             ActionId::Dash => dash(),
             ActionId::GutsPnch => guts_punch(),
             ActionId::IcePunch => ice_punch(),
+            ActionId::Mine => mine(),
 
             // Panel chips
             ActionId::Steal => area_steal(),
             ActionId::Geddon1 => geddon(1),
             ActionId::Geddon2 => geddon(2),
             ActionId::Repair => repair(),
+
+            // Program Advances
+            ActionId::LifeSwrd => life_sword(),
+            ActionId::ZCannon => z_cannon(),
         }
     }
 
@@ -179,6 +196,7 @@ fn recov(amount: i32, tier: i32, rarity: Rarity) -> ActionBlueprint {
         charge_time: 0.0,                  // Instant
         target: ActionTarget::OnSelf,
         effect: ActionEffect::heal(amount),
+        charged_effect: None,
         modifiers: ActionModifiers::default(),
         visuals: ActionVisuals::heal(colors::HEAL_GREEN, colors::HEAL_GREEN),
     }
@@ -202,6 +220,7 @@ fn barrier() -> ActionBlueprint {
             duration: 10.0,     // Lasts until hit
             threshold: Some(0), // Blocks any damage, but breaks after 1 hit
         },
+        charged_effect: None,
         modifiers: ActionModifiers::default(),
         visuals: ActionVisuals::shield(colors::BARRIER_CYAN, colors::BARRIER_CYAN),
     }
@@ -218,6 +237,7 @@ fn shield() -> ActionBlueprint {
         charge_time: 0.0,
         target: ActionTarget::OnSelf,
         effect: ActionEffect::shield(2.0),
+        charged_effect: None,
         modifiers: ActionModifiers::default(),
         visuals: ActionVisuals::shield(colors::SHIELD_BLUE, colors::SHIELD_BLUE),
     }
@@ -227,13 +247,14 @@ fn met_guard() -> ActionBlueprint {
     ActionBlueprint {
         id: ActionId::MetGuard,
         name: "MetGuard",
-        description: "Hold for 3 sec defense",
+        description: "Guard the front for 3 sec",
         element: Element::None,
         rarity: Rarity::Common,
         cooldown: 3.0,
-        charge_time: 0.0, // Defensive stance handled separately
+        charge_time: 0.0,
         target: ActionTarget::OnSelf,
-        effect: ActionEffect::shield(3.0),
+        effect: ActionEffect::Guard { duration: 3.0 },
+        charged_effect: None,
         modifiers: ActionModifiers::default(),
         visuals: ActionVisuals::shield(colors::WAVE_GRAY, colors::WAVE_GRAY),
     }
@@ -263,6 +284,7 @@ fn invis(tier: i32) -> ActionBlueprint {
         charge_time: 0.0,
         target: ActionTarget::OnSelf,
         effect: ActionEffect::Invisibility { duration },
+        charged_effect: None,
         modifiers: ActionModifiers::default(),
         visuals: ActionVisuals {
             icon_color: Color::srgba(0.8, 0.8, 1.0, 0.5),
@@ -283,11 +305,32 @@ fn life_aura() -> ActionBlueprint {
         charge_time: 0.0,
         target: ActionTarget::OnSelf,
         effect: ActionEffect::aura(15.0, 100),
+        charged_effect: None,
         modifiers: ActionModifiers::default(),
         visuals: ActionVisuals::shield(colors::AURA_GOLD, colors::AURA_GOLD),
     }
 }
 
+fn over_hp() -> ActionBlueprint {
+    ActionBlueprint {
+        id: ActionId::OverHP,
+        name: "OverHP",
+        description: "+60 HP barrier that fades in 8 sec",
+        element: Element::None,
+        rarity: Rarity::Rare,
+        cooldown: 12.0,
+        charge_time: 0.0,
+        target: ActionTarget::OnSelf,
+        effect: ActionEffect::OverHealth {
+            amount: 60,
+            duration: 8.0,
+        },
+        charged_effect: None,
+        modifiers: ActionModifiers::default(),
+        visuals: ActionVisuals::shield(colors::SHIELD_BLUE, colors::SHIELD_BLUE),
+    }
+}
+
 // ============================================================================
 // Sword Chips
 // ============================================================================
@@ -303,7 +346,11 @@ fn sword(damage: i32, rarity: Rarity, name: &'static str, range: i32) -> ActionB
         charge_time: 0.2, // Quick melee
         target: ActionTarget::SingleTile { range },
         effect: ActionEffect::damage(damage),
-        modifiers: ActionModifiers::default(),
+        charged_effect: None,
+        modifiers: ActionModifiers {
+            recharge_on_hit: Some(0.5),
+            ..default()
+        },
         visuals: ActionVisuals::sword_slash(colors::SWORD_WHITE, colors::SWORD_WHITE),
     }
 }
@@ -312,14 +359,21 @@ fn wide_sword() -> ActionBlueprint {
     ActionBlueprint {
         id: ActionId::WideSwrd,
         name: "WideSwrd",
-        description: "Cut down column! Range=1",
+        description: "Cut 3 panels ahead! Range=1",
         element: Element::None,
         rarity: Rarity::Common,
         cooldown: 4.0,
         charge_time: 0.3,
-        target: ActionTarget::Column { x_offset: 1 },
+        // The front tile and the ones diagonally above and below it
+        target: ActionTarget::Pattern {
+            tiles: vec![(1, -1), (1, 0), (1, 1)],
+        },
         effect: ActionEffect::damage(80),
-        modifiers: ActionModifiers::default(),
+        charged_effect: None,
+        modifiers: ActionModifiers {
+            recharge_on_hit: Some(0.5),
+            ..default()
+        },
         visuals: ActionVisuals::sword_slash(colors::SWORD_PINK, colors::SWORD_PINK),
     }
 }
@@ -337,7 +391,11 @@ fn long_sword() -> ActionBlueprint {
             tiles: vec![(1, 0), (2, 0)], // Hits 2 tiles forward
         },
         effect: ActionEffect::damage(100),
-        modifiers: ActionModifiers::default(),
+        charged_effect: None,
+        modifiers: ActionModifiers {
+            recharge_on_hit: Some(0.5),
+            ..default()
+        },
         visuals: ActionVisuals::sword_slash(colors::SWORD_WHITE, colors::SWORD_WHITE),
     }
 }
@@ -353,7 +411,11 @@ fn fire_sword() -> ActionBlueprint {
         charge_time: 0.3,
         target: ActionTarget::Column { x_offset: 1 },
         effect: ActionEffect::elemental_damage(120, Element::Fire),
-        modifiers: ActionModifiers::default(),
+        charged_effect: None,
+        modifiers: ActionModifiers {
+            burns_panels: Some(3.0),
+            ..default()
+        },
         visuals: ActionVisuals::sword_slash(colors::FIRE, colors::SWORD_FIRE),
     }
 }
@@ -369,6 +431,7 @@ fn aqua_sword() -> ActionBlueprint {
         charge_time: 0.3,
         target: ActionTarget::Column { x_offset: 1 },
         effect: ActionEffect::elemental_damage(150, Element::Aqua),
+        charged_effect: None,
         modifiers: ActionModifiers::default(),
         visuals: ActionVisuals::sword_slash(colors::AQUA, colors::SWORD_AQUA),
     }
@@ -385,6 +448,7 @@ fn elec_sword() -> ActionBlueprint {
         charge_time: 0.3,
         target: ActionTarget::Column { x_offset: 1 },
         effect: ActionEffect::elemental_damage(130, Element::Elec),
+        charged_effect: None,
         modifiers: ActionModifiers::default(),
         visuals: ActionVisuals::sword_slash(colors::ELEC, colors::SWORD_ELEC),
     }
@@ -403,6 +467,7 @@ fn fighter_sword() -> ActionBlueprint {
             tiles: vec![(1, 0), (2, 0), (3, 0)],
         },
         effect: ActionEffect::damage(100),
+        charged_effect: None,
         modifiers: ActionModifiers::default(),
         visuals: ActionVisuals::sword_slash(colors::SWORD_WHITE, colors::SWORD_WHITE),
     }
@@ -421,6 +486,7 @@ fn knight_sword() -> ActionBlueprint {
             tiles: vec![(1, 0), (2, 0), (3, 0)],
         },
         effect: ActionEffect::damage(150),
+        charged_effect: None,
         modifiers: ActionModifiers::default(),
         visuals: ActionVisuals::sword_slash(colors::SWORD_WHITE, colors::SWORD_WHITE),
     }
@@ -439,6 +505,7 @@ fn hero_sword() -> ActionBlueprint {
             tiles: vec![(1, 0), (2, 0), (3, 0)],
         },
         effect: ActionEffect::damage(200),
+        charged_effect: None,
         modifiers: ActionModifiers {
             guard_break: true,
             ..default()
@@ -469,7 +536,16 @@ fn cannon(damage: i32, rarity: Rarity, name: &'static str) -> ActionBlueprint {
             piercing: false,
         },
         effect: ActionEffect::damage(damage),
-        modifiers: ActionModifiers::default(),
+        charged_effect: Some(ActionEffect::damage(damage * 2)),
+        // Mildly weaker against the back columns
+        modifiers: ActionModifiers {
+            falloff: Some(FalloffConfig {
+                start_range: 3,
+                end_range: 5,
+                min_multiplier: 0.75,
+            }),
+            ..Default::default()
+        },
         visuals: ActionVisuals::projectile(colors::CANNON_YELLOW, colors::CANNON_ORANGE),
     }
 }
@@ -500,6 +576,7 @@ fn bomb(damage: i32, radius: i32, rarity: Rarity, name: &'static str) -> ActionB
             delay: 0.8,
             effect: Box::new(ActionEffect::damage(damage)),
         },
+        charged_effect: None,
         modifiers: ActionModifiers::default(),
         visuals: ActionVisuals::explosion(
             colors::BOMB_RED,
@@ -527,6 +604,7 @@ fn cross_bomb() -> ActionBlueprint {
             delay: 0.8,
             effect: Box::new(ActionEffect::damage(80)),
         },
+        charged_effect: None,
         modifiers: ActionModifiers::default(),
         visuals: ActionVisuals::explosion(
             colors::BOMB_RED,
@@ -563,6 +641,7 @@ fn shockwave(damage: i32, rarity: Rarity, name: &'static str) -> ActionBlueprint
             can_crit: false,
             guard_break: false,
         },
+        charged_effect: None,
         modifiers: ActionModifiers {
             destroys_obstacles: true, // Breaks rocks
             ..default()
@@ -589,6 +668,7 @@ fn shotgun() -> ActionBlueprint {
             spread_rows: vec![0], // Just hits in a line, but continues
         },
         effect: ActionEffect::damage(30),
+        charged_effect: None,
         modifiers: ActionModifiers::default(),
         visuals: ActionVisuals::projectile(colors::CANNON_YELLOW, colors::CANNON_YELLOW),
     }
@@ -603,12 +683,12 @@ fn spreader() -> ActionBlueprint {
         rarity: Rarity::Uncommon,
         cooldown: 3.5,
         charge_time: 0.2,
-        target: ActionTarget::AreaAtPosition {
-            x_offset: 3,
-            y_offset: 0,
-            pattern: vec![(0, 0), (0, 1), (0, -1), (-1, 0)], // Splash pattern
+        target: ActionTarget::SplashProjectile {
+            x_offset: 1,
+            pattern: vec![(0, 0), (0, 1), (0, -1), (1, 0)], // Splash around the hit tile
         },
         effect: ActionEffect::damage(30),
+        charged_effect: None,
         modifiers: ActionModifiers::default(),
         visuals: ActionVisuals::projectile(colors::CANNON_YELLOW, colors::CANNON_YELLOW),
     }
@@ -623,12 +703,12 @@ fn bubbler() -> ActionBlueprint {
         rarity: Rarity::Common,
         cooldown: 3.5,
         charge_time: 0.2,
-        target: ActionTarget::AreaAtPosition {
-            x_offset: 3,
-            y_offset: 0,
-            pattern: vec![(0, 0), (0, 1), (0, -1), (-1, 0)],
+        target: ActionTarget::SplashProjectile {
+            x_offset: 1,
+            pattern: vec![(0, 0), (0, 1), (0, -1), (1, 0)], // Splash around the hit tile
         },
         effect: ActionEffect::elemental_damage(50, Element::Aqua),
+        charged_effect: None,
         modifiers: ActionModifiers::default(),
         visuals: ActionVisuals::projectile(colors::AQUA, colors::AQUA),
     }
@@ -647,9 +727,13 @@ fn fire_tower() -> ActionBlueprint {
         rarity: Rarity::Uncommon,
         cooldown: 5.0,
         charge_time: 0.4,
-        target: ActionTarget::Column { x_offset: 1 },
+        target: ActionTarget::Tower { x_offset: 1 },
         effect: ActionEffect::elemental_damage(100, Element::Fire),
-        modifiers: ActionModifiers::default(),
+        charged_effect: None,
+        modifiers: ActionModifiers {
+            burns_panels: Some(3.0),
+            ..default()
+        },
         visuals: ActionVisuals::sword_slash(colors::FIRE, colors::FIRE),
     }
 }
@@ -663,8 +747,9 @@ fn aqua_tower() -> ActionBlueprint {
         rarity: Rarity::Uncommon,
         cooldown: 5.0,
         charge_time: 0.4,
-        target: ActionTarget::Column { x_offset: 1 },
+        target: ActionTarget::Tower { x_offset: 1 },
         effect: ActionEffect::elemental_damage(120, Element::Aqua),
+        charged_effect: None,
         modifiers: ActionModifiers::default(),
         visuals: ActionVisuals::sword_slash(colors::AQUA, colors::AQUA),
     }
@@ -679,8 +764,9 @@ fn wood_tower() -> ActionBlueprint {
         rarity: Rarity::Uncommon,
         cooldown: 5.0,
         charge_time: 0.4,
-        target: ActionTarget::Column { x_offset: 1 },
+        target: ActionTarget::Tower { x_offset: 1 },
         effect: ActionEffect::elemental_damage(140, Element::Wood),
+        charged_effect: None,
         modifiers: ActionModifiers::default(),
         visuals: ActionVisuals::sword_slash(colors::WOOD, colors::WOOD),
     }
@@ -714,6 +800,7 @@ fn quake(damage: i32, rarity: Rarity, name: &'static str) -> ActionBlueprint {
                 ActionEffect::CrackPanel { crack_only: true },
             ],
         },
+        charged_effect: None,
         modifiers: ActionModifiers::default(),
         visuals: ActionVisuals::explosion(
             colors::WAVE_GRAY,
@@ -745,6 +832,7 @@ fn thunder(damage: i32, rarity: Rarity, name: &'static str) -> ActionBlueprint {
             piercing: true, // Thunder goes through enemies
         },
         effect: ActionEffect::elemental_damage(damage, Element::Elec),
+        charged_effect: None,
         modifiers: ActionModifiers::default(),
         visuals: ActionVisuals::projectile(colors::ELEC, colors::ELEC),
     }
@@ -767,11 +855,9 @@ fn ratton(damage: i32, rarity: Rarity, name: &'static str) -> ActionBlueprint {
         rarity,
         cooldown: 3.5,
         charge_time: 0.2,
-        target: ActionTarget::Projectile {
-            x_offset: 1,
-            piercing: false,
-        },
+        target: ActionTarget::TurningProjectile { x_offset: 1 },
         effect: ActionEffect::damage(damage),
+        charged_effect: None,
         modifiers: ActionModifiers::default(),
         visuals: ActionVisuals::projectile(colors::BOMB_ORANGE, colors::BOMB_ORANGE),
     }
@@ -800,6 +886,7 @@ fn dash() -> ActionBlueprint {
                 ActionEffect::Knockback { distance: 1 },
             ],
         },
+        charged_effect: None,
         modifiers: ActionModifiers::default(),
         visuals: ActionVisuals::projectile(colors::SWORD_WHITE, colors::SWORD_WHITE),
     }
@@ -821,6 +908,7 @@ fn guts_punch() -> ActionBlueprint {
                 ActionEffect::Knockback { distance: 2 },
             ],
         },
+        charged_effect: None,
         modifiers: ActionModifiers {
             guard_break: true,
             ..default()
@@ -845,6 +933,7 @@ fn ice_punch() -> ActionBlueprint {
                 ActionEffect::Knockback { distance: 2 },
             ],
         },
+        charged_effect: None,
         modifiers: ActionModifiers {
             guard_break: true,
             ..default()
@@ -853,6 +942,34 @@ fn ice_punch() -> ActionBlueprint {
     }
 }
 
+fn mine() -> ActionBlueprint {
+    ActionBlueprint {
+        id: ActionId::Mine,
+        name: "Mine",
+        description: "Lays a mine 3 panels ahead",
+        element: Element::None,
+        rarity: Rarity::Uncommon,
+        cooldown: 6.0,
+        charge_time: 0.2,
+        target: ActionTarget::Mine {
+            x_offset: 3,
+            pattern: vec![(0, 0), (0, 1), (0, -1), (1, 0), (-1, 0)], // Cross around the mine
+        },
+        effect: ActionEffect::damage(90),
+        charged_effect: None,
+        modifiers: ActionModifiers::default(),
+        // The blast comes later, when an enemy steps on the mine
+        visuals: ActionVisuals {
+            sound: None,
+            ..ActionVisuals::explosion(
+                colors::CANNON_ORANGE,
+                colors::CANNON_ORANGE,
+                Vec2::new(160.0, 160.0),
+            )
+        },
+    }
+}
+
 // ============================================================================
 // Panel Chips
 // ============================================================================
@@ -868,6 +985,7 @@ fn area_steal() -> ActionBlueprint {
         charge_time: 0.0,
         target: ActionTarget::Column { x_offset: 3 }, // First enemy column
         effect: ActionEffect::StealPanel { columns: 1 },
+        charged_effect: None,
         modifiers: ActionModifiers::default(),
         visuals: ActionVisuals {
             icon_color: Color::srgb(0.8, 0.2, 0.8),
@@ -902,6 +1020,7 @@ fn geddon(tier: i32) -> ActionBlueprint {
         effect: ActionEffect::CrackPanel {
             crack_only: tier == 1,
         },
+        charged_effect: None,
         modifiers: ActionModifiers::default(),
         visuals: ActionVisuals::explosion(
             Color::srgb(0.5, 0.1, 0.5),
@@ -922,11 +1041,55 @@ fn repair() -> ActionBlueprint {
         charge_time: 0.0,
         target: ActionTarget::AreaAroundSelf { radius: 3 },
         effect: ActionEffect::RepairPanel,
+        charged_effect: None,
         modifiers: ActionModifiers::default(),
         visuals: ActionVisuals::heal(colors::HEAL_GREEN, colors::HEAL_GREEN),
     }
 }
 
+// ============================================================================
+// Program Advances
+// ============================================================================
+
+fn life_sword() -> ActionBlueprint {
+    ActionBlueprint {
+        id: ActionId::LifeSwrd,
+        name: "LifeSwrd",
+        description: "Program Advance! Cuts a 2x3 area",
+        element: Element::None,
+        rarity: Rarity::UltraRare,
+        cooldown: 0.0,
+        charge_time: 0.0,
+        target: ActionTarget::Pattern {
+            tiles: vec![(1, -1), (1, 0), (1, 1), (2, -1), (2, 0), (2, 1)],
+        },
+        effect: ActionEffect::damage(400),
+        charged_effect: None,
+        modifiers: ActionModifiers::default(),
+        visuals: ActionVisuals::sword_slash(colors::SWORD_PINK, colors::SWORD_WHITE),
+    }
+}
+
+fn z_cannon() -> ActionBlueprint {
+    ActionBlueprint {
+        id: ActionId::ZCannon,
+        name: "Z-Cannon",
+        description: "Program Advance! Piercing mega shot",
+        element: Element::None,
+        rarity: Rarity::UltraRare,
+        cooldown: 0.0,
+        charge_time: 0.0,
+        target: ActionTarget::Projectile {
+            x_offset: 1,
+            piercing: true,
+        },
+        effect: ActionEffect::damage(300),
+        charged_effect: None,
+        modifiers: ActionModifiers::default(),
+        visuals: ActionVisuals::projectile(colors::CANNON_YELLOW, colors::CANNON_ORANGE),
+    }
+}
+
 // ============================================================================
 // Helper Functions
 // ============================================================================
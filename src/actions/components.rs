@@ -3,10 +3,14 @@
 // ============================================================================
 
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::constants::{INPUT_BUFFER_TIME, SWORD_SWEEP_TIME};
+use crate::weapons::FalloffConfig;
 
 /// Unique identifier for action types (like Battle Chip IDs)
 /// Add new actions here!
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
 pub enum ActionId {
     // Recovery chips
     #[default]
@@ -27,6 +31,7 @@ pub enum ActionId {
     Invis2,
     Invis3,
     LifeAura,
+    OverHP,
 
     // Sword chips
     Sword,
@@ -82,12 +87,17 @@ pub enum ActionId {
     Dash,
     GutsPnch,
     IcePunch,
+    Mine,
 
     // Panel manipulation
     Steal,
     Geddon1,
     Geddon2,
     Repair,
+
+    // Program Advances (never in the inventory; see program_advance.rs)
+    LifeSwrd,
+    ZCannon,
 }
 
 /// Element type for actions (affects damage and weaknesses)
@@ -162,6 +172,9 @@ pub struct ActionSlot {
     pub cooldown_duration: f32,
     /// Cached charge duration
     pub charge_duration: f32,
+    /// A press made during cooldown fires once the cooldown ends, if it ends
+    /// before this time (seconds since startup)
+    pub buffered_until: Option<f32>,
 }
 
 impl ActionSlot {
@@ -174,6 +187,7 @@ impl ActionSlot {
             charge_timer: None,
             cooldown_duration: cooldown,
             charge_duration: charge,
+            buffered_until: None,
         }
     }
 
@@ -188,12 +202,45 @@ impl ActionSlot {
         }
     }
 
+    /// Remember a press made at `now` during cooldown for `INPUT_BUFFER_TIME`
+    pub fn buffer_press(&mut self, now: f32) {
+        if self.state == ActionState::OnCooldown {
+            self.buffered_until = Some(now + INPUT_BUFFER_TIME);
+        }
+    }
+
+    /// Consume a buffered press that is still fresh at `now`
+    pub fn take_buffered(&mut self, now: f32) -> bool {
+        self.buffered_until.take().is_some_and(|until| now <= until)
+    }
+
     pub fn start_cooldown(&mut self) {
         self.state = ActionState::OnCooldown;
         self.cooldown_timer = Timer::from_seconds(self.cooldown_duration, TimerMode::Once);
         self.charge_timer = None;
     }
 
+    /// Drop a charge in progress without firing (the player was hit); the
+    /// chip isn't spent, so the slot is ready again. Returns false if it
+    /// wasn't charging.
+    pub fn interrupt_charge(&mut self) -> bool {
+        if self.state != ActionState::Charging {
+            return false;
+        }
+        self.state = ActionState::Ready;
+        self.charge_timer = None;
+        true
+    }
+
+    /// Skip ahead a fraction of the full cooldown (used by recharge-on-hit)
+    pub fn refund_cooldown(&mut self, fraction: f32) {
+        if self.state == ActionState::OnCooldown {
+            let refund = self.cooldown_duration * fraction.clamp(0.0, 1.0);
+            self.cooldown_timer
+                .tick(std::time::Duration::from_secs_f32(refund));
+        }
+    }
+
     pub fn cooldown_progress(&self) -> f32 {
         if self.state == ActionState::OnCooldown {
             self.cooldown_timer.fraction()
@@ -211,12 +258,22 @@ impl ActionSlot {
     }
 }
 
+/// Highlights the tiles a charging slot's chip will hit (alongside `TargetsTiles`)
+#[derive(Component)]
+pub struct ChipTargetPreview {
+    pub slot_index: usize,
+}
+
 /// Marker for a pending action execution
 #[derive(Component)]
 pub struct PendingAction {
     pub action_id: ActionId,
     pub source_entity: Entity,
     pub source_position: (i32, i32),
+    /// Slot that triggered the action (None for actions not fired from a slot)
+    pub source_slot: Option<usize>,
+    /// Slot was held through the full charge (uses the blueprint's `charged_effect`)
+    pub charged: bool,
 }
 
 /// Active shield effect on an entity
@@ -230,6 +287,38 @@ pub struct ActiveShield {
     pub shield_type: ShieldType,
 }
 
+impl ActiveShield {
+    /// Whether a hit of `damage` is nullified by this shield
+    pub fn blocks(&self, damage: i32) -> bool {
+        match self.shield_type {
+            ShieldType::Aura => damage < self.damage_threshold.unwrap_or(0),
+            ShieldType::Basic | ShieldType::Barrier | ShieldType::Guard | ShieldType::Invis => true,
+        }
+    }
+
+    /// Whether the shield covers a hit arriving from `from` (the attacker's
+    /// tile offset from the shielded entity): a guard stance only faces the
+    /// enemy side straight ahead, everything else covers all around
+    pub fn faces(&self, from: IVec2) -> bool {
+        self.shield_type != ShieldType::Guard || (from.x > 0 && from.y == 0)
+    }
+
+    /// Whether hits pass through the entity instead of striking the shield
+    pub fn dodges(&self) -> bool {
+        self.shield_type == ShieldType::Invis
+    }
+
+    /// Whether taking a hit of `damage` destroys this shield
+    pub fn breaks_on(&self, damage: i32) -> bool {
+        match self.shield_type {
+            // Barrier absorbs one hit, aura shatters on anything it can't negate
+            ShieldType::Barrier => true,
+            ShieldType::Aura => !self.blocks(damage),
+            ShieldType::Basic | ShieldType::Guard | ShieldType::Invis => false,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ShieldType {
     /// Basic shield - blocks all damage
@@ -238,8 +327,10 @@ pub enum ShieldType {
     Barrier,
     /// Aura - blocks damage under threshold
     Aura,
-    /// Invisibility - complete invincibility
+    /// Invisibility - attacks pass through harmlessly
     Invis,
+    /// Guard stance (MetGuard) - blocks frontal hits only
+    Guard,
 }
 
 /// Marker for action visual effects (slashes, projectiles, etc.)
@@ -256,10 +347,26 @@ pub struct ActionVisual {
 pub struct DamageZone {
     pub damage: i32,
     pub element: Element,
+    /// Bypasses enemy armor
+    pub guard_break: bool,
     /// Tiles that will be hit
     pub hit_tiles: Vec<(i32, i32)>,
     /// Whether damage has been applied (prevents double-hit)
     pub applied: bool,
+    /// Slot that fired this zone, refunded on hit
+    pub source_slot: Option<usize>,
+    /// Cooldown fraction refunded to the source slot if anything is hit
+    pub recharge_on_hit: Option<f32>,
+    /// Seconds the hit panels burn for (fire chips)
+    pub burn_time: Option<f32>,
+    /// Breaks any obstacle in `hit_tiles` regardless of its HP
+    pub destroys_obstacles: bool,
+    /// Screen shake added when the zone hits
+    pub trauma: f32,
+    /// Tiles a hit enemy is pushed back (see `resolve_knockback`)
+    pub knockback: i32,
+    /// Projectile falloff and the column it was fired from
+    pub falloff: Option<(FalloffConfig, i32)>,
 }
 
 /// Component for projectiles spawned by actions
@@ -275,6 +382,116 @@ pub struct ActionProjectile {
     pub piercing: bool,
 }
 
+/// Missile that flies forward, then turns once toward an enemy's row (Ratton)
+#[derive(Component)]
+pub struct RattonProjectile {
+    pub damage: i32,
+    pub element: Element,
+    /// Bypasses enemy armor
+    pub guard_break: bool,
+    /// Time per tile moved
+    pub move_timer: Timer,
+    /// Whether the single allowed turn has been used
+    pub has_turned: bool,
+    /// Vertical direction after turning (0 while still flying forward)
+    pub turn_dir: i32,
+}
+
+/// Pending panel steal, resolved by `process_panel_steals`
+#[derive(Component)]
+pub struct PanelSteal {
+    pub columns: i32,
+}
+
+/// Tower pillar climbing its column from the bottom row (FireTowr, AquaTowr, WoodTowr)
+#[derive(Component)]
+pub struct TowerEffect {
+    pub damage: i32,
+    pub element: Element,
+    /// Bypasses enemy armor
+    pub guard_break: bool,
+    /// Time spent on each row before climbing
+    pub step_timer: Timer,
+    /// Last row whose panel and enemies the tower has already hit (each row is
+    /// hit once, on the step the tower reaches it)
+    pub last_row: Option<i32>,
+    /// Seconds each panel the tower passes keeps burning (fire chips)
+    pub burn_time: Option<f32>,
+}
+
+/// Pellet travelling down a row that damages every enemy it passes through (Shotgun)
+#[derive(Component)]
+pub struct PiercingShot {
+    pub damage: i32,
+    pub element: Element,
+    /// Bypasses enemy armor
+    pub guard_break: bool,
+    /// Breaks rocks and keeps going instead of stopping on them
+    pub destroys_obstacles: bool,
+    /// Time per tile moved
+    pub move_timer: Timer,
+    /// Enemies already damaged by this pellet
+    pub hit_enemies: Vec<Entity>,
+}
+
+/// Shot travelling down a row that bursts into a splash pattern on the first thing it hits
+#[derive(Component)]
+pub struct SplashShot {
+    /// Chip that fired the shot (visuals for the burst)
+    pub action_id: ActionId,
+    pub damage: i32,
+    pub element: Element,
+    /// Bypasses enemy armor
+    pub guard_break: bool,
+    /// Tiles hit relative to the impact tile
+    pub pattern: Vec<(i32, i32)>,
+    /// Slot that fired this shot, refunded on hit
+    pub source_slot: Option<usize>,
+    /// Time per tile moved
+    pub move_timer: Timer,
+}
+
+/// Mine sitting on an enemy panel until an enemy steps on it (or it times out)
+#[derive(Component)]
+pub struct Mine {
+    /// Chip that laid the mine (visuals for the burst)
+    pub action_id: ActionId,
+    pub damage: i32,
+    pub element: Element,
+    /// Bypasses enemy armor
+    pub guard_break: bool,
+    /// Tiles hit relative to the mine
+    pub pattern: Vec<(i32, i32)>,
+    /// Slot that laid the mine, refunded on hit
+    pub source_slot: Option<usize>,
+    /// Time left before the mine fizzles out untriggered
+    pub timeout: Timer,
+}
+
+/// Slash sprite sweeping across a pattern sword's tiles (LongSwrd, FtrSwrd);
+/// the hits themselves all land at once through the `DamageZone`
+#[derive(Component)]
+pub struct SwordSweep {
+    /// Tiles crossed, in pattern order
+    pub tiles: Vec<(i32, i32)>,
+    /// Time to cross from the first tile to the last
+    pub timer: Timer,
+}
+
+impl SwordSweep {
+    pub fn new(tiles: Vec<(i32, i32)>) -> Self {
+        Self {
+            tiles,
+            timer: Timer::from_seconds(SWORD_SWEEP_TIME, TimerMode::Once),
+        }
+    }
+
+    /// Position along the pattern in tiles, 0.0 (first tile) to `tiles.len() - 1` (last)
+    pub fn progress(&self) -> f32 {
+        self.timer.fraction() * self.tiles.len().saturating_sub(1) as f32
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ProjectileDirection {
     /// Travels horizontally toward enemy side
@@ -293,3 +510,58 @@ pub struct HealFlash {
     pub timer: Timer,
     pub heal_amount: i32,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recharge_on_hit_shortens_cooldown() {
+        let mut hit = ActionSlot::new(0, ActionId::Sword, 3.0, 0.2);
+        let mut whiff = ActionSlot::new(0, ActionId::Sword, 3.0, 0.2);
+        hit.start_cooldown();
+        whiff.start_cooldown();
+
+        hit.refund_cooldown(0.5);
+
+        assert!(hit.cooldown_timer.remaining_secs() < whiff.cooldown_timer.remaining_secs());
+        assert!((hit.cooldown_timer.remaining_secs() - 1.5).abs() < 1e-4);
+    }
+
+    #[test]
+    fn refund_only_applies_during_cooldown() {
+        let mut slot = ActionSlot::new(0, ActionId::Sword, 3.0, 0.2);
+        slot.refund_cooldown(0.5);
+        assert_eq!(slot.cooldown_timer.elapsed_secs(), 0.0);
+    }
+
+    fn shield(shield_type: ShieldType, damage_threshold: Option<i32>) -> ActiveShield {
+        ActiveShield {
+            duration_timer: Timer::from_seconds(5.0, TimerMode::Once),
+            damage_threshold,
+            shield_type,
+        }
+    }
+
+    #[test]
+    fn barrier_blocks_one_bullet_then_breaks() {
+        let barrier = shield(ShieldType::Barrier, None);
+        assert!(barrier.blocks(200));
+        assert!(barrier.breaks_on(1));
+        assert!(!shield(ShieldType::Basic, None).breaks_on(200));
+    }
+
+    #[test]
+    fn aura_negates_weak_hits_and_shatters_on_strong_ones() {
+        let aura = shield(ShieldType::Aura, Some(80));
+
+        assert!(aura.blocks(50));
+        assert!(!aura.breaks_on(50));
+
+        // A hit exactly at the threshold already goes through
+        assert!(!aura.blocks(80));
+        assert!(aura.breaks_on(80));
+        assert!(!aura.blocks(100));
+        assert!(aura.breaks_on(100));
+    }
+}
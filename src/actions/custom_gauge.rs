@@ -0,0 +1,411 @@
+// ============================================================================
+// Custom Gauge - MMBN-style chip select instead of per-slot cooldowns
+// ============================================================================
+//
+// The chip mode is picked on the settings screen. In `ChipMode::CustomGauge`
+// the slot keys no longer fire chips on their own:
+// - a shared gauge fills over `CUSTOM_GAUGE_TIME`
+// - once it is full, any slot key opens the chip select, freezing the battle
+//   (see `not_paused`)
+// - slot keys add (or take back) that slot's chip, in press order
+// - Confirm (Enter / Select by default) closes it: the picked chips fire one at
+//   a time in pick order, `CUSTOM_CHIP_INTERVAL` apart, each spending its share
+//   of the gauge. It starts filling again once the last one has fired.
+
+use std::collections::VecDeque;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::{ActionBlueprint, ActionSlot, PendingAction};
+use crate::bindings::{InputAction, KeyBindings};
+use crate::components::{CleanupOnStateExit, GameState, GridPosition, Player};
+use crate::constants::*;
+use crate::systems::pause::PauseState;
+
+/// Seconds for the custom gauge to fill
+pub const CUSTOM_GAUGE_TIME: f32 = 8.0;
+/// Seconds between picked chips firing once the chip select closes
+pub const CUSTOM_CHIP_INTERVAL: f32 = 0.4;
+
+/// Size of the gauge bar in pixels
+const CUSTOM_GAUGE_SIZE: Vec2 = Vec2::new(240.0, 10.0);
+/// Center of the gauge bar (just above the action bar)
+const CUSTOM_GAUGE_POS: Vec2 = Vec2::new(0.0, ACTION_BAR_Y + 48.0);
+const COLOR_CUSTOM_GAUGE_FILL: Color = Color::srgb(0.3, 0.7, 1.0);
+const COLOR_CUSTOM_GAUGE_FULL: Color = Color::srgb(1.0, 0.85, 0.3);
+
+/// How battles fire chips (picked on the settings screen)
+#[derive(Resource, Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ChipMode {
+    /// Each slot fires on its key press and recharges on its own cooldown
+    #[default]
+    Cooldown,
+    /// Chips are picked from a chip select whenever the custom gauge fills
+    CustomGauge,
+}
+
+impl ChipMode {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ChipMode::Cooldown => "Cooldowns",
+            ChipMode::CustomGauge => "Custom Gauge",
+        }
+    }
+
+    /// The other mode
+    pub fn next(self) -> Self {
+        match self {
+            ChipMode::Cooldown => ChipMode::CustomGauge,
+            ChipMode::CustomGauge => ChipMode::Cooldown,
+        }
+    }
+}
+
+/// Present during a battle fought in `ChipMode::CustomGauge`
+#[derive(Resource, Debug)]
+pub struct CustomGauge {
+    pub fill: Timer,
+    /// The chip select is open (the battle is frozen)
+    pub selecting: bool,
+    /// Slots picked in the chip select, in pick order
+    pub hand: Vec<usize>,
+    /// Confirmed picks still to fire, next first
+    pub queue: VecDeque<usize>,
+    /// Picks confirmed with the current queue (each spends an equal share of the gauge)
+    confirmed: usize,
+    /// Seconds until the next queued chip fires
+    next_chip_in: f32,
+}
+
+impl Default for CustomGauge {
+    fn default() -> Self {
+        Self {
+            fill: Timer::from_seconds(CUSTOM_GAUGE_TIME, TimerMode::Once),
+            selecting: false,
+            hand: Vec::new(),
+            queue: VecDeque::new(),
+            confirmed: 0,
+            next_chip_in: 0.0,
+        }
+    }
+}
+
+impl CustomGauge {
+    pub fn is_full(&self) -> bool {
+        self.fill.is_finished()
+    }
+
+    /// Pick a slot's chip, or put it back if it is already in the hand
+    pub fn toggle(&mut self, slot_index: usize) {
+        if let Some(index) = self.hand.iter().position(|&slot| slot == slot_index) {
+            self.hand.remove(index);
+        } else {
+            self.hand.push(slot_index);
+        }
+    }
+
+    /// Close the chip select and queue the picks; the first one fires right
+    /// away. An empty hand spends the whole gauge at once.
+    pub fn confirm(&mut self) {
+        self.selecting = false;
+        self.queue.extend(self.hand.drain(..));
+        self.confirmed = self.queue.len();
+        self.next_chip_in = 0.0;
+        if self.queue.is_empty() {
+            self.fill.reset();
+        }
+    }
+
+    /// Count down `delta` seconds and return the queued slot that fires now, if
+    /// any. Each chip drains its share of the gauge, which is empty (and fills
+    /// again) once the queue is.
+    pub fn next_chip(&mut self, delta: f32) -> Option<usize> {
+        if self.queue.is_empty() {
+            return None;
+        }
+        self.next_chip_in -= delta;
+        if self.next_chip_in > 0.0 {
+            return None;
+        }
+        self.next_chip_in = CUSTOM_CHIP_INTERVAL;
+        let slot_index = self.queue.pop_front();
+        let left = self.queue.len() as f32 / self.confirmed as f32;
+        let full = self.fill.duration();
+        self.fill.reset();
+        self.fill.set_elapsed(full.mul_f32(left));
+        slot_index
+    }
+}
+
+/// Gauge bar fill marker
+#[derive(Component)]
+pub struct CustomGaugeFill;
+
+/// Chip select text marker
+#[derive(Component)]
+pub struct ChipSelectText;
+
+/// Run condition: the current battle uses the custom gauge
+pub fn custom_gauge_active(gauge: Option<Res<CustomGauge>>) -> bool {
+    gauge.is_some()
+}
+
+/// Start the gauge (and its HUD) when the battle uses the custom gauge
+pub fn setup_custom_gauge(mut commands: Commands, mode: Res<ChipMode>) {
+    if *mode != ChipMode::CustomGauge {
+        return;
+    }
+    commands.insert_resource(CustomGauge::default());
+    commands
+        .spawn((
+            Sprite::from_color(COLOR_HP_BAR_BG, CUSTOM_GAUGE_SIZE),
+            Transform::from_translation(CUSTOM_GAUGE_POS.extend(Z_UI)),
+            CleanupOnStateExit(GameState::Playing),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Sprite::from_color(COLOR_CUSTOM_GAUGE_FILL, CUSTOM_GAUGE_SIZE),
+                Transform::from_xyz(0.0, 0.0, 0.1),
+                CustomGaugeFill,
+            ));
+        });
+    commands.spawn((
+        Text2d::new(""),
+        TextLayout::new_with_justify(Justify::Center),
+        TextFont::from_font_size(26.0),
+        TextColor(COLOR_TEXT),
+        Transform::from_xyz(0.0, 120.0, Z_UI),
+        ChipSelectText,
+        CleanupOnStateExit(GameState::Playing),
+    ));
+}
+
+/// Fill the gauge, then run the chip select: open it on a slot key once the
+/// gauge is full, pick chips with the slot keys and queue them on confirm.
+/// Queued chips fire one at a time from wherever the player stands then.
+pub fn update_custom_gauge(
+    mut commands: Commands,
+    time: Res<Time>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<&Gamepad>,
+    bindings: Res<KeyBindings>,
+    pause: Option<Res<PauseState>>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+    mut gauge: ResMut<CustomGauge>,
+    player_query: Query<(Entity, &GridPosition), With<Player>>,
+    slot_query: Query<&ActionSlot>,
+) {
+    if pause.is_some_and(|pause| pause.paused) {
+        return;
+    }
+    let slot_pressed: Vec<usize> = InputAction::SLOTS
+        .iter()
+        .enumerate()
+        .filter(|(_, input)| bindings.just_pressed(**input, &keyboard, &gamepads))
+        .map(|(index, _)| index)
+        .collect();
+
+    if gauge.selecting {
+        for slot_index in slot_pressed {
+            if slot_query.iter().any(|slot| slot.slot_index == slot_index) {
+                gauge.toggle(slot_index);
+            }
+        }
+        if !bindings.just_pressed(InputAction::Confirm, &keyboard, &gamepads) {
+            return;
+        }
+        gauge.confirm();
+        virtual_time.unpause();
+    } else if gauge.queue.is_empty() {
+        gauge.fill.tick(time.delta());
+        if gauge.is_full() && !slot_pressed.is_empty() {
+            gauge.selecting = true;
+            virtual_time.pause();
+        }
+        return;
+    }
+
+    let Some(slot_index) = gauge.next_chip(time.delta_secs()) else {
+        return;
+    };
+    let Ok((player_entity, player_pos)) = player_query.single() else {
+        return;
+    };
+    let Some(slot) = slot_query.iter().find(|slot| slot.slot_index == slot_index) else {
+        return;
+    };
+    commands.spawn((
+        PendingAction {
+            action_id: slot.action_id,
+            source_entity: player_entity,
+            source_position: (player_pos.x, player_pos.y),
+            source_slot: Some(slot_index),
+            charged: false,
+        },
+        CleanupOnStateExit(GameState::Playing),
+    ));
+}
+
+/// Size the gauge fill and show the chip select's slots and picks
+pub fn update_custom_gauge_hud(
+    gauge: Res<CustomGauge>,
+    bindings: Res<KeyBindings>,
+    slot_query: Query<&ActionSlot>,
+    mut fill_query: Query<(&mut Sprite, &mut Transform), With<CustomGaugeFill>>,
+    mut text_query: Query<&mut Text2d, With<ChipSelectText>>,
+) {
+    let fraction = gauge.fill.fraction();
+    for (mut sprite, mut transform) in &mut fill_query {
+        let width = CUSTOM_GAUGE_SIZE.x * fraction;
+        sprite.custom_size = Some(Vec2::new(width, CUSTOM_GAUGE_SIZE.y));
+        sprite.color = if gauge.is_full() {
+            COLOR_CUSTOM_GAUGE_FULL
+        } else {
+            COLOR_CUSTOM_GAUGE_FILL
+        };
+        transform.translation.x = (width - CUSTOM_GAUGE_SIZE.x) / 2.0;
+    }
+
+    let text = if gauge.selecting {
+        let mut slots: Vec<&ActionSlot> = slot_query.iter().collect();
+        slots.sort_by_key(|slot| slot.slot_index);
+        let options: Vec<String> = slots
+            .iter()
+            .map(|slot| {
+                let name = ActionBlueprint::get(slot.action_id).name;
+                match gauge
+                    .hand
+                    .iter()
+                    .position(|&picked| picked == slot.slot_index)
+                {
+                    Some(order) => format!("[{}] {name} #{}", slot.slot_index + 1, order + 1),
+                    None => format!("[{}] {name}", slot.slot_index + 1),
+                }
+            })
+            .collect();
+        format!(
+            "CHIP SELECT\n{}\n{}: confirm",
+            options.join("   "),
+            bindings.keys_label(InputAction::Confirm)
+        )
+    } else if gauge.is_full() {
+        "CUSTOM FULL - press a chip key".to_string()
+    } else {
+        String::new()
+    };
+    for mut select_text in &mut text_query {
+        if select_text.0 != text {
+            select_text.0 = text.clone();
+        }
+    }
+}
+
+/// Drop the gauge when the battle ends
+pub fn cleanup_custom_gauge(mut commands: Commands) {
+    commands.remove_resource::<CustomGauge>();
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::actions::ActionId;
+
+    fn advance(world: &mut World, secs: f32) {
+        world
+            .resource_mut::<Time>()
+            .advance_by(Duration::from_secs_f32(secs));
+    }
+
+    /// Run one frame with `key` just pressed
+    fn press(world: &mut World, schedule: &mut Schedule, key: KeyCode) {
+        let mut keyboard = world.resource_mut::<ButtonInput<KeyCode>>();
+        keyboard.reset_all();
+        keyboard.press(key);
+        schedule.run(world);
+    }
+
+    fn queued(world: &mut World) -> Vec<(ActionId, (i32, i32))> {
+        world
+            .query::<&PendingAction>()
+            .iter(world)
+            .map(|pending| (pending.action_id, pending.source_position))
+            .collect()
+    }
+
+    #[test]
+    fn full_gauge_opens_chip_select_and_picks_fire_one_at_a_time() {
+        let mut world = World::new();
+        world.init_resource::<Time>();
+        world.init_resource::<Time<Virtual>>();
+        world.init_resource::<ButtonInput<KeyCode>>();
+        world.init_resource::<KeyBindings>();
+        world.init_resource::<CustomGauge>();
+        let player = world.spawn((Player, GridPosition { x: 1, y: 1 })).id();
+        for (index, action_id) in [ActionId::Cannon, ActionId::Sword, ActionId::Recov10]
+            .into_iter()
+            .enumerate()
+        {
+            world.spawn(ActionSlot::new(index, action_id, 3.0, 0.0));
+        }
+        let mut schedule = Schedule::default();
+        schedule.add_systems(update_custom_gauge);
+
+        // Not full yet: slot keys do nothing
+        advance(&mut world, CUSTOM_GAUGE_TIME - 0.5);
+        press(&mut world, &mut schedule, KeyCode::Digit1);
+        assert!(!world.resource::<CustomGauge>().selecting);
+
+        advance(&mut world, 0.5);
+        press(&mut world, &mut schedule, KeyCode::Digit1);
+        assert!(world.resource::<CustomGauge>().is_full());
+        assert!(world.resource::<CustomGauge>().selecting);
+        assert!(world.resource::<Time<Virtual>>().is_paused());
+
+        // Pick Sword, then Cannon; Recov10 is picked and put back
+        for key in [
+            KeyCode::Digit2,
+            KeyCode::Digit3,
+            KeyCode::Digit1,
+            KeyCode::Digit3,
+        ] {
+            press(&mut world, &mut schedule, key);
+        }
+        assert_eq!(world.resource::<CustomGauge>().hand, vec![1, 0]);
+        assert!(queued(&mut world).is_empty());
+
+        // Confirm fires only the first pick and spends its half of the gauge
+        advance(&mut world, 0.0);
+        press(&mut world, &mut schedule, KeyCode::Enter);
+        assert_eq!(queued(&mut world), vec![(ActionId::Sword, (1, 1))]);
+        let gauge = world.resource::<CustomGauge>();
+        assert!(!gauge.selecting);
+        assert!(gauge.hand.is_empty());
+        assert_eq!(gauge.fill.fraction(), 0.5);
+        assert!(!world.resource::<Time<Virtual>>().is_paused());
+
+        // Cannon waits for the interval and fires from where the player is by then
+        world.get_mut::<GridPosition>(player).unwrap().y = 0;
+        advance(&mut world, CUSTOM_CHIP_INTERVAL / 2.0);
+        press(&mut world, &mut schedule, KeyCode::Digit1);
+        assert_eq!(queued(&mut world).len(), 1);
+        assert!(world.resource::<CustomGauge>().hand.is_empty());
+
+        advance(&mut world, CUSTOM_CHIP_INTERVAL / 2.0);
+        schedule.run(&mut world);
+        assert_eq!(
+            queued(&mut world),
+            vec![(ActionId::Sword, (1, 1)), (ActionId::Cannon, (1, 0))]
+        );
+
+        // Spent: the gauge is empty and fills again
+        let gauge = world.resource::<CustomGauge>();
+        assert!(gauge.queue.is_empty());
+        assert_eq!(gauge.fill.fraction(), 0.0);
+        advance(&mut world, 1.0);
+        schedule.run(&mut world);
+        assert!(world.resource::<CustomGauge>().fill.fraction() > 0.0);
+    }
+}
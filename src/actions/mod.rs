@@ -18,12 +18,16 @@
 mod behaviors;
 mod blueprints;
 mod components;
+mod custom_gauge;
+mod program_advance;
 mod systems;
 mod visuals;
 
 pub use behaviors::*;
 pub use blueprints::*;
 pub use components::*;
+pub use custom_gauge::*;
+pub use program_advance::*;
 pub use systems::*;
 pub use visuals::*;
 
@@ -34,23 +38,54 @@ pub struct ActionsPlugin;
 
 impl Plugin for ActionsPlugin {
     fn build(&self, app: &mut App) {
+        app.init_resource::<ChipInputHistory>();
+        app.init_resource::<ChipMode>();
         app.add_systems(
             Update,
             (
-                action_input_system,
+                // The custom gauge's chip select replaces slot input
+                action_input_system.run_if(not(custom_gauge_active)),
                 execute_pending_actions,
                 update_action_cooldowns,
                 // Effect systems
                 process_damage_effects,
+                process_ratton,
+                process_tower,
+                process_piercing_shots,
+                process_splash_shots,
+                process_mines,
+                process_burning_panels,
+                process_panel_steals,
                 process_heal_effects,
                 process_shield_effects,
                 update_active_shields,
+                ghost_invisible_player,
                 // Visual systems
+                update_sword_sweeps,
                 update_action_visuals,
                 despawn_action_visuals,
             )
                 .chain()
-                .run_if(in_state(crate::components::GameState::Playing)),
+                .run_if(in_state(crate::components::GameState::Playing))
+                .run_if(crate::systems::pause::not_paused),
+        );
+        app.add_systems(
+            OnEnter(crate::components::GameState::Playing),
+            (setup_custom_gauge, reset_chip_input_history),
+        );
+        // Runs through its own freeze; the pause menu is checked inside
+        app.add_systems(
+            Update,
+            (update_custom_gauge, update_custom_gauge_hud)
+                .chain()
+                .run_if(in_state(crate::components::GameState::Playing))
+                .run_if(crate::systems::intro::intro_complete)
+                .run_if(crate::systems::outro::outro_not_active)
+                .run_if(custom_gauge_active),
+        );
+        app.add_systems(
+            OnExit(crate::components::GameState::Playing),
+            cleanup_custom_gauge,
         );
     }
 }
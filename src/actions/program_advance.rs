@@ -0,0 +1,216 @@
+// ============================================================================
+// Program Advances - Chip combos that fuse into a stronger action
+// ============================================================================
+//
+// A Program Advance (PA) is available when every chip in its sequence is
+// equipped. Activating those chips in order, each within `PA_INPUT_WINDOW`
+// seconds of the first, fires the combined action on top of the last chip.
+//
+// A lighter combo: two copies of a chip in adjacent loadout slots each deal
+// `COMBO_CHIP_BONUS` extra damage (see `adjacency_bonus`).
+
+use bevy::prelude::*;
+
+use super::ActionId;
+use crate::resources::PlayerLoadout;
+
+/// Seconds allowed between the first and last chip of a sequence
+pub const PA_INPUT_WINDOW: f32 = 2.0;
+
+/// Extra damage (fraction) for a chip with a copy in an adjacent slot
+pub const COMBO_CHIP_BONUS: f32 = 0.2;
+
+/// Longest sequence in `PROGRAM_ADVANCES`; older inputs are dropped
+const PA_HISTORY_LEN: usize = 3;
+
+/// An ordered chip sequence and the action it unlocks
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProgramAdvance {
+    pub name: &'static str,
+    pub sequence: &'static [ActionId],
+    pub result: ActionId,
+}
+
+/// Every Program Advance in the game
+pub const PROGRAM_ADVANCES: &[ProgramAdvance] = &[
+    ProgramAdvance {
+        name: "LifeSword",
+        sequence: &[ActionId::Sword, ActionId::WideSwrd, ActionId::LongSwrd],
+        result: ActionId::LifeSwrd,
+    },
+    ProgramAdvance {
+        name: "Z-Cannon",
+        sequence: &[ActionId::Cannon, ActionId::HiCannon, ActionId::MCannon],
+        result: ActionId::ZCannon,
+    },
+];
+
+/// Program Advances whose chips are all equipped
+pub fn available_program_advances(loadout: &PlayerLoadout) -> Vec<&'static ProgramAdvance> {
+    PROGRAM_ADVANCES
+        .iter()
+        .filter(|pa| pa.sequence.iter().all(|id| loadout.is_equipped(*id)))
+        .collect()
+}
+
+/// The Program Advance completed by the most recent inputs, if any.
+///
+/// `history` holds `(chip, time)` pairs, oldest first. A PA matches when its
+/// sequence is the tail of the history and spans at most `PA_INPUT_WINDOW`.
+pub fn match_program_advance(
+    loadout: &PlayerLoadout,
+    history: &[(ActionId, f32)],
+) -> Option<&'static ProgramAdvance> {
+    available_program_advances(loadout).into_iter().find(|pa| {
+        let len = pa.sequence.len();
+        if history.len() < len {
+            return false;
+        }
+        let tail = &history[history.len() - len..];
+        let in_order = tail.iter().map(|(id, _)| id).eq(pa.sequence.iter());
+        in_order && tail[len - 1].1 - tail[0].1 <= PA_INPUT_WINDOW
+    })
+}
+
+/// Damage bonus (0.2 = +20%) for the chip in battle `slot` (an index into
+/// `FighterConfig::actions`): `COMBO_CHIP_BONUS` when the same chip sits in a
+/// neighboring slot, 0.0 otherwise
+pub fn adjacency_bonus(actions: &[ActionId], slot: usize) -> f32 {
+    let Some(chip) = actions.get(slot) else {
+        return 0.0;
+    };
+    let holds_chip =
+        |index: Option<usize>| index.and_then(|index| actions.get(index)) == Some(chip);
+    if holds_chip(slot.checked_sub(1)) || holds_chip(Some(slot + 1)) {
+        COMBO_CHIP_BONUS
+    } else {
+        0.0
+    }
+}
+
+/// Chips the player activated recently (battle input for `match_program_advance`)
+#[derive(Resource, Debug, Default, Clone)]
+pub struct ChipInputHistory {
+    pub entries: Vec<(ActionId, f32)>,
+}
+
+impl ChipInputHistory {
+    /// Record a chip activation, keeping only the inputs a PA could still use
+    pub fn record(&mut self, action_id: ActionId, time: f32) {
+        self.entries.push((action_id, time));
+        if self.entries.len() > PA_HISTORY_LEN {
+            self.entries.remove(0);
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+/// Start every battle with an empty history, so a PA can't span two battles
+pub fn reset_chip_input_history(mut history: ResMut<ChipInputHistory>) {
+    history.clear();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cannon_loadout() -> PlayerLoadout {
+        PlayerLoadout {
+            slots: [
+                Some(ActionId::Cannon),
+                Some(ActionId::HiCannon),
+                Some(ActionId::MCannon),
+                None,
+            ],
+        }
+    }
+
+    #[test]
+    fn full_sequence_in_time_fires_the_program_advance() {
+        let history = [
+            (ActionId::Cannon, 1.0),
+            (ActionId::HiCannon, 1.5),
+            (ActionId::MCannon, 2.5),
+        ];
+        let pa = match_program_advance(&cannon_loadout(), &history).unwrap();
+        assert_eq!(pa.result, ActionId::ZCannon);
+    }
+
+    #[test]
+    fn partial_or_slow_sequences_do_not_match() {
+        let loadout = cannon_loadout();
+        let partial = [(ActionId::Cannon, 1.0), (ActionId::HiCannon, 1.5)];
+        assert_eq!(match_program_advance(&loadout, &partial), None);
+
+        let out_of_order = [
+            (ActionId::HiCannon, 1.0),
+            (ActionId::Cannon, 1.5),
+            (ActionId::MCannon, 2.0),
+        ];
+        assert_eq!(match_program_advance(&loadout, &out_of_order), None);
+
+        let too_slow = [
+            (ActionId::Cannon, 1.0),
+            (ActionId::HiCannon, 2.0),
+            (ActionId::MCannon, 1.0 + PA_INPUT_WINDOW + 0.1),
+        ];
+        assert_eq!(match_program_advance(&loadout, &too_slow), None);
+    }
+
+    #[test]
+    fn program_advance_needs_every_chip_equipped() {
+        let mut loadout = cannon_loadout();
+        loadout.slots[2] = None;
+        let history = [
+            (ActionId::Cannon, 1.0),
+            (ActionId::HiCannon, 1.5),
+            (ActionId::MCannon, 2.0),
+        ];
+        assert_eq!(match_program_advance(&loadout, &history), None);
+    }
+
+    #[test]
+    fn adjacent_copies_of_a_chip_share_a_damage_bonus() {
+        let adjacent = [ActionId::Sword, ActionId::Cannon, ActionId::Cannon];
+        assert_eq!(adjacency_bonus(&adjacent, 1), COMBO_CHIP_BONUS);
+        assert_eq!(adjacency_bonus(&adjacent, 2), COMBO_CHIP_BONUS);
+        assert_eq!(adjacency_bonus(&adjacent, 0), 0.0);
+        assert_eq!(adjacency_bonus(&adjacent, 3), 0.0);
+
+        let apart = [ActionId::Cannon, ActionId::Sword, ActionId::Cannon];
+        assert_eq!(adjacency_bonus(&apart, 0), 0.0);
+        assert_eq!(adjacency_bonus(&apart, 2), 0.0);
+
+        // Copies may only be equipped side by side
+        let adjacent = PlayerLoadout {
+            slots: [
+                Some(ActionId::Sword),
+                Some(ActionId::Cannon),
+                Some(ActionId::Cannon),
+                None,
+            ],
+        };
+        assert!(adjacent.can_equip(3, ActionId::HiCannon));
+        assert!(!adjacent.can_equip(3, ActionId::Cannon));
+        assert!(adjacent.can_equip(1, ActionId::Sword));
+        assert!(!adjacent.can_equip(2, ActionId::Sword));
+    }
+
+    #[test]
+    fn entering_a_battle_forgets_the_last_battles_chips() {
+        let mut world = World::new();
+        let mut history = ChipInputHistory::default();
+        history.record(ActionId::Cannon, 1.0);
+        history.record(ActionId::HiCannon, 1.5);
+        world.insert_resource(history);
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(reset_chip_input_history);
+        schedule.run(&mut world);
+
+        assert!(world.resource::<ChipInputHistory>().entries.is_empty());
+    }
+}
@@ -2,50 +2,59 @@
 // Action Systems - Execution and effects
 // ============================================================================
 
+use bevy::audio::{AudioPlayer, PlaybackSettings};
 use bevy::prelude::*;
+use rand::Rng;
+use rand::seq::SliceRandom;
 
 use super::{
     ActionBlueprint, ActionEffect, ActionId, ActionSlot, ActionState, ActionTarget, ActionVisual,
-    ActiveShield, DamageZone, Element, HealFlash, ShieldType,
+    ActiveShield, ChipInputHistory, ChipTargetPreview, DamageZone, Element, HealFlash, Mine,
+    PanelSteal, PiercingShot, RattonProjectile, ShieldType, SplashShot, SwordSweep, TowerEffect,
+    adjacency_bonus, match_program_advance,
 };
+use crate::bindings::{InputAction, KeyBindings};
 use crate::components::{
-    BaseColor, CleanupOnStateExit, Enemy, FlashTimer, GameState, GridPosition, Health, HealthText,
-    Player, PlayerHealthText, TargetsTiles,
+    ArenaConfig, BaseColor, CleanupOnStateExit, Enemy, FlashTimer, GameState, GridPosition, Health,
+    HealthText, Obstacle, OverHealth, PanelStatus, Player, PlayerHealthText, ProjectileTrail,
+    RenderConfig, TargetsTiles, TileHighlightState, TilePanel, Untargetable,
 };
 use crate::constants::*;
-use crate::resources::ArenaLayout;
+use crate::enemies::{EnemyAttack, EnemyGuard, EnemyTraitContainer};
+use crate::resources::{
+    ArenaLayout, AssistOptions, AudioSettings, BattleStats, GameRng, GridSize, PlayerLoadout,
+    TileOwner, TileOwnership,
+};
+use crate::systems::combat::{counter_hit, kill_enemy};
+use crate::systems::shake::{ScreenShake, TRAUMA_EXPLOSION};
+use crate::weapons::FalloffConfig;
 
 // ============================================================================
 // Input Handling
 // ============================================================================
 
-/// Process action inputs (keys 1-3)
+/// Process action inputs (Action1-4 bindings, keys 1-4 by default)
 pub fn action_input_system(
     keyboard: Res<ButtonInput<KeyCode>>,
     gamepads: Query<&Gamepad>,
     time: Res<Time>,
-    _layout: Res<ArenaLayout>,
-    player_query: Query<(Entity, &GridPosition), With<Player>>,
+    layout: Res<ArenaLayout>,
+    bindings: Res<KeyBindings>,
+    loadout: Res<PlayerLoadout>,
+    assist: Res<AssistOptions>,
+    ownership: Res<TileOwnership>,
+    mut history: ResMut<ChipInputHistory>,
+    player_query: Query<(Entity, &GridPosition, Option<&Health>), With<Player>>,
     mut action_query: Query<&mut ActionSlot>,
+    mut preview_query: Query<(Entity, &ChipTargetPreview, &mut TargetsTiles)>,
     mut commands: Commands,
 ) {
-    let keys = [
-        (KeyCode::Digit1, 0),
-        (KeyCode::Digit2, 1),
-        (KeyCode::Digit3, 2),
-        (KeyCode::Digit4, 3),
-    ];
-
-    let gamepad_buttons = [
-        (GamepadButton::West, 0),
-        (GamepadButton::North, 1),
-        (GamepadButton::East, 2),
-        (GamepadButton::South, 3),
-    ];
-
-    let Ok((player_entity, player_pos)) = player_query.single() else {
+    let Ok((player_entity, player_pos, health)) = player_query.single() else {
         return;
     };
+    // Panic heal assist: one ready heal chip fires on its own at critical HP
+    let mut panic_heal =
+        assist.auto_heal && health.is_some_and(|health| health.fraction() < PANIC_HEAL_THRESHOLD);
 
     for mut action in &mut action_query {
         // Update cooldown timers
@@ -56,47 +65,120 @@ pub fn action_input_system(
             }
         }
 
+        let slot_input = InputAction::SLOTS.get(action.slot_index).copied();
+
         // Update charge timers - execute when done
         if action.state == ActionState::Charging {
             if let Some(ref mut timer) = action.charge_timer {
                 timer.tick(time.delta());
                 if timer.is_finished() {
-                    // Queue the action for execution
-                    queue_action(&mut commands, action.action_id, player_entity, *player_pos);
+                    // Still holding the slot button: the charged variant fires
+                    let charged = slot_input
+                        .is_some_and(|input| bindings.pressed(input, &keyboard, &gamepads));
+                    queue_action(
+                        &mut commands,
+                        action.action_id,
+                        player_entity,
+                        *player_pos,
+                        action.slot_index,
+                        charged,
+                    );
                     action.start_cooldown();
                 }
             }
         }
 
         // Check for input
-        let mut triggered = false;
-
-        for (key, slot_idx) in &keys {
-            if action.slot_index == *slot_idx && keyboard.just_pressed(*key) {
-                triggered = true;
-            }
+        let pressed =
+            slot_input.is_some_and(|input| bindings.just_pressed(input, &keyboard, &gamepads));
+        if pressed && !action.is_ready() {
+            action.buffer_press(time.elapsed_secs());
         }
-
-        for gamepad in gamepads.iter() {
-            for (button, slot_idx) in &gamepad_buttons {
-                if action.slot_index == *slot_idx && gamepad.just_pressed(*button) {
-                    triggered = true;
-                }
-            }
+        let auto_heal = panic_heal
+            && matches!(
+                ActionBlueprint::get(action.action_id).effect,
+                ActionEffect::Heal { .. }
+            );
+        let triggered = action.is_ready()
+            && (pressed || auto_heal || action.take_buffered(time.elapsed_secs()));
+        if triggered && auto_heal {
+            panic_heal = false;
         }
 
-        if triggered && action.is_ready() {
+        if triggered {
             let blueprint = ActionBlueprint::get(action.action_id);
 
+            // Completing a chip sequence fires its Program Advance as well
+            history.record(action.action_id, time.elapsed_secs());
+            if let Some(pa) = match_program_advance(&loadout, &history.entries) {
+                info!("Program Advance: {}", pa.name);
+                commands.spawn((
+                    super::PendingAction {
+                        action_id: pa.result,
+                        source_entity: player_entity,
+                        source_position: (player_pos.x, player_pos.y),
+                        source_slot: None,
+                        charged: false,
+                    },
+                    CleanupOnStateExit(GameState::Playing),
+                ));
+                history.clear();
+            }
+
             if blueprint.charge_time > 0.0 {
                 action.start_charging();
             } else {
                 // Instant action - queue immediately
-                queue_action(&mut commands, action.action_id, player_entity, *player_pos);
+                queue_action(
+                    &mut commands,
+                    action.action_id,
+                    player_entity,
+                    *player_pos,
+                    action.slot_index,
+                    false,
+                );
                 action.start_cooldown();
             }
         }
     }
+
+    // Preview where charging chips will land; a slot that fired (or stopped
+    // charging) loses its preview
+    let mut charging: Vec<(usize, Vec<(i32, i32)>)> = action_query
+        .iter()
+        .filter(|action| action.state == ActionState::Charging)
+        .map(|action| {
+            let target = &ActionBlueprint::get(action.action_id).target;
+            let tiles = calculate_hit_tiles(
+                target,
+                (player_pos.x, player_pos.y),
+                &ownership,
+                layout.grid,
+            );
+            (action.slot_index, tiles)
+        })
+        .collect();
+    for (entity, preview, mut targets) in &mut preview_query {
+        match charging
+            .iter()
+            .position(|(slot_index, _)| *slot_index == preview.slot_index)
+        {
+            Some(index) => {
+                let (_, tiles) = charging.swap_remove(index);
+                if targets.tiles != tiles {
+                    targets.tiles = tiles;
+                }
+            }
+            None => commands.entity(entity).despawn(),
+        }
+    }
+    for (slot_index, tiles) in charging {
+        commands.spawn((
+            ChipTargetPreview { slot_index },
+            TargetsTiles::multiple(tiles),
+            CleanupOnStateExit(GameState::Playing),
+        ));
+    }
 }
 
 /// Queue an action for execution
@@ -105,12 +187,16 @@ fn queue_action(
     action_id: ActionId,
     source_entity: Entity,
     source_position: GridPosition,
+    slot_index: usize,
+    charged: bool,
 ) {
     commands.spawn((
         super::PendingAction {
             action_id,
             source_entity,
             source_position: (source_position.x, source_position.y),
+            source_slot: Some(slot_index),
+            charged,
         },
         CleanupOnStateExit(GameState::Playing),
     ));
@@ -120,19 +206,44 @@ fn queue_action(
 // Action Execution
 // ============================================================================
 
+/// Base loudness of chip sounds (scaled by `AudioSettings::sfx_volume`)
+const CHIP_SOUND_VOLUME: f32 = 0.6;
+
 /// Execute pending actions
 pub fn execute_pending_actions(
     mut commands: Commands,
     pending_query: Query<(Entity, &super::PendingAction)>,
     mut player_query: Query<&mut Health, With<Player>>,
     mut hp_text_query: Query<&mut Text2d, With<PlayerHealthText>>,
+    enemy_query: Query<&GridPosition, (With<Enemy>, Without<Untargetable>)>,
+    obstacle_query: Query<&GridPosition, With<Obstacle>>,
     layout: Res<ArenaLayout>,
+    ownership: Res<TileOwnership>,
+    mut rng: ResMut<GameRng>,
+    mut stats: ResMut<BattleStats>,
+    config: Res<ArenaConfig>,
+    asset_server: Res<AssetServer>,
+    audio_settings: Res<AudioSettings>,
 ) {
+    let enemy_tiles: Vec<(i32, i32)> = enemy_query.iter().map(|pos| (pos.x, pos.y)).collect();
+    let obstacle_tiles: Vec<(i32, i32)> = obstacle_query.iter().map(|pos| (pos.x, pos.y)).collect();
+
     for (pending_entity, pending) in &pending_query {
         let blueprint = ActionBlueprint::get(pending.action_id);
+        stats.record_chip(pending.action_id);
+        if let Some(sound) = blueprint.visuals.sound {
+            commands.spawn((
+                AudioPlayer::new(asset_server.load(sound)),
+                PlaybackSettings::DESPAWN.with_volume(audio_settings.sfx_volume(CHIP_SOUND_VOLUME)),
+                CleanupOnStateExit(GameState::Playing),
+            ));
+        }
+        let combo_bonus = pending
+            .source_slot
+            .map_or(0.0, |slot| adjacency_bonus(&config.fighter.actions, slot));
 
         // Execute based on effect type
-        match &blueprint.effect {
+        match blueprint.effect_for(pending.charged) {
             ActionEffect::Heal { amount } => {
                 execute_heal(
                     &mut commands,
@@ -154,19 +265,74 @@ pub fn execute_pending_actions(
                 execute_invis(&mut commands, pending.source_entity, *duration);
             }
 
+            ActionEffect::Guard { duration } => {
+                execute_guard(&mut commands, pending.source_entity, *duration);
+            }
+
+            ActionEffect::OverHealth { amount, duration } => {
+                // A fresh barrier replaces whatever is left of the last one
+                commands
+                    .entity(pending.source_entity)
+                    .insert(OverHealth::new(*amount, *duration));
+            }
+
             ActionEffect::Damage {
-                amount, element, ..
+                amount,
+                element,
+                guard_break,
+                ..
             } => {
                 execute_damage_action(
                     &mut commands,
                     &blueprint,
                     pending.source_position,
+                    pending.source_slot,
                     *amount,
+                    combo_bonus,
                     *element,
+                    *guard_break,
+                    &enemy_tiles,
+                    &obstacle_tiles,
+                    &ownership,
                     &layout,
+                    &mut rng.0,
                 );
             }
 
+            ActionEffect::Delayed { effect, .. } => {
+                // Bombs: the inner damage lands right away and shakes the screen
+                if let ActionEffect::Damage {
+                    amount,
+                    element,
+                    guard_break,
+                    ..
+                } = effect.as_ref()
+                {
+                    execute_damage_action(
+                        &mut commands,
+                        &blueprint,
+                        pending.source_position,
+                        pending.source_slot,
+                        *amount,
+                        combo_bonus,
+                        *element,
+                        *guard_break,
+                        &enemy_tiles,
+                        &obstacle_tiles,
+                        &ownership,
+                        &layout,
+                        &mut rng.0,
+                    );
+                }
+            }
+
+            ActionEffect::StealPanel { columns } => {
+                commands.spawn((
+                    PanelSteal { columns: *columns },
+                    CleanupOnStateExit(GameState::Playing),
+                ));
+            }
+
             ActionEffect::Combo { effects } => {
                 // Execute each sub-effect
                 for effect in effects {
@@ -181,15 +347,25 @@ pub fn execute_pending_actions(
                             );
                         }
                         ActionEffect::Damage {
-                            amount, element, ..
+                            amount,
+                            element,
+                            guard_break,
+                            ..
                         } => {
                             execute_damage_action(
                                 &mut commands,
                                 &blueprint,
                                 pending.source_position,
+                                pending.source_slot,
                                 *amount,
+                                combo_bonus,
                                 *element,
+                                *guard_break,
+                                &enemy_tiles,
+                                &obstacle_tiles,
+                                &ownership,
                                 &layout,
+                                &mut rng.0,
                             );
                         }
                         _ => {
@@ -209,6 +385,54 @@ pub fn execute_pending_actions(
     }
 }
 
+/// Claim the front enemy columns for the player (Steal chip).
+/// Enemies standing on a claimed tile are pushed back along their row; if
+/// there is no free enemy tile behind them, that tile stays theirs.
+pub fn process_panel_steals(
+    mut commands: Commands,
+    steal_query: Query<(Entity, &PanelSteal)>,
+    mut ownership: ResMut<TileOwnership>,
+    mut enemy_query: Query<&mut GridPosition, With<Enemy>>,
+    obstacle_query: Query<&GridPosition, (With<Obstacle>, Without<Enemy>)>,
+    mut panel_query: Query<(&TilePanel, &mut TileHighlightState)>,
+) {
+    for (steal_entity, steal) in &steal_query {
+        commands.entity(steal_entity).despawn();
+
+        let targets = ownership.steal_targets(steal.columns);
+        for &(x, y) in &targets {
+            ownership.set(x, y, TileOwner::Player);
+        }
+
+        let mut occupied: Vec<(i32, i32)> = enemy_query
+            .iter()
+            .map(|pos| (pos.x, pos.y))
+            .chain(obstacle_query.iter().map(|pos| (pos.x, pos.y)))
+            .collect();
+
+        for mut pos in &mut enemy_query {
+            if !targets.contains(&(pos.x, pos.y)) {
+                continue;
+            }
+            let free = (pos.x + 1..ownership.width())
+                .find(|&x| ownership.is_enemy_tile(x, pos.y) && !occupied.contains(&(x, pos.y)));
+            match free {
+                Some(x) => {
+                    occupied.retain(|tile| *tile != (pos.x, pos.y));
+                    occupied.push((x, pos.y));
+                    pos.x = x;
+                }
+                None => ownership.set(pos.x, pos.y, TileOwner::Enemy),
+            }
+        }
+
+        // Stolen panels switch to the player's colour
+        for (panel, mut highlight) in &mut panel_query {
+            highlight.is_player_side = ownership.is_player_tile(panel.x, panel.y);
+        }
+    }
+}
+
 /// Execute a heal effect
 fn execute_heal(
     commands: &mut Commands,
@@ -274,27 +498,255 @@ fn execute_invis(commands: &mut Commands, target: Entity, duration: f32) {
     });
 }
 
+/// Execute a guard stance: a plate held out in front of the target
+fn execute_guard(commands: &mut Commands, target: Entity, duration: f32) {
+    commands.entity(target).insert(ActiveShield {
+        duration_timer: Timer::from_seconds(duration, TimerMode::Once),
+        damage_threshold: None,
+        shield_type: ShieldType::Guard,
+    });
+
+    commands.entity(target).with_children(|parent| {
+        parent.spawn((
+            Sprite {
+                color: Color::srgba(0.75, 0.75, 0.8, 0.7),
+                custom_size: Some(Vec2::new(24.0, 120.0)),
+                ..default()
+            },
+            Transform::from_xyz(60.0, 40.0, 0.5),
+            ShieldVisualMarker,
+        ));
+    });
+}
+
 /// Execute a damage-dealing action
 fn execute_damage_action(
     commands: &mut Commands,
     blueprint: &ActionBlueprint,
     source_pos: (i32, i32),
+    source_slot: Option<usize>,
     damage: i32,
+    combo_bonus: f32,
     element: Element,
+    guard_break: bool,
+    enemy_tiles: &[(i32, i32)],
+    obstacle_tiles: &[(i32, i32)],
+    ownership: &TileOwnership,
     layout: &ArenaLayout,
+    rng: &mut impl Rng,
 ) {
-    let hit_tiles = calculate_hit_tiles(&blueprint.target, source_pos);
+    let damage = (damage as f32 * (1.0 + combo_bonus)).round() as i32;
+    let guard_break =
+        guard_break || blueprint.modifiers.guard_break || blueprint.modifiers.ignore_defense;
+
+    // Turning missiles resolve their hits while travelling (see process_ratton)
+    if let ActionTarget::TurningProjectile { x_offset } = blueprint.target {
+        let start = GridPosition {
+            x: source_pos.0 + x_offset,
+            y: source_pos.1,
+        };
+        if start.x >= layout.grid.width {
+            return;
+        }
+        commands.spawn((
+            Sprite {
+                color: blueprint.visuals.effect_color,
+                custom_size: Some(blueprint.visuals.effect_size * layout.scale),
+                ..default()
+            },
+            Transform::default(),
+            start,
+            RenderConfig {
+                offset: Vec2::new(0.0, 20.0),
+                base_z: Z_BULLET,
+            },
+            RattonProjectile {
+                damage,
+                element,
+                guard_break,
+                move_timer: Timer::from_seconds(BULLET_MOVE_TIMER, TimerMode::Repeating),
+                has_turned: false,
+                turn_dir: 0,
+            },
+            ProjectileTrail::new(blueprint.visuals.effect_color),
+            CleanupOnStateExit(GameState::Playing),
+        ));
+        return;
+    }
+
+    // Towers climb their column row by row (see process_tower)
+    if let ActionTarget::Tower { x_offset } = blueprint.target {
+        let start = GridPosition {
+            x: source_pos.0 + x_offset,
+            y: 0,
+        };
+        if start.x >= layout.grid.width {
+            return;
+        }
+        commands.spawn((
+            Sprite {
+                color: blueprint.visuals.effect_color,
+                custom_size: Some(blueprint.visuals.effect_size * layout.scale),
+                ..default()
+            },
+            Transform::default(),
+            start,
+            RenderConfig {
+                offset: Vec2::new(0.0, 20.0),
+                base_z: Z_BULLET,
+            },
+            TowerEffect {
+                damage,
+                element,
+                guard_break,
+                step_timer: Timer::from_seconds(TOWER_STEP_TIME, TimerMode::Repeating),
+                last_row: None,
+                burn_time: blueprint.modifiers.burns_panels,
+            },
+            TargetsTiles::single(),
+            ProjectileTrail::new(blueprint.visuals.effect_color),
+            CleanupOnStateExit(GameState::Playing),
+        ));
+        return;
+    }
+
+    // Spread pellets travel down their rows and pierce enemies (see process_piercing_shots)
+    if let ActionTarget::ProjectileSpread {
+        x_offset,
+        spread_rows,
+    } = &blueprint.target
+    {
+        let start_x = source_pos.0 + x_offset;
+        if start_x >= layout.grid.width {
+            return;
+        }
+        for row_offset in spread_rows {
+            let row = source_pos.1 + row_offset;
+            if !(0..layout.grid.height).contains(&row) {
+                continue;
+            }
+            commands.spawn((
+                Sprite {
+                    color: blueprint.visuals.effect_color,
+                    custom_size: Some(blueprint.visuals.effect_size * layout.scale),
+                    ..default()
+                },
+                Transform::default(),
+                GridPosition { x: start_x, y: row },
+                RenderConfig {
+                    offset: Vec2::new(0.0, 20.0),
+                    base_z: Z_BULLET,
+                },
+                PiercingShot {
+                    damage,
+                    element,
+                    guard_break,
+                    destroys_obstacles: blueprint.modifiers.destroys_obstacles,
+                    move_timer: Timer::from_seconds(BULLET_MOVE_TIMER, TimerMode::Repeating),
+                    hit_enemies: Vec::new(),
+                },
+                TargetsTiles::single(),
+                ProjectileTrail::new(blueprint.visuals.effect_color),
+                CleanupOnStateExit(GameState::Playing),
+            ));
+        }
+        return;
+    }
+
+    // Splash shots travel down the row and burst where they land (see process_splash_shots)
+    if let ActionTarget::SplashProjectile { x_offset, pattern } = &blueprint.target {
+        let start = GridPosition {
+            x: source_pos.0 + x_offset,
+            y: source_pos.1,
+        };
+        if start.x >= layout.grid.width {
+            return;
+        }
+        commands.spawn((
+            Sprite {
+                color: blueprint.visuals.effect_color,
+                custom_size: Some(Vec2::splat(24.0) * layout.scale),
+                ..default()
+            },
+            Transform::default(),
+            start,
+            RenderConfig {
+                offset: Vec2::new(0.0, 20.0),
+                base_z: Z_BULLET,
+            },
+            SplashShot {
+                action_id: blueprint.id,
+                damage,
+                element,
+                guard_break,
+                pattern: pattern.clone(),
+                source_slot,
+                move_timer: Timer::from_seconds(BULLET_MOVE_TIMER, TimerMode::Repeating),
+            },
+            TargetsTiles::single(),
+            ProjectileTrail::new(blueprint.visuals.effect_color),
+            CleanupOnStateExit(GameState::Playing),
+        ));
+        return;
+    }
+
+    // Mines sit on an enemy panel until something steps on them (see process_mines)
+    if let ActionTarget::Mine { x_offset, pattern } = &blueprint.target {
+        let tile = GridPosition {
+            x: source_pos.0 + x_offset,
+            y: source_pos.1,
+        };
+        if !ownership.is_enemy_tile(tile.x, tile.y) {
+            return;
+        }
+        commands.spawn((
+            Sprite::from_color(blueprint.visuals.effect_color, MINE_SIZE * layout.scale),
+            Transform::default(),
+            tile,
+            RenderConfig {
+                offset: Vec2::ZERO,
+                base_z: Z_BULLET,
+            },
+            Mine {
+                action_id: blueprint.id,
+                damage,
+                element,
+                guard_break,
+                pattern: pattern.clone(),
+                source_slot,
+                timeout: Timer::from_seconds(MINE_LIFETIME, TimerMode::Once),
+            },
+            CleanupOnStateExit(GameState::Playing),
+        ));
+        return;
+    }
+
+    let mut hit_tiles = match &blueprint.target {
+        ActionTarget::RandomEnemy { count } => pick_random_enemy_tiles(enemy_tiles, *count, rng),
+        target => calculate_hit_tiles(target, source_pos, ownership, layout.grid),
+    };
+
+    if stopped_by_obstacles(blueprint) {
+        hit_tiles = stop_at_obstacles(hit_tiles, obstacle_tiles);
+    }
 
     if hit_tiles.is_empty() {
         return;
     }
 
-    // Calculate visual position (center of affected area)
-    let center_tile = hit_tiles[hit_tiles.len() / 2];
-    let floor_pos = layout.tile_floor_world(center_tile.0, center_tile.1);
+    // Pattern swords sweep their slash from the first tile; other zones sit
+    // at the center of the affected area
+    let sweep = matches!(blueprint.target, ActionTarget::Pattern { .. })
+        .then(|| SwordSweep::new(hit_tiles.clone()));
+    let visual_tile = if sweep.is_some() {
+        hit_tiles[0]
+    } else {
+        hit_tiles[hit_tiles.len() / 2]
+    };
+    let floor_pos = layout.tile_floor_world(visual_tile.0, visual_tile.1);
 
     // Spawn damage zone with visual
-    commands.spawn((
+    let mut zone = commands.spawn((
         Sprite {
             color: blueprint.visuals.effect_color,
             custom_size: Some(blueprint.visuals.effect_size * layout.scale),
@@ -308,8 +760,20 @@ fn execute_damage_action(
         DamageZone {
             damage,
             element,
+            guard_break,
             hit_tiles: hit_tiles.clone(),
             applied: false,
+            source_slot,
+            recharge_on_hit: blueprint.modifiers.recharge_on_hit,
+            burn_time: blueprint.modifiers.burns_panels,
+            destroys_obstacles: blueprint.modifiers.destroys_obstacles,
+            trauma: if matches!(blueprint.effect, ActionEffect::Delayed { .. }) {
+                TRAUMA_EXPLOSION
+            } else {
+                0.0
+            },
+            knockback: blueprint.effect.knockback(),
+            falloff: projectile_falloff(blueprint, source_pos),
         },
         TargetsTiles::multiple(hit_tiles),
         ActionVisual {
@@ -318,10 +782,18 @@ fn execute_damage_action(
         },
         CleanupOnStateExit(GameState::Playing),
     ));
+    if let Some(sweep) = sweep {
+        zone.insert(sweep);
+    }
 }
 
 /// Calculate which tiles an action hits based on targeting
-fn calculate_hit_tiles(target: &ActionTarget, source_pos: (i32, i32)) -> Vec<(i32, i32)> {
+fn calculate_hit_tiles(
+    target: &ActionTarget,
+    source_pos: (i32, i32),
+    ownership: &TileOwnership,
+    grid: GridSize,
+) -> Vec<(i32, i32)> {
     match target {
         ActionTarget::OnSelf => vec![source_pos],
 
@@ -331,7 +803,7 @@ fn calculate_hit_tiles(target: &ActionTarget, source_pos: (i32, i32)) -> Vec<(i3
 
         ActionTarget::Column { x_offset } => {
             let target_x = source_pos.0 + x_offset;
-            (0..GRID_HEIGHT).map(|y| (target_x, y)).collect()
+            (0..grid.height).map(|y| (target_x, y)).collect()
         }
 
         ActionTarget::Row {
@@ -341,41 +813,35 @@ fn calculate_hit_tiles(target: &ActionTarget, source_pos: (i32, i32)) -> Vec<(i3
             let start_x = source_pos.0 + x_offset;
             if *traveling {
                 // Hits entire row from start to edge
-                (start_x..GRID_WIDTH).map(|x| (x, source_pos.1)).collect()
+                (start_x..grid.width).map(|x| (x, source_pos.1)).collect()
             } else {
                 // Instant - hits just the row
-                (start_x..GRID_WIDTH).map(|x| (x, source_pos.1)).collect()
+                (start_x..grid.width).map(|x| (x, source_pos.1)).collect()
             }
         }
 
-        ActionTarget::Pattern { tiles } => tiles
-            .iter()
-            .map(|(dx, dy)| (source_pos.0 + dx, source_pos.1 + dy))
-            .filter(|(x, y)| *x >= 0 && *x < GRID_WIDTH && *y >= 0 && *y < GRID_HEIGHT)
-            .collect(),
+        ActionTarget::Pattern { tiles } => splash_tiles(source_pos, tiles, grid),
 
         ActionTarget::Projectile { x_offset, .. } => {
             // For now, projectile just hits the first enemy in row
             // Full projectile system would track movement
             let start_x = source_pos.0 + x_offset;
-            (start_x..GRID_WIDTH).map(|x| (x, source_pos.1)).collect()
+            (start_x..grid.width).map(|x| (x, source_pos.1)).collect()
         }
 
-        ActionTarget::ProjectileSpread {
-            x_offset,
-            spread_rows,
-        } => {
-            let start_x = source_pos.0 + x_offset;
-            let mut tiles = Vec::new();
-            for row_offset in spread_rows {
-                let row = source_pos.1 + row_offset;
-                if row >= 0 && row < GRID_HEIGHT {
-                    for x in start_x..GRID_WIDTH {
-                        tiles.push((x, row));
-                    }
-                }
-            }
-            tiles
+        ActionTarget::ProjectileSpread { .. } => {
+            // Spawned as travelling pellets - hits resolved in process_piercing_shots
+            Vec::new()
+        }
+
+        ActionTarget::SplashProjectile { .. } => {
+            // Spawned as a travelling shot - splash resolved in process_splash_shots
+            Vec::new()
+        }
+
+        ActionTarget::Mine { .. } => {
+            // Spawned as a mine - burst resolved in process_mines
+            Vec::new()
         }
 
         ActionTarget::AreaAroundSelf { radius } => {
@@ -384,7 +850,7 @@ fn calculate_hit_tiles(target: &ActionTarget, source_pos: (i32, i32)) -> Vec<(i3
                 for dy in -radius..=*radius {
                     let x = source_pos.0 + dx;
                     let y = source_pos.1 + dy;
-                    if x >= 0 && x < GRID_WIDTH && y >= 0 && y < GRID_HEIGHT {
+                    if grid.contains(x, y) {
                         tiles.push((x, y));
                     }
                 }
@@ -396,34 +862,93 @@ fn calculate_hit_tiles(target: &ActionTarget, source_pos: (i32, i32)) -> Vec<(i3
             x_offset,
             y_offset,
             pattern,
-        } => {
-            let center_x = source_pos.0 + x_offset;
-            let center_y = source_pos.1 + y_offset;
-            pattern
-                .iter()
-                .map(|(dx, dy)| (center_x + dx, center_y + dy))
-                .filter(|(x, y)| *x >= 0 && *x < GRID_WIDTH && *y >= 0 && *y < GRID_HEIGHT)
-                .collect()
+        } => splash_tiles(
+            (source_pos.0 + x_offset, source_pos.1 + y_offset),
+            pattern,
+            grid,
+        ),
+
+        ActionTarget::EnemyArea => ownership.tiles_owned_by(TileOwner::Enemy),
+
+        ActionTarget::TurningProjectile { .. } => {
+            // Spawned as a moving missile - hits resolved in process_ratton
+            Vec::new()
         }
 
-        ActionTarget::EnemyArea => {
-            let mut tiles = Vec::new();
-            for x in PLAYER_AREA_WIDTH..GRID_WIDTH {
-                for y in 0..GRID_HEIGHT {
-                    tiles.push((x, y));
-                }
-            }
-            tiles
+        ActionTarget::Tower { .. } => {
+            // Spawned as a climbing pillar - hits resolved in process_tower
+            Vec::new()
         }
 
         ActionTarget::RandomEnemy { count: _ } => {
-            // TODO: Pick random tiles with enemies
-            // For now, just return empty
+            // Needs live enemy positions - resolved in execute_damage_action
             Vec::new()
         }
     }
 }
 
+/// Tiles of `pattern` placed around `center`, clipped to the grid
+pub fn splash_tiles(center: (i32, i32), pattern: &[(i32, i32)], grid: GridSize) -> Vec<(i32, i32)> {
+    pattern
+        .iter()
+        .map(|(dx, dy)| (center.0 + dx, center.1 + dy))
+        .filter(|&(x, y)| grid.contains(x, y))
+        .collect()
+}
+
+/// Falloff for shots travelling down the row, measured from the firing column
+fn projectile_falloff(
+    blueprint: &ActionBlueprint,
+    source_pos: (i32, i32),
+) -> Option<(FalloffConfig, i32)> {
+    let is_projectile = matches!(blueprint.target, ActionTarget::Projectile { .. });
+    blueprint
+        .modifiers
+        .falloff
+        .filter(|_| is_projectile)
+        .map(|falloff| (falloff, source_pos.0))
+}
+
+/// Shots travelling down a row stop at the first rock, unless they pierce or break it
+fn stopped_by_obstacles(blueprint: &ActionBlueprint) -> bool {
+    let travels_row = match &blueprint.target {
+        ActionTarget::Projectile { piercing, .. } => !piercing,
+        ActionTarget::Row { .. } => true,
+        _ => false,
+    };
+    travels_row && !blueprint.modifiers.destroys_obstacles
+}
+
+/// Drop every tile behind the first obstacle in each row (the obstacle tile itself is kept)
+fn stop_at_obstacles(hit_tiles: Vec<(i32, i32)>, obstacle_tiles: &[(i32, i32)]) -> Vec<(i32, i32)> {
+    let first_obstacle_x = |row: i32| {
+        hit_tiles
+            .iter()
+            .filter(|(x, y)| *y == row && obstacle_tiles.contains(&(*x, *y)))
+            .map(|(x, _)| *x)
+            .min()
+    };
+    hit_tiles
+        .iter()
+        .copied()
+        .filter(|(x, y)| first_obstacle_x(*y).is_none_or(|stop_x| *x <= stop_x))
+        .collect()
+}
+
+/// Pick up to `count` distinct enemy-occupied tiles at random
+fn pick_random_enemy_tiles(
+    enemy_tiles: &[(i32, i32)],
+    count: i32,
+    rng: &mut impl Rng,
+) -> Vec<(i32, i32)> {
+    let mut tiles = enemy_tiles.to_vec();
+    tiles.sort_unstable();
+    tiles.dedup();
+    tiles.shuffle(rng);
+    tiles.truncate(count.max(0) as usize);
+    tiles
+}
+
 // ============================================================================
 // Cooldown Updates
 // ============================================================================
@@ -442,40 +967,122 @@ pub fn update_action_cooldowns(_time: Res<Time>, _action_query: Query<&mut Actio
 pub fn process_damage_effects(
     mut commands: Commands,
     mut damage_query: Query<(Entity, &mut DamageZone)>,
-    mut enemy_query: Query<(Entity, &GridPosition, &mut Health, &Children), With<Enemy>>,
+    mut enemy_query: Query<
+        (
+            Entity,
+            &mut GridPosition,
+            &mut Health,
+            &Children,
+            Option<&EnemyTraitContainer>,
+            Option<&mut EnemyGuard>,
+            Option<&mut EnemyAttack>,
+        ),
+        (With<Enemy>, Without<Untargetable>),
+    >,
     mut text_query: Query<&mut Text2d, With<HealthText>>,
+    mut slot_query: Query<&mut ActionSlot>,
+    mut obstacle_query: Query<(Entity, &GridPosition, &mut Obstacle), Without<Enemy>>,
+    mut panel_query: Query<(&TilePanel, &mut PanelStatus)>,
+    ownership: Res<TileOwnership>,
+    mut shake: ResMut<ScreenShake>,
 ) {
     for (_zone_entity, mut zone) in &mut damage_query {
         if zone.applied {
             continue;
         }
 
-        for (enemy_entity, enemy_pos, mut health, children) in &mut enemy_query {
+        shake.add_trauma(zone.trauma);
+
+        let mut hit_any = false;
+
+        apply_panel_element(
+            &mut panel_query,
+            &zone.hit_tiles,
+            zone.element,
+            zone.burn_time,
+        );
+
+        for (obstacle_entity, obstacle_pos, mut obstacle) in &mut obstacle_query {
+            if zone.hit_tiles.contains(&(obstacle_pos.x, obstacle_pos.y)) {
+                hit_obstacle(
+                    &mut commands,
+                    obstacle_entity,
+                    &mut obstacle,
+                    zone.damage,
+                    zone.destroys_obstacles,
+                );
+            }
+        }
+
+        // Tiles a knocked-back enemy can't be pushed onto
+        let mut occupied: Vec<(i32, i32)> = if zone.knockback > 0 {
+            let enemies = enemy_query.iter().map(|(_, pos, ..)| (pos.x, pos.y));
+            let obstacles = obstacle_query.iter().map(|(_, pos, _)| (pos.x, pos.y));
+            enemies.chain(obstacles).collect()
+        } else {
+            Vec::new()
+        };
+
+        for (enemy_entity, mut enemy_pos, mut health, children, traits, guard, attack) in
+            &mut enemy_query
+        {
             if zone
                 .hit_tiles
                 .iter()
                 .any(|(x, y)| *x == enemy_pos.x && *y == enemy_pos.y)
             {
-                // Apply damage with element bonus
-                let final_damage = zone.damage;
-
-                // TODO: Check enemy element and apply weakness bonus
+                hit_any = true;
 
-                health.current -= final_damage;
+                // Apply damage with element bonus, weakened by the distance travelled
+                let mut final_damage = zone.damage;
+                if let Some((falloff, source_x)) = zone.falloff {
+                    let multiplier = falloff.get_multiplier(enemy_pos.x - source_x);
+                    final_damage = (final_damage as f32 * multiplier).round() as i32;
+                }
+                if let Some(traits) = traits {
+                    final_damage = traits.traits.reduce_damage(final_damage, zone.guard_break);
+                }
 
-                // Update HP text
-                for child in children.iter() {
-                    if let Ok(mut text) = text_query.get_mut(child) {
-                        text.0 = health.current.max(0).to_string();
+                // Knockback pushes toward the back wall; every tile it can't
+                // push through slams the enemy for extra damage
+                let super_armor = traits.is_some_and(|traits| traits.traits.super_armor);
+                if zone.knockback > 0 && !super_armor {
+                    let from = (enemy_pos.x, enemy_pos.y);
+                    let (x, blocked) = resolve_knockback(from, zone.knockback, |x, y| {
+                        ownership.is_enemy_tile(x, y) && !occupied.contains(&(x, y))
+                    });
+                    if x != from.0 {
+                        occupied.retain(|tile| *tile != from);
+                        occupied.push((x, from.1));
+                        enemy_pos.x = x;
                     }
+                    final_damage += blocked * WALL_SLAM_DAMAGE;
                 }
 
-                if health.current <= 0 {
-                    commands.entity(enemy_entity).despawn();
-                } else {
-                    commands
-                        .entity(enemy_entity)
-                        .insert(FlashTimer(Timer::from_seconds(FLASH_TIME, TimerMode::Once)));
+                // TODO: Check enemy element and apply weakness bonus
+
+                apply_enemy_damage(
+                    &mut commands,
+                    enemy_entity,
+                    *enemy_pos,
+                    &mut health,
+                    children,
+                    &mut text_query,
+                    guard,
+                    attack,
+                    final_damage,
+                    zone.guard_break,
+                );
+            }
+        }
+
+        // Reward accuracy: refund part of the originating slot's cooldown
+        if hit_any {
+            if let (Some(slot_index), Some(fraction)) = (zone.source_slot, zone.recharge_on_hit) {
+                for mut slot in &mut slot_query {
+                    if slot.slot_index == slot_index {
+                        slot.refund_cooldown(fraction);
+                    }
                 }
             }
         }
@@ -484,85 +1091,721 @@ pub fn process_damage_effects(
     }
 }
 
-// ============================================================================
-// Heal Processing
-// ============================================================================
-
-/// Process heal flash effects
-pub fn process_heal_effects(
+/// Move Ratton missiles and apply damage when they reach an enemy.
+///
+/// The missile flies forward until it is in the column of the nearest enemy
+/// ahead of it, then turns (once) toward that enemy's row and keeps going.
+pub fn process_ratton(
     mut commands: Commands,
     time: Res<Time>,
-    mut query: Query<(Entity, &mut Sprite, &BaseColor, &mut HealFlash)>,
+    layout: Res<ArenaLayout>,
+    mut ratton_query: Query<(Entity, &mut GridPosition, &mut RattonProjectile), Without<Enemy>>,
+    mut enemy_query: Query<
+        (
+            Entity,
+            &GridPosition,
+            &mut Health,
+            &Children,
+            Option<&EnemyTraitContainer>,
+            Option<&mut EnemyGuard>,
+            Option<&mut EnemyAttack>,
+        ),
+        (With<Enemy>, Without<Untargetable>),
+    >,
+    mut text_query: Query<&mut Text2d, With<HealthText>>,
+    mut obstacle_query: Query<
+        (Entity, &GridPosition, &mut Obstacle),
+        (Without<Enemy>, Without<RattonProjectile>),
+    >,
 ) {
-    for (entity, mut sprite, base, mut flash) in &mut query {
-        flash.timer.tick(time.delta());
-
-        if flash.timer.is_finished() {
-            sprite.color = base.0;
-            commands.entity(entity).remove::<HealFlash>();
-        } else {
-            let t = flash.timer.fraction();
-            let green = Color::srgb(0.3, 1.0, 0.4);
-            sprite.color = base.0.mix(&green, 1.0 - t);
+    for (entity, mut pos, mut ratton) in &mut ratton_query {
+        // Hit check on the current tile
+        let mut hit = false;
+        for (obstacle_entity, obstacle_pos, mut obstacle) in &mut obstacle_query {
+            if *obstacle_pos == *pos {
+                hit_obstacle(
+                    &mut commands,
+                    obstacle_entity,
+                    &mut obstacle,
+                    ratton.damage,
+                    false,
+                );
+                hit = true;
+            }
+        }
+        for (enemy_entity, enemy_pos, mut health, children, traits, guard, attack) in
+            &mut enemy_query
+        {
+            if *enemy_pos == *pos {
+                let mut damage = ratton.damage;
+                if let Some(traits) = traits {
+                    damage = traits.traits.reduce_damage(damage, ratton.guard_break);
+                }
+                apply_enemy_damage(
+                    &mut commands,
+                    enemy_entity,
+                    *enemy_pos,
+                    &mut health,
+                    children,
+                    &mut text_query,
+                    guard,
+                    attack,
+                    damage,
+                    ratton.guard_break,
+                );
+                hit = true;
+                break;
+            }
+        }
+        if hit {
+            commands.entity(entity).despawn();
+            continue;
         }
-    }
-}
 
-// ============================================================================
-// Shield Processing
-// ============================================================================
+        ratton.move_timer.tick(time.delta());
+        if !ratton.move_timer.just_finished() {
+            continue;
+        }
 
-/// Process shield duration and removal
-pub fn process_shield_effects(
-    mut commands: Commands,
-    shield_query: Query<&ActiveShield, With<Player>>,
-    enemy_bullet_query: Query<(Entity, &GridPosition), With<crate::components::EnemyBullet>>,
-    player_query: Query<&GridPosition, With<Player>>,
-) {
-    if shield_query.is_empty() {
-        return;
-    }
+        // One allowed turn: once in the nearest enemy's column, head for its row
+        if !ratton.has_turned {
+            let nearest = enemy_query
+                .iter()
+                .map(|(_, enemy_pos, ..)| *enemy_pos)
+                .filter(|enemy_pos| enemy_pos.x >= pos.x)
+                .min_by_key(|enemy_pos| (enemy_pos.x - pos.x) + (enemy_pos.y - pos.y).abs());
+            if let Some(target) = nearest {
+                if target.x == pos.x && target.y != pos.y {
+                    ratton.has_turned = true;
+                    ratton.turn_dir = (target.y - pos.y).signum();
+                }
+            }
+        }
 
-    let Ok(player_pos) = player_query.single() else {
-        return;
-    };
+        if ratton.turn_dir != 0 {
+            pos.y += ratton.turn_dir;
+        } else {
+            pos.x += 1;
+        }
 
-    // Block enemy bullets
-    for (bullet_entity, bullet_pos) in &enemy_bullet_query {
-        if bullet_pos == player_pos {
-            commands.entity(bullet_entity).despawn();
+        if !layout.grid.contains(pos.x, pos.y) {
+            commands.entity(entity).despawn();
         }
     }
 }
 
-/// Update active shields (duration countdown)
-pub fn update_active_shields(
+/// Climb tower pillars up their column, damaging whatever is on the current row.
+///
+/// Each row is hit at most once; the tower then moves up a row every
+/// `TOWER_STEP_TIME` and disappears past the top row. Rocks stop the tower.
+pub fn process_tower(
     mut commands: Commands,
     time: Res<Time>,
-    mut player_query: Query<(Entity, &mut ActiveShield, Option<&Children>), With<Player>>,
-    shield_visual_query: Query<Entity, With<ShieldVisualMarker>>,
+    layout: Res<ArenaLayout>,
+    mut tower_query: Query<(Entity, &mut GridPosition, &mut TowerEffect), Without<Enemy>>,
+    mut enemy_query: Query<
+        (
+            Entity,
+            &GridPosition,
+            &mut Health,
+            &Children,
+            Option<&EnemyTraitContainer>,
+            Option<&mut EnemyGuard>,
+            Option<&mut EnemyAttack>,
+        ),
+        (With<Enemy>, Without<Untargetable>),
+    >,
+    mut text_query: Query<&mut Text2d, With<HealthText>>,
+    mut obstacle_query: Query<
+        (Entity, &GridPosition, &mut Obstacle),
+        (Without<Enemy>, Without<TowerEffect>),
+    >,
+    mut panel_query: Query<(&TilePanel, &mut PanelStatus)>,
 ) {
-    for (player_entity, mut shield, children) in &mut player_query {
-        shield.duration_timer.tick(time.delta());
-
-        if shield.duration_timer.is_finished() {
-            commands.entity(player_entity).remove::<ActiveShield>();
+    for (entity, mut pos, mut tower) in &mut tower_query {
+        if tower.last_row != Some(pos.y) {
+            tower.last_row = Some(pos.y);
+            apply_panel_element(
+                &mut panel_query,
+                &[(pos.x, pos.y)],
+                tower.element,
+                tower.burn_time,
+            );
+
+            let mut blocked = false;
+            for (obstacle_entity, obstacle_pos, mut obstacle) in &mut obstacle_query {
+                if *obstacle_pos == *pos {
+                    hit_obstacle(
+                        &mut commands,
+                        obstacle_entity,
+                        &mut obstacle,
+                        tower.damage,
+                        false,
+                    );
+                    blocked = true;
+                }
+            }
+            if blocked {
+                commands.entity(entity).despawn();
+                continue;
+            }
 
-            // Remove shield visuals
-            if let Some(children) = children {
-                for child in children.iter() {
-                    if shield_visual_query.get(child).is_ok() {
-                        commands.entity(child).despawn();
+            for (enemy_entity, enemy_pos, mut health, children, traits, guard, attack) in
+                &mut enemy_query
+            {
+                if *enemy_pos == *pos {
+                    let mut damage = tower.damage;
+                    if let Some(traits) = traits {
+                        damage = traits.traits.reduce_damage(damage, tower.guard_break);
                     }
+                    apply_enemy_damage(
+                        &mut commands,
+                        enemy_entity,
+                        *enemy_pos,
+                        &mut health,
+                        children,
+                        &mut text_query,
+                        guard,
+                        attack,
+                        damage,
+                        tower.guard_break,
+                    );
                 }
             }
         }
-    }
-}
 
-// ============================================================================
-// Visual Updates
-// ============================================================================
+        tower.step_timer.tick(time.delta());
+        if !tower.step_timer.just_finished() {
+            continue;
+        }
+
+        pos.y += 1;
+        if pos.y >= layout.grid.height {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+/// Move spread pellets down their rows, damaging each enemy they pass once.
+///
+/// Pellets stop on a rock (damaging it) unless the chip destroys obstacles,
+/// and disappear past the last column.
+pub fn process_piercing_shots(
+    mut commands: Commands,
+    time: Res<Time>,
+    layout: Res<ArenaLayout>,
+    mut shot_query: Query<(Entity, &mut GridPosition, &mut PiercingShot), Without<Enemy>>,
+    mut enemy_query: Query<
+        (
+            Entity,
+            &GridPosition,
+            &mut Health,
+            &Children,
+            Option<&EnemyTraitContainer>,
+            Option<&mut EnemyGuard>,
+            Option<&mut EnemyAttack>,
+        ),
+        (With<Enemy>, Without<Untargetable>),
+    >,
+    mut text_query: Query<&mut Text2d, With<HealthText>>,
+    mut obstacle_query: Query<
+        (Entity, &GridPosition, &mut Obstacle),
+        (Without<Enemy>, Without<PiercingShot>),
+    >,
+) {
+    for (entity, mut pos, mut shot) in &mut shot_query {
+        let mut blocked = false;
+        for (obstacle_entity, obstacle_pos, mut obstacle) in &mut obstacle_query {
+            if *obstacle_pos == *pos && obstacle.hp > 0 {
+                hit_obstacle(
+                    &mut commands,
+                    obstacle_entity,
+                    &mut obstacle,
+                    shot.damage,
+                    shot.destroys_obstacles,
+                );
+                blocked = !shot.destroys_obstacles;
+            }
+        }
+        if blocked {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        for (enemy_entity, enemy_pos, mut health, children, traits, guard, attack) in
+            &mut enemy_query
+        {
+            if *enemy_pos == *pos && !shot.hit_enemies.contains(&enemy_entity) {
+                let mut damage = shot.damage;
+                if let Some(traits) = traits {
+                    damage = traits.traits.reduce_damage(damage, shot.guard_break);
+                }
+                apply_enemy_damage(
+                    &mut commands,
+                    enemy_entity,
+                    *enemy_pos,
+                    &mut health,
+                    children,
+                    &mut text_query,
+                    guard,
+                    attack,
+                    damage,
+                    shot.guard_break,
+                );
+                shot.hit_enemies.push(enemy_entity);
+            }
+        }
+
+        shot.move_timer.tick(time.delta());
+        if !shot.move_timer.just_finished() {
+            continue;
+        }
+
+        pos.x += 1;
+        if pos.x >= layout.grid.width {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+/// Move splash shots down their row. On the first enemy or rock they burst into a
+/// `DamageZone` covering their pattern around the impact tile.
+pub fn process_splash_shots(
+    mut commands: Commands,
+    time: Res<Time>,
+    layout: Res<ArenaLayout>,
+    mut shot_query: Query<
+        (Entity, &mut GridPosition, &mut SplashShot),
+        (Without<Enemy>, Without<Obstacle>),
+    >,
+    enemy_query: Query<&GridPosition, (With<Enemy>, Without<Untargetable>)>,
+    obstacle_query: Query<(&GridPosition, &Obstacle)>,
+) {
+    for (entity, mut pos, mut shot) in &mut shot_query {
+        let hit_enemy = enemy_query.iter().any(|enemy_pos| *enemy_pos == *pos);
+        let hit_obstacle = obstacle_query
+            .iter()
+            .any(|(obstacle_pos, obstacle)| *obstacle_pos == *pos && obstacle.hp > 0);
+
+        if hit_enemy || hit_obstacle {
+            spawn_burst(
+                &mut commands,
+                &layout,
+                &ActionBlueprint::get(shot.action_id),
+                (pos.x, pos.y),
+                &shot.pattern,
+                shot.damage,
+                shot.element,
+                shot.guard_break,
+                shot.source_slot,
+            );
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        shot.move_timer.tick(time.delta());
+        if !shot.move_timer.just_finished() {
+            continue;
+        }
+
+        pos.x += 1;
+        if pos.x >= layout.grid.width {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+/// Mines burst once an enemy stands on their panel, or fizzle out after `MINE_LIFETIME`
+pub fn process_mines(
+    mut commands: Commands,
+    time: Res<Time>,
+    layout: Res<ArenaLayout>,
+    mut mine_query: Query<(Entity, &GridPosition, &mut Mine), Without<Enemy>>,
+    enemy_query: Query<&GridPosition, (With<Enemy>, Without<Untargetable>)>,
+) {
+    for (entity, pos, mut mine) in &mut mine_query {
+        if enemy_query.iter().any(|enemy_pos| enemy_pos == pos) {
+            spawn_burst(
+                &mut commands,
+                &layout,
+                &ActionBlueprint::get(mine.action_id),
+                (pos.x, pos.y),
+                &mine.pattern,
+                mine.damage,
+                mine.element,
+                mine.guard_break,
+                mine.source_slot,
+            );
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        mine.timeout.tick(time.delta());
+        if mine.timeout.is_finished() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+/// Damage zone over `pattern` around `center`, with the chip's burst visual
+/// (splash shots and mines)
+fn spawn_burst(
+    commands: &mut Commands,
+    layout: &ArenaLayout,
+    blueprint: &ActionBlueprint,
+    center: (i32, i32),
+    pattern: &[(i32, i32)],
+    damage: i32,
+    element: Element,
+    guard_break: bool,
+    source_slot: Option<usize>,
+) {
+    let hit_tiles = splash_tiles(center, pattern, layout.grid);
+    let floor_pos = layout.tile_floor_world(center.0, center.1);
+    commands.spawn((
+        Sprite {
+            color: blueprint.visuals.effect_color,
+            custom_size: Some(blueprint.visuals.effect_size * layout.scale),
+            ..default()
+        },
+        Transform::from_xyz(
+            floor_pos.x,
+            floor_pos.y + 20.0 * layout.scale,
+            Z_BULLET + 1.0,
+        ),
+        DamageZone {
+            damage,
+            element,
+            guard_break,
+            hit_tiles: hit_tiles.clone(),
+            applied: false,
+            source_slot,
+            recharge_on_hit: blueprint.modifiers.recharge_on_hit,
+            burn_time: blueprint.modifiers.burns_panels,
+            destroys_obstacles: blueprint.modifiers.destroys_obstacles,
+            trauma: 0.0,
+            knockback: blueprint.effect.knockback(),
+            falloff: None,
+        },
+        TargetsTiles::multiple(hit_tiles),
+        ActionVisual {
+            lifetime: Timer::from_seconds(blueprint.visuals.effect_duration, TimerMode::Once),
+            source: None,
+        },
+        CleanupOnStateExit(GameState::Playing),
+    ));
+}
+
+/// Damage (or break) an obstacle, flashing it or despawning it when destroyed
+pub fn hit_obstacle(
+    commands: &mut Commands,
+    obstacle_entity: Entity,
+    obstacle: &mut Obstacle,
+    damage: i32,
+    destroys_obstacles: bool,
+) {
+    // Already destroyed earlier this frame (despawn still pending)
+    if obstacle.hp <= 0 {
+        return;
+    }
+
+    if obstacle.take_hit(damage, destroys_obstacles) {
+        commands.entity(obstacle_entity).despawn();
+    } else {
+        commands
+            .entity(obstacle_entity)
+            .insert(FlashTimer(Timer::from_seconds(FLASH_TIME, TimerMode::Once)));
+    }
+}
+
+/// Push an enemy at `from` back `distance` tiles, stopping before the first tile
+/// `is_free` rejects (the back wall or an occupied tile). Returns the column it
+/// ends up in and how many tiles of the push were blocked.
+pub fn resolve_knockback(
+    from: (i32, i32),
+    distance: i32,
+    is_free: impl Fn(i32, i32) -> bool,
+) -> (i32, i32) {
+    let mut x = from.0;
+    for moved in 0..distance {
+        if !is_free(x + 1, from.1) {
+            return (x, distance - moved);
+        }
+        x += 1;
+    }
+    (x, 0)
+}
+
+/// Subtract damage from an enemy, refresh its HP text, and flash or kill it.
+/// A raised guard blocks most of the hit unless it is `guard_break`ing, and a
+/// charging enemy takes a `counter_hit`.
+fn apply_enemy_damage(
+    commands: &mut Commands,
+    enemy_entity: Entity,
+    enemy_pos: GridPosition,
+    health: &mut Health,
+    children: &Children,
+    text_query: &mut Query<&mut Text2d, With<HealthText>>,
+    guard: Option<Mut<EnemyGuard>>,
+    attack: Option<Mut<EnemyAttack>>,
+    damage: i32,
+    guard_break: bool,
+) {
+    let damage = match guard {
+        Some(mut guard) => guard.absorb(damage, guard_break),
+        None => damage,
+    };
+    let damage = counter_hit(
+        commands,
+        enemy_entity,
+        enemy_pos,
+        attack.map(Mut::into_inner),
+        damage,
+    );
+    health.current -= damage;
+
+    // Update HP text
+    for child in children.iter() {
+        if let Ok(mut text) = text_query.get_mut(child) {
+            text.0 = health.current.max(0).to_string();
+        }
+    }
+
+    if health.current <= 0 {
+        kill_enemy(commands, enemy_entity);
+    } else {
+        commands
+            .entity(enemy_entity)
+            .insert(FlashTimer(Timer::from_seconds(FLASH_TIME, TimerMode::Once)));
+    }
+}
+
+// ============================================================================
+// Panel Status
+// ============================================================================
+
+/// Ignite the tiles a fire chip hits, or put them out if the attack is Aqua
+fn apply_panel_element(
+    panel_query: &mut Query<(&TilePanel, &mut PanelStatus)>,
+    tiles: &[(i32, i32)],
+    element: Element,
+    burn_time: Option<f32>,
+) {
+    for (panel, mut status) in panel_query.iter_mut() {
+        if !tiles.contains(&(panel.x, panel.y)) {
+            continue;
+        }
+        if element == Element::Aqua {
+            if status.is_burning() {
+                *status = PanelStatus::Normal;
+            }
+        } else if let Some(duration) = burn_time {
+            *status = PanelStatus::burning(duration);
+        }
+    }
+}
+
+/// Tick burning panels: each tick damages the enemy standing on the panel,
+/// and the panel goes back to normal once its burn runs out
+pub fn process_burning_panels(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut panel_query: Query<(&TilePanel, &mut PanelStatus)>,
+    mut enemy_query: Query<
+        (
+            Entity,
+            &GridPosition,
+            &mut Health,
+            &Children,
+            Option<&EnemyTraitContainer>,
+            Option<&mut EnemyGuard>,
+            Option<&mut EnemyAttack>,
+        ),
+        (With<Enemy>, Without<Untargetable>),
+    >,
+    mut text_query: Query<&mut Text2d, With<HealthText>>,
+) {
+    for (panel, mut status) in &mut panel_query {
+        let PanelStatus::Burning { remaining, tick } = &mut *status else {
+            continue;
+        };
+
+        remaining.tick(time.delta());
+        tick.tick(time.delta());
+
+        if tick.just_finished() {
+            for (enemy_entity, enemy_pos, mut health, children, traits, guard, attack) in
+                &mut enemy_query
+            {
+                if enemy_pos.x != panel.x || enemy_pos.y != panel.y || health.current <= 0 {
+                    continue;
+                }
+                let mut damage = BURN_TICK_DAMAGE;
+                if let Some(traits) = traits {
+                    damage = traits.traits.reduce_damage(damage, false);
+                }
+                apply_enemy_damage(
+                    &mut commands,
+                    enemy_entity,
+                    *enemy_pos,
+                    &mut health,
+                    children,
+                    &mut text_query,
+                    guard,
+                    attack,
+                    damage,
+                    false,
+                );
+            }
+        }
+
+        if remaining.is_finished() {
+            *status = PanelStatus::Normal;
+        }
+    }
+}
+
+// ============================================================================
+// Heal Processing
+// ============================================================================
+
+/// Process heal flash effects
+pub fn process_heal_effects(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut Sprite, &BaseColor, &mut HealFlash)>,
+) {
+    for (entity, mut sprite, base, mut flash) in &mut query {
+        flash.timer.tick(time.delta());
+
+        if flash.timer.is_finished() {
+            sprite.color = base.0;
+            commands.entity(entity).remove::<HealFlash>();
+        } else {
+            let t = flash.timer.fraction();
+            let green = Color::srgb(0.3, 1.0, 0.4);
+            sprite.color = base.0.mix(&green, 1.0 - t);
+        }
+    }
+}
+
+// ============================================================================
+// Shield Processing
+// ============================================================================
+
+/// Block enemy bullets that reach a shielded player.
+///
+/// - Basic (Shield) blocks every hit for its duration
+/// - Guard (MetGuard) blocks hits from the front only (see `ActiveShield::faces`).
+///   Enemy bullets fly in along the row from the enemy side, so it stops them;
+///   area attacks from another row go through (see `execute_area_attack`)
+/// - Barrier blocks exactly one hit, then breaks
+/// - Aura negates hits below its threshold; anything at or above it breaks
+///   the aura and goes through for full damage
+/// - Invis doesn't block: bullets fly on through (see `enemy_bullet_hit_player`)
+pub fn process_shield_effects(
+    mut commands: Commands,
+    player_query: Query<(Entity, &GridPosition, &ActiveShield, Option<&Children>), With<Player>>,
+    enemy_bullet_query: Query<(Entity, &GridPosition, &crate::components::EnemyBullet)>,
+    shield_visual_query: Query<Entity, With<ShieldVisualMarker>>,
+) {
+    let Ok((player_entity, player_pos, shield, children)) = player_query.single() else {
+        return;
+    };
+    // Enemy bullets fly in along the row from the enemy side
+    if shield.dodges() || !shield.faces(IVec2::X) {
+        return;
+    }
+
+    for (bullet_entity, bullet_pos, bullet) in &enemy_bullet_query {
+        if bullet_pos != player_pos {
+            continue;
+        }
+
+        if shield.blocks(bullet.damage) {
+            commands.entity(bullet_entity).despawn();
+        }
+
+        if shield.breaks_on(bullet.damage) {
+            remove_shield(&mut commands, player_entity, children, &shield_visual_query);
+            // Shield is gone - any further hits this frame go through
+            break;
+        }
+    }
+}
+
+/// Update active shields (duration countdown)
+pub fn update_active_shields(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut player_query: Query<(Entity, &mut ActiveShield, Option<&Children>), With<Player>>,
+    shield_visual_query: Query<Entity, With<ShieldVisualMarker>>,
+) {
+    for (player_entity, mut shield, children) in &mut player_query {
+        shield.duration_timer.tick(time.delta());
+
+        if shield.duration_timer.is_finished() {
+            remove_shield(&mut commands, player_entity, children, &shield_visual_query);
+        }
+    }
+}
+
+/// Ghost the player's sprite while they are invisible
+pub fn ghost_invisible_player(
+    mut player_query: Query<(&mut Sprite, Option<&ActiveShield>), With<Player>>,
+) {
+    for (mut sprite, shield) in &mut player_query {
+        let alpha = if shield.is_some_and(ActiveShield::dodges) {
+            INVIS_ALPHA
+        } else {
+            1.0
+        };
+        if sprite.color.alpha() != alpha {
+            sprite.color.set_alpha(alpha);
+        }
+    }
+}
+
+/// Remove an entity's shield along with its visuals
+pub fn remove_shield(
+    commands: &mut Commands,
+    entity: Entity,
+    children: Option<&Children>,
+    shield_visual_query: &Query<Entity, With<ShieldVisualMarker>>,
+) {
+    commands.entity(entity).remove::<ActiveShield>();
+
+    if let Some(children) = children {
+        for child in children.iter() {
+            if shield_visual_query.get(child).is_ok() {
+                commands.entity(child).despawn();
+            }
+        }
+    }
+}
+
+// ============================================================================
+// Visual Updates
+// ============================================================================
+
+/// Move pattern sword slashes across their tiles
+pub fn update_sword_sweeps(
+    time: Res<Time>,
+    layout: Res<ArenaLayout>,
+    mut query: Query<(&mut SwordSweep, &mut Transform)>,
+) {
+    for (mut sweep, mut transform) in &mut query {
+        sweep.timer.tick(time.delta());
+        let progress = sweep.progress();
+        let from = sweep.tiles[progress.floor() as usize];
+        let to = sweep.tiles[progress.ceil() as usize];
+        let pos = layout
+            .tile_floor_world(from.0, from.1)
+            .lerp(layout.tile_floor_world(to.0, to.1), progress.fract());
+        transform.translation.x = pos.x;
+        transform.translation.y = pos.y + 20.0 * layout.scale;
+    }
+}
 
 /// Update action visual effects (lifetimes, animations)
 pub fn update_action_visuals(time: Res<Time>, mut query: Query<&mut ActionVisual>) {
@@ -579,3 +1822,1087 @@ pub fn despawn_action_visuals(mut commands: Commands, query: Query<(Entity, &Act
         }
     }
 }
+
+/// Insert the resources `execute_pending_actions` reads, for tests that run it
+/// (the asset server has no loaders, so nothing is ever loaded)
+#[cfg(test)]
+pub fn init_chip_resources(world: &mut World) {
+    let mut assets = App::new();
+    assets
+        .add_plugins((MinimalPlugins, AssetPlugin::default()))
+        .init_asset::<bevy::audio::AudioSource>();
+    world.insert_resource(assets.world().resource::<AssetServer>().clone());
+    world.init_resource::<ArenaLayout>();
+    world.init_resource::<TileOwnership>();
+    world.init_resource::<BattleStats>();
+    world.init_resource::<ArenaConfig>();
+    world.init_resource::<AudioSettings>();
+    if !world.contains_resource::<GameRng>() {
+        world.insert_resource(GameRng::from_seed(1));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::systems::rumble::init_hit_feedback;
+
+    /// Bare world with the resources the battle systems read
+    fn battle_world() -> World {
+        let mut world = World::new();
+        world.init_resource::<Time>();
+        world.init_resource::<ArenaLayout>();
+        world
+    }
+
+    fn spawn_enemy(world: &mut World, x: i32, y: i32, hp: i32) -> Entity {
+        world
+            .spawn((
+                Enemy,
+                GridPosition { x, y },
+                Health {
+                    current: hp,
+                    max: hp,
+                },
+            ))
+            .with_children(|parent| {
+                parent.spawn((HealthText, Text2d::new(hp.to_string())));
+            })
+            .id()
+    }
+
+    fn advance(world: &mut World, secs: f32) {
+        world
+            .resource_mut::<Time>()
+            .advance_by(Duration::from_secs_f32(secs));
+    }
+
+    fn hp(world: &World, enemy: Entity) -> i32 {
+        world.get::<Health>(enemy).unwrap().current
+    }
+
+    /// Fire `action_id` for `damage` from `source` at the enemies standing on
+    /// `enemies`, then let its damage zone land
+    fn hit_with(
+        world: &mut World,
+        action_id: ActionId,
+        source: (i32, i32),
+        damage: i32,
+        enemies: &[(i32, i32)],
+    ) {
+        world.init_resource::<ArenaLayout>();
+        init_hit_feedback(world);
+        world.init_resource::<TileOwnership>();
+        let mut rng = GameRng::from_seed(1);
+        execute_damage_action(
+            &mut world.commands(),
+            &ActionBlueprint::get(action_id),
+            source,
+            None,
+            damage,
+            0.0,
+            Element::None,
+            false,
+            enemies,
+            &[],
+            &TileOwnership::default(),
+            &ArenaLayout::default(),
+            &mut rng.0,
+        );
+        world.flush();
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(process_damage_effects);
+        schedule.run(world);
+    }
+
+    #[test]
+    fn random_enemy_picks_distinct_enemy_tiles() {
+        let enemy_tiles = [(3, 0), (4, 1), (5, 2), (4, 2)];
+        for seed in 0..20 {
+            let mut rng = GameRng::from_seed(seed);
+            let picked = pick_random_enemy_tiles(&enemy_tiles, 2, &mut rng.0);
+
+            assert_eq!(picked.len(), 2);
+            assert_ne!(picked[0], picked[1]);
+            assert!(picked.iter().all(|tile| enemy_tiles.contains(tile)));
+        }
+    }
+
+    #[test]
+    fn random_enemy_never_picks_more_tiles_than_enemies() {
+        let mut rng = GameRng::from_seed(7);
+        let picked = pick_random_enemy_tiles(&[(4, 1), (4, 1)], 3, &mut rng.0);
+        assert_eq!(picked, vec![(4, 1)]);
+    }
+
+    fn shielded_player_hit_by(
+        shield_type: ShieldType,
+        threshold: Option<i32>,
+        damage: i32,
+    ) -> World {
+        let mut world = World::new();
+        world.spawn((
+            Player,
+            GridPosition { x: 1, y: 1 },
+            ActiveShield {
+                duration_timer: Timer::from_seconds(5.0, TimerMode::Once),
+                damage_threshold: threshold,
+                shield_type,
+            },
+        ));
+        world.spawn((
+            GridPosition { x: 1, y: 1 },
+            crate::components::EnemyBullet::new(damage),
+        ));
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(process_shield_effects);
+        schedule.run(&mut world);
+        world
+    }
+
+    fn bullets_left(world: &mut World) -> usize {
+        world
+            .query::<&crate::components::EnemyBullet>()
+            .iter(world)
+            .count()
+    }
+
+    fn shields_left(world: &mut World) -> usize {
+        world.query::<&ActiveShield>().iter(world).count()
+    }
+
+    #[test]
+    fn barrier_blocks_one_bullet_then_breaks() {
+        let mut world = shielded_player_hit_by(ShieldType::Barrier, None, 200);
+        assert_eq!(bullets_left(&mut world), 0);
+        assert_eq!(shields_left(&mut world), 0);
+
+        let mut world = shielded_player_hit_by(ShieldType::Basic, None, 200);
+        assert_eq!(bullets_left(&mut world), 0);
+        assert_eq!(shields_left(&mut world), 1);
+    }
+
+    #[test]
+    fn aura_lets_hits_at_its_threshold_through() {
+        let mut world = shielded_player_hit_by(ShieldType::Aura, Some(100), 99);
+        assert_eq!(bullets_left(&mut world), 0);
+
+        let mut world = shielded_player_hit_by(ShieldType::Aura, Some(100), 100);
+        assert_eq!(bullets_left(&mut world), 1);
+    }
+
+    #[test]
+    fn ratton_turns_once_toward_an_enemy_off_its_row() {
+        let mut world = battle_world();
+        let enemy = spawn_enemy(&mut world, 4, 2, 100);
+        let ratton = world
+            .spawn((
+                GridPosition { x: 1, y: 0 },
+                RattonProjectile {
+                    damage: 40,
+                    element: Element::None,
+                    guard_break: false,
+                    move_timer: Timer::from_seconds(0.1, TimerMode::Repeating),
+                    has_turned: false,
+                    turn_dir: 0,
+                },
+            ))
+            .id();
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(process_ratton);
+        let mut path = Vec::new();
+        for _ in 0..20 {
+            advance(&mut world, 0.1);
+            schedule.run(&mut world);
+            match world.get::<GridPosition>(ratton) {
+                Some(pos) => path.push((pos.x, pos.y)),
+                None => break,
+            }
+        }
+
+        assert_eq!(path, vec![(2, 0), (3, 0), (4, 0), (4, 1), (4, 2)]);
+        assert!(world.get_entity(ratton).is_err());
+        assert_eq!(hp(&world, enemy), 60);
+    }
+
+    /// Tiles a chip fired from `source` hits with a rock on `rock`
+    fn tiles_past_rock(
+        action_id: ActionId,
+        source: (i32, i32),
+        rock: (i32, i32),
+    ) -> Vec<(i32, i32)> {
+        let blueprint = ActionBlueprint::get(action_id);
+        let tiles = calculate_hit_tiles(
+            &blueprint.target,
+            source,
+            &TileOwnership::default(),
+            GridSize::default(),
+        );
+        if stopped_by_obstacles(&blueprint) {
+            stop_at_obstacles(tiles, &[rock])
+        } else {
+            tiles
+        }
+    }
+
+    #[test]
+    fn shockwave_breaks_a_rock_that_blocks_a_cannon() {
+        let rock = (3, 1);
+
+        let cannon = tiles_past_rock(ActionId::Cannon, (1, 1), rock);
+        assert_eq!(cannon.last(), Some(&rock));
+        let ActionEffect::Damage { amount, .. } = ActionBlueprint::get(ActionId::Cannon).effect
+        else {
+            panic!("Cannon is a damage chip");
+        };
+        let mut obstacle = Obstacle {
+            hp: OBSTACLE_ROCK_HP,
+        };
+        assert!(!obstacle.take_hit(amount, false));
+
+        let wave = tiles_past_rock(ActionId::ShokWave, (1, 1), rock);
+        assert!(wave.contains(&rock) && wave.contains(&(5, 1)));
+        let breaks_rocks = ActionBlueprint::get(ActionId::ShokWave)
+            .modifiers
+            .destroys_obstacles;
+        assert!(obstacle.take_hit(0, breaks_rocks));
+    }
+
+    #[test]
+    fn tower_hits_lower_rows_first_as_it_climbs() {
+        let mut world = battle_world();
+        let low = spawn_enemy(&mut world, 3, 0, 100);
+        let high = spawn_enemy(&mut world, 3, 2, 100);
+        world.spawn((
+            GridPosition { x: 3, y: 0 },
+            TowerEffect {
+                damage: 30,
+                element: Element::None,
+                guard_break: false,
+                step_timer: Timer::from_seconds(0.2, TimerMode::Repeating),
+                last_row: None,
+                burn_time: None,
+            },
+        ));
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(process_tower);
+        let (mut low_hit_at, mut high_hit_at) = (None, None);
+        for frame in 0..10 {
+            advance(&mut world, 0.1);
+            schedule.run(&mut world);
+            if hp(&world, low) < 100 {
+                low_hit_at.get_or_insert(frame);
+            }
+            if hp(&world, high) < 100 {
+                high_hit_at.get_or_insert(frame);
+            }
+        }
+
+        assert!(low_hit_at.unwrap() < high_hit_at.unwrap());
+        assert_eq!(hp(&world, low), 70);
+        assert_eq!(hp(&world, high), 70);
+    }
+
+    #[test]
+    fn tower_burns_each_empty_row_once() {
+        let mut world = battle_world();
+        let panels: Vec<Entity> = (0..GRID_HEIGHT)
+            .map(|y| {
+                world
+                    .spawn((TilePanel { x: 3, y }, PanelStatus::Normal))
+                    .id()
+            })
+            .collect();
+        world.spawn((
+            GridPosition { x: 3, y: 0 },
+            TowerEffect {
+                damage: 30,
+                element: Element::Fire,
+                guard_break: false,
+                step_timer: Timer::from_seconds(0.25, TimerMode::Repeating),
+                last_row: None,
+                burn_time: Some(2.0),
+            },
+        ));
+        let burning =
+            |world: &World, panel: Entity| world.get::<PanelStatus>(panel).unwrap().is_burning();
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(process_tower);
+        advance(&mut world, 0.1);
+        schedule.run(&mut world);
+        assert!(burning(&world, panels[0]));
+
+        // Put the fire out: the tower is still on the row, but does not relight it
+        *world.get_mut::<PanelStatus>(panels[0]).unwrap() = PanelStatus::Normal;
+        advance(&mut world, 0.1);
+        schedule.run(&mut world);
+        assert!(!burning(&world, panels[0]));
+        assert!(!burning(&world, panels[1]));
+
+        // The next step climbs a row and lights that one
+        advance(&mut world, 0.1);
+        schedule.run(&mut world);
+        advance(&mut world, 0.1);
+        schedule.run(&mut world);
+        assert!(!burning(&world, panels[0]));
+        assert!(burning(&world, panels[1]));
+    }
+
+    #[test]
+    fn shotgun_pellet_pierces_every_enemy_in_its_row() {
+        let mut world = battle_world();
+        let near = spawn_enemy(&mut world, 3, 1, 100);
+        let far = spawn_enemy(&mut world, 5, 1, 100);
+        let other_row = spawn_enemy(&mut world, 4, 0, 100);
+        let ActionEffect::Damage { amount, .. } = ActionBlueprint::get(ActionId::Shotgun).effect
+        else {
+            panic!("Shotgun is a damage chip");
+        };
+        let pellet = world
+            .spawn((
+                GridPosition { x: 2, y: 1 },
+                PiercingShot {
+                    damage: amount,
+                    element: Element::None,
+                    guard_break: false,
+                    destroys_obstacles: false,
+                    move_timer: Timer::from_seconds(0.1, TimerMode::Repeating),
+                    hit_enemies: Vec::new(),
+                },
+            ))
+            .id();
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(process_piercing_shots);
+        for _ in 0..10 {
+            advance(&mut world, 0.1);
+            schedule.run(&mut world);
+        }
+
+        assert!(world.get_entity(pellet).is_err());
+        assert_eq!(hp(&world, near), 100 - amount);
+        assert_eq!(hp(&world, far), 100 - amount);
+        assert_eq!(hp(&world, other_row), 100);
+    }
+
+    #[test]
+    fn burning_panel_ticks_damage_on_the_enemy_standing_on_it() {
+        let mut world = battle_world();
+        let burned = spawn_enemy(&mut world, 4, 1, 100);
+        let beside = spawn_enemy(&mut world, 4, 2, 100);
+        let panel = world
+            .spawn((TilePanel { x: 4, y: 1 }, PanelStatus::burning(1.0)))
+            .id();
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(process_burning_panels);
+        advance(&mut world, BURN_TICK_INTERVAL);
+        schedule.run(&mut world);
+        assert_eq!(hp(&world, burned), 100 - BURN_TICK_DAMAGE);
+        assert_eq!(hp(&world, beside), 100);
+
+        advance(&mut world, 1.0);
+        schedule.run(&mut world);
+        assert!(!world.get::<PanelStatus>(panel).unwrap().is_burning());
+    }
+
+    #[test]
+    fn aqua_attack_puts_out_a_burning_panel() {
+        let mut world = battle_world();
+        let burning = world
+            .spawn((TilePanel { x: 4, y: 1 }, PanelStatus::burning(3.0)))
+            .id();
+        let untouched = world
+            .spawn((TilePanel { x: 4, y: 2 }, PanelStatus::burning(3.0)))
+            .id();
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(|mut panels: Query<(&TilePanel, &mut PanelStatus)>| {
+            apply_panel_element(&mut panels, &[(4, 1)], Element::Aqua, None);
+        });
+        schedule.run(&mut world);
+
+        assert!(!world.get::<PanelStatus>(burning).unwrap().is_burning());
+        assert!(world.get::<PanelStatus>(untouched).unwrap().is_burning());
+    }
+
+    #[test]
+    fn steal_claims_the_front_enemy_column_and_pushes_enemies_back() {
+        let mut world = battle_world();
+        world.init_resource::<TileOwnership>();
+        let front = spawn_enemy(&mut world, 3, 1, 100);
+        world.spawn(PanelSteal { columns: 1 });
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(process_panel_steals);
+        schedule.run(&mut world);
+
+        let ownership = world.resource::<TileOwnership>();
+        for y in 0..3 {
+            // The player may now step onto column 3; enemies may not
+            assert!(ownership.is_player_tile(3, y));
+            assert!(!ownership.is_enemy_tile(3, y));
+            assert!(ownership.is_enemy_tile(4, y));
+        }
+        assert_eq!(
+            ownership.tiles_owned_by(TileOwner::Player).len(),
+            4 * 3,
+            "only one column changes hands"
+        );
+        assert_eq!(
+            *world.get::<GridPosition>(front).unwrap(),
+            GridPosition { x: 4, y: 1 }
+        );
+    }
+
+    #[test]
+    fn bubbler_splashes_around_the_enemy_it_hits() {
+        let mut world = battle_world();
+        world.init_resource::<ArenaLayout>();
+        init_hit_feedback(&mut world);
+        world.init_resource::<TileOwnership>();
+        let hit = spawn_enemy(&mut world, 3, 1, 100);
+        let below = spawn_enemy(&mut world, 3, 2, 100);
+        let behind = spawn_enemy(&mut world, 4, 1, 100);
+        let out_of_reach = spawn_enemy(&mut world, 5, 1, 100);
+        let ActionTarget::SplashProjectile { pattern, .. } =
+            ActionBlueprint::get(ActionId::Bubbler).target
+        else {
+            panic!("Bubbler is a splash projectile");
+        };
+        world.spawn((
+            GridPosition { x: 2, y: 1 },
+            SplashShot {
+                action_id: ActionId::Bubbler,
+                damage: 50,
+                element: Element::Aqua,
+                guard_break: false,
+                pattern,
+                source_slot: None,
+                move_timer: Timer::from_seconds(0.1, TimerMode::Repeating),
+            },
+        ));
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems((process_splash_shots, process_damage_effects).chain());
+        for _ in 0..5 {
+            advance(&mut world, 0.1);
+            schedule.run(&mut world);
+        }
+
+        assert_eq!(hp(&world, hit), 50);
+        assert_eq!(hp(&world, below), 50);
+        assert_eq!(hp(&world, behind), 50);
+        assert_eq!(hp(&world, out_of_reach), 100);
+    }
+
+    #[test]
+    fn enemy_stepping_onto_a_mine_sets_off_its_blast() {
+        let mut world = battle_world();
+        world.init_resource::<ArenaLayout>();
+        init_hit_feedback(&mut world);
+        world.init_resource::<TileOwnership>();
+        let walker = spawn_enemy(&mut world, 5, 1, 200);
+        let beside = spawn_enemy(&mut world, 4, 2, 200);
+        let far = spawn_enemy(&mut world, 5, 0, 200);
+        let ActionTarget::Mine { pattern, .. } = ActionBlueprint::get(ActionId::Mine).target else {
+            panic!("Mine lays a mine");
+        };
+        let mine = world
+            .spawn((
+                GridPosition { x: 4, y: 1 },
+                Mine {
+                    action_id: ActionId::Mine,
+                    damage: 90,
+                    element: Element::None,
+                    guard_break: false,
+                    pattern,
+                    source_slot: None,
+                    timeout: Timer::from_seconds(MINE_LIFETIME, TimerMode::Once),
+                },
+            ))
+            .id();
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems((process_mines, process_damage_effects).chain());
+        for _ in 0..3 {
+            advance(&mut world, 0.1);
+            schedule.run(&mut world);
+        }
+        assert!(world.get_entity(mine).is_ok(), "nobody on the mine yet");
+        assert_eq!(hp(&world, walker), 200);
+
+        // The walker steps onto the mine: it bursts over the cross around it
+        world.get_mut::<GridPosition>(walker).unwrap().x = 4;
+        for _ in 0..2 {
+            advance(&mut world, 0.1);
+            schedule.run(&mut world);
+        }
+        assert!(world.get_entity(mine).is_err());
+        assert_eq!(hp(&world, walker), 110);
+        assert_eq!(hp(&world, beside), 110);
+        assert_eq!(hp(&world, far), 200);
+    }
+
+    /// Press the Cannon chip's slot, then either hold it or let go at once;
+    /// returns whether the queued action came out charged
+    fn cannon_charge(hold: bool) -> bool {
+        let mut world = battle_world();
+        world.init_resource::<ButtonInput<KeyCode>>();
+        world.init_resource::<ArenaLayout>();
+        world.init_resource::<KeyBindings>();
+        world.init_resource::<PlayerLoadout>();
+        world.init_resource::<AssistOptions>();
+        world.init_resource::<TileOwnership>();
+        world.init_resource::<ChipInputHistory>();
+        world.spawn((Player, GridPosition { x: 1, y: 1 }));
+        let blueprint = ActionBlueprint::get(ActionId::Cannon);
+        world.spawn(ActionSlot::new(
+            0,
+            ActionId::Cannon,
+            blueprint.cooldown,
+            blueprint.charge_time,
+        ));
+        let mut schedule = Schedule::default();
+        schedule.add_systems(action_input_system);
+
+        world
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(KeyCode::Digit1);
+        advance(&mut world, 0.05);
+        schedule.run(&mut world);
+
+        let mut keyboard = world.resource_mut::<ButtonInput<KeyCode>>();
+        if hold {
+            keyboard.clear();
+        } else {
+            keyboard.release(KeyCode::Digit1);
+        }
+        for _ in 0..5 {
+            advance(&mut world, 0.05);
+            schedule.run(&mut world);
+        }
+
+        let pending: Vec<bool> = world
+            .query::<&crate::actions::PendingAction>()
+            .iter(&world)
+            .map(|pending| pending.charged)
+            .collect();
+        assert_eq!(pending.len(), 1, "the charge completes exactly once");
+        pending[0]
+    }
+
+    #[test]
+    fn panic_heal_fires_a_ready_recov_chip_at_critical_hp_once_per_cooldown() {
+        let mut world = battle_world();
+        world.init_resource::<ButtonInput<KeyCode>>();
+        world.init_resource::<ArenaLayout>();
+        world.init_resource::<KeyBindings>();
+        world.init_resource::<PlayerLoadout>();
+        world.init_resource::<TileOwnership>();
+        world.init_resource::<ChipInputHistory>();
+        world.insert_resource(AssistOptions { auto_heal: true });
+        let player = world
+            .spawn((
+                Player,
+                GridPosition { x: 1, y: 1 },
+                Health {
+                    current: 100,
+                    max: 100,
+                },
+            ))
+            .id();
+        for (index, action_id) in [ActionId::Cannon, ActionId::Recov10, ActionId::Recov30]
+            .into_iter()
+            .enumerate()
+        {
+            let blueprint = ActionBlueprint::get(action_id);
+            world.spawn(ActionSlot::new(
+                index,
+                action_id,
+                blueprint.cooldown,
+                blueprint.charge_time,
+            ));
+        }
+        let mut schedule = Schedule::default();
+        schedule.add_systems(action_input_system);
+        let queued = |world: &mut World| -> Vec<ActionId> {
+            world
+                .query::<&crate::actions::PendingAction>()
+                .iter(world)
+                .map(|pending| pending.action_id)
+                .collect()
+        };
+
+        // Healthy: nothing fires on its own
+        advance(&mut world, 0.05);
+        schedule.run(&mut world);
+        assert!(queued(&mut world).is_empty());
+
+        // Critical: only the first ready heal chip fires
+        world.get_mut::<Health>(player).unwrap().current = 20;
+        advance(&mut world, 0.05);
+        schedule.run(&mut world);
+        assert_eq!(queued(&mut world), vec![ActionId::Recov10]);
+
+        // Recov10 is cooling down, so the next frame falls to Recov30
+        advance(&mut world, 0.05);
+        schedule.run(&mut world);
+        assert_eq!(queued(&mut world).len(), 2);
+        advance(&mut world, 0.05);
+        schedule.run(&mut world);
+        assert_eq!(queued(&mut world).len(), 2);
+
+        // With the assist off, critical HP fires nothing
+        world.resource_mut::<AssistOptions>().auto_heal = false;
+        advance(&mut world, 60.0);
+        schedule.run(&mut world);
+        assert_eq!(queued(&mut world).len(), 2);
+    }
+
+    #[test]
+    fn charging_chip_previews_its_hit_tiles_until_it_fires() {
+        let mut world = battle_world();
+        world.init_resource::<ButtonInput<KeyCode>>();
+        world.init_resource::<ArenaLayout>();
+        world.init_resource::<KeyBindings>();
+        world.init_resource::<PlayerLoadout>();
+        world.init_resource::<AssistOptions>();
+        world.init_resource::<TileOwnership>();
+        world.init_resource::<ChipInputHistory>();
+        let player = world.spawn((Player, GridPosition { x: 1, y: 1 })).id();
+        let blueprint = ActionBlueprint::get(ActionId::WideSwrd);
+        world.spawn(ActionSlot::new(
+            0,
+            ActionId::WideSwrd,
+            blueprint.cooldown,
+            blueprint.charge_time,
+        ));
+        let mut schedule = Schedule::default();
+        schedule.add_systems(action_input_system);
+        let previewed = |world: &mut World| -> Vec<Vec<(i32, i32)>> {
+            world
+                .query_filtered::<&TargetsTiles, With<ChipTargetPreview>>()
+                .iter(world)
+                .map(|targets| targets.tiles.clone())
+                .collect()
+        };
+        let expected = |x: i32, y: i32| {
+            calculate_hit_tiles(
+                &blueprint.target,
+                (x, y),
+                &TileOwnership::default(),
+                GridSize::default(),
+            )
+        };
+
+        world
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(KeyCode::Digit1);
+        advance(&mut world, 0.05);
+        schedule.run(&mut world);
+        assert_eq!(previewed(&mut world), vec![expected(1, 1)]);
+
+        // The preview follows the player while the chip charges
+        world.resource_mut::<ButtonInput<KeyCode>>().clear();
+        world.get_mut::<GridPosition>(player).unwrap().x = 2;
+        advance(&mut world, 0.05);
+        schedule.run(&mut world);
+        assert_eq!(previewed(&mut world), vec![expected(2, 1)]);
+
+        // Firing clears it
+        advance(&mut world, blueprint.charge_time);
+        schedule.run(&mut world);
+        assert_eq!(
+            world
+                .query::<&crate::actions::PendingAction>()
+                .iter(&world)
+                .count(),
+            1
+        );
+        assert!(previewed(&mut world).is_empty());
+    }
+
+    #[test]
+    fn holding_a_chip_through_its_charge_fires_the_charged_effect() {
+        let blueprint = ActionBlueprint::get(ActionId::Cannon);
+        let damage = |effect: &ActionEffect| match effect {
+            ActionEffect::Damage { amount, .. } => *amount,
+            _ => panic!("Cannon is a damage chip"),
+        };
+
+        let charged = cannon_charge(true);
+        assert!(charged);
+        assert_eq!(
+            damage(blueprint.effect_for(charged)),
+            damage(&blueprint.effect) * 2
+        );
+
+        let quick = cannon_charge(false);
+        assert!(!quick);
+        assert_eq!(
+            damage(blueprint.effect_for(quick)),
+            damage(&blueprint.effect)
+        );
+    }
+
+    #[test]
+    fn wide_sword_hits_three_front_tiles_clipped_to_the_grid() {
+        let target = ActionBlueprint::get(ActionId::WideSwrd).target;
+        let ownership = TileOwnership::default();
+
+        assert_eq!(
+            calculate_hit_tiles(&target, (1, 1), &ownership, GridSize::default()),
+            vec![(2, 0), (2, 1), (2, 2)]
+        );
+        // Top and bottom rows lose the tile off the grid
+        assert_eq!(
+            calculate_hit_tiles(&target, (1, 0), &ownership, GridSize::default()),
+            vec![(2, 0), (2, 1)]
+        );
+        assert_eq!(
+            calculate_hit_tiles(
+                &target,
+                (0, GRID_HEIGHT - 1),
+                &ownership,
+                GridSize::default()
+            ),
+            vec![(1, GRID_HEIGHT - 2), (1, GRID_HEIGHT - 1)]
+        );
+    }
+
+    #[test]
+    fn row_chips_reach_the_far_edge_of_a_wide_grid() {
+        let target = ActionBlueprint::get(ActionId::ShokWave).target;
+        let wide = GridSize::new(8, 3, 3);
+        let ownership = TileOwnership::new(wide);
+
+        let tiles = calculate_hit_tiles(&target, (1, 1), &ownership, wide);
+        assert_eq!(tiles.last(), Some(&(7, 1)));
+        assert_eq!(
+            calculate_hit_tiles(&target, (1, 1), &ownership, GridSize::default()).last(),
+            Some(&(GRID_WIDTH - 1, 1))
+        );
+    }
+
+    #[test]
+    fn pattern_sword_hits_every_tile_at_once_while_its_slash_sweeps() {
+        let mut world = battle_world();
+        let in_reach = [
+            spawn_enemy(&mut world, 2, 1, 200),
+            spawn_enemy(&mut world, 3, 1, 200),
+            spawn_enemy(&mut world, 4, 1, 200),
+        ];
+        let out_of_reach = spawn_enemy(&mut world, 5, 1, 200);
+
+        hit_with(
+            &mut world,
+            ActionId::FtrSwrd,
+            (1, 1),
+            100,
+            &[(2, 1), (3, 1), (4, 1), (5, 1)],
+        );
+        for enemy in in_reach {
+            assert_eq!(hp(&world, enemy), 100);
+        }
+        assert_eq!(hp(&world, out_of_reach), 200);
+
+        let layout = ArenaLayout::default();
+        let mut schedule = Schedule::default();
+        schedule.add_systems((process_damage_effects, update_sword_sweeps).chain());
+        advance(&mut world, 0.01);
+        schedule.run(&mut world);
+
+        let sweep_x = |world: &mut World| {
+            world
+                .query_filtered::<&Transform, With<SwordSweep>>()
+                .single(world)
+                .unwrap()
+                .translation
+                .x
+        };
+        let tiles = world
+            .query::<&SwordSweep>()
+            .single(&world)
+            .unwrap()
+            .tiles
+            .clone();
+        assert_eq!(tiles, vec![(2, 1), (3, 1), (4, 1)]);
+        let start = sweep_x(&mut world);
+        assert!(start < layout.tile_floor_world(3, 1).x);
+
+        advance(&mut world, SWORD_SWEEP_TIME);
+        schedule.run(&mut world);
+        assert_eq!(sweep_x(&mut world), layout.tile_floor_world(4, 1).x);
+        // The zone already hit: nothing lands twice
+        assert_eq!(hp(&world, in_reach[2]), 100);
+    }
+
+    /// Fire `action_id` from `source` at a guarding enemy on (3, 1); returns
+    /// the enemy's HP afterwards and whether it still guards
+    fn hit_guarding_enemy(action_id: ActionId, source: (i32, i32), damage: i32) -> (i32, bool) {
+        let mut world = battle_world();
+        let enemy = spawn_enemy(&mut world, 3, 1, 500);
+        let mut guard = EnemyGuard::new(crate::enemies::GuardCycle {
+            guard_duration: 2.0,
+            open_duration: 3.0,
+        });
+        guard.guarding = true;
+        world.entity_mut(enemy).insert(guard);
+
+        hit_with(&mut world, action_id, source, damage, &[(3, 1)]);
+        let guarding = world.get::<EnemyGuard>(enemy).unwrap().guarding;
+        (hp(&world, enemy), guarding)
+    }
+
+    #[test]
+    fn guard_blocks_most_of_a_cannon_but_guts_punch_breaks_it() {
+        let (cannon_hp, still_guarding) = hit_guarding_enemy(ActionId::Cannon, (1, 1), 40);
+        assert_eq!(cannon_hp, 496);
+        assert!(still_guarding);
+
+        let (punch_hp, still_guarding) = hit_guarding_enemy(ActionId::GutsPnch, (2, 1), 160);
+        assert_eq!(punch_hp, 340);
+        assert!(!still_guarding);
+    }
+
+    #[test]
+    fn guard_also_blocks_burning_panel_ticks() {
+        let mut world = battle_world();
+        let enemy = spawn_enemy(&mut world, 4, 1, 100);
+        let mut guard = EnemyGuard::new(crate::enemies::GuardCycle {
+            guard_duration: 2.0,
+            open_duration: 3.0,
+        });
+        guard.guarding = true;
+        world.entity_mut(enemy).insert(guard);
+        world.spawn((TilePanel { x: 4, y: 1 }, PanelStatus::burning(1.0)));
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(process_burning_panels);
+        advance(&mut world, BURN_TICK_INTERVAL);
+        schedule.run(&mut world);
+
+        let guarded = ((BURN_TICK_DAMAGE as f32 * GUARDED_DAMAGE_SCALE) as i32).max(1);
+        assert!(guarded < BURN_TICK_DAMAGE);
+        assert_eq!(hp(&world, enemy), 100 - guarded);
+    }
+
+    /// Punch an enemy on `tile` with GutsPnch from the tile in front of it;
+    /// returns its HP and position afterwards
+    fn guts_punch(tile: (i32, i32)) -> (i32, GridPosition) {
+        let mut world = battle_world();
+        let enemy = spawn_enemy(&mut world, tile.0, tile.1, 500);
+
+        hit_with(
+            &mut world,
+            ActionId::GutsPnch,
+            (tile.0 - 1, tile.1),
+            160,
+            &[tile],
+        );
+        (
+            hp(&world, enemy),
+            *world.get::<GridPosition>(enemy).unwrap(),
+        )
+    }
+
+    #[test]
+    fn knockback_into_the_back_wall_slams_for_bonus_damage() {
+        // Room to fly: pushed two columns back, no bonus
+        let (room_hp, room_pos) = guts_punch((3, 1));
+        assert_eq!(room_hp, 340);
+        assert_eq!(room_pos, GridPosition { x: 5, y: 1 });
+
+        // Already against the wall: both tiles of the push become slam damage
+        let back = GRID_WIDTH - 1;
+        let (wall_hp, wall_pos) = guts_punch((back, 1));
+        assert_eq!(wall_hp, 340 - 2 * WALL_SLAM_DAMAGE);
+        assert_eq!(wall_pos, GridPosition { x: back, y: 1 });
+
+        // One tile short of the wall: moves one, slams for the other
+        assert_eq!(
+            resolve_knockback((4, 1), 2, |x, _| x < GRID_WIDTH),
+            (GRID_WIDTH - 1, 1)
+        );
+    }
+
+    /// Fire a Cannon from the front column at a lone enemy in `column`; returns its HP
+    fn cannon_at(column: i32) -> i32 {
+        let mut world = battle_world();
+        let enemy = spawn_enemy(&mut world, column, 1, 100);
+
+        hit_with(&mut world, ActionId::Cannon, (0, 1), 40, &[(column, 1)]);
+        hp(&world, enemy)
+    }
+
+    #[test]
+    fn heal_chip_plays_the_heal_sound_at_the_effects_volume() {
+        let mut world = battle_world();
+        init_chip_resources(&mut world);
+        world.insert_resource(AudioSettings {
+            master: 0.5,
+            sfx: 0.5,
+            ..Default::default()
+        });
+        let player = world
+            .spawn((
+                Player,
+                Health {
+                    current: 50,
+                    max: 100,
+                },
+            ))
+            .id();
+        world.spawn(crate::actions::PendingAction {
+            action_id: ActionId::Recov10,
+            source_entity: player,
+            source_position: (1, 1),
+            source_slot: None,
+            charged: false,
+        });
+        world.run_system_cached(execute_pending_actions).unwrap();
+
+        let played: Vec<(String, f32)> = world
+            .query::<(&AudioPlayer, &PlaybackSettings)>()
+            .iter(&world)
+            .map(|(player, settings)| {
+                (
+                    player.0.path().unwrap().to_string(),
+                    settings.volume.to_linear(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            played,
+            vec![(
+                crate::actions::sounds::HEAL.to_string(),
+                CHIP_SOUND_VOLUME * 0.25
+            )]
+        );
+        assert_eq!(world.get::<Health>(player).unwrap().current, 60);
+    }
+
+    #[test]
+    fn travelling_chip_projectiles_leave_trails_in_their_color() {
+        let mut world = battle_world();
+        init_chip_resources(&mut world);
+        let player = world.spawn(Player).id();
+        let chips = [ActionId::FireTowr, ActionId::Shotgun, ActionId::Bubbler];
+        for action_id in chips {
+            world.spawn(crate::actions::PendingAction {
+                action_id,
+                source_entity: player,
+                source_position: (1, 1),
+                source_slot: None,
+                charged: false,
+            });
+        }
+        world.run_system_cached(execute_pending_actions).unwrap();
+
+        let trails: Vec<Color> = world
+            .query::<&ProjectileTrail>()
+            .iter(&world)
+            .map(|trail| trail.color)
+            .collect();
+        let towers = world.query::<&TowerEffect>().iter(&world).count();
+        let pellets = world.query::<&PiercingShot>().iter(&world).count();
+        let splashes = world.query::<&SplashShot>().iter(&world).count();
+        assert!(towers > 0 && pellets > 0 && splashes > 0);
+        assert_eq!(trails.len(), towers + pellets + splashes);
+        for action_id in chips {
+            let color = ActionBlueprint::get(action_id).visuals.effect_color;
+            assert!(trails.contains(&color));
+        }
+    }
+
+    #[test]
+    fn cannon_falloff_weakens_hits_on_distant_enemies() {
+        let falloff = ActionBlueprint::get(ActionId::Cannon)
+            .modifiers
+            .falloff
+            .expect("Cannon has falloff");
+
+        // Within the start range: full damage
+        let near = cannon_at(falloff.start_range);
+        assert_eq!(near, 60);
+
+        // Back wall: damage scaled down to the minimum multiplier
+        let far = cannon_at(GRID_WIDTH - 1);
+        assert!(far > near, "distant enemy takes less damage");
+        assert_eq!(far, 100 - (40.0 * falloff.min_multiplier).round() as i32);
+    }
+
+    /// Spawn a Mettaur on (4, 1), mid-charge or ready to attack
+    fn spawn_mettaur(world: &mut World, charging: bool) -> Entity {
+        use crate::enemies::{AttackState, ChargingTelegraph, EnemyBlueprint, EnemyId};
+
+        let enemy = spawn_enemy(world, 4, 1, 100);
+        let mut attack = EnemyAttack::new(EnemyBlueprint::get(EnemyId::Mettaur).attack, 1.0);
+        if charging {
+            attack.state = AttackState::Charging;
+            attack.charge_timer = Some(Timer::from_seconds(0.5, TimerMode::Once));
+            world.entity_mut(enemy).insert(ChargingTelegraph {
+                timer: Timer::from_seconds(0.5, TimerMode::Once),
+            });
+        }
+        world.entity_mut(enemy).insert(attack);
+        enemy
+    }
+
+    #[test]
+    fn hitting_a_charging_enemy_cancels_its_attack_for_bonus_damage() {
+        use crate::enemies::{AttackState, ChargingTelegraph};
+
+        // A ready enemy just takes the plain hit
+        let mut world = battle_world();
+        let ready = spawn_mettaur(&mut world, false);
+        hit_with(&mut world, ActionId::Cannon, (0, 1), 40, &[(4, 1)]);
+        let plain = 100 - hp(&world, ready);
+        assert!(plain > 0);
+        assert_eq!(
+            world.get::<EnemyAttack>(ready).unwrap().state,
+            AttackState::Ready
+        );
+
+        // Counter hit: the charge is cancelled and the damage multiplied
+        let mut world = battle_world();
+        let charging = spawn_mettaur(&mut world, true);
+        hit_with(&mut world, ActionId::Cannon, (0, 1), 40, &[(4, 1)]);
+        let countered_damage = (plain as f32 * COUNTER_HIT_MULTIPLIER).round() as i32;
+        assert_eq!(hp(&world, charging), 100 - countered_damage);
+        let countered = world.get::<EnemyAttack>(charging).unwrap();
+        assert_eq!(countered.state, AttackState::Ready);
+        assert!(countered.charge_timer.is_none());
+        assert!(world.get::<ChargingTelegraph>(charging).is_none());
+    }
+
+    #[test]
+    fn a_burning_panel_counter_hits_a_charging_enemy() {
+        let mut world = battle_world();
+        let charging = spawn_mettaur(&mut world, true);
+        world.spawn((TilePanel { x: 4, y: 1 }, PanelStatus::burning(1.0)));
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(process_burning_panels);
+        advance(&mut world, BURN_TICK_INTERVAL);
+        schedule.run(&mut world);
+
+        let countered_damage = (BURN_TICK_DAMAGE as f32 * COUNTER_HIT_MULTIPLIER).round() as i32;
+        assert_eq!(hp(&world, charging), 100 - countered_damage);
+        assert!(
+            world
+                .get::<EnemyAttack>(charging)
+                .unwrap()
+                .charge_timer
+                .is_none()
+        );
+    }
+}
@@ -27,6 +27,9 @@ pub struct ActionVisuals {
 
     /// Whether the effect has animation frames
     pub animated: bool,
+
+    /// Sound played when the action executes (see `sounds`)
+    pub sound: Option<&'static str>,
 }
 
 impl Default for ActionVisuals {
@@ -39,6 +42,7 @@ impl Default for ActionVisuals {
             effect_duration: 0.25,
             flash_color: None,
             animated: false,
+            sound: None,
         }
     }
 }
@@ -59,6 +63,7 @@ impl ActionVisuals {
             effect_color: slash_color,
             effect_size: Vec2::new(80.0, 200.0),
             effect_duration: 0.25,
+            sound: Some(sounds::SLASH),
             ..default()
         }
     }
@@ -82,6 +87,7 @@ impl ActionVisuals {
             effect_size: Vec2::ZERO, // No projectile
             effect_duration: 0.3,
             flash_color: Some(flash),
+            sound: Some(sounds::HEAL),
             ..default()
         }
     }
@@ -93,6 +99,7 @@ impl ActionVisuals {
             effect_color: shield_color,
             effect_size: Vec2::new(120.0, 160.0),
             effect_duration: 0.0, // Stays until shield expires
+            sound: Some(sounds::SHIELD),
             ..default()
         }
     }
@@ -105,11 +112,23 @@ impl ActionVisuals {
             effect_size: size,
             effect_duration: 0.4,
             flash_color: Some(Color::WHITE),
+            sound: Some(sounds::EXPLOSION),
             ..default()
         }
     }
 }
 
+// ============================================================================
+// Sound Effects (one per effect family)
+// ============================================================================
+
+pub mod sounds {
+    pub const SLASH: &str = "audio/sound/slash.mp3";
+    pub const EXPLOSION: &str = "audio/sound/explosion.mp3";
+    pub const HEAL: &str = "audio/sound/heal.mp3";
+    pub const SHIELD: &str = "audio/sound/shield.mp3";
+}
+
 // ============================================================================
 // Preset Colors (MMBN style palette)
 // ============================================================================
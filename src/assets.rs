@@ -59,4 +59,24 @@ impl ProjectileAnimation {
             is_charged,
         }
     }
+
+    /// Short impact animation played when a projectile hits something
+    pub fn impact(is_charged: bool) -> Self {
+        Self {
+            frame_indices: [0, 1, 2, 3],
+            state: ProjectileAnimationState::Impact,
+            timer: Timer::from_seconds(0.1, TimerMode::Once), // Short duration for impact
+            is_charged,
+        }
+    }
+
+    /// Finish frames played when a projectile runs out of range without a hit
+    pub fn fizzle(is_charged: bool) -> Self {
+        Self {
+            frame_indices: [0, 1, 2, 3],
+            state: ProjectileAnimationState::Finish,
+            timer: Timer::from_seconds(0.15, TimerMode::Once),
+            is_charged,
+        }
+    }
 }
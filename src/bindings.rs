@@ -0,0 +1,393 @@
+//! Rebindable player controls.
+//!
+//! `KeyBindings` maps each logical [`InputAction`] to keyboard keys and gamepad
+//! buttons. Battle input systems ask it whether an action is pressed instead of
+//! checking `KeyCode`s directly. Bindings are stored in settings.json by key
+//! name (`KeyCode`/`GamepadButton` debug names), limited to the keys listed in
+//! [`BINDABLE_KEYS`] and [`BINDABLE_BUTTONS`].
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Logical player controls that can be rebound
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum InputAction {
+    MoveUp,
+    MoveDown,
+    MoveLeft,
+    MoveRight,
+    Fire,
+    SwitchWeapon,
+    Action1,
+    Action2,
+    Action3,
+    Action4,
+    Dodge,
+    /// Confirms the custom gauge's chip select
+    Confirm,
+}
+
+impl InputAction {
+    /// All actions, in settings-screen order
+    pub const ALL: [InputAction; 12] = [
+        InputAction::MoveUp,
+        InputAction::MoveDown,
+        InputAction::MoveLeft,
+        InputAction::MoveRight,
+        InputAction::Fire,
+        InputAction::SwitchWeapon,
+        InputAction::Action1,
+        InputAction::Action2,
+        InputAction::Action3,
+        InputAction::Action4,
+        InputAction::Dodge,
+        InputAction::Confirm,
+    ];
+
+    /// Action slot triggers, indexed by slot
+    pub const SLOTS: [InputAction; 4] = [
+        InputAction::Action1,
+        InputAction::Action2,
+        InputAction::Action3,
+        InputAction::Action4,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            InputAction::MoveUp => "Move Up",
+            InputAction::MoveDown => "Move Down",
+            InputAction::MoveLeft => "Move Left",
+            InputAction::MoveRight => "Move Right",
+            InputAction::Fire => "Fire",
+            InputAction::SwitchWeapon => "Switch Weapon",
+            InputAction::Action1 => "Action 1",
+            InputAction::Action2 => "Action 2",
+            InputAction::Action3 => "Action 3",
+            InputAction::Action4 => "Action 4",
+            InputAction::Dodge => "Dodge",
+            InputAction::Confirm => "Confirm",
+        }
+    }
+}
+
+/// Keys and buttons bound to one action
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Binding {
+    pub keys: Vec<KeyCode>,
+    pub buttons: Vec<GamepadButton>,
+}
+
+impl Binding {
+    fn new(keys: &[KeyCode], buttons: &[GamepadButton]) -> Self {
+        Self {
+            keys: keys.to_vec(),
+            buttons: buttons.to_vec(),
+        }
+    }
+}
+
+/// Player control bindings
+#[derive(Resource, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "Vec<BindingEntry>", into = "Vec<BindingEntry>")]
+pub struct KeyBindings {
+    bindings: Vec<(InputAction, Binding)>,
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        use GamepadButton as Pad;
+        use InputAction as A;
+
+        Self {
+            bindings: vec![
+                (
+                    A::MoveUp,
+                    Binding::new(&[KeyCode::KeyW, KeyCode::ArrowUp], &[Pad::DPadUp]),
+                ),
+                (
+                    A::MoveDown,
+                    Binding::new(&[KeyCode::KeyS, KeyCode::ArrowDown], &[Pad::DPadDown]),
+                ),
+                (
+                    A::MoveLeft,
+                    Binding::new(&[KeyCode::KeyA, KeyCode::ArrowLeft], &[Pad::DPadLeft]),
+                ),
+                (
+                    A::MoveRight,
+                    Binding::new(&[KeyCode::KeyD, KeyCode::ArrowRight], &[Pad::DPadRight]),
+                ),
+                (
+                    A::Fire,
+                    Binding::new(&[KeyCode::Space], &[Pad::South, Pad::RightTrigger2]),
+                ),
+                (
+                    A::SwitchWeapon,
+                    Binding::new(&[KeyCode::Tab], &[Pad::RightTrigger]),
+                ),
+                (A::Action1, Binding::new(&[KeyCode::Digit1], &[Pad::West])),
+                (A::Action2, Binding::new(&[KeyCode::Digit2], &[Pad::North])),
+                (A::Action3, Binding::new(&[KeyCode::Digit3], &[Pad::East])),
+                (
+                    A::Action4,
+                    Binding::new(&[KeyCode::Digit4], &[Pad::LeftTrigger2]),
+                ),
+                (
+                    A::Dodge,
+                    Binding::new(&[KeyCode::ShiftLeft], &[Pad::LeftTrigger]),
+                ),
+                (A::Confirm, Binding::new(&[KeyCode::Enter], &[Pad::Select])),
+            ],
+        }
+    }
+}
+
+impl KeyBindings {
+    pub fn get(&self, action: InputAction) -> Option<&Binding> {
+        self.bindings
+            .iter()
+            .find(|(bound, _)| *bound == action)
+            .map(|(_, binding)| binding)
+    }
+
+    /// Replace the keyboard keys of an action with a single key (gamepad buttons are kept)
+    pub fn rebind_key(&mut self, action: InputAction, key: KeyCode) {
+        if let Some((_, binding)) = self.bindings.iter_mut().find(|(bound, _)| *bound == action) {
+            binding.keys = vec![key];
+        }
+    }
+
+    /// Short description of an action's keyboard keys (e.g. "KeyW / ArrowUp")
+    pub fn keys_label(&self, action: InputAction) -> String {
+        match self.get(action) {
+            Some(binding) if !binding.keys.is_empty() => binding
+                .keys
+                .iter()
+                .map(|key| format!("{key:?}"))
+                .collect::<Vec<_>>()
+                .join(" / "),
+            _ => "-".to_string(),
+        }
+    }
+
+    pub fn pressed(
+        &self,
+        action: InputAction,
+        keyboard: &ButtonInput<KeyCode>,
+        gamepads: &Query<&Gamepad>,
+    ) -> bool {
+        self.check(
+            action,
+            gamepads,
+            |key| keyboard.pressed(key),
+            |pad, b| pad.pressed(b),
+        )
+    }
+
+    pub fn just_pressed(
+        &self,
+        action: InputAction,
+        keyboard: &ButtonInput<KeyCode>,
+        gamepads: &Query<&Gamepad>,
+    ) -> bool {
+        self.check(
+            action,
+            gamepads,
+            |key| keyboard.just_pressed(key),
+            |pad, b| pad.just_pressed(b),
+        )
+    }
+
+    pub fn just_released(
+        &self,
+        action: InputAction,
+        keyboard: &ButtonInput<KeyCode>,
+        gamepads: &Query<&Gamepad>,
+    ) -> bool {
+        self.check(
+            action,
+            gamepads,
+            |key| keyboard.just_released(key),
+            |pad, b| pad.just_released(b),
+        )
+    }
+
+    fn check(
+        &self,
+        action: InputAction,
+        gamepads: &Query<&Gamepad>,
+        key_check: impl Fn(KeyCode) -> bool,
+        button_check: impl Fn(&Gamepad, GamepadButton) -> bool,
+    ) -> bool {
+        let Some(binding) = self.get(action) else {
+            return false;
+        };
+        binding.keys.iter().any(|key| key_check(*key))
+            || gamepads
+                .iter()
+                .any(|pad| binding.buttons.iter().any(|b| button_check(pad, *b)))
+    }
+}
+
+// ============================================================================
+// Serialization (by key name)
+// ============================================================================
+
+/// Keys the settings screen accepts when rebinding (and that can be loaded back)
+pub const BINDABLE_KEYS: &[KeyCode] = &[
+    KeyCode::KeyA,
+    KeyCode::KeyB,
+    KeyCode::KeyC,
+    KeyCode::KeyD,
+    KeyCode::KeyE,
+    KeyCode::KeyF,
+    KeyCode::KeyG,
+    KeyCode::KeyH,
+    KeyCode::KeyI,
+    KeyCode::KeyJ,
+    KeyCode::KeyK,
+    KeyCode::KeyL,
+    KeyCode::KeyM,
+    KeyCode::KeyN,
+    KeyCode::KeyO,
+    KeyCode::KeyP,
+    KeyCode::KeyQ,
+    KeyCode::KeyR,
+    KeyCode::KeyS,
+    KeyCode::KeyT,
+    KeyCode::KeyU,
+    KeyCode::KeyV,
+    KeyCode::KeyW,
+    KeyCode::KeyX,
+    KeyCode::KeyY,
+    KeyCode::KeyZ,
+    KeyCode::Digit0,
+    KeyCode::Digit1,
+    KeyCode::Digit2,
+    KeyCode::Digit3,
+    KeyCode::Digit4,
+    KeyCode::Digit5,
+    KeyCode::Digit6,
+    KeyCode::Digit7,
+    KeyCode::Digit8,
+    KeyCode::Digit9,
+    KeyCode::ArrowUp,
+    KeyCode::ArrowDown,
+    KeyCode::ArrowLeft,
+    KeyCode::ArrowRight,
+    KeyCode::Space,
+    KeyCode::Tab,
+    KeyCode::Enter,
+    KeyCode::ShiftLeft,
+    KeyCode::ShiftRight,
+    KeyCode::ControlLeft,
+    KeyCode::ControlRight,
+    KeyCode::AltLeft,
+    KeyCode::AltRight,
+    KeyCode::Comma,
+    KeyCode::Period,
+    KeyCode::Slash,
+    KeyCode::Semicolon,
+];
+
+/// Gamepad buttons that can be loaded from settings.json
+pub const BINDABLE_BUTTONS: &[GamepadButton] = &[
+    GamepadButton::South,
+    GamepadButton::East,
+    GamepadButton::North,
+    GamepadButton::West,
+    GamepadButton::LeftTrigger,
+    GamepadButton::LeftTrigger2,
+    GamepadButton::RightTrigger,
+    GamepadButton::RightTrigger2,
+    GamepadButton::DPadUp,
+    GamepadButton::DPadDown,
+    GamepadButton::DPadLeft,
+    GamepadButton::DPadRight,
+    GamepadButton::Select,
+];
+
+fn key_from_name(name: &str) -> Option<KeyCode> {
+    BINDABLE_KEYS
+        .iter()
+        .copied()
+        .find(|key| format!("{key:?}") == name)
+}
+
+fn button_from_name(name: &str) -> Option<GamepadButton> {
+    BINDABLE_BUTTONS
+        .iter()
+        .copied()
+        .find(|button| format!("{button:?}") == name)
+}
+
+/// One action's binding as stored on disk
+#[derive(Serialize, Deserialize)]
+struct BindingEntry {
+    action: InputAction,
+    keys: Vec<String>,
+    buttons: Vec<String>,
+}
+
+impl From<KeyBindings> for Vec<BindingEntry> {
+    fn from(bindings: KeyBindings) -> Self {
+        bindings
+            .bindings
+            .into_iter()
+            .map(|(action, binding)| BindingEntry {
+                action,
+                keys: binding.keys.iter().map(|key| format!("{key:?}")).collect(),
+                buttons: binding.buttons.iter().map(|b| format!("{b:?}")).collect(),
+            })
+            .collect()
+    }
+}
+
+impl From<Vec<BindingEntry>> for KeyBindings {
+    /// Start from the defaults and apply every stored entry that still parses,
+    /// so unknown names or newly added actions never leave a control unbound
+    fn from(entries: Vec<BindingEntry>) -> Self {
+        let mut bindings = KeyBindings::default();
+        for entry in entries {
+            let keys: Vec<KeyCode> = entry.keys.iter().filter_map(|k| key_from_name(k)).collect();
+            let buttons: Vec<GamepadButton> = entry
+                .buttons
+                .iter()
+                .filter_map(|b| button_from_name(b))
+                .collect();
+            if keys.is_empty() && buttons.is_empty() {
+                continue;
+            }
+            if let Some((_, binding)) = bindings
+                .bindings
+                .iter_mut()
+                .find(|(action, _)| *action == entry.action)
+            {
+                *binding = Binding { keys, buttons };
+            }
+        }
+        bindings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_bindings_never_share_an_input() {
+        let bindings = KeyBindings::default();
+        let mut keys = Vec::new();
+        let mut buttons = Vec::new();
+        for action in InputAction::ALL {
+            let binding = bindings.get(action).unwrap();
+            for key in &binding.keys {
+                assert!(!keys.contains(key), "{key:?} is bound twice");
+                keys.push(*key);
+            }
+            for button in &binding.buttons {
+                assert!(!buttons.contains(button), "{button:?} is bound twice");
+                buttons.push(*button);
+            }
+        }
+    }
+}
@@ -1,5 +1,7 @@
 use bevy::prelude::*;
 
+use crate::resources::{ColorScheme, GridSize};
+
 // ============================================================================
 // Game State
 // ============================================================================
@@ -13,6 +15,11 @@ pub enum GameState {
     Shop,
     Campaign,
     Playing,
+    Settings,
+    /// Discovered enemies and their behaviors (see `systems::encyclopedia`)
+    Encyclopedia,
+    /// Post-victory chip pick (see `systems::reward`)
+    Reward,
 }
 
 /// Marker component for entities that should be despawned when leaving a state
@@ -92,25 +99,28 @@ pub struct VictoryOutro {
     pub battle_time: f32,
     /// Reward earned
     pub reward: u64,
+    /// Extra zenny for a fast clear, paid out when the outro completes
+    pub time_bonus: u64,
     /// Whether player has pressed confirm to continue
     pub confirmed: bool,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum OutroPhase {
-    HitStop,     // 0.0 - 0.1s: Brief freeze on killing blow
+    HitStop,     // 0.0 - 0.1s: Killing blow in slow motion (see outro::HitStop)
     Clear,       // 0.1 - 0.5s: "CLEAR!" banner appears
     Stats,       // 0.5 - 1.5s: Stats panel slides in, numbers count up
     WaitConfirm, // 1.5s+: Wait for player to press confirm
 }
 
 impl VictoryOutro {
-    pub fn new(battle_time: f32, reward: u64) -> Self {
+    pub fn new(battle_time: f32, reward: u64, time_bonus: u64) -> Self {
         Self {
             elapsed: 0.0,
             phase: OutroPhase::HitStop,
             battle_time,
             reward,
+            time_bonus,
             confirmed: false,
         }
     }
@@ -120,6 +130,10 @@ impl VictoryOutro {
     }
 }
 
+/// Marker for the victory time bonus line
+#[derive(Component)]
+pub struct VictoryBonusText;
+
 /// Marker for the victory "CLEAR!" text
 #[derive(Component)]
 pub struct VictoryClearText;
@@ -140,6 +154,10 @@ pub struct VictoryRewardText;
 #[derive(Component)]
 pub struct VictoryContinueText;
 
+/// Marker for the battle stats breakdown, toggled under the victory stats panel
+#[derive(Component)]
+pub struct VictoryBreakdownText;
+
 // ============================================================================
 // Post-Battle Defeat Outro
 // ============================================================================
@@ -155,6 +173,27 @@ pub struct DefeatOutro {
     pub battle_time: f32,
     /// Whether player has pressed confirm to continue
     pub confirmed: bool,
+    /// Highlighted option on the confirm prompt
+    pub choice: DefeatChoice,
+}
+
+/// What confirming the defeat screen does
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DefeatChoice {
+    /// Replay the same battle from the start
+    #[default]
+    Retry,
+    /// Give up and go back to the campaign
+    Quit,
+}
+
+impl DefeatChoice {
+    pub fn toggle(self) -> Self {
+        match self {
+            DefeatChoice::Retry => DefeatChoice::Quit,
+            DefeatChoice::Quit => DefeatChoice::Retry,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -172,6 +211,7 @@ impl DefeatOutro {
             phase: DefeatPhase::HitStop,
             battle_time,
             confirmed: false,
+            choice: DefeatChoice::default(),
         }
     }
 
@@ -237,6 +277,12 @@ pub struct EnemyConfig {
     pub start_y: i32,
     /// Override HP (if None, uses blueprint's scaled HP)
     pub hp_override: Option<i32>,
+    /// Override attack (if None, uses blueprint's attack) - e.g. a boss that summons
+    pub attack_override: Option<AttackBehavior>,
+    /// Override movement (if None, uses blueprint's movement) - e.g. a stationary dummy
+    pub movement_override: Option<MovementBehavior>,
+    /// Spawn as the battle's boss (shown on the boss health bar)
+    pub boss: bool,
 }
 
 impl Default for EnemyConfig {
@@ -246,6 +292,9 @@ impl Default for EnemyConfig {
             start_x: 4,
             start_y: 1,
             hp_override: None,
+            attack_override: None,
+            movement_override: None,
+            boss: false,
         }
     }
 }
@@ -258,6 +307,9 @@ impl EnemyConfig {
             start_x: x,
             start_y: y,
             hp_override: None,
+            attack_override: None,
+            movement_override: None,
+            boss: false,
         }
     }
 
@@ -266,27 +318,125 @@ impl EnemyConfig {
         self.hp_override = Some(hp);
         self
     }
+
+    /// Create a config with a different attack than the blueprint's
+    pub fn with_attack(mut self, attack: AttackBehavior) -> Self {
+        self.attack_override = Some(attack);
+        self
+    }
+
+    /// Create a config with a different movement than the blueprint's
+    pub fn with_movement(mut self, movement: MovementBehavior) -> Self {
+        self.movement_override = Some(movement);
+        self
+    }
+
+    /// Create a config flagged as the battle's boss
+    pub fn with_boss(mut self) -> Self {
+        self.boss = true;
+        self
+    }
 }
 
 /// Types of enemies - re-export from enemies module for convenience
 pub use crate::enemies::EnemyId;
+use crate::enemies::{AttackBehavior, MovementBehavior};
+
+/// Configuration for placing an obstacle (rock) on the field
+#[derive(Clone, Copy, Debug)]
+pub struct ObstacleConfig {
+    pub x: i32,
+    pub y: i32,
+    pub hp: i32,
+}
+
+impl ObstacleConfig {
+    /// A standard rock with `OBSTACLE_ROCK_HP`
+    pub fn rock(x: i32, y: i32) -> Self {
+        Self {
+            x,
+            y,
+            hp: crate::constants::OBSTACLE_ROCK_HP,
+        }
+    }
+}
 
 /// Configuration for a complete arena battle
 #[derive(Resource, Clone, Debug)]
 pub struct ArenaConfig {
     pub fighter: FighterConfig,
-    pub enemies: Vec<EnemyConfig>,
+    /// Enemy waves in spawn order; each spawns once the previous one is cleared
+    pub waves: Vec<Vec<EnemyConfig>>,
+    pub obstacles: Vec<ObstacleConfig>,
+    /// Practice sandbox: the enemies respawn when deleted and the battle never ends
+    pub practice: bool,
+    /// First-launch tutorial: scripted prompts walk the player through the controls
+    pub tutorial: bool,
+    /// Grid dimensions and the starting player/enemy split
+    pub grid: GridSize,
 }
 
 impl Default for ArenaConfig {
     fn default() -> Self {
         Self {
             fighter: FighterConfig::default(),
-            enemies: vec![EnemyConfig::default()],
+            waves: vec![vec![EnemyConfig::default()]],
+            obstacles: Vec::new(),
+            practice: false,
+            tutorial: false,
+            grid: GridSize::default(),
         }
     }
 }
 
+impl ArenaConfig {
+    /// Practice arena: a single high-HP dummy that neither moves nor attacks
+    pub fn practice(actions: Vec<ActionId>) -> Self {
+        Self {
+            fighter: FighterConfig {
+                actions,
+                ..default()
+            },
+            waves: vec![vec![
+                EnemyConfig::new(EnemyId::Slime, 4, 1)
+                    .with_hp(crate::constants::PRACTICE_DUMMY_HP)
+                    .with_attack(AttackBehavior::None)
+                    .with_movement(MovementBehavior::Stationary),
+            ]],
+            obstacles: Vec::new(),
+            practice: true,
+            tutorial: false,
+            grid: GridSize::default(),
+        }
+    }
+
+    /// Tutorial arena: one harmless slime and a Cannon chip to try out
+    pub fn tutorial() -> Self {
+        Self {
+            fighter: FighterConfig {
+                actions: vec![ActionId::Cannon],
+                ..default()
+            },
+            waves: vec![vec![
+                EnemyConfig::new(EnemyId::Slime, 4, 1)
+                    .with_hp(crate::constants::TUTORIAL_SLIME_HP)
+                    .with_attack(AttackBehavior::None)
+                    .with_movement(MovementBehavior::Stationary),
+            ]],
+            obstacles: Vec::new(),
+            practice: false,
+            tutorial: true,
+            grid: GridSize::default(),
+        }
+    }
+
+    /// Clear time that earns half the time bonus: a base plus a share per enemy
+    pub fn par_time(&self) -> f32 {
+        let enemies = self.waves.iter().map(Vec::len).sum::<usize>();
+        crate::constants::PAR_TIME_BASE + crate::constants::PAR_TIME_PER_ENEMY * enemies as f32
+    }
+}
+
 // ============================================================================
 // Core Components
 // ============================================================================
@@ -303,12 +453,18 @@ pub struct Player;
 #[derive(Component)]
 pub struct Enemy;
 
+/// Enemy whose HP is shown on the large boss bar at the top of the screen
 #[derive(Component)]
+pub struct Boss;
+
+#[derive(Component)]
+#[require(PreviousTile)]
 pub struct Bullet;
 
 /// Marker for enemy bullets (travel left instead of right)
 /// Contains the damage value from the attack behavior
 #[derive(Component)]
+#[require(PreviousTile)]
 pub struct EnemyBullet {
     pub damage: i32,
 }
@@ -336,9 +492,85 @@ pub struct Health {
     pub max: i32,
 }
 
+impl Health {
+    /// Remaining HP as `0.0..=1.0` of max
+    pub fn fraction(&self) -> f32 {
+        if self.max <= 0 {
+            return 0.0;
+        }
+        (self.current as f32 / self.max as f32).clamp(0.0, 1.0)
+    }
+}
+
+/// Temporary HP on top of `Health` (OverHP chip): hits drain it first, and
+/// it fades away over its `decay` timer
+#[derive(Component, Debug)]
+pub struct OverHealth {
+    pub current: i32,
+    pub max: i32,
+    pub decay: Timer,
+}
+
+impl OverHealth {
+    pub fn new(amount: i32, duration: f32) -> Self {
+        Self {
+            current: amount,
+            max: amount,
+            decay: Timer::from_seconds(duration, TimerMode::Once),
+        }
+    }
+
+    /// Soak up as much of a hit as the pool holds; returns what gets through
+    pub fn absorb(&mut self, damage: i32) -> i32 {
+        let soaked = damage.clamp(0, self.current);
+        self.current -= soaked;
+        damage - soaked
+    }
+
+    /// Tick the fade: the pool shrinks evenly from `max` to nothing, never
+    /// refilling what hits already took. True once it is used up.
+    pub fn decay(&mut self, delta: std::time::Duration) -> bool {
+        self.decay.tick(delta);
+        let left = (self.max as f32 * self.decay.fraction_remaining()).ceil() as i32;
+        self.current = self.current.min(left);
+        self.current <= 0
+    }
+}
+
 #[derive(Component)]
 pub struct HealthText;
 
+/// Over-health segment after the player's HP text (a span on `PlayerHealthText`)
+#[derive(Component)]
+pub struct OverHealthText;
+
+/// Enemy that player attacks pass through (e.g. mid-teleport)
+#[derive(Component)]
+pub struct Untargetable;
+
+/// Fill layer of an enemy's HP bar (child of the enemy)
+#[derive(Component)]
+pub struct EnemyHpBarFill;
+
+/// A rock occupying a tile: blocks movement and projectiles until destroyed
+#[derive(Component, Debug)]
+pub struct Obstacle {
+    pub hp: i32,
+}
+
+impl Obstacle {
+    /// Apply a hit. Returns true if the obstacle is destroyed.
+    /// Obstacle-breaking attacks (e.g. shockwaves) destroy it outright.
+    pub fn take_hit(&mut self, damage: i32, destroys_obstacles: bool) -> bool {
+        if destroys_obstacles {
+            self.hp = 0;
+        } else {
+            self.hp -= damage.max(0);
+        }
+        self.hp <= 0
+    }
+}
+
 #[derive(Component, Clone, Copy)]
 pub struct RenderConfig {
     pub offset: Vec2,
@@ -348,15 +580,50 @@ pub struct RenderConfig {
 #[derive(Component)]
 pub struct MoveTimer(pub Timer);
 
+/// Holds a projectile on its spawn tile until the timer finishes
+#[derive(Component)]
+pub struct LaunchDelay(pub Timer);
+
+/// Tile a bullet last stepped off (None until it first moves), so shots that
+/// swap tiles in one frame still meet
+#[derive(Component, Default)]
+pub struct PreviousTile(pub Option<GridPosition>);
+
 #[derive(Component)]
 pub struct Lifetime(pub Timer);
 
+/// Leaves a fading trail of sprites behind a moving projectile
+#[derive(Component)]
+pub struct ProjectileTrail {
+    /// Trail color (matches the projectile's tint)
+    pub color: Color,
+    /// Tile the projectile was on when last checked
+    pub last: Option<GridPosition>,
+}
+
+impl ProjectileTrail {
+    pub fn new(color: Color) -> Self {
+        Self { color, last: None }
+    }
+}
+
+/// One trail sprite; fades out over its `Lifetime`
+#[derive(Component)]
+pub struct TrailSegment {
+    pub color: Color,
+}
+
 #[derive(Component)]
 pub struct BaseColor(pub Color);
 
 #[derive(Component)]
 pub struct FlashTimer(pub Timer);
 
+/// The player took an unshielded hit; cleared by `interrupt_charge_on_hit`
+/// once any charge in progress has been knocked out
+#[derive(Component)]
+pub struct Struck;
+
 #[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
 pub enum FighterAnimState {
     Idle,
@@ -385,6 +652,19 @@ pub struct SlimeAnim {
     pub timer: Timer,
 }
 
+/// Death sheet of a slime, swapped in by `animate_slime` once it is `Dying`
+#[derive(Component, Clone)]
+pub struct SlimeSprites {
+    pub dead: Handle<Image>,
+    pub dead_layout: Handle<TextureAtlasLayout>,
+    pub dead_frames: usize,
+    pub dead_fps: f32,
+}
+
+/// Deleted enemy playing its death animation; no longer an `Enemy`
+#[derive(Component)]
+pub struct Dying;
+
 /// Marker for the inner panel surface that can be highlighted
 #[derive(Component)]
 pub struct TilePanel {
@@ -417,6 +697,41 @@ impl TileHighlightState {
     }
 }
 
+/// Temporary state of a tile panel
+#[derive(Component, Debug, Clone, Default)]
+pub enum PanelStatus {
+    #[default]
+    Normal,
+    /// Damages whoever stands on it every tick until `remaining` runs out
+    Burning { remaining: Timer, tick: Timer },
+}
+
+impl PanelStatus {
+    pub fn burning(duration: f32) -> Self {
+        PanelStatus::Burning {
+            remaining: Timer::from_seconds(duration, TimerMode::Once),
+            tick: Timer::from_seconds(crate::constants::BURN_TICK_INTERVAL, TimerMode::Repeating),
+        }
+    }
+
+    pub fn is_burning(&self) -> bool {
+        matches!(self, PanelStatus::Burning { .. })
+    }
+
+    /// Color multiplied into the panel sprite
+    pub fn tint(&self, scheme: ColorScheme) -> Color {
+        match (self, scheme) {
+            (PanelStatus::Normal, _) => Color::WHITE,
+            (PanelStatus::Burning { .. }, ColorScheme::Default) => {
+                crate::constants::COLOR_PANEL_BURNING
+            }
+            (PanelStatus::Burning { .. }, ColorScheme::Colorblind) => {
+                crate::constants::COLOR_CB_PANEL_BURNING
+            }
+        }
+    }
+}
+
 /// Resource holding tile texture assets for normal and highlighted states
 #[derive(Resource)]
 pub struct TileAssets {
@@ -430,6 +745,26 @@ pub struct TileAssets {
 #[derive(Component)]
 pub struct PlayerHealthText;
 
+/// In-battle zenny tally text marker
+#[derive(Component)]
+pub struct BattleRewardText;
+
+/// Running battle clock in the HUD
+#[derive(Component)]
+pub struct BattleTimerText;
+
+/// "WAVE n" banner shown between enemy waves
+#[derive(Component)]
+pub struct WaveBanner;
+
+/// Kill combo text marker (pulses while a combo is running)
+#[derive(Component)]
+pub struct ComboText;
+
+/// "COUNTER" text rising off an enemy hit mid-charge (see `counter_hit`)
+#[derive(Component)]
+pub struct CounterPopup;
+
 /// Enemy AI timers
 #[derive(Component)]
 pub struct EnemyAI {
@@ -461,7 +796,7 @@ pub enum ActionType {
 #[allow(deprecated)]
 impl ActionType {
     /// Convert old ActionType to new ActionId
-    pub fn to_action_id(&self) -> ActionId {
+    pub fn to_action_id(self) -> ActionId {
         match self {
             ActionType::Heal => ActionId::Recov50,
             ActionType::Shield => ActionId::Shield,
@@ -554,8 +889,22 @@ pub struct ActionChargeBar {
     pub slot_index: usize,
 }
 
+/// Weapon charge bar above the player, visible only while charging
+#[derive(Component)]
+pub struct ChargeBar;
+
+/// Fill sprite of the weapon charge bar
+#[derive(Component)]
+pub struct ChargeBarFill;
+
 /// Marker for the key binding text on action slot
 #[derive(Component)]
 pub struct ActionKeyText {
     pub slot_index: usize,
 }
+
+/// Text above the action bar describing the focused slot (charging or last used)
+#[derive(Component, Default)]
+pub struct ActionTooltip {
+    pub slot_index: usize,
+}
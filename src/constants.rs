@@ -66,6 +66,10 @@ pub const BULLET_OFFSET: Vec2 = Vec2::new(110.0, 110.0);
 // We use 0.12s (approx 7.2 frames) for parity with standard attacks
 pub const BULLET_MOVE_TIMER: f32 = 0.12;
 pub const BULLET_DRAW_SIZE: Vec2 = Vec2::new(64.0, 64.0);
+// Enemy shots are drawn large and dim at the slow speed, small and bright at the fast one
+pub const ENEMY_SHOT_SLOW_SPEED: f32 = 4.0; // Tiles per second
+pub const ENEMY_SHOT_FAST_SPEED: f32 = 10.0; // Tiles per second; shots this fast are telegraphed
+pub const ENEMY_SHOT_TELEGRAPH_TIME: f32 = 0.3; // A fast shot flashes its spawn tile this long before moving
 
 // Muzzle flash
 pub const MUZZLE_OFFSET: Vec2 = Vec2::new(135.0, 110.0);
@@ -74,10 +78,19 @@ pub const MUZZLE_OFFSET: Vec2 = Vec2::new(135.0, 110.0);
 // Scale to be roughly 2 panels tall (like in MMBN)
 pub const FIGHTER_DRAW_SIZE: Vec2 = Vec2::new(340.0, 340.0);
 
+// Weapon charge bar (above the fighter while the fire button is held, unscaled)
+pub const CHARGE_BAR_SIZE: Vec2 = Vec2::new(70.0, 8.0);
+pub const CHARGE_BAR_OFFSET_Y: f32 = 190.0;
+pub const COLOR_CHARGE_BAR_BG: Color = Color::srgba(0.0, 0.0, 0.0, 0.6);
+
 // Slime enemy rendering (16x16 base sprites, scaled up)
 pub const SLIME_DRAW_SIZE: Vec2 = Vec2::new(128.0, 128.0);
 pub const SLIME_ANCHOR: Vec2 = Vec2::new(0.0, -0.40);
 pub const SLIME_OFFSET: Vec2 = Vec2::new(0.0, -8.0);
+
+// Obstacle (rock) rendering
+pub const OBSTACLE_DRAW_SIZE: Vec2 = Vec2::new(70.0, 70.0);
+pub const OBSTACLE_OFFSET: Vec2 = Vec2::new(0.0, 30.0);
 // Z layers
 pub const Z_BACKGROUND: f32 = -10.0;
 pub const Z_GRID_LINES: f32 = -5.0;
@@ -133,29 +146,103 @@ pub const COLOR_ENEMY_PANEL_SIDE: Color = Color::srgb(0.10, 0.20, 0.55);
 // ============================================================================
 pub const COLOR_PANEL_HIGHLIGHT: Color = Color::srgba(1.0, 1.0, 1.0, 0.35);
 pub const COLOR_PANEL_SHADOW: Color = Color::srgba(0.0, 0.0, 0.0, 0.4);
+// Tint over tiles a charging enemy is about to hit
+pub const COLOR_PANEL_TELEGRAPH: Color = Color::srgb(1.0, 0.35, 0.35);
+pub const COLOR_FALLOFF_FULL: Color = Color::srgb(0.5, 1.0, 0.5); // Falloff overlay: full damage
+pub const COLOR_FALLOFF_MIN: Color = Color::srgb(1.0, 0.6, 0.3); // Falloff overlay: weakest damage
+// Colorblind panel palette (blue/orange/magenta instead of red/green)
+pub const COLOR_CB_PANEL_TELEGRAPH: Color = Color::srgb(1.0, 0.3, 0.85);
+pub const COLOR_CB_FALLOFF_FULL: Color = Color::srgb(0.45, 0.75, 1.0);
+pub const COLOR_CB_FALLOFF_MIN: Color = Color::srgb(1.0, 0.65, 0.1);
+pub const COLOR_CB_PANEL_BURNING: Color = Color::srgb(0.95, 0.8, 0.35);
+// Telegraphed panels blink in the colorblind scheme so they never rely on hue alone
+pub const TELEGRAPH_BLINK_SPEED: f32 = 12.0;
 
 // Bullet trail highlight (yellow glow on tiles)
 pub const COLOR_BULLET_HIGHLIGHT: Color = Color::srgba(1.0, 0.9, 0.3, 0.5);
 
 // Characters
 pub const COLOR_ENEMY: Color = Color::srgb(0.82, 0.2, 0.86);
+pub const COLOR_OBSTACLE: Color = Color::srgb(0.5, 0.45, 0.4); // Rock gray-brown
 
 // Combat effects
 pub const COLOR_BULLET: Color = Color::srgb(1.0, 0.95, 0.2);
 pub const COLOR_MUZZLE: Color = Color::srgba(1.0, 0.7, 0.2, 0.9);
+pub const COLOR_SPARK: Color = Color::srgba(1.0, 1.0, 0.8, 0.95); // Shots cancelling each other
 
 // UI
 pub const COLOR_TEXT: Color = Color::WHITE;
 pub const COLOR_TEXT_SHADOW: Color = Color::srgba(0.0, 0.0, 0.0, 0.7);
 pub const COLOR_HP_PLATE: Color = Color::srgba(0.0, 0.0, 0.0, 0.5);
+pub const COLOR_HP_BAR_BG: Color = Color::srgba(0.1, 0.1, 0.1, 0.8);
+pub const COLOR_HP_BAR_HIGH: Color = Color::srgb(0.2, 0.85, 0.3);
+pub const COLOR_HP_BAR_MID: Color = Color::srgb(0.95, 0.85, 0.2);
+pub const COLOR_HP_BAR_LOW: Color = Color::srgb(0.9, 0.2, 0.2);
+pub const ENEMY_HP_BAR_SIZE: Vec2 = Vec2::new(56.0, 6.0);
+pub const COLOR_INTENT_IDLE: Color = Color::srgb(0.7, 0.7, 0.75);
+pub const COLOR_INTENT_MOVE: Color = Color::srgb(0.3, 0.8, 1.0);
+pub const COLOR_INTENT_ATTACK: Color = Color::srgb(1.0, 0.3, 0.25);
+pub const COLOR_MINIMAP_BG: Color = Color::srgba(0.0, 0.0, 0.0, 0.5);
+pub const COLOR_MINIMAP_PLAYER: Color = Color::srgb(0.3, 0.8, 1.0);
+pub const COLOR_MINIMAP_ENEMY: Color = Color::srgb(1.0, 0.35, 0.35);
+pub const COLOR_BOSS_BAR_FILL: Color = Color::srgb(0.85, 0.15, 0.3);
+pub const COLOR_BOSS_BAR_CHIP: Color = Color::srgb(1.0, 0.9, 0.6);
 
 // Gameplay
 pub const SHOOT_COOLDOWN: f32 = 0.35; // Player shoot cooldown
 pub const MOVE_COOLDOWN: f32 = 0.15;
+pub const DASH_WINDOW: f32 = 0.25; // Max gap between two taps of a direction to dash
+pub const DASH_COOLDOWN: f32 = 0.3; // Move cooldown after a two-tile dash
+pub const DODGE_IFRAMES: f32 = 0.3; // Invulnerability after a dodge
+pub const DODGE_COOLDOWN: f32 = 1.5; // Time between dodges
+pub const INPUT_BUFFER_TIME: f32 = 0.15; // A fire/chip press this close to cooldown end still fires
+pub const OBSTACLE_ROCK_HP: i32 = 100; // Rocks soak this much before breaking
+pub const WAVE_BANNER_TIME: f32 = 1.5; // Pause (with "WAVE n" banner) before the next wave
+pub const PRACTICE_DUMMY_HP: i32 = 5000; // HP of the practice mode dummy
+pub const TUTORIAL_SLIME_HP: i32 = 120; // HP of the tutorial slime
+pub const TUTORIAL_BEAT_GAP: f32 = 1.5; // Free play between tutorial prompts
+pub const PAR_TIME_BASE: f32 = 15.0; // Par clear time before counting enemies (seconds)
+pub const PAR_TIME_PER_ENEMY: f32 = 10.0; // Par time added per enemy across all waves
+pub const TIME_BONUS_MAX: u64 = 100; // Zenny for clearing in half the par time or less
+pub const ENEMY_AGGRESSION_RAMP: f32 = 0.25; // Share of an enemy's attack cooldown shaved per minute of battle
+pub const ENEMY_MIN_COOLDOWN_FRACTION: f32 = 0.5; // Aggression never cuts a cooldown below this share
 
 // Visual feedback timing (used by both player and enemies)
 pub const FLASH_TIME: f32 = 0.08; // Hit flash duration
+pub const INVIS_ALPHA: f32 = 0.35; // Player sprite alpha while invisible
 pub const MUZZLE_TIME: f32 = 0.06; // Muzzle flash duration
+pub const SPARK_TIME: f32 = 0.1; // Spark where two shots cancel out
+pub const TRAIL_TIME: f32 = 0.2; // Fade-out time of a projectile trail segment
+pub const TRAIL_SEGMENT_SIZE: Vec2 = Vec2::new(28.0, 6.0);
+pub const AREA_FLASH_TIME: f32 = 0.25; // Tile flash where an enemy area attack lands
+pub const TELEPORT_FX_TIME: f32 = 0.3; // Fade out at the old tile + fade in at the new one
+pub const DEATH_FX_TIME: f32 = 0.45; // Explosion / dissolve / shatter left by a deleted enemy
+pub const DEATH_SHARD_COUNT: usize = 6; // Shards a shattered enemy breaks into
+pub const DEATH_SHARD_SPEED: f32 = 260.0; // World units per second (at layout scale 1)
+pub const DEATH_SHARD_SIZE: f32 = 12.0;
+pub const DEATH_BURST_SIZE: f32 = 48.0; // Explosion before it swells
+pub const DEATH_BURST_SWELL: f32 = 2.0; // Extra scale an explosion gains over its lifetime
+pub const DEATH_DISSOLVE_SIZE: Vec2 = Vec2::new(64.0, 80.0);
+pub const DEATH_DISSOLVE_RISE: f32 = 60.0; // World units per second (at layout scale 1)
+pub const GUARD_TELEGRAPH_TIME: f32 = 0.5; // Guard plate blinks this long before an enemy guards
+pub const GUARD_PLATE_SIZE: Vec2 = Vec2::new(0.34, 0.16); // Share of a tile's width / visible height
+pub const GUARD_PLATE_RISE: f32 = 0.32; // Height above the enemy, as a share of a tile's visible height
+pub const WALL_SLAM_DAMAGE: i32 = 20; // Per tile a knocked-back enemy couldn't be pushed
+pub const GUARDED_DAMAGE_SCALE: f32 = 0.1; // Share of a non-guard-breaking hit a guarding enemy takes
+pub const COUNTER_HIT_MULTIPLIER: f32 = 1.5; // Damage bonus for hitting an enemy while it charges
+pub const COUNTER_POPUP_TIME: f32 = 0.6; // "COUNTER" text rises and fades over this long
+pub const COLOR_COUNTER: Color = Color::srgb(1.0, 0.85, 0.2);
+pub const REGEN_PULSE_TIME: f32 = 0.4; // Green glow fading off an enemy after a regen tick
+pub const REGEN_PULSE_TINT: f32 = 0.45; // Peak share of green mixed into the enemy's sprite
+pub const COLOR_REGEN_PULSE: Color = Color::srgb(0.3, 1.0, 0.4);
+pub const COLOR_OVER_HEALTH: Color = Color::srgb(0.45, 0.85, 1.0); // Over-health segment of the HP display
+pub const PANIC_HEAL_THRESHOLD: f32 = 0.25; // HP fraction below which the auto-heal assist fires
+pub const COLOR_GUARD_PLATE: Color = Color::srgba(0.75, 0.8, 0.95, 0.75);
+pub const COLOR_AREA_FLASH: Color = Color::srgba(1.0, 0.45, 0.2, 0.6);
+pub const COLOR_DEATH_BURST: Color = Color::srgba(1.0, 0.7, 0.25, 0.85);
+pub const COLOR_SHOT_TELEGRAPH: Color = Color::srgba(1.0, 0.95, 0.5, 0.55);
+pub const COLOR_DAMAGE_FLASH: Color = Color::srgb(1.0, 0.3, 0.3); // Hit flash and charge warning red
+pub const REDUCED_FLASH_TINT: f32 = 0.5; // Share of the warning red held steady when flashing is reduced
 
 // ============================================================================
 // Action System
@@ -168,6 +255,21 @@ pub const CHARGED_SHOT_DAMAGE: i32 = 25; // Damage dealt
 pub const CHARGED_SHOT_SIZE: Vec2 = Vec2::new(40.0, 40.0);
 pub const COLOR_CHARGED_SHOT: Color = Color::srgb(1.0, 0.5, 0.1); // Orange
 
+// Mine chip
+pub const MINE_LIFETIME: f32 = 10.0; // An untriggered mine fizzles out after this long
+pub const MINE_SIZE: Vec2 = Vec2::new(28.0, 14.0);
+
+// Tower chips (FireTowr etc.)
+pub const TOWER_STEP_TIME: f32 = 0.2; // Time the tower spends on each row
+
+// Pattern swords (LongSwrd, FtrSwrd etc.)
+pub const SWORD_SWEEP_TIME: f32 = 0.15; // Time the slash takes to cross its pattern
+
+// Burning panels (left by fire chips with `burns_panels`)
+pub const BURN_TICK_INTERVAL: f32 = 0.5; // Time between burn damage ticks
+pub const BURN_TICK_DAMAGE: i32 = 10; // Damage per tick to the enemy on the panel
+pub const COLOR_PANEL_BURNING: Color = Color::srgb(1.0, 0.55, 0.3); // Tint over burning panels
+
 // Heal action
 pub const HEAL_COOLDOWN: f32 = 8.0; // Longer cooldown for heal
 pub const HEAL_CHARGE_TIME: f32 = 0.0; // Instant cast
@@ -4,6 +4,11 @@
 
 use bevy::prelude::*;
 
+use super::AttackState;
+use crate::components::EnemyConfig;
+use crate::constants::{COLOR_INTENT_ATTACK, COLOR_INTENT_IDLE, COLOR_INTENT_MOVE};
+use crate::resources::GridSize;
+
 // ============================================================================
 // Movement Behaviors
 // ============================================================================
@@ -57,6 +62,12 @@ pub enum MovementBehavior {
         /// Columns to advance before retreating
         max_advance: i32,
     },
+
+    /// Random movement until hurt, then retreats to the back column
+    Flee {
+        /// HP fraction (0.0-1.0) below which the enemy retreats
+        hp_threshold: f32,
+    },
 }
 
 impl Default for MovementBehavior {
@@ -80,6 +91,7 @@ impl MovementBehavior {
             MovementBehavior::BackRowOnly => 1.5,
             MovementBehavior::MirrorPlayer => 0.3,
             MovementBehavior::Advance { .. } => 2.0,
+            MovementBehavior::Flee { .. } => 1.0,
         }
     }
 }
@@ -135,7 +147,8 @@ pub enum AttackBehavior {
     AreaAttack {
         damage: i32,
         charge_time: f32,
-        /// Tiles to hit relative to enemy position
+        /// Tiles to hit relative to enemy position, authored facing left
+        /// (mirrored when the player stands to the enemy's right)
         pattern: Vec<(i32, i32)>,
     },
 
@@ -156,12 +169,12 @@ pub enum AttackBehavior {
         duration: f32,
     },
 
-    /// Summons other enemies
+    /// Summons other enemies onto a free tile of the enemy area
     Summon {
-        /// Enemy type to summon (by ID string for now)
-        summon_id: String,
+        /// Minion to spawn (its start position is ignored)
+        minion: Box<EnemyConfig>,
         /// Max summons alive at once
-        max_summons: i32,
+        max_summons: usize,
         charge_time: f32,
     },
 }
@@ -226,6 +239,136 @@ impl AttackBehavior {
             AttackBehavior::Summon { .. } => 0,
         }
     }
+
+    /// Copy of this attack with its damage passed through `scale`
+    /// (summoned minions are scaled when they spawn)
+    pub fn with_damage(&self, scale: impl Fn(i32) -> i32) -> Self {
+        let mut scaled = self.clone();
+        match &mut scaled {
+            AttackBehavior::Projectile { damage, .. }
+            | AttackBehavior::ProjectileSpread { damage, .. }
+            | AttackBehavior::ShockWave { damage, .. }
+            | AttackBehavior::Melee { damage, .. }
+            | AttackBehavior::AreaAttack { damage, .. }
+            | AttackBehavior::Bomb { damage, .. }
+            | AttackBehavior::LaserBeam { damage, .. } => *damage = scale(*damage),
+            AttackBehavior::None | AttackBehavior::Summon { .. } => {}
+        }
+        scaled
+    }
+}
+
+/// Tiles an attack will strike, for the warning shown while the enemy charges.
+///
+/// Shots and beams cover their row from the enemy to the left edge, melee the
+/// tiles in reach, area attacks their pattern, and bombs the player's tile (plus
+/// `radius - 1` around it). Tiles outside the grid are dropped.
+pub fn telegraph_tiles(
+    behavior: &AttackBehavior,
+    enemy_pos: (i32, i32),
+    player_pos: Option<(i32, i32)>,
+    grid: GridSize,
+) -> Vec<(i32, i32)> {
+    let (ex, ey) = enemy_pos;
+    let row_ahead = |y: i32| (0..ex).map(move |x| (x, y));
+
+    let tiles: Vec<(i32, i32)> = match behavior {
+        AttackBehavior::None | AttackBehavior::Summon { .. } => Vec::new(),
+        AttackBehavior::Projectile { .. }
+        | AttackBehavior::ShockWave { .. }
+        | AttackBehavior::LaserBeam { .. } => row_ahead(ey).collect(),
+        AttackBehavior::ProjectileSpread { row_offsets, .. } => row_offsets
+            .iter()
+            .flat_map(|offset| row_ahead(ey + offset))
+            .collect(),
+        AttackBehavior::Melee { range, .. } => (1..=*range).map(|dx| (ex - dx, ey)).collect(),
+        AttackBehavior::AreaAttack { pattern, .. } => {
+            area_attack_tiles(pattern, enemy_pos, player_pos, grid)
+        }
+        AttackBehavior::Bomb { radius, .. } => {
+            let Some((px, py)) = player_pos else {
+                return Vec::new();
+            };
+            let reach = (*radius - 1).max(0);
+            (-reach..=reach)
+                .flat_map(|dx| (-reach..=reach).map(move |dy| (px + dx, py + dy)))
+                .collect()
+        }
+    };
+
+    tiles
+        .into_iter()
+        .filter(|&(x, y)| grid.contains(x, y))
+        .collect()
+}
+
+// ============================================================================
+// Enemy Intent
+// ============================================================================
+
+/// What an enemy is about to do, shown as an icon above it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EnemyIntent {
+    Idle,
+    Move,
+    Attack,
+}
+
+impl EnemyIntent {
+    pub fn glyph(self) -> &'static str {
+        match self {
+            EnemyIntent::Idle => "...",
+            EnemyIntent::Move => "<>",
+            EnemyIntent::Attack => "!",
+        }
+    }
+
+    pub fn color(self) -> Color {
+        match self {
+            EnemyIntent::Idle => COLOR_INTENT_IDLE,
+            EnemyIntent::Move => COLOR_INTENT_MOVE,
+            EnemyIntent::Attack => COLOR_INTENT_ATTACK,
+        }
+    }
+}
+
+/// Intent for an enemy's attack phase: charging or firing reads as an attack,
+/// otherwise the enemy is about to move unless it never does.
+pub fn enemy_intent(
+    state: &AttackState,
+    behavior: &AttackBehavior,
+    movement: &MovementBehavior,
+) -> EnemyIntent {
+    let attacking = !matches!(behavior, AttackBehavior::None)
+        && matches!(state, AttackState::Charging | AttackState::Attacking);
+    if attacking {
+        EnemyIntent::Attack
+    } else if matches!(movement, MovementBehavior::Stationary) {
+        EnemyIntent::Idle
+    } else {
+        EnemyIntent::Move
+    }
+}
+
+/// Grid tiles covered by an area attack pattern. Offsets face left; they are
+/// flipped horizontally when the player is to the enemy's right.
+pub fn area_attack_tiles(
+    pattern: &[(i32, i32)],
+    enemy_pos: (i32, i32),
+    player_pos: Option<(i32, i32)>,
+    grid: GridSize,
+) -> Vec<(i32, i32)> {
+    let (ex, ey) = enemy_pos;
+    let facing = match player_pos {
+        Some((px, _)) if px > ex => -1,
+        _ => 1,
+    };
+
+    pattern
+        .iter()
+        .map(|(dx, dy)| (ex + dx * facing, ey + dy))
+        .filter(|&(x, y)| grid.contains(x, y))
+        .collect()
 }
 
 // ============================================================================
@@ -258,6 +401,21 @@ pub struct EnemyTraits {
 
     /// Becomes invulnerable periodically
     pub phase_immunity: Option<PhaseImmunity>,
+
+    /// Raises a guard periodically that only guard-breaking attacks get through
+    pub guard: Option<GuardCycle>,
+}
+
+impl EnemyTraits {
+    /// Apply flat armor to incoming damage.
+    /// Armored hits always deal at least 1; guard-breaking attacks ignore armor entirely.
+    pub fn reduce_damage(&self, damage: i32, guard_break: bool) -> i32 {
+        if guard_break || self.armor <= 0 {
+            damage
+        } else {
+            (damage - self.armor).max(1)
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -289,3 +447,108 @@ pub struct PhaseImmunity {
     /// Duration of vulnerable phase
     pub vulnerable_duration: f32,
 }
+
+#[derive(Debug, Clone)]
+pub struct GuardCycle {
+    /// Duration of the guarding phase
+    pub guard_duration: f32,
+    /// Duration of the open phase between guards
+    pub open_duration: f32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn armored(armor: i32) -> EnemyTraits {
+        EnemyTraits { armor, ..default() }
+    }
+
+    #[test]
+    fn armor_reduces_damage() {
+        assert_eq!(armored(5).reduce_damage(20, false), 15);
+        assert_eq!(armored(0).reduce_damage(20, false), 20);
+    }
+
+    #[test]
+    fn armored_hit_deals_at_least_one() {
+        assert_eq!(armored(10).reduce_damage(4, false), 1);
+        assert_eq!(armored(10).reduce_damage(10, false), 1);
+    }
+
+    #[test]
+    fn guard_break_ignores_armor() {
+        assert_eq!(armored(10).reduce_damage(4, true), 4);
+        assert_eq!(armored(5).reduce_damage(20, true), 20);
+    }
+
+    #[test]
+    fn projectile_telegraphs_its_row_ahead_of_the_enemy() {
+        let shot = AttackBehavior::Projectile {
+            damage: 10,
+            speed: 4.0,
+            charge_time: 0.5,
+            projectile_asset: String::new(),
+        };
+        let tiles = telegraph_tiles(&shot, (4, 1), Some((1, 2)), GridSize::default());
+        assert_eq!(tiles, vec![(0, 1), (1, 1), (2, 1), (3, 1)]);
+    }
+
+    #[test]
+    fn bomb_telegraphs_the_players_tile() {
+        let bomb = |radius| AttackBehavior::Bomb {
+            damage: 30,
+            fuse_time: 1.0,
+            radius,
+        };
+        assert_eq!(
+            telegraph_tiles(&bomb(1), (4, 1), Some((1, 2)), GridSize::default()),
+            vec![(1, 2)]
+        );
+        assert!(telegraph_tiles(&bomb(1), (4, 1), None, GridSize::default()).is_empty());
+
+        // A wider blast is clipped to the grid
+        let corner = telegraph_tiles(&bomb(2), (4, 1), Some((0, 0)), GridSize::default());
+        assert_eq!(corner, vec![(0, 0), (0, 1), (1, 0), (1, 1)]);
+    }
+
+    #[test]
+    fn intent_follows_attack_state_and_behavior() {
+        let shot = AttackBehavior::default();
+        let roaming = MovementBehavior::Random { idle_chance: 0.3 };
+        let cases = [
+            (AttackState::Ready, &shot, EnemyIntent::Move),
+            (AttackState::Charging, &shot, EnemyIntent::Attack),
+            (AttackState::Attacking, &shot, EnemyIntent::Attack),
+            (AttackState::Recovering, &shot, EnemyIntent::Move),
+            (AttackState::Ready, &AttackBehavior::None, EnemyIntent::Move),
+            (
+                AttackState::Charging,
+                &AttackBehavior::None,
+                EnemyIntent::Move,
+            ),
+        ];
+        for (state, behavior, expected) in cases {
+            assert_eq!(
+                enemy_intent(&state, behavior, &roaming),
+                expected,
+                "{state:?} with {behavior:?}"
+            );
+        }
+
+        // Turrets idle between attacks instead of moving
+        let still = MovementBehavior::Stationary;
+        assert_eq!(
+            enemy_intent(&AttackState::Ready, &shot, &still),
+            EnemyIntent::Idle
+        );
+        assert_eq!(
+            enemy_intent(&AttackState::Charging, &shot, &still),
+            EnemyIntent::Attack
+        );
+        assert_eq!(
+            enemy_intent(&AttackState::Charging, &AttackBehavior::None, &still),
+            EnemyIntent::Idle
+        );
+    }
+}
@@ -6,9 +6,10 @@
 // It combines stats, behaviors, traits, and visuals into one package.
 
 use super::{
-    AttackBehavior, EnemyAnimations, EnemyId, EnemyStats, EnemyTraits, EnemyVisuals,
-    MovementBehavior,
+    AttackBehavior, DeathEffect, EnemyAnimations, EnemyId, EnemyStats, EnemyTraits, EnemyVisuals,
+    GuardCycle, MovementBehavior,
 };
+use crate::actions::Element;
 use bevy::prelude::*;
 
 /// Complete blueprint for an enemy type
@@ -18,6 +19,10 @@ pub struct EnemyBlueprint {
     pub id: EnemyId,
     /// Display name
     pub name: &'static str,
+    /// Elemental affinity (weak to `element.weak_to()`)
+    pub element: Element,
+    /// Zenny added to `BattleRewards` when this enemy is deleted
+    pub zenny_reward: u64,
     /// Base stats
     pub stats: EnemyStats,
     /// Movement behavior
@@ -37,6 +42,9 @@ impl EnemyBlueprint {
             EnemyId::Slime => slime_blueprint(),
             EnemyId::Slime2 => slime2_blueprint(),
             EnemyId::Slime3 => slime3_blueprint(),
+            EnemyId::Mettaur => mettaur_blueprint(),
+            EnemyId::Canodumb => canodumb_blueprint(),
+            EnemyId::Spreader => spreader_blueprint(),
         }
     }
 
@@ -56,6 +64,8 @@ fn slime_blueprint() -> EnemyBlueprint {
     EnemyBlueprint {
         id: EnemyId::Slime,
         name: "Slime",
+        element: Element::Fire,
+        zenny_reward: 30,
         stats: EnemyStats {
             base_hp: 40,
             contact_damage: 10,
@@ -76,6 +86,7 @@ fn slime_blueprint() -> EnemyBlueprint {
             anchor: Vec2::new(0.0, -0.40),
             offset: Vec2::new(0.0, -8.0),
             flip_x: true,
+            tint: Color::WHITE,
             animations: EnemyAnimations {
                 idle_grid: (3, 3),
                 attack_grid: Some((3, 4)),
@@ -97,6 +108,7 @@ fn slime_blueprint() -> EnemyBlueprint {
                 hurt_file: None,
                 dead_file: Some("DEAD.png".into()),
             },
+            death_effect: DeathEffect::Dissolve,
         },
     }
 }
@@ -106,6 +118,8 @@ fn slime2_blueprint() -> EnemyBlueprint {
     EnemyBlueprint {
         id: EnemyId::Slime2,
         name: "Slime II",
+        element: Element::Aqua,
+        zenny_reward: 50,
         stats: EnemyStats {
             base_hp: 60,
             contact_damage: 10,
@@ -126,6 +140,7 @@ fn slime2_blueprint() -> EnemyBlueprint {
             anchor: Vec2::new(0.0, -0.40),
             offset: Vec2::new(0.0, -8.0),
             flip_x: true,
+            tint: Color::WHITE,
             animations: EnemyAnimations {
                 idle_grid: (3, 3),
                 attack_grid: Some((3, 4)),
@@ -147,6 +162,7 @@ fn slime2_blueprint() -> EnemyBlueprint {
                 hurt_file: None,
                 dead_file: Some("DEAD.png".into()),
             },
+            death_effect: DeathEffect::Dissolve,
         },
     }
 }
@@ -156,6 +172,8 @@ fn slime3_blueprint() -> EnemyBlueprint {
     EnemyBlueprint {
         id: EnemyId::Slime3,
         name: "King Slime",
+        element: Element::None,
+        zenny_reward: 80,
         stats: EnemyStats {
             base_hp: 100,
             contact_damage: 10,
@@ -176,6 +194,7 @@ fn slime3_blueprint() -> EnemyBlueprint {
             anchor: Vec2::new(0.0, -0.40),
             offset: Vec2::new(0.0, -8.0),
             flip_x: true,
+            tint: Color::WHITE,
             animations: EnemyAnimations {
                 idle_grid: (3, 3),
                 attack_grid: Some((3, 4)),
@@ -197,78 +216,133 @@ fn slime3_blueprint() -> EnemyBlueprint {
                 hurt_file: None,
                 dead_file: Some("DEAD.png".into()),
             },
+            death_effect: DeathEffect::Dissolve,
         },
     }
 }
 
-// ============================================================================
-// Example blueprints for future enemies (commented out)
-// ============================================================================
-
-/*
-/// Mettaur - Hides under helmet, peeks out to attack with shockwave
+/// Mettaur - Stationary, sends a shockwave down its row and guards periodically
 fn mettaur_blueprint() -> EnemyBlueprint {
     EnemyBlueprint {
         id: EnemyId::Mettaur,
         name: "Mettaur",
+        element: Element::None,
+        zenny_reward: 40,
         stats: EnemyStats {
             base_hp: 40,
             contact_damage: 10,
-            move_speed: 0.8,
-            attack_speed: 1.0,
-        },
-        movement: MovementBehavior::HideAndPeek {
-            hide_duration: 2.0,
-            peek_duration: 1.5,
+            move_speed: 0.0,
+            attack_speed: 0.8,
         },
+        movement: MovementBehavior::Stationary,
         attack: AttackBehavior::ShockWave {
             damage: 20,
             speed: 6.0,
-            charge_time: 0.3,
+            charge_time: 0.6,
         },
+        // Ducks under its helmet: only guard-breaking chips get through
         traits: EnemyTraits {
-            // Invulnerable while hidden (handled by HideAndPeek behavior)
+            guard: Some(GuardCycle {
+                guard_duration: 2.0,
+                open_duration: 3.0,
+            }),
             ..default()
         },
         visuals: EnemyVisuals {
             sprite_path: "enemies/mettaur".into(),
-            draw_size: Vec2::new(96.0, 96.0),
-            anchor: Vec2::new(0.0, -0.35),
-            offset: Vec2::ZERO,
-            flip_x: true,
-            animations: EnemyAnimations::default(),
+            tint: Color::WHITE,
+            animations: EnemyAnimations {
+                idle_file: "IDLE.png".into(),
+                ..default()
+            },
+            death_effect: DeathEffect::Shatter,
+            ..default()
         },
     }
 }
 
-/// Canodumb - Stationary turret that shoots when player is in its row
+/// Canodumb - Holds the back row and fires fast shots down its lane
 fn canodumb_blueprint() -> EnemyBlueprint {
     EnemyBlueprint {
         id: EnemyId::Canodumb,
         name: "Canodumb",
+        element: Element::None,
+        zenny_reward: 40,
         stats: EnemyStats {
             base_hp: 60,
             contact_damage: 0,
-            move_speed: 0.0,
-            attack_speed: 1.2,
+            move_speed: 0.5,
+            attack_speed: 0.8,
         },
-        movement: MovementBehavior::Stationary,
+        movement: MovementBehavior::BackRowOnly,
         attack: AttackBehavior::Projectile {
             damage: 15,
             speed: 10.0,
-            charge_time: 0.2,
+            charge_time: 0.4,
             projectile_asset: "projectile/blaster".to_string(),
         },
         traits: EnemyTraits::default(),
-        visuals: EnemyVisuals::default(),
+        visuals: EnemyVisuals {
+            sprite_path: "enemies/canodumb".into(),
+            tint: Color::WHITE,
+            animations: EnemyAnimations {
+                idle_file: "IDLE.png".into(),
+                ..default()
+            },
+            death_effect: DeathEffect::Explosion,
+            ..default()
+        },
+    }
+}
+
+/// Spreader - Wanders its area and fires a 3-way shot across its row and both neighbors
+fn spreader_blueprint() -> EnemyBlueprint {
+    EnemyBlueprint {
+        id: EnemyId::Spreader,
+        name: "Spreader",
+        element: Element::Wood,
+        zenny_reward: 50,
+        stats: EnemyStats {
+            base_hp: 70,
+            contact_damage: 10,
+            move_speed: 0.8,
+            attack_speed: 0.6,
+        },
+        movement: MovementBehavior::Random { idle_chance: 0.5 },
+        // Rows off the grid are skipped, so edge rows fire only two shots
+        attack: AttackBehavior::ProjectileSpread {
+            damage: 15,
+            speed: 4.0,
+            charge_time: 0.7,
+            count: 3,
+            row_offsets: vec![-1, 0, 1],
+        },
+        traits: EnemyTraits::default(),
+        visuals: EnemyVisuals {
+            sprite_path: "enemies/slime".into(),
+            tint: Color::srgb(0.5, 1.0, 0.5),
+            animations: EnemyAnimations {
+                idle_file: "IDLE.png".into(),
+                ..default()
+            },
+            death_effect: DeathEffect::Dissolve,
+            ..default()
+        },
     }
 }
 
+// ============================================================================
+// Example blueprints for future enemies (commented out)
+// ============================================================================
+
+/*
 /// Swordy - Teleports next to player and slashes
 fn swordy_blueprint() -> EnemyBlueprint {
     EnemyBlueprint {
         id: EnemyId::Swordy,
         name: "Swordy",
+        element: Element::None,
+        zenny_reward: 40,
         stats: EnemyStats {
             base_hp: 80,
             contact_damage: 15,
@@ -294,6 +368,8 @@ fn bunny_blueprint() -> EnemyBlueprint {
     EnemyBlueprint {
         id: EnemyId::Bunny,
         name: "Bunny",
+        element: Element::None,
+        zenny_reward: 40,
         stats: EnemyStats {
             base_hp: 30,
             contact_damage: 5,
@@ -313,3 +389,21 @@ fn bunny_blueprint() -> EnemyBlueprint {
     }
 }
 */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_enemy_sheet_is_on_disk() {
+        let assets = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("assets");
+        for id in EnemyId::ALL {
+            let visuals = EnemyBlueprint::get(id).visuals;
+            let anims = &visuals.animations;
+            for file in std::iter::once(&anims.idle_file).chain(anims.dead_file.as_ref()) {
+                let path = assets.join(&visuals.sprite_path).join(file);
+                assert!(path.is_file(), "{id:?} is missing {}", path.display());
+            }
+        }
+    }
+}
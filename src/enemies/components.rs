@@ -2,22 +2,45 @@
 // Enemy Components - ECS components for the enemy system
 // ============================================================================
 
-use super::{AttackBehavior, EnemyTraits, MovementBehavior};
+use super::{AttackBehavior, EnemyTraits, GuardCycle, MovementBehavior};
+use crate::constants::{
+    ENEMY_AGGRESSION_RAMP, ENEMY_MIN_COOLDOWN_FRACTION, GUARD_TELEGRAPH_TIME, GUARDED_DAMAGE_SCALE,
+    REGEN_PULSE_TIME, REGEN_PULSE_TINT,
+};
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
 
 /// Unique identifier for enemy types (used for blueprints and save data)
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
 pub enum EnemyId {
     #[default]
     Slime,
     Slime2,
     Slime3,
+    Mettaur,
+    Canodumb,
+    Spreader,
     // Future enemies:
-    // Mettaur,
-    // Canodumb,
     // Swordy,
 }
 
+impl EnemyId {
+    /// Every enemy type, in encyclopedia order
+    pub const ALL: [EnemyId; 6] = [
+        EnemyId::Slime,
+        EnemyId::Slime2,
+        EnemyId::Slime3,
+        EnemyId::Mettaur,
+        EnemyId::Canodumb,
+        EnemyId::Spreader,
+    ];
+}
+
+/// Blueprint an enemy was spawned from
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EnemyKind(pub EnemyId);
+
 /// Core stats for an enemy - attached as a component
 #[derive(Component, Debug, Clone)]
 pub struct EnemyStats {
@@ -56,12 +79,19 @@ impl EnemyMovement {
         let base_cooldown = behavior.base_cooldown();
         Self {
             behavior,
-            move_timer: Timer::from_seconds(base_cooldown / speed_multiplier, TimerMode::Repeating),
+            move_timer: cooldown_timer(base_cooldown / speed_multiplier),
             state: MovementState::default(),
         }
     }
 }
 
+/// Repeating cooldown timer. Cooldowns too long for a `Duration` (stationary or
+/// attack-less enemies, or a speed of zero) give a timer that never fires.
+fn cooldown_timer(seconds: f32) -> Timer {
+    let duration = Duration::try_from_secs_f32(seconds).unwrap_or(Duration::MAX);
+    Timer::new(duration, TimerMode::Repeating)
+}
+
 /// Internal state for movement behaviors
 #[derive(Debug, Clone, Default)]
 pub struct MovementState {
@@ -83,21 +113,68 @@ pub struct EnemyAttack {
     pub charge_timer: Option<Timer>,
     /// Internal state for attacks
     pub state: AttackState,
+    /// Cooldown is done, but fire is held until the shot lines up
+    pub primed: bool,
+    /// Cooldown at the start of the battle, before aggression shortens it
+    pub base_cooldown: f32,
+    /// Share of `base_cooldown` shaved off per minute of battle
+    pub aggression_ramp: f32,
 }
 
 impl EnemyAttack {
     pub fn new(behavior: AttackBehavior, speed_multiplier: f32) -> Self {
-        let base_cooldown = behavior.base_cooldown();
+        let base_cooldown = behavior.base_cooldown() / speed_multiplier;
         Self {
             behavior,
-            cooldown_timer: Timer::from_seconds(
-                base_cooldown / speed_multiplier,
-                TimerMode::Repeating,
-            ),
+            cooldown_timer: cooldown_timer(base_cooldown),
             charge_timer: None,
             state: AttackState::Ready,
+            primed: false,
+            base_cooldown,
+            aggression_ramp: ENEMY_AGGRESSION_RAMP,
+        }
+    }
+
+    /// Cooldown after `elapsed` seconds of battle: shortened by the aggression
+    /// ramp, but never below `ENEMY_MIN_COOLDOWN_FRACTION` of the base
+    pub fn aggressive_cooldown(&self, elapsed: f32) -> f32 {
+        let fraction =
+            (1.0 - self.aggression_ramp * elapsed / 60.0).max(ENEMY_MIN_COOLDOWN_FRACTION);
+        self.base_cooldown * fraction
+    }
+
+    /// Interrupt a charging attack (a counter hit): back to `Ready` with a
+    /// fresh cooldown. Returns false if the enemy wasn't charging.
+    pub fn cancel_charge(&mut self) -> bool {
+        if self.state != AttackState::Charging {
+            return false;
         }
+        self.state = AttackState::Ready;
+        self.charge_timer = None;
+        self.cooldown_timer.reset();
+        self.primed = false;
+        true
     }
+
+    /// Tick the cooldown (paused while primed); true when it is time to start
+    /// charging, which waits for `clear_shot`
+    pub fn ready_to_charge(&mut self, delta: Duration, clear_shot: bool) -> bool {
+        if !self.primed {
+            self.cooldown_timer.tick(delta);
+            self.primed = self.cooldown_timer.just_finished();
+        }
+        if self.primed && clear_shot {
+            self.primed = false;
+            return true;
+        }
+        false
+    }
+}
+
+/// Minions spawned by an enemy with `AttackBehavior::Summon`
+#[derive(Component, Debug, Default)]
+pub struct Summoner {
+    pub minions: Vec<Entity>,
 }
 
 /// State machine for attack behaviors
@@ -136,6 +213,92 @@ impl EnemyTraitContainer {
     }
 }
 
+/// Green glow fading off a regenerating enemy after it heals (see `apply_enemy_traits`)
+#[derive(Component, Debug, Clone)]
+pub struct RegenPulse {
+    pub timer: Timer,
+}
+
+impl Default for RegenPulse {
+    fn default() -> Self {
+        Self {
+            timer: Timer::from_seconds(REGEN_PULSE_TIME, TimerMode::Once),
+        }
+    }
+}
+
+impl RegenPulse {
+    /// Share of green mixed into the sprite: peaks on the tick, fades to nothing
+    pub fn tint(&self) -> f32 {
+        REGEN_PULSE_TINT * (1.0 - self.timer.fraction())
+    }
+}
+
+/// Guard phase of an enemy with `EnemyTraits::guard` (cycled by `update_enemy_guard`)
+#[derive(Component, Debug, Clone)]
+pub struct EnemyGuard {
+    pub cycle: GuardCycle,
+    pub guarding: bool,
+    /// Time left in the current phase
+    pub timer: Timer,
+}
+
+impl EnemyGuard {
+    /// Starts open, raising the guard after the first open phase
+    pub fn new(cycle: GuardCycle) -> Self {
+        let timer = Timer::from_seconds(cycle.open_duration, TimerMode::Once);
+        Self {
+            cycle,
+            guarding: false,
+            timer,
+        }
+    }
+
+    /// Switch phase once the current one runs out
+    pub fn tick(&mut self, delta: Duration) {
+        self.timer.tick(delta);
+        if self.timer.is_finished() {
+            self.set_guarding(!self.guarding);
+        }
+    }
+
+    fn set_guarding(&mut self, guarding: bool) {
+        let duration = if guarding {
+            self.cycle.guard_duration
+        } else {
+            self.cycle.open_duration
+        };
+        self.guarding = guarding;
+        self.timer = Timer::from_seconds(duration, TimerMode::Once);
+    }
+
+    /// About to raise the guard (the plate blinks as a warning)
+    pub fn telegraphing(&self) -> bool {
+        !self.guarding && self.timer.remaining_secs() <= GUARD_TELEGRAPH_TIME
+    }
+
+    /// Damage a hit deals: guard-breaking hits land in full and drop the guard,
+    /// anything else is mostly blocked while guarding (at least 1, like armor)
+    pub fn absorb(&mut self, damage: i32, guard_break: bool) -> i32 {
+        if !self.guarding {
+            return damage;
+        }
+        if guard_break {
+            self.set_guarding(false);
+            return damage;
+        }
+        ((damage as f32 * GUARDED_DAMAGE_SCALE) as i32).max(1)
+    }
+}
+
+/// Plate over a guarding enemy, shown while its `EnemyGuard` is up
+#[derive(Component)]
+pub struct GuardPlate;
+
+/// Zenny paid out when this enemy is deleted (see `collect_enemy_reward`)
+#[derive(Component, Debug, Clone, Copy)]
+pub struct EnemyReward(pub u64);
+
 /// Marker component indicating this enemy uses the new behavior system
 #[derive(Component)]
 pub struct BehaviorEnemy;
@@ -147,6 +310,67 @@ pub struct ChargingTelegraph {
     pub timer: Timer,
 }
 
+/// Icon above an enemy showing its next action (see `update_enemy_intent`)
+#[derive(Component)]
+pub struct EnemyIntentIcon;
+
+/// Short-lived tile flash marking where an area attack landed (or a fast shot
+/// is about to launch)
+#[derive(Component)]
+pub struct AreaAttackFlash {
+    pub color: Color,
+    pub timer: Timer,
+}
+
+/// Death effect playing where an enemy was deleted; despawned when the timer
+/// runs out. Carries one of `DeathBurst`, `DeathDissolve` or `DeathShard`.
+#[derive(Component, Debug, Clone)]
+pub struct DeathFx {
+    pub color: Color,
+    pub timer: Timer,
+}
+
+/// Flash swelling out of an exploded enemy (`DeathEffect::Explosion`)
+#[derive(Component)]
+pub struct DeathBurst;
+
+/// Afterimage rising off a dissolved enemy (`DeathEffect::Dissolve`)
+#[derive(Component)]
+pub struct DeathDissolve;
+
+/// Shard flying out of a shattered enemy (`DeathEffect::Shatter`)
+#[derive(Component)]
+pub struct DeathShard {
+    /// World units per second
+    pub velocity: Vec2,
+}
+
+/// Teleport in progress: fades out at `from`, jumps to `to` halfway, then fades back in.
+/// The enemy carries `Untargetable` until it finishes.
+#[derive(Component, Debug, Clone)]
+pub struct TeleportFx {
+    pub to: (i32, i32),
+    pub timer: Timer,
+    /// Whether the enemy has moved to `to` yet
+    pub arrived: bool,
+}
+
+impl TeleportFx {
+    pub fn new(to: (i32, i32), duration: f32) -> Self {
+        Self {
+            to,
+            timer: Timer::from_seconds(duration, TimerMode::Once),
+            arrived: false,
+        }
+    }
+
+    /// Sprite alpha: 1 -> 0 over the first half, 0 -> 1 over the second
+    pub fn alpha(&self) -> f32 {
+        let t = self.timer.fraction();
+        (1.0 - 2.0 * t).abs()
+    }
+}
+
 /// Component to track the enemy's current animation state generically
 #[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum EnemyAnimState {
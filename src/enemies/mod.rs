@@ -18,7 +18,7 @@
 //     stats: EnemyStats { base_hp: 40, contact_damage: 10, .. },
 //     movement: MovementBehavior::Stationary,
 //     attack: AttackBehavior::ShockWave { damage: 20, speed: 0.15 },
-//     traits: vec![EnemyTrait::Armored { damage_reduction: 5 }],
+//     traits: EnemyTraits { armor: 5, ..default() },
 //     visuals: EnemyVisuals { ... },
 // };
 // ```
@@ -42,12 +42,36 @@ pub struct EnemyPlugin;
 
 impl Plugin for EnemyPlugin {
     fn build(&self, app: &mut App) {
+        app.init_resource::<crate::resources::TelegraphedTiles>();
         app.add_systems(
             Update,
-            (execute_movement_behavior, execute_attack_behavior)
+            (
+                execute_movement_behavior,
+                process_teleport,
+                execute_attack_behavior,
+                update_attack_telegraphs,
+                update_area_attack_flashes,
+                update_enemy_intent,
+                update_enemy_guard,
+                apply_enemy_traits,
+                update_regen_pulses,
+                ramp_enemy_aggression,
+            )
                 .chain()
                 .run_if(in_state(crate::components::GameState::Playing))
-                .run_if(crate::systems::intro::intro_complete),
+                .run_if(crate::systems::intro::intro_complete)
+                .run_if(crate::systems::pause::not_paused),
+        );
+        // Death effects keep playing through the outro, like the death frames
+        app.add_systems(
+            Update,
+            (spawn_death_effects, update_death_effects)
+                .run_if(in_state(crate::components::GameState::Playing))
+                .run_if(crate::systems::pause::not_paused),
+        );
+        app.add_systems(
+            OnExit(crate::components::GameState::Playing),
+            clear_attack_telegraphs,
         );
     }
 }
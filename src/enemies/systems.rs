@@ -7,14 +7,29 @@ use bevy::prelude::*;
 use rand::Rng;
 
 use super::{
-    AttackBehavior, AttackState, BehaviorEnemy, ChargingTelegraph, EnemyAnimState, EnemyAttack,
-    EnemyMovement, EnemyStats, EnemyTraitContainer, MovementBehavior,
+    AreaAttackFlash, AttackBehavior, AttackState, BehaviorEnemy, ChargingTelegraph, DeathBurst,
+    DeathDissolve, DeathEffect, DeathFx, DeathShard, EnemyAnimState, EnemyAttack, EnemyBlueprint,
+    EnemyGuard, EnemyIntentIcon, EnemyKind, EnemyMovement, EnemyReward, EnemyStats,
+    EnemyTraitContainer, GuardPlate, MovementBehavior, RegenPulse, Summoner, TeleportFx,
+    area_attack_tiles, enemy_intent, telegraph_tiles,
 };
+use crate::actions::{ActiveShield, ShieldVisualMarker, remove_shield};
 use crate::assets::{ProjectileAnimation, ProjectileSprites};
 use crate::components::{
-    BaseColor, Bullet, EnemyBullet, GridPosition, Health, MoveTimer, RenderConfig, TargetsTiles,
+    BaseColor, Bullet, CleanupOnStateExit, Dying, Enemy, EnemyBullet, EnemyConfig, FlashTimer,
+    GameState, GridPosition, Health, HealthText, LaunchDelay, MoveTimer, Obstacle, OverHealth,
+    Player, PlayerHealthText, RenderConfig, TargetsTiles, Untargetable,
 };
 use crate::constants::*;
+use crate::resources::{
+    ArenaLayout, BattleTimer, Difficulty, GameRng, ReduceFlashing, TelegraphedTiles, TileOwner,
+    TileOwnership,
+};
+use crate::systems::combat::damage_player;
+use crate::systems::overtime::{Overtime, overtime_damage};
+use crate::systems::rumble::Rumble;
+use crate::systems::setup::spawn_enemy;
+use crate::systems::shake::ScreenShake;
 
 // ============================================================================
 // Movement System
@@ -22,29 +37,44 @@ use crate::constants::*;
 
 /// Execute movement behaviors for all enemies using the new system
 pub fn execute_movement_behavior(
+    mut commands: Commands,
     time: Res<Time>,
     // NOTE: player_query removed to avoid conflict with move_player system
     // For behaviors that need player position (ChasePlayer, MirrorPlayer),
     // we'd need to either chain systems or use a resource to share player position
     mut enemy_query: Query<
-        (Entity, &mut GridPosition, &mut EnemyMovement, &EnemyStats),
+        (
+            Entity,
+            &mut GridPosition,
+            &mut EnemyMovement,
+            &EnemyStats,
+            &Health,
+            Has<TeleportFx>,
+        ),
         With<BehaviorEnemy>,
     >,
+    obstacle_query: Query<&GridPosition, (With<Obstacle>, Without<BehaviorEnemy>)>,
+    ownership: Res<TileOwnership>,
+    mut rng: ResMut<GameRng>,
 ) {
     use std::collections::HashSet;
 
     let player_pos: Option<&GridPosition> = None; // TODO: Get from resource
-    let mut rng = rand::rng();
 
     // Collect all current enemy positions - use HashSet for O(1) lookups
     // Track positions dynamically as enemies move to prevent two enemies
     // from moving to the same empty tile in the same frame
+    // Obstacles are permanently occupied for the purposes of movement
     let mut occupied_positions: HashSet<(i32, i32)> = enemy_query
         .iter()
-        .map(|(_, pos, _, _)| (pos.x, pos.y))
+        .map(|(_, pos, _, _, _, _)| (pos.x, pos.y))
+        .chain(obstacle_query.iter().map(|pos| (pos.x, pos.y)))
         .collect();
 
-    for (_, mut pos, mut movement, stats) in &mut enemy_query {
+    for (entity, mut pos, mut movement, stats, health, teleporting) in &mut enemy_query {
+        if teleporting {
+            continue;
+        }
         movement.move_timer.tick(time.delta());
 
         if !movement.move_timer.just_finished() {
@@ -57,9 +87,11 @@ pub fn execute_movement_behavior(
             &behavior,
             &mut movement.state,
             &pos,
+            health,
             player_pos,
             stats.move_speed,
-            &mut rng,
+            &ownership,
+            &mut rng.0,
         );
 
         // Skip if no movement requested
@@ -72,44 +104,76 @@ pub fn execute_movement_behavior(
         let new_y = pos.y + dy;
 
         // Check if position is valid AND not occupied by another enemy
-        if is_valid_enemy_position(new_x, new_y) && !occupied_positions.contains(&(new_x, new_y)) {
+        if is_valid_enemy_position(new_x, new_y, &ownership)
+            && !occupied_positions.contains(&(new_x, new_y))
+        {
             // Update occupied set: remove old position, add new position
             occupied_positions.remove(&(pos.x, pos.y));
             occupied_positions.insert((new_x, new_y));
 
+            // Teleports play out over TELEPORT_FX_TIME (see process_teleport)
+            if matches!(behavior, MovementBehavior::Teleport { .. }) {
+                commands.entity(entity).insert((
+                    TeleportFx::new((new_x, new_y), TELEPORT_FX_TIME),
+                    Untargetable,
+                ));
+                continue;
+            }
+
             pos.x = new_x;
             pos.y = new_y;
         }
     }
 }
 
+/// Fade teleporting enemies out, move them at the midpoint, and fade them back in.
+/// If another enemy took the destination meanwhile, the enemy reappears where it was.
+pub fn process_teleport(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut GridPosition, &mut TeleportFx, &mut Sprite), With<Enemy>>,
+    others: Query<(Entity, &GridPosition), (With<Enemy>, Without<TeleportFx>)>,
+) {
+    for (entity, mut pos, mut fx, mut sprite) in &mut query {
+        fx.timer.tick(time.delta());
+
+        if !fx.arrived && fx.timer.fraction() >= 0.5 {
+            fx.arrived = true;
+            let taken = others
+                .iter()
+                .any(|(other, other_pos)| other != entity && (other_pos.x, other_pos.y) == fx.to);
+            if !taken {
+                pos.x = fx.to.0;
+                pos.y = fx.to.1;
+            }
+        }
+
+        if fx.timer.is_finished() {
+            sprite.color.set_alpha(1.0);
+            commands
+                .entity(entity)
+                .remove::<(TeleportFx, Untargetable)>();
+        } else {
+            sprite.color.set_alpha(fx.alpha());
+        }
+    }
+}
+
 /// Calculate movement delta based on behavior
 fn calculate_movement(
     behavior: &MovementBehavior,
     state: &mut super::MovementState,
     pos: &GridPosition,
+    health: &Health,
     player_pos: Option<&GridPosition>,
     _speed_mult: f32,
+    ownership: &TileOwnership,
     rng: &mut impl Rng,
 ) -> (i32, i32) {
     match behavior {
         MovementBehavior::Stationary => (0, 0),
 
-        MovementBehavior::Random { idle_chance } => {
-            // Random chance to stay idle
-            if rng.random::<f32>() < *idle_chance {
-                return (0, 0);
-            }
-
-            // Random direction
-            match rng.random_range(0..4) {
-                0 => (0, 1),  // up
-                1 => (0, -1), // down
-                2 => (-1, 0), // left
-                3 => (1, 0),  // right
-                _ => (0, 0),
-            }
-        }
+        MovementBehavior::Random { idle_chance } => random_step(*idle_chance, rng),
 
         MovementBehavior::ChaseRow => {
             // Move to match player's Y position
@@ -131,7 +195,7 @@ fn calculate_movement(
                 // Prioritize getting in the same row first
                 if pos.y != player.y {
                     if pos.y < player.y { (0, 1) } else { (0, -1) }
-                } else if pos.x > PLAYER_AREA_WIDTH {
+                } else if ownership.is_enemy_tile(pos.x - 1, pos.y) {
                     // Move toward player (but stay in enemy territory)
                     (-1, 0)
                 } else {
@@ -147,7 +211,7 @@ fn calculate_movement(
             let new_x = pos.x + dx;
 
             // Reverse at boundaries
-            if !is_valid_enemy_position(new_x, pos.y) {
+            if !is_valid_enemy_position(new_x, pos.y, ownership) {
                 state.patrol_forward = !state.patrol_forward;
                 (if state.patrol_forward { 1 } else { -1 }, 0)
             } else {
@@ -160,7 +224,7 @@ fn calculate_movement(
             let new_y = pos.y + dy;
 
             // Reverse at boundaries
-            if !(0..GRID_HEIGHT).contains(&new_y) {
+            if !(0..ownership.height()).contains(&new_y) {
                 state.patrol_forward = !state.patrol_forward;
                 (0, if state.patrol_forward { 1 } else { -1 })
             } else {
@@ -170,7 +234,7 @@ fn calculate_movement(
 
         MovementBehavior::BackRowOnly => {
             // Stay at rightmost column, move vertically only
-            if pos.x < GRID_WIDTH - 1 {
+            if pos.x < ownership.width() - 1 {
                 (1, 0) // Move to back
             } else {
                 // Random vertical movement
@@ -205,16 +269,19 @@ fn calculate_movement(
 
         MovementBehavior::Teleport { .. } => {
             // Random position in enemy territory
-            let new_x = rng.random_range(PLAYER_AREA_WIDTH..GRID_WIDTH);
-            let new_y = rng.random_range(0..GRID_HEIGHT);
+            let tiles = ownership.tiles_owned_by(TileOwner::Enemy);
+            if tiles.is_empty() {
+                return (0, 0);
+            }
+            let (new_x, new_y) = tiles[rng.random_range(0..tiles.len())];
             (new_x - pos.x, new_y - pos.y)
         }
 
         MovementBehavior::Advance { max_advance } => {
-            let min_x = GRID_WIDTH - *max_advance;
+            let min_x = ownership.width() - *max_advance;
             if pos.x > min_x && rng.random::<f32>() < 0.5 {
                 (-1, 0) // Advance
-            } else if pos.x < GRID_WIDTH - 1 && rng.random::<f32>() < 0.3 {
+            } else if pos.x < ownership.width() - 1 && rng.random::<f32>() < 0.3 {
                 (1, 0) // Retreat
             } else {
                 // Random vertical
@@ -225,12 +292,52 @@ fn calculate_movement(
                 }
             }
         }
+
+        MovementBehavior::Flee { hp_threshold } => {
+            if health.fraction() >= *hp_threshold {
+                random_step(0.33, rng)
+            } else if pos.x < ownership.width() - 1 {
+                (1, 0) // Retreat toward the back wall
+            } else {
+                (0, 0)
+            }
+        }
+    }
+}
+
+/// One random step (or none, with `idle_chance`)
+fn random_step(idle_chance: f32, rng: &mut impl Rng) -> (i32, i32) {
+    // Random chance to stay idle
+    if rng.random::<f32>() < idle_chance {
+        return (0, 0);
+    }
+
+    // Random direction
+    match rng.random_range(0..4) {
+        0 => (0, 1),  // up
+        1 => (0, -1), // down
+        2 => (-1, 0), // left
+        3 => (1, 0),  // right
+        _ => (0, 0),
+    }
+}
+
+/// Whether an enemy may start charging from `pos`: row chasers (`ChaseRow`)
+/// hold fire until they share the player's row
+fn has_clear_shot(
+    movement: Option<&MovementBehavior>,
+    pos: &GridPosition,
+    player_pos: Option<&GridPosition>,
+) -> bool {
+    match movement {
+        Some(MovementBehavior::ChaseRow) => player_pos.is_some_and(|player| player.y == pos.y),
+        _ => true,
     }
 }
 
-/// Check if a position is valid for an enemy
-fn is_valid_enemy_position(x: i32, y: i32) -> bool {
-    (PLAYER_AREA_WIDTH..GRID_WIDTH).contains(&x) && (0..GRID_HEIGHT).contains(&y)
+/// Check if a position is valid for an enemy (an enemy-owned tile)
+fn is_valid_enemy_position(x: i32, y: i32, ownership: &TileOwnership) -> bool {
+    ownership.is_enemy_tile(x, y)
 }
 
 // ============================================================================
@@ -242,18 +349,51 @@ pub fn execute_attack_behavior(
     mut commands: Commands,
     time: Res<Time>,
     projectiles: Res<ProjectileSprites>,
+    asset_server: Res<AssetServer>,
+    mut atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
+    arena_layout: Res<ArenaLayout>,
+    (difficulty, overtime): (Res<Difficulty>, Option<Res<Overtime>>),
+    ownership: Res<TileOwnership>,
+    mut rng: ResMut<GameRng>,
     mut enemy_query: Query<
-        (Entity, &GridPosition, &mut EnemyAttack, &mut EnemyAnimState),
+        (
+            Entity,
+            &GridPosition,
+            &mut EnemyAttack,
+            &mut EnemyAnimState,
+            Option<&mut Summoner>,
+            Option<&EnemyMovement>,
+        ),
         With<BehaviorEnemy>,
     >,
+    tile_query: Query<(Entity, &GridPosition), Or<(With<Enemy>, With<Obstacle>)>>,
+    mut player_query: Query<
+        (
+            Entity,
+            &GridPosition,
+            &mut Health,
+            Option<&ActiveShield>,
+            Option<&mut OverHealth>,
+            Option<&Children>,
+        ),
+        (With<Player>, Without<BehaviorEnemy>),
+    >,
+    mut hp_text_query: Query<&mut Text2d, With<PlayerHealthText>>,
+    shield_visual_query: Query<Entity, With<ShieldVisualMarker>>,
+    mut rumble: Rumble,
+    mut shake: ResMut<ScreenShake>,
 ) {
-    for (entity, pos, mut attack, mut anim_state) in &mut enemy_query {
+    let player_pos = player_query.iter().next().map(|(_, pos, ..)| *pos);
+
+    for (entity, pos, mut attack, mut anim_state, summoner, movement) in &mut enemy_query {
         match attack.state {
             AttackState::Ready => {
-                // Tick cooldown
-                attack.cooldown_timer.tick(time.delta());
-
-                if attack.cooldown_timer.just_finished() {
+                let clear_shot = has_clear_shot(
+                    movement.map(|movement| &movement.behavior),
+                    pos,
+                    player_pos.as_ref(),
+                );
+                if attack.ready_to_charge(time.delta(), clear_shot) {
                     // Start charging
                     let charge_time = attack.behavior.charge_time();
                     if charge_time > 0.0 {
@@ -288,7 +428,52 @@ pub fn execute_attack_behavior(
 
             AttackState::Attacking => {
                 // Execute the attack based on behavior
-                execute_attack(&mut commands, &attack.behavior, pos, &projectiles);
+                if let AttackBehavior::Summon {
+                    minion,
+                    max_summons,
+                    ..
+                } = &attack.behavior
+                {
+                    if let Some(mut summoner) = summoner {
+                        summon_minion(
+                            &mut commands,
+                            &asset_server,
+                            &mut atlas_layouts,
+                            &arena_layout,
+                            &mut summoner,
+                            minion,
+                            *max_summons,
+                            &tile_query,
+                            *difficulty,
+                            &ownership,
+                            &mut rng.0,
+                        );
+                    }
+                } else if let AttackBehavior::AreaAttack {
+                    damage, pattern, ..
+                } = &attack.behavior
+                {
+                    execute_area_attack(
+                        &mut commands,
+                        &arena_layout,
+                        pos,
+                        pattern,
+                        overtime_damage(*damage, overtime.as_deref()),
+                        &mut player_query,
+                        &mut hp_text_query,
+                        &shield_visual_query,
+                        &mut rumble,
+                        &mut shake,
+                    );
+                } else {
+                    execute_attack(
+                        &mut commands,
+                        &arena_layout,
+                        &attack.behavior,
+                        pos,
+                        &projectiles,
+                    );
+                }
 
                 // Move to recovery/ready
                 attack.state = AttackState::Ready;
@@ -309,6 +494,7 @@ pub fn execute_attack_behavior(
 /// Execute a specific attack type
 fn execute_attack(
     commands: &mut Commands,
+    layout: &ArenaLayout,
     behavior: &AttackBehavior,
     pos: &GridPosition,
     projectiles: &ProjectileSprites,
@@ -317,7 +503,7 @@ fn execute_attack(
         AttackBehavior::None => {}
 
         AttackBehavior::Projectile { damage, speed, .. } => {
-            spawn_enemy_projectile(commands, pos.x, pos.y, *speed, *damage, projectiles);
+            spawn_enemy_projectile(commands, layout, pos.x, pos.y, *speed, *damage, projectiles);
         }
 
         AttackBehavior::ProjectileSpread {
@@ -328,15 +514,23 @@ fn execute_attack(
         } => {
             for offset in row_offsets {
                 let target_y = pos.y + offset;
-                if (0..GRID_HEIGHT).contains(&target_y) {
-                    spawn_enemy_projectile(commands, pos.x, target_y, *speed, *damage, projectiles);
+                if (0..layout.grid.height).contains(&target_y) {
+                    spawn_enemy_projectile(
+                        commands,
+                        layout,
+                        pos.x,
+                        target_y,
+                        *speed,
+                        *damage,
+                        projectiles,
+                    );
                 }
             }
         }
 
         AttackBehavior::ShockWave { damage, speed, .. } => {
             // Shockwave is similar to projectile but could have different visuals
-            spawn_enemy_projectile(commands, pos.x, pos.y, *speed, *damage, projectiles);
+            spawn_enemy_projectile(commands, layout, pos.x, pos.y, *speed, *damage, projectiles);
         }
 
         AttackBehavior::Melee { .. } => {
@@ -344,7 +538,7 @@ fn execute_attack(
         }
 
         AttackBehavior::AreaAttack { .. } => {
-            // TODO: Implement area attack
+            // Needs the player and arena layout; see execute_area_attack
         }
 
         AttackBehavior::Bomb { .. } => {
@@ -356,35 +550,344 @@ fn execute_attack(
         }
 
         AttackBehavior::Summon { .. } => {
-            // TODO: Implement summon
+            // Needs asset access and the summoner's minion list; see summon_minion
+        }
+    }
+}
+
+/// Strike every tile of an area attack pattern at once: the player takes the
+/// damage if standing on one of them, and each tile flashes briefly.
+fn execute_area_attack(
+    commands: &mut Commands,
+    layout: &ArenaLayout,
+    pos: &GridPosition,
+    pattern: &[(i32, i32)],
+    damage: i32,
+    player_query: &mut Query<
+        (
+            Entity,
+            &GridPosition,
+            &mut Health,
+            Option<&ActiveShield>,
+            Option<&mut OverHealth>,
+            Option<&Children>,
+        ),
+        (With<Player>, Without<BehaviorEnemy>),
+    >,
+    hp_text_query: &mut Query<&mut Text2d, With<PlayerHealthText>>,
+    shield_visual_query: &Query<Entity, With<ShieldVisualMarker>>,
+    rumble: &mut Rumble,
+    shake: &mut ScreenShake,
+) {
+    let player_pos = player_query.iter().next().map(|(_, p, ..)| (p.x, p.y));
+    let tiles = area_attack_tiles(pattern, (pos.x, pos.y), player_pos, layout.grid);
+
+    for &(x, y) in &tiles {
+        commands.spawn((
+            Sprite::from_color(
+                COLOR_AREA_FLASH,
+                Vec2::new(layout.tile_width, layout.visible_height) * 0.9,
+            ),
+            Transform::default(),
+            GridPosition { x, y },
+            RenderConfig {
+                offset: Vec2::ZERO,
+                base_z: Z_PANEL_SHINE + 0.1,
+            },
+            AreaAttackFlash {
+                color: COLOR_AREA_FLASH,
+                timer: Timer::from_seconds(AREA_FLASH_TIME, TimerMode::Once),
+            },
+            CleanupOnStateExit(GameState::Playing),
+        ));
+    }
+
+    for (player_entity, player_pos, mut health, shield, over_health, children) in
+        player_query.iter_mut()
+    {
+        // Invisibility dodges the blast
+        if !tiles.contains(&(player_pos.x, player_pos.y))
+            || shield.is_some_and(ActiveShield::dodges)
+        {
+            continue;
+        }
+        // Other shields stop it like a bullet, if they face the attacking enemy
+        let from = IVec2::new(pos.x - player_pos.x, pos.y - player_pos.y);
+        if let Some(shield) = shield.filter(|shield| shield.faces(from)) {
+            if shield.breaks_on(damage) {
+                remove_shield(commands, player_entity, children, shield_visual_query);
+            }
+            if shield.blocks(damage) {
+                continue;
+            }
+        }
+        damage_player(
+            commands,
+            player_entity,
+            &mut health,
+            over_health,
+            damage,
+            hp_text_query,
+            rumble,
+            shake,
+        );
+    }
+}
+
+/// Fade out and remove area attack tile flashes
+pub fn update_area_attack_flashes(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut AreaAttackFlash, &mut Sprite)>,
+) {
+    for (entity, mut flash, mut sprite) in &mut query {
+        flash.timer.tick(time.delta());
+        if flash.timer.is_finished() {
+            commands.entity(entity).despawn();
+        } else {
+            let alpha = flash.color.alpha() * (1.0 - flash.timer.fraction());
+            sprite.color = flash.color.with_alpha(alpha);
         }
     }
 }
 
-/// Spawn an enemy projectile traveling left
+/// Leave the blueprint's death effect where each newly deleted enemy stood
+pub fn spawn_death_effects(
+    mut commands: Commands,
+    layout: Res<ArenaLayout>,
+    query: Query<(&EnemyKind, &Transform), Added<Dying>>,
+) {
+    for (kind, transform) in &query {
+        let visuals = EnemyBlueprint::get(kind.0).visuals;
+        spawn_death_effect(
+            &mut commands,
+            visuals.death_effect,
+            transform.translation,
+            visuals.tint,
+            &layout,
+        );
+    }
+}
+
+/// Spawn a death effect at `at`, sized for the arena; dissolves and shards take
+/// the enemy's `tint`
+pub fn spawn_death_effect(
+    commands: &mut Commands,
+    effect: DeathEffect,
+    at: Vec3,
+    tint: Color,
+    layout: &ArenaLayout,
+) {
+    let at = at.with_z(Z_BULLET + 1.0);
+    let fx = |color: Color| DeathFx {
+        color,
+        timer: Timer::from_seconds(DEATH_FX_TIME, TimerMode::Once),
+    };
+    match effect {
+        DeathEffect::Explosion => {
+            commands.spawn((
+                Sprite::from_color(
+                    COLOR_DEATH_BURST,
+                    Vec2::splat(DEATH_BURST_SIZE * layout.scale),
+                ),
+                Transform::from_translation(at),
+                fx(COLOR_DEATH_BURST),
+                DeathBurst,
+                CleanupOnStateExit(GameState::Playing),
+            ));
+        }
+        DeathEffect::Dissolve => {
+            let color = tint.with_alpha(0.6);
+            commands.spawn((
+                Sprite::from_color(color, DEATH_DISSOLVE_SIZE * layout.scale),
+                Transform::from_translation(at),
+                fx(color),
+                DeathDissolve,
+                CleanupOnStateExit(GameState::Playing),
+            ));
+        }
+        DeathEffect::Shatter => {
+            for index in 0..DEATH_SHARD_COUNT {
+                let angle = std::f32::consts::TAU * index as f32 / DEATH_SHARD_COUNT as f32;
+                commands.spawn((
+                    Sprite::from_color(tint, Vec2::splat(DEATH_SHARD_SIZE * layout.scale)),
+                    Transform::from_translation(at),
+                    fx(tint),
+                    DeathShard {
+                        velocity: Vec2::from_angle(angle) * DEATH_SHARD_SPEED * layout.scale,
+                    },
+                    CleanupOnStateExit(GameState::Playing),
+                ));
+            }
+        }
+    }
+}
+
+/// Play out death effects: bursts swell, dissolves rise and thin, shards fly;
+/// all of them fade and despawn after `DEATH_FX_TIME`
+pub fn update_death_effects(
+    mut commands: Commands,
+    time: Res<Time>,
+    layout: Res<ArenaLayout>,
+    mut query: Query<(
+        Entity,
+        &mut DeathFx,
+        &mut Sprite,
+        &mut Transform,
+        Has<DeathBurst>,
+        Has<DeathDissolve>,
+        Option<&DeathShard>,
+    )>,
+) {
+    let dt = time.delta_secs();
+    for (entity, mut fx, mut sprite, mut transform, burst, dissolve, shard) in &mut query {
+        fx.timer.tick(time.delta());
+        if fx.timer.is_finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        let progress = fx.timer.fraction();
+        sprite.color = fx.color.with_alpha(fx.color.alpha() * (1.0 - progress));
+        if burst {
+            transform.scale = Vec3::splat(1.0 + DEATH_BURST_SWELL * progress);
+        }
+        if dissolve {
+            transform.translation.y += DEATH_DISSOLVE_RISE * layout.scale * dt;
+            transform.scale.x = 1.0 - progress;
+        }
+        if let Some(shard) = shard {
+            transform.translation += (shard.velocity * dt).extend(0.0);
+        }
+    }
+}
+
+/// Collect the tiles every charging enemy is about to hit (see `telegraph_tiles`).
+/// Enemies stop contributing as soon as their charge completes.
+pub fn update_attack_telegraphs(
+    mut telegraphed: ResMut<TelegraphedTiles>,
+    layout: Res<ArenaLayout>,
+    enemy_query: Query<(&GridPosition, &EnemyAttack), With<ChargingTelegraph>>,
+    player_query: Query<&GridPosition, With<Player>>,
+) {
+    let player_pos = player_query.iter().next().map(|pos| (pos.x, pos.y));
+
+    telegraphed.0.clear();
+    for (pos, attack) in &enemy_query {
+        if attack.state != AttackState::Charging {
+            continue;
+        }
+        for tile in telegraph_tiles(&attack.behavior, (pos.x, pos.y), player_pos, layout.grid) {
+            if !telegraphed.0.contains(&tile) {
+                telegraphed.0.push(tile);
+            }
+        }
+    }
+}
+
+/// Drop leftover warnings so they don't show during the next battle's intro
+pub fn clear_attack_telegraphs(mut telegraphed: ResMut<TelegraphedTiles>) {
+    telegraphed.0.clear();
+}
+
+/// Spawn one minion on a random free enemy-area tile.
+/// Fizzles if the summoner already has `max_summons` living minions or no tile is free.
+fn summon_minion(
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+    atlas_layouts: &mut Assets<TextureAtlasLayout>,
+    arena_layout: &ArenaLayout,
+    summoner: &mut Summoner,
+    minion: &EnemyConfig,
+    max_summons: usize,
+    tile_query: &Query<(Entity, &GridPosition), Or<(With<Enemy>, With<Obstacle>)>>,
+    difficulty: Difficulty,
+    ownership: &TileOwnership,
+    rng: &mut impl Rng,
+) {
+    // Forget minions that have been deleted
+    summoner
+        .minions
+        .retain(|minion| tile_query.contains(*minion));
+    if summoner.minions.len() >= max_summons {
+        return;
+    }
+
+    let free_tiles: Vec<(i32, i32)> = ownership
+        .tiles_owned_by(TileOwner::Enemy)
+        .into_iter()
+        .filter(|&(x, y)| !tile_query.iter().any(|(_, pos)| pos.x == x && pos.y == y))
+        .collect();
+    if free_tiles.is_empty() {
+        return;
+    }
+    let (x, y) = free_tiles[rng.random_range(0..free_tiles.len())];
+
+    let config = EnemyConfig {
+        start_x: x,
+        start_y: y,
+        boss: false,
+        ..minion.clone()
+    };
+    let minion_entity = spawn_enemy(
+        commands,
+        asset_server,
+        atlas_layouts,
+        &config,
+        0,
+        arena_layout,
+        difficulty,
+    );
+    // Summoned adds drop no zenny, so stalling a summoner can't be farmed
+    commands.entity(minion_entity).insert(EnemyReward(0));
+    summoner.minions.push(minion_entity);
+}
+
+/// Seconds per tile for an enemy shot traveling `speed` tiles per second
+pub fn enemy_projectile_move_time(speed: f32) -> f32 {
+    if speed > 0.0 {
+        1.0 / speed
+    } else {
+        BULLET_MOVE_TIMER
+    }
+}
+
+/// Where `speed` falls between the slow and fast enemy shot speeds (0.0 - 1.0)
+fn enemy_projectile_fastness(speed: f32) -> f32 {
+    ((speed - ENEMY_SHOT_SLOW_SPEED) / (ENEMY_SHOT_FAST_SPEED - ENEMY_SHOT_SLOW_SPEED))
+        .clamp(0.0, 1.0)
+}
+
+/// Draw size of an enemy shot: slow shots are drawn larger, fast ones smaller
+pub fn enemy_projectile_size(speed: f32) -> Vec2 {
+    BULLET_DRAW_SIZE * (1.25 - 0.5 * enemy_projectile_fastness(speed))
+}
+
+/// Tint of an enemy shot: slow shots are dimmed, fast ones drawn at full brightness
+pub fn enemy_projectile_tint(speed: f32) -> Color {
+    let brightness = 0.6 + 0.4 * enemy_projectile_fastness(speed);
+    Color::srgb(brightness, brightness, brightness)
+}
+
+/// Spawn an enemy projectile traveling left. Fast shots flash their spawn
+/// tile and hold there for `ENEMY_SHOT_TELEGRAPH_TIME` before moving
 fn spawn_enemy_projectile(
     commands: &mut Commands,
+    layout: &ArenaLayout,
     x: i32,
     y: i32,
     speed: f32,
     damage: i32,
     projectiles: &ProjectileSprites,
 ) {
-    // Convert speed (tiles per second) to move timer duration
-    let move_timer = if speed > 0.0 {
-        1.0 / speed
-    } else {
-        BULLET_MOVE_TIMER
-    };
-
-    commands.spawn((
+    let mut projectile = commands.spawn((
         Sprite {
             image: projectiles.blaster_image.clone(),
             texture_atlas: Some(TextureAtlas {
                 layout: projectiles.blaster_layout.clone(),
                 index: 1, // Start at travel frame
             }),
-            custom_size: Some(BULLET_DRAW_SIZE),
+            color: enemy_projectile_tint(speed),
+            custom_size: Some(enemy_projectile_size(speed)),
             ..default()
         },
         Transform::default(),
@@ -396,31 +899,69 @@ fn spawn_enemy_projectile(
         Bullet,
         EnemyBullet::new(damage),
         ProjectileAnimation::blaster(false), // Enemy projectiles are not charged
-        MoveTimer(Timer::from_seconds(move_timer, TimerMode::Repeating)),
+        MoveTimer(Timer::from_seconds(
+            enemy_projectile_move_time(speed),
+            TimerMode::Repeating,
+        )),
         TargetsTiles::single(), // Highlight tile at projectile's position
     ));
+    if speed < ENEMY_SHOT_FAST_SPEED {
+        return;
+    }
+    projectile.insert(LaunchDelay(Timer::from_seconds(
+        ENEMY_SHOT_TELEGRAPH_TIME,
+        TimerMode::Once,
+    )));
+    commands.spawn((
+        Sprite::from_color(
+            COLOR_SHOT_TELEGRAPH,
+            Vec2::new(layout.tile_width, layout.visible_height) * 0.9,
+        ),
+        Transform::default(),
+        GridPosition { x, y },
+        RenderConfig {
+            offset: Vec2::ZERO,
+            base_z: Z_PANEL_SHINE + 0.1,
+        },
+        AreaAttackFlash {
+            color: COLOR_SHOT_TELEGRAPH,
+            timer: Timer::from_seconds(ENEMY_SHOT_TELEGRAPH_TIME, TimerMode::Once),
+        },
+        CleanupOnStateExit(GameState::Playing),
+    ));
 }
 
 // ============================================================================
 // Charging Telegraph Visual Effect
 // ============================================================================
 
+/// Sprite color `elapsed` seconds into a charge telegraph: a fast red
+/// flash, or a steady red tint when flashing is reduced
+pub fn telegraph_color(base: Color, elapsed: f32, reduce_flashing: ReduceFlashing) -> Color {
+    if reduce_flashing.0 {
+        base.mix(&COLOR_DAMAGE_FLASH, REDUCED_FLASH_TINT)
+    } else if (elapsed * 30.0).sin() > 0.0 {
+        COLOR_DAMAGE_FLASH
+    } else {
+        base
+    }
+}
+
 /// Animate the charging telegraph (flashing effect)
 pub fn animate_charging_telegraph(
     mut commands: Commands,
     time: Res<Time>,
+    reduce_flashing: Res<ReduceFlashing>,
     mut query: Query<(Entity, &mut Sprite, &BaseColor, &mut ChargingTelegraph)>,
 ) {
     for (entity, mut sprite, base_color, mut telegraph) in &mut query {
         telegraph.timer.tick(time.delta());
 
-        // Flash effect using sine wave
-        let t = telegraph.timer.elapsed_secs();
-        if (t * 30.0).sin() > 0.0 {
-            sprite.color = Color::srgb(1.0, 0.3, 0.3); // Red warning flash
-        } else {
-            sprite.color = base_color.0;
-        }
+        sprite.color = telegraph_color(
+            base_color.0,
+            telegraph.timer.elapsed_secs(),
+            *reduce_flashing,
+        );
 
         // Remove when done (backup cleanup - normally removed by attack system)
         if telegraph.timer.just_finished() {
@@ -430,22 +971,91 @@ pub fn animate_charging_telegraph(
     }
 }
 
+// ============================================================================
+// Intent Icon
+// ============================================================================
+
+/// Show each enemy's next action (idle, move, attack) on its intent icon
+pub fn update_enemy_intent(
+    enemy_query: Query<(&EnemyAttack, &EnemyMovement, &Children), With<BehaviorEnemy>>,
+    mut icon_query: Query<(&mut Text2d, &mut TextColor), With<EnemyIntentIcon>>,
+) {
+    for (attack, movement, children) in &enemy_query {
+        let intent = enemy_intent(&attack.state, &attack.behavior, &movement.behavior);
+        for child in children.iter() {
+            let Ok((mut text, mut color)) = icon_query.get_mut(child) else {
+                continue;
+            };
+            if text.0 != intent.glyph() {
+                text.0 = intent.glyph().to_string();
+                color.0 = intent.color();
+            }
+        }
+    }
+}
+
 // ============================================================================
 // Trait System
 // ============================================================================
 
+/// Cycle guarding enemies between open and guarding, and show their guard plate:
+/// blinking in the warning window before the guard goes up (shown steadily when
+/// flashing is reduced), solid while it holds
+pub fn update_enemy_guard(
+    time: Res<Time>,
+    reduce_flashing: Res<ReduceFlashing>,
+    mut guard_query: Query<&mut EnemyGuard>,
+    mut plate_query: Query<(&ChildOf, &mut Visibility), With<GuardPlate>>,
+) {
+    for mut guard in &mut guard_query {
+        guard.tick(time.delta());
+    }
+
+    let blink_on = reduce_flashing.0 || (time.elapsed_secs() * TELEGRAPH_BLINK_SPEED).sin() > 0.0;
+    for (child_of, mut visibility) in &mut plate_query {
+        let Ok(guard) = guard_query.get(child_of.parent()) else {
+            continue;
+        };
+        let shown = guard.guarding || (guard.telegraphing() && blink_on);
+        *visibility = if shown {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+    }
+}
+
 /// Apply trait effects (regeneration, enrage, etc.)
 pub fn apply_enemy_traits(
+    mut commands: Commands,
     time: Res<Time>,
-    mut query: Query<(&mut Health, &mut EnemyTraitContainer, &EnemyStats), With<BehaviorEnemy>>,
+    mut query: Query<
+        (
+            Entity,
+            &mut Health,
+            &mut EnemyTraitContainer,
+            &EnemyStats,
+            Option<&Children>,
+        ),
+        (With<BehaviorEnemy>, Without<Dying>),
+    >,
+    mut text_query: Query<&mut Text2d, With<HealthText>>,
 ) {
-    for (mut health, mut traits, _stats) in &mut query {
-        // HP Regeneration
+    for (entity, mut health, mut traits, _stats, children) in &mut query {
+        // HP Regeneration, never past max and never reviving a deleted enemy
         if let Some(ref mut timer) = traits.hp_regen_timer {
             timer.tick(time.delta());
-            if timer.just_finished() {
+            if timer.just_finished() && health.current > 0 && health.current < health.max {
                 let regen = traits.traits.hp_regen_per_sec as i32;
                 health.current = (health.current + regen).min(health.max);
+                commands.entity(entity).insert(RegenPulse::default());
+                if let Some(children) = children {
+                    for child in children.iter() {
+                        if let Ok(mut text) = text_query.get_mut(child) {
+                            text.0 = health.current.to_string();
+                        }
+                    }
+                }
             }
         }
 
@@ -458,3 +1068,674 @@ pub fn apply_enemy_traits(
         }
     }
 }
+
+/// Fade the green regen glow off enemies. A hit flash or charge telegraph
+/// owns the sprite color while active, so the pulse just runs out underneath it.
+pub fn update_regen_pulses(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(
+        Entity,
+        &mut Sprite,
+        &BaseColor,
+        &mut RegenPulse,
+        Has<FlashTimer>,
+        Has<ChargingTelegraph>,
+    )>,
+) {
+    for (entity, mut sprite, base, mut pulse, flashing, charging) in &mut query {
+        pulse.timer.tick(time.delta());
+        let owns_color = !flashing && !charging;
+
+        if pulse.timer.is_finished() {
+            if owns_color {
+                sprite.color = base.0;
+            }
+            commands.entity(entity).remove::<RegenPulse>();
+        } else if owns_color {
+            sprite.color = base.0.mix(&COLOR_REGEN_PULSE, pulse.tint());
+        }
+    }
+}
+
+/// Shorten attack cooldowns the longer the battle runs, so it can't stall
+pub fn ramp_enemy_aggression(
+    battle_timer: Res<BattleTimer>,
+    mut query: Query<&mut EnemyAttack, With<BehaviorEnemy>>,
+) {
+    for mut attack in &mut query {
+        let cooldown = attack.aggressive_cooldown(battle_timer.elapsed);
+        let Ok(duration) = std::time::Duration::try_from_secs_f32(cooldown) else {
+            continue;
+        };
+        if attack.cooldown_timer.duration() != duration {
+            attack.cooldown_timer.set_duration(duration);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::actions::{ShieldType, process_shield_effects};
+    use crate::enemies::{EnemyBlueprint, EnemyId, EnemyTraits};
+    use crate::resources::GridSize;
+
+    /// Headless app with asset storage, so minions can be spawned without a window
+    fn summon_app() -> App {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+            .init_asset::<Image>()
+            .init_asset::<TextureAtlasLayout>()
+            .init_resource::<ArenaLayout>()
+            .init_resource::<TileOwnership>()
+            .insert_resource(GameRng::from_seed(7));
+        app
+    }
+
+    /// One summon attempt per update, capped at two living minions
+    fn summon_slime(
+        mut commands: Commands,
+        asset_server: Res<AssetServer>,
+        mut atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
+        arena_layout: Res<ArenaLayout>,
+        ownership: Res<TileOwnership>,
+        mut rng: ResMut<GameRng>,
+        mut summoners: Query<&mut Summoner>,
+        tile_query: Query<(Entity, &GridPosition), Or<(With<Enemy>, With<Obstacle>)>>,
+    ) {
+        for mut summoner in &mut summoners {
+            summon_minion(
+                &mut commands,
+                &asset_server,
+                &mut atlas_layouts,
+                &arena_layout,
+                &mut summoner,
+                &EnemyConfig::new(EnemyId::Slime, 0, 0),
+                2,
+                &tile_query,
+                Difficulty::default(),
+                &ownership,
+                &mut rng.0,
+            );
+        }
+    }
+
+    fn minions(app: &mut App) -> usize {
+        let world = app.world_mut();
+        world
+            .query_filtered::<(), With<Enemy>>()
+            .iter(world)
+            .count()
+    }
+
+    #[test]
+    fn summoner_stops_at_max_living_minions() {
+        let mut app = summon_app();
+        app.add_systems(Update, summon_slime);
+        app.world_mut().spawn(Summoner::default());
+
+        for _ in 0..4 {
+            app.update();
+        }
+        assert_eq!(minions(&mut app), 2);
+
+        // Losing a minion frees a slot again
+        let minion = app
+            .world_mut()
+            .query::<&Summoner>()
+            .single(app.world())
+            .unwrap()
+            .minions[0];
+        app.world_mut().despawn(minion);
+        app.update();
+        assert_eq!(minions(&mut app), 2);
+        app.update();
+        assert_eq!(minions(&mut app), 2);
+    }
+
+    /// Cross centered two tiles in front of the enemy at (4, 1)
+    const CROSS: [(i32, i32); 5] = [(-2, 0), (-1, 0), (-3, 0), (-2, -1), (-2, 1)];
+
+    fn cross_attack(
+        mut commands: Commands,
+        layout: Res<ArenaLayout>,
+        mut player_query: Query<
+            (
+                Entity,
+                &GridPosition,
+                &mut Health,
+                Option<&ActiveShield>,
+                Option<&mut OverHealth>,
+                Option<&Children>,
+            ),
+            (With<Player>, Without<BehaviorEnemy>),
+        >,
+        mut hp_text_query: Query<&mut Text2d, With<PlayerHealthText>>,
+        shield_visual_query: Query<Entity, With<ShieldVisualMarker>>,
+        mut rumble: Rumble,
+        mut shake: ResMut<ScreenShake>,
+    ) {
+        execute_area_attack(
+            &mut commands,
+            &layout,
+            &GridPosition { x: 4, y: 1 },
+            &CROSS,
+            20,
+            &mut player_query,
+            &mut hp_text_query,
+            &shield_visual_query,
+            &mut rumble,
+            &mut shake,
+        );
+    }
+
+    /// Player HP after one cross attack with the player standing on `tile`,
+    /// behind `shield` if any
+    fn hp_after_cross_attack(tile: (i32, i32), shield: Option<ShieldType>) -> i32 {
+        let mut world = World::new();
+        world.init_resource::<ArenaLayout>();
+        crate::systems::rumble::init_hit_feedback(&mut world);
+        let player = world
+            .spawn((
+                Player,
+                GridPosition {
+                    x: tile.0,
+                    y: tile.1,
+                },
+                Health {
+                    current: 100,
+                    max: 100,
+                },
+            ))
+            .id();
+        if let Some(shield_type) = shield {
+            world.entity_mut(player).insert(ActiveShield {
+                duration_timer: Timer::from_seconds(3.0, TimerMode::Once),
+                damage_threshold: None,
+                shield_type,
+            });
+        }
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(cross_attack);
+        schedule.run(&mut world);
+        world.get::<Health>(player).unwrap().current
+    }
+
+    #[test]
+    fn cross_area_attack_hits_only_covered_tiles() {
+        for covered in [(2, 1), (1, 1), (3, 1), (2, 0), (2, 2)] {
+            assert_eq!(hp_after_cross_attack(covered, None), 80, "{covered:?}");
+        }
+        for open in [(1, 0), (0, 1), (3, 2)] {
+            assert_eq!(hp_after_cross_attack(open, None), 100, "{open:?}");
+        }
+    }
+
+    #[test]
+    fn guard_stance_blocks_a_frontal_bullet() {
+        let mut world = World::new();
+        let player = world
+            .spawn((
+                Player,
+                GridPosition { x: 2, y: 1 },
+                ActiveShield {
+                    duration_timer: Timer::from_seconds(3.0, TimerMode::Once),
+                    damage_threshold: None,
+                    shield_type: ShieldType::Guard,
+                },
+            ))
+            .id();
+        let bullet = world
+            .spawn((GridPosition { x: 2, y: 1 }, EnemyBullet::new(20)))
+            .id();
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(process_shield_effects);
+        schedule.run(&mut world);
+
+        assert!(world.get_entity(bullet).is_err());
+        assert!(world.get::<ActiveShield>(player).is_some());
+    }
+
+    #[test]
+    fn guard_stance_only_stops_blasts_from_straight_ahead() {
+        // The cross comes from the enemy on (4, 1)
+        assert_eq!(hp_after_cross_attack((2, 1), Some(ShieldType::Guard)), 100);
+        // From a row off the player's, the hit isn't frontal and gets through
+        assert_eq!(hp_after_cross_attack((2, 0), Some(ShieldType::Guard)), 80);
+        // A plain shield covers every side
+        assert_eq!(hp_after_cross_attack((2, 0), Some(ShieldType::Basic)), 100);
+    }
+
+    #[test]
+    fn enemies_only_stand_on_enemy_tiles() {
+        let mut ownership = TileOwnership::default();
+        assert!(is_valid_enemy_position(3, 1, &ownership));
+        assert!(!is_valid_enemy_position(2, 1, &ownership));
+
+        ownership.set(3, 1, TileOwner::Player);
+        ownership.set(2, 1, TileOwner::Enemy);
+        assert!(!is_valid_enemy_position(3, 1, &ownership));
+        assert!(is_valid_enemy_position(2, 1, &ownership));
+    }
+
+    #[test]
+    fn hurt_fleeing_enemy_retreats_to_the_back_column() {
+        let ownership = TileOwnership::default();
+        let flee = MovementBehavior::Flee { hp_threshold: 0.3 };
+        let mut state = crate::enemies::MovementState::default();
+        let mut rng = GameRng::from_seed(5).0;
+        let pos = GridPosition { x: 3, y: 1 };
+        let mut step = |current: i32| {
+            let health = Health { current, max: 100 };
+            calculate_movement(
+                &flee, &mut state, &pos, &health, None, 1.0, &ownership, &mut rng,
+            )
+        };
+
+        for _ in 0..20 {
+            assert_eq!(step(20), (1, 0));
+        }
+        // Healthy enough: wanders instead of always retreating
+        let steps: Vec<_> = (0..20).map(|_| step(80)).collect();
+        assert!(steps.iter().any(|&step| step != (1, 0)));
+
+        // Already at the back wall: stays put
+        let back = GridPosition { x: 5, y: 1 };
+        let health = Health {
+            current: 10,
+            max: 100,
+        };
+        assert_eq!(
+            calculate_movement(
+                &flee, &mut state, &back, &health, None, 1.0, &ownership, &mut rng,
+            ),
+            (0, 0)
+        );
+    }
+
+    #[test]
+    fn wider_player_area_pushes_back_the_enemy_bounds() {
+        let ownership = TileOwnership::new(GridSize {
+            width: 7,
+            height: 3,
+            player_area_width: 4,
+        });
+        assert!(!is_valid_enemy_position(3, 1, &ownership));
+        assert!(is_valid_enemy_position(4, 1, &ownership));
+        assert!(is_valid_enemy_position(6, 2, &ownership));
+        assert!(!is_valid_enemy_position(7, 1, &ownership));
+        assert_eq!(ownership.tiles_owned_by(TileOwner::Enemy).len(), 9);
+    }
+
+    #[test]
+    fn teleporting_enemy_is_untargetable_until_it_reappears() {
+        let mut world = World::new();
+        world.init_resource::<Time>();
+        world.init_resource::<TileOwnership>();
+        world.insert_resource(GameRng::from_seed(3));
+        let enemy = world
+            .spawn((
+                Enemy,
+                BehaviorEnemy,
+                GridPosition { x: 4, y: 1 },
+                EnemyMovement::new(
+                    MovementBehavior::Teleport {
+                        min_interval: 0.5,
+                        max_interval: 0.5,
+                    },
+                    1.0,
+                ),
+                EnemyStats {
+                    base_hp: 100,
+                    contact_damage: 0,
+                    move_speed: 1.0,
+                    attack_speed: 1.0,
+                },
+                Health {
+                    current: 100,
+                    max: 100,
+                },
+                Sprite::default(),
+            ))
+            .id();
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems((execute_movement_behavior, process_teleport).chain());
+        let step = |world: &mut World, schedule: &mut Schedule| {
+            world
+                .resource_mut::<Time>()
+                .advance_by(std::time::Duration::from_secs_f32(0.05));
+            schedule.run(world);
+        };
+
+        // Wait for a teleport to start
+        let mut frames = 0;
+        while !world.entity(enemy).contains::<TeleportFx>() {
+            step(&mut world, &mut schedule);
+            frames += 1;
+            assert!(frames < 200, "enemy never teleported");
+        }
+        let destination = world.get::<TeleportFx>(enemy).unwrap().to;
+
+        while world.entity(enemy).contains::<TeleportFx>() {
+            assert!(world.entity(enemy).contains::<Untargetable>());
+            step(&mut world, &mut schedule);
+        }
+        assert!(!world.entity(enemy).contains::<Untargetable>());
+        let pos = world.get::<GridPosition>(enemy).unwrap();
+        assert_eq!((pos.x, pos.y), destination);
+        assert_eq!(world.get::<Sprite>(enemy).unwrap().color.alpha(), 1.0);
+    }
+
+    #[test]
+    fn row_chaser_defers_charging_until_it_shares_the_players_row() {
+        let chase = MovementBehavior::ChaseRow;
+        let player = GridPosition { x: 1, y: 2 };
+        let clear = |pos: &GridPosition| has_clear_shot(Some(&chase), pos, Some(&player));
+        let mut attack = EnemyAttack::new(AttackBehavior::default(), 1.0);
+        let cooldown = attack.cooldown_timer.duration();
+        let mut pos = GridPosition { x: 4, y: 0 };
+
+        // Cooldown done off the player's row: primed, but no charge yet
+        assert!(!attack.ready_to_charge(cooldown, clear(&pos)));
+        assert!(attack.primed);
+        pos.y = 1;
+        assert!(!attack.ready_to_charge(cooldown, clear(&pos)));
+        assert!(attack.primed);
+
+        // Lined up: charges at once instead of waiting out another cooldown
+        pos.y = 2;
+        assert!(attack.ready_to_charge(std::time::Duration::ZERO, clear(&pos)));
+        assert!(!attack.primed);
+
+        // Other movement never holds fire
+        let off_row = GridPosition { x: 4, y: 0 };
+        assert!(has_clear_shot(
+            Some(&MovementBehavior::Stationary),
+            &off_row,
+            Some(&player)
+        ));
+        assert!(has_clear_shot(None, &off_row, Some(&player)));
+    }
+
+    /// Rows of the shots a Spreader standing on `row` fires in one attack
+    fn spreader_shot_rows(row: i32) -> Vec<i32> {
+        let mut world = World::new();
+        let projectiles = ProjectileSprites {
+            blaster_image: Handle::default(),
+            blaster_layout: Handle::default(),
+            blaster_charged_image: Handle::default(),
+            blaster_charged_layout: Handle::default(),
+        };
+        execute_attack(
+            &mut world.commands(),
+            &ArenaLayout::default(),
+            &EnemyBlueprint::get(EnemyId::Spreader).attack,
+            &GridPosition { x: 5, y: row },
+            &projectiles,
+        );
+        world.flush();
+
+        // Plain enemy bullets, so shields and invis handle them like any shot
+        let mut rows: Vec<i32> = world
+            .query_filtered::<&GridPosition, With<EnemyBullet>>()
+            .iter(&world)
+            .map(|pos| pos.y)
+            .collect();
+        rows.sort_unstable();
+        rows
+    }
+
+    #[test]
+    fn spread_shot_covers_three_rows_and_skips_rows_off_the_grid() {
+        assert_eq!(spreader_shot_rows(1), vec![0, 1, 2]);
+        assert_eq!(spreader_shot_rows(0), vec![0, 1]);
+        assert_eq!(spreader_shot_rows(GRID_HEIGHT - 1), vec![1, 2]);
+    }
+
+    #[test]
+    fn enemy_shot_timing_and_look_follow_its_speed() {
+        let mut world = World::new();
+        let layout = ArenaLayout::default();
+        let projectiles = ProjectileSprites {
+            blaster_image: Handle::default(),
+            blaster_layout: Handle::default(),
+            blaster_charged_image: Handle::default(),
+            blaster_charged_layout: Handle::default(),
+        };
+        {
+            let mut commands = world.commands();
+            spawn_enemy_projectile(&mut commands, &layout, 5, 0, 4.0, 10, &projectiles);
+            spawn_enemy_projectile(&mut commands, &layout, 5, 2, 12.0, 10, &projectiles);
+        }
+        world.flush();
+
+        let mut shots: Vec<(f32, Vec2, bool)> = world
+            .query_filtered::<(&MoveTimer, &Sprite, Has<LaunchDelay>), With<EnemyBullet>>()
+            .iter(&world)
+            .map(|(timer, sprite, delayed)| {
+                (
+                    timer.0.duration().as_secs_f32(),
+                    sprite.custom_size.unwrap(),
+                    delayed,
+                )
+            })
+            .collect();
+        shots.sort_by(|a, b| b.0.total_cmp(&a.0));
+        assert_eq!(
+            shots,
+            vec![
+                (1.0 / 4.0, enemy_projectile_size(4.0), false),
+                (1.0 / 12.0, enemy_projectile_size(12.0), true),
+            ]
+        );
+
+        // Slow shots read large and dim, fast ones small and bright
+        assert!(enemy_projectile_size(4.0).x > BULLET_DRAW_SIZE.x);
+        assert!(enemy_projectile_size(12.0).x < BULLET_DRAW_SIZE.x);
+        assert!(enemy_projectile_size(6.0).x > enemy_projectile_size(8.0).x);
+        assert!(
+            enemy_projectile_tint(4.0).to_srgba().red < enemy_projectile_tint(10.0).to_srgba().red
+        );
+        assert_eq!(enemy_projectile_move_time(0.0), BULLET_MOVE_TIMER);
+
+        // Only the fast shot telegraphs its spawn tile
+        let flashes: Vec<(i32, i32)> = world
+            .query_filtered::<&GridPosition, With<AreaAttackFlash>>()
+            .iter(&world)
+            .map(|pos| (pos.x, pos.y))
+            .collect();
+        assert_eq!(flashes, vec![(5, 2)]);
+    }
+
+    #[test]
+    fn long_battles_shorten_enemy_cooldowns_down_to_the_floor() {
+        let mut world = World::new();
+        world.init_resource::<BattleTimer>();
+        let attack = EnemyAttack::new(AttackBehavior::default(), 1.0);
+        let base = attack.cooldown_timer.duration().as_secs_f32();
+        let enemy = world.spawn((BehaviorEnemy, attack)).id();
+        let mut schedule = Schedule::default();
+        schedule.add_systems(ramp_enemy_aggression);
+        let cooldown = |world: &World| {
+            world
+                .get::<EnemyAttack>(enemy)
+                .unwrap()
+                .cooldown_timer
+                .duration()
+                .as_secs_f32()
+        };
+
+        schedule.run(&mut world);
+        assert!((cooldown(&world) - base).abs() < 1e-4);
+
+        world.resource_mut::<BattleTimer>().elapsed = 60.0;
+        schedule.run(&mut world);
+        let ramped = base * (1.0 - ENEMY_AGGRESSION_RAMP);
+        assert!((cooldown(&world) - ramped).abs() < 1e-4);
+
+        // A very long battle bottoms out at the floor
+        world.resource_mut::<BattleTimer>().elapsed = 3600.0;
+        schedule.run(&mut world);
+        let floor = base * ENEMY_MIN_COOLDOWN_FRACTION;
+        assert!((cooldown(&world) - floor).abs() < 1e-4);
+    }
+
+    #[test]
+    fn each_death_effect_spawns_its_own_visual() {
+        let spawned = |effect: DeathEffect| {
+            let mut world = World::new();
+            let layout = ArenaLayout::default();
+            spawn_death_effect(
+                &mut world.commands(),
+                effect,
+                Vec3::ZERO,
+                Color::WHITE,
+                &layout,
+            );
+            world.flush();
+            let fx = world.query::<&DeathFx>().iter(&world).count();
+            let bursts = world.query::<&DeathBurst>().iter(&world).count();
+            let dissolves = world.query::<&DeathDissolve>().iter(&world).count();
+            let shards = world.query::<&DeathShard>().iter(&world).count();
+            assert_eq!(fx, bursts + dissolves + shards, "every piece fades out");
+            (bursts, dissolves, shards)
+        };
+        assert_eq!(spawned(DeathEffect::Explosion), (1, 0, 0));
+        assert_eq!(spawned(DeathEffect::Dissolve), (0, 1, 0));
+        assert_eq!(spawned(DeathEffect::Shatter), (0, 0, DEATH_SHARD_COUNT));
+
+        // The effect comes from the deleted enemy's blueprint
+        let mut world = World::new();
+        world.init_resource::<ArenaLayout>();
+        world.spawn((EnemyKind(EnemyId::Canodumb), Transform::default(), Dying));
+        let mut schedule = Schedule::default();
+        schedule.add_systems(spawn_death_effects);
+        schedule.run(&mut world);
+        assert_eq!(
+            EnemyBlueprint::get(EnemyId::Canodumb).visuals.death_effect,
+            DeathEffect::Explosion
+        );
+        assert_eq!(world.query::<&DeathBurst>().iter(&world).count(), 1);
+    }
+
+    #[test]
+    fn death_effects_scale_with_the_arena() {
+        let sizes = |scale: f32| {
+            let mut world = World::new();
+            let layout = ArenaLayout { scale, ..default() };
+            for effect in [DeathEffect::Explosion, DeathEffect::Dissolve] {
+                spawn_death_effect(
+                    &mut world.commands(),
+                    effect,
+                    Vec3::ZERO,
+                    Color::WHITE,
+                    &layout,
+                );
+            }
+            world.flush();
+            world.insert_resource(layout);
+            world.init_resource::<Time>();
+            world
+                .resource_mut::<Time>()
+                .advance_by(std::time::Duration::from_secs_f32(0.1));
+            world.run_system_cached(update_death_effects).unwrap();
+            let burst = world
+                .query_filtered::<&Sprite, With<DeathBurst>>()
+                .single(&world)
+                .unwrap()
+                .custom_size
+                .unwrap();
+            let rise = world
+                .query_filtered::<&Transform, With<DeathDissolve>>()
+                .single(&world)
+                .unwrap()
+                .translation
+                .y;
+            (burst, rise)
+        };
+
+        let (burst, rise) = sizes(1.0);
+        assert_eq!(burst, Vec2::splat(DEATH_BURST_SIZE));
+        let (big_burst, big_rise) = sizes(2.0);
+        assert_eq!(big_burst, burst * 2.0);
+        assert!((big_rise - rise * 2.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn reduced_flashing_holds_the_telegraph_color_steady() {
+        let base = Color::srgb(0.2, 0.4, 0.9);
+        // A quarter second of telegraph, frame by frame
+        let frames: Vec<f32> = (0..15).map(|frame| frame as f32 / 60.0).collect();
+
+        let flashing: Vec<Color> = frames
+            .iter()
+            .map(|&t| telegraph_color(base, t, ReduceFlashing(false)))
+            .collect();
+        assert!(flashing.contains(&base));
+        assert!(flashing.contains(&COLOR_DAMAGE_FLASH));
+
+        let steady = telegraph_color(base, 0.0, ReduceFlashing(true));
+        assert_ne!(steady, base);
+        for &t in &frames {
+            assert_eq!(telegraph_color(base, t, ReduceFlashing(true)), steady);
+        }
+    }
+
+    #[test]
+    fn damaged_regenerating_enemy_heals_up_to_its_max() {
+        let mut world = World::new();
+        world.init_resource::<Time>();
+        let traits = EnemyTraits {
+            hp_regen_per_sec: 15.0,
+            ..default()
+        };
+        let enemy = world
+            .spawn((
+                BehaviorEnemy,
+                EnemyStats {
+                    base_hp: 100,
+                    contact_damage: 0,
+                    move_speed: 1.0,
+                    attack_speed: 1.0,
+                },
+                EnemyTraitContainer::new(traits),
+                Health {
+                    current: 70,
+                    max: 100,
+                },
+                Sprite::default(),
+                BaseColor(Color::WHITE),
+            ))
+            .id();
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems((apply_enemy_traits, update_regen_pulses).chain());
+        // Quarter-second frames, so a fresh pulse is still fading when checked
+        let run_secs = |world: &mut World, schedule: &mut Schedule, secs: u32| {
+            for _ in 0..secs * 4 {
+                world
+                    .resource_mut::<Time>()
+                    .advance_by(std::time::Duration::from_secs_f32(0.25));
+                schedule.run(world);
+            }
+        };
+        let hp = |world: &World| world.get::<Health>(enemy).unwrap().current;
+
+        run_secs(&mut world, &mut schedule, 1);
+        assert_eq!(hp(&world), 85);
+        assert!(world.get::<RegenPulse>(enemy).is_some());
+        assert_ne!(world.get::<Sprite>(enemy).unwrap().color, Color::WHITE);
+
+        // The last tick is capped at max, and a full enemy stops pulsing
+        run_secs(&mut world, &mut schedule, 1);
+        assert_eq!(hp(&world), 100);
+        run_secs(&mut world, &mut schedule, 3);
+        assert_eq!(hp(&world), 100);
+        assert!(world.get::<RegenPulse>(enemy).is_none());
+        assert_eq!(world.get::<Sprite>(enemy).unwrap().color, Color::WHITE);
+    }
+}
@@ -17,8 +17,24 @@ pub struct EnemyVisuals {
     pub offset: Vec2,
     /// Whether to flip sprite to face player (most enemies face left)
     pub flip_x: bool,
+    /// Color multiplied into the sprite (tells apart enemies sharing a sheet)
+    pub tint: Color,
     /// Animation configuration
     pub animations: EnemyAnimations,
+    /// Effect left behind when the enemy is deleted
+    pub death_effect: DeathEffect,
+}
+
+/// How an enemy goes out when deleted (see `spawn_death_effect`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DeathEffect {
+    /// Flash that swells and fades
+    Explosion,
+    /// Afterimage that rises and thins out
+    #[default]
+    Dissolve,
+    /// Shards flying out in every direction
+    Shatter,
 }
 
 impl Default for EnemyVisuals {
@@ -29,7 +45,9 @@ impl Default for EnemyVisuals {
             anchor: Vec2::new(0.0, -0.40),
             offset: Vec2::new(0.0, -8.0),
             flip_x: true,
+            tint: Color::WHITE,
             animations: EnemyAnimations::default(),
+            death_effect: DeathEffect::default(),
         }
     }
 }
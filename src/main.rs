@@ -8,52 +8,90 @@ use bevy::prelude::*;
 
 mod actions;
 mod assets;
+mod bindings;
 mod components;
 mod constants;
 mod enemies;
 mod resources;
+mod save;
 mod systems;
 mod weapons;
 
 use actions::ActionsPlugin;
+use bindings::KeyBindings;
 use components::{GameState, InputCooldown};
 use constants::MOVE_COOLDOWN;
 use enemies::EnemyPlugin;
 use resources::{
-    BattleTimer, CampaignProgress, GameProgress, PlayerCurrency, PlayerLoadout, PlayerUpgrades,
-    SelectedBattle, WaveState,
+    AssistOptions, AudioSettings, BattleRecords, BattleRewards, BattleStats, BattleTimer,
+    CampaignProgress, ColorScheme, ComboTracker, Difficulty, DiscoveredEnemies, GameProgress,
+    GameRng, LoadoutPresets, OwnedChips, PlayerCurrency, PlayerLoadout, PlayerUpgrades,
+    ReduceFlashing, SaveSlot, SelectedBattle, TileOwnership, WaveQueue, WaveState, battle_seed,
+    daily_seed,
 };
 use systems::{
-    action_ui::update_action_bar_ui,
+    action_ui::{update_action_bar_ui, update_action_tooltip},
     animation::{animate_player, animate_slime},
+    autosave::{SaveToast, setup_save_toast, update_save_toast},
+    boss_bar::{setup_boss_bar, update_boss_bar},
+    camera::{CameraConfig, fit_camera_to_window},
     campaign::{cleanup_campaign, setup_campaign, update_campaign},
     combat::{
-        bullet_movement, check_defeat_condition, check_victory_condition, enemy_bullet_hit_player,
-        enemy_bullet_movement, entity_flash, muzzle_lifetime, projectile_animation_system,
-        tile_attack_highlight, update_wave_state,
+        bullet_hit_obstacle, bullet_movement, check_defeat_condition, check_victory_condition,
+        collect_enemy_reward, decay_over_health, enemy_bullet_hit_player, enemy_bullet_movement,
+        entity_flash, fade_trail_segments, interrupt_charge_on_hit, muzzle_lifetime,
+        projectile_animation_system, projectile_collision_system, spawn_projectile_trails,
+        tile_attack_highlight, track_battle_damage, update_battle_timer_text, update_combo_text,
+        update_counter_popups, update_enemy_hp_bars, update_reward_text, update_wave_state,
     },
     common::update_transforms,
-    growth::{GrowthTreeState, cleanup_growth, setup_growth_tree, update_growth_tree},
+    encyclopedia::{
+        discover_enemies, persist_discovered_enemies, setup_encyclopedia, update_encyclopedia,
+    },
+    growth::{
+        GrowthTreeState, cleanup_growth, commit_pending_purchase, navigate_growth_tree,
+        setup_growth_tree, update_growth_tree,
+    },
     intro::{cleanup_intro, intro_complete, setup_intro, update_intro},
     loadout::{
-        cleanup_loadout, handle_inventory_selection, setup_loadout, update_details_panel,
-        update_inventory_details, update_inventory_visuals, update_loadout_input,
-        update_slot_visuals,
+        cleanup_loadout, handle_inventory_selection, handle_loadout_presets,
+        handle_recommend_loadout, setup_loadout, update_details_panel, update_inventory_details,
+        update_inventory_visuals, update_loadout_input, update_slot_visuals,
     },
     menu::{cleanup_menu, handle_menu_selection, setup_menu, update_menu_visuals},
+    minimap::{setup_minimap, update_minimap},
+    music::{cleanup_music_layers, setup_music_layers, update_music_layers},
     outro::{
         check_defeat_outro_complete, check_outro_complete, cleanup_outro, defeat_outro_active,
-        outro_not_active, setup_defeat_outro, setup_outro, update_defeat_outro, update_outro,
-        victory_outro_active,
+        outro_not_active, release_hit_stop, setup_defeat_outro, setup_outro,
+        toggle_battle_breakdown, update_defeat_outro, update_outro, victory_outro_active,
     },
+    overtime::{OvertimeRule, cleanup_overtime, process_overtime},
+    pause::{PauseState, cleanup_pause, not_paused, toggle_pause, update_pause_menu},
     player::move_player,
+    practice::{
+        DpsMeter, practice_active, record_dummy_deletion, respawn_practice_dummy, setup_practice,
+        track_practice_damage, update_dps_text,
+    },
+    replay::{
+        ReplayMode, drive_ghost, finish_replay, record_player_input, start_replay,
+        toggle_replay_mode,
+    },
+    reward::{cleanup_reward, setup_reward, update_reward},
+    settings::{cleanup_settings, setup_settings, update_settings},
     setup::{
-        cleanup_arena, cleanup_campaign_entities, cleanup_loadout_entities, cleanup_menu_entities,
-        cleanup_splash_entities, setup_action_bar, setup_arena, setup_global, spawn_player_actions,
+        advance_wave, cleanup_arena, cleanup_campaign_entities, cleanup_encyclopedia_entities,
+        cleanup_loadout_entities, cleanup_menu_entities, cleanup_reward_entities,
+        cleanup_settings_entities, cleanup_splash_entities, setup_action_bar, setup_arena,
+        setup_global, spawn_player_actions,
     },
+    shake::{ScreenShake, apply_screen_shake},
     splash::{animate_splash, cleanup_splash, setup_splash, update_splash},
+    tutorial::{
+        cleanup_tutorial, finish_tutorial, setup_tutorial, tutorial_active, update_tutorial,
+    },
 };
-use weapons::WeaponPlugin;
+use weapons::{PlayerWeapons, WeaponPlugin};
 
 fn main() {
     App::new()
@@ -75,14 +113,39 @@ fn main() {
             TimerMode::Once,
         )))
         .init_resource::<PlayerCurrency>()
+        .init_resource::<PauseState>()
         .init_resource::<GameProgress>()
         .init_resource::<PlayerUpgrades>()
         .init_resource::<WaveState>()
+        .init_resource::<WaveQueue>()
         .init_resource::<BattleTimer>()
+        .init_resource::<BattleRewards>()
+        .init_resource::<BattleStats>()
+        .init_resource::<ComboTracker>()
+        .init_resource::<DpsMeter>()
+        .init_resource::<ScreenShake>()
+        .init_resource::<TileOwnership>()
+        .init_resource::<GameRng>()
         .init_resource::<GrowthTreeState>()
         .init_resource::<CampaignProgress>()
+        .init_resource::<BattleRecords>()
         .init_resource::<SelectedBattle>()
         .init_resource::<PlayerLoadout>()
+        .init_resource::<OwnedChips>()
+        .init_resource::<LoadoutPresets>()
+        .init_resource::<AudioSettings>()
+        .init_resource::<Difficulty>()
+        .init_resource::<ColorScheme>()
+        .init_resource::<AssistOptions>()
+        .init_resource::<ReduceFlashing>()
+        .init_resource::<OvertimeRule>()
+        .init_resource::<CameraConfig>()
+        .init_resource::<DiscoveredEnemies>()
+        .init_resource::<KeyBindings>()
+        .init_resource::<PlayerWeapons>()
+        .init_resource::<ReplayMode>()
+        .init_resource::<SaveSlot>()
+        .init_resource::<SaveToast>()
         // Weapon system plugin
         .add_plugins(WeaponPlugin)
         // Action/chip system plugin
@@ -94,7 +157,13 @@ fn main() {
         // ====================================================================
         // Global startup (runs once)
         // ====================================================================
-        .add_systems(Startup, setup_global)
+        .add_systems(Startup, (setup_global, setup_save_toast))
+        // Camera shake decays on every screen, so it never sticks after a battle
+        .add_systems(Update, apply_screen_shake)
+        // Keep the arena fitted to the window as it is resized
+        .add_systems(Update, fit_camera_to_window)
+        // The "Saved" toast can follow an auto-save on any screen
+        .add_systems(Update, update_save_toast)
         // ====================================================================
         // Splash Screen
         // ====================================================================
@@ -132,6 +201,30 @@ fn main() {
             (cleanup_campaign, cleanup_campaign_entities),
         )
         // ====================================================================
+        // Settings
+        // ====================================================================
+        .add_systems(OnEnter(GameState::Settings), setup_settings)
+        .add_systems(
+            Update,
+            update_settings.run_if(in_state(GameState::Settings)),
+        )
+        .add_systems(
+            OnExit(GameState::Settings),
+            (cleanup_settings, cleanup_settings_entities),
+        )
+        // ====================================================================
+        // Enemy Encyclopedia
+        // ====================================================================
+        .add_systems(OnEnter(GameState::Encyclopedia), setup_encyclopedia)
+        .add_systems(
+            Update,
+            update_encyclopedia.run_if(in_state(GameState::Encyclopedia)),
+        )
+        .add_systems(
+            OnExit(GameState::Encyclopedia),
+            cleanup_encyclopedia_entities,
+        )
+        // ====================================================================
         // Loadout Menu
         // ====================================================================
         .add_systems(OnEnter(GameState::Loadout), setup_loadout)
@@ -140,6 +233,8 @@ fn main() {
             (
                 update_loadout_input,
                 handle_inventory_selection,
+                handle_recommend_loadout,
+                handle_loadout_presets,
                 update_slot_visuals,
                 update_details_panel,
                 update_inventory_visuals,
@@ -153,11 +248,28 @@ fn main() {
             (cleanup_loadout, cleanup_loadout_entities),
         )
         // ====================================================================
+        // Reward Screen
+        // ====================================================================
+        .add_systems(OnEnter(GameState::Reward), setup_reward)
+        .add_systems(Update, update_reward.run_if(in_state(GameState::Reward)))
+        .add_systems(
+            OnExit(GameState::Reward),
+            (cleanup_reward, cleanup_reward_entities),
+        )
+        // ====================================================================
         // Shop / Growth Tree
         // ====================================================================
         .add_systems(OnEnter(GameState::Shop), setup_growth_tree)
-        .add_systems(Update, update_growth_tree.run_if(in_state(GameState::Shop)))
-        .add_systems(OnExit(GameState::Shop), cleanup_growth)
+        .add_systems(
+            Update,
+            (navigate_growth_tree, update_growth_tree)
+                .chain()
+                .run_if(in_state(GameState::Shop)),
+        )
+        .add_systems(
+            OnExit(GameState::Shop),
+            (commit_pending_purchase, cleanup_growth),
+        )
         // ====================================================================
         // Playing (Arena)
         // ====================================================================
@@ -169,17 +281,43 @@ fn main() {
                 spawn_player_actions,
                 setup_intro,
                 reset_battle_timer,
+                reset_battle_rewards,
+                seed_battle_rng,
+                setup_practice,
+                setup_tutorial,
+                setup_minimap.after(setup_arena),
+                setup_boss_bar,
+                setup_music_layers,
+                start_replay.after(setup_arena),
             ),
         )
         // Pre-battle intro system (runs until countdown complete)
-        .add_systems(Update, update_intro.run_if(in_state(GameState::Playing)))
+        .add_systems(
+            Update,
+            update_intro
+                .run_if(in_state(GameState::Playing))
+                .run_if(not_paused),
+        )
         // Battle timer (only runs during active gameplay, not during outro)
         .add_systems(
             Update,
-            tick_battle_timer
+            (tick_battle_timer, process_overtime)
+                .chain()
                 .run_if(in_state(GameState::Playing))
                 .run_if(intro_complete)
-                .run_if(outro_not_active),
+                .run_if(outro_not_active)
+                .run_if(not_paused),
+        )
+        // Tutorial prompts (first launch only)
+        .add_systems(
+            Update,
+            (update_tutorial, finish_tutorial)
+                .chain()
+                .run_if(in_state(GameState::Playing))
+                .run_if(tutorial_active)
+                .run_if(intro_complete)
+                .run_if(outro_not_active)
+                .run_if(not_paused),
         )
         // Player input systems (only run after intro complete and not during outro)
         // NOTE: Action input is now handled by ActionsPlugin
@@ -188,24 +326,32 @@ fn main() {
             (
                 // Player systems
                 move_player,
+                record_player_input,
+                drive_ghost,
                 // Animation
                 animate_player,
             )
                 .run_if(in_state(GameState::Playing))
                 .run_if(intro_complete)
-                .run_if(outro_not_active),
+                .run_if(outro_not_active)
+                .run_if(not_paused),
         )
-        // Enemy animation and effects - chained to avoid Sprite conflicts
+        // Slime frames keep playing through the outro so the last kill's death animation finishes
         .add_systems(
             Update,
-            (
-                animate_slime,
-                enemies::animate_charging_telegraph,
-                entity_flash,
-            )
+            animate_slime
+                .before(enemies::animate_charging_telegraph)
+                .run_if(in_state(GameState::Playing))
+                .run_if(not_paused),
+        )
+        // Enemy effects - chained to avoid Sprite conflicts
+        .add_systems(
+            Update,
+            (enemies::animate_charging_telegraph, entity_flash)
                 .chain()
                 .run_if(in_state(GameState::Playing))
-                .run_if(outro_not_active),
+                .run_if(outro_not_active)
+                .run_if(not_paused),
         )
         .add_systems(
             Update,
@@ -215,34 +361,83 @@ fn main() {
                 // Combat
                 bullet_movement,
                 enemy_bullet_movement,
-                enemy_bullet_hit_player,
+                bullet_hit_obstacle,
+                projectile_collision_system
+                    .after(bullet_movement)
+                    .after(enemy_bullet_movement)
+                    .before(actions::process_shield_effects),
+                // Shields get first look at bullets on the player's tile
+                enemy_bullet_hit_player.after(actions::process_shield_effects),
+                interrupt_charge_on_hit.after(enemy_bullet_hit_player),
+                decay_over_health.after(enemy_bullet_hit_player),
                 tile_attack_highlight,
                 // Game Loop
                 update_wave_state,
                 check_victory_condition,
+                advance_wave,
                 check_defeat_condition,
             )
                 .run_if(in_state(GameState::Playing))
-                .run_if(outro_not_active),
+                .run_if(outro_not_active)
+                .run_if(not_paused),
         )
         .add_systems(
             Update,
             (
                 // Other effects
                 muzzle_lifetime,
+                (spawn_projectile_trails, fade_trail_segments).chain(),
                 // UI
                 update_action_bar_ui,
+                update_action_tooltip,
+                update_reward_text,
+                update_battle_timer_text,
+                update_combo_text,
+                update_counter_popups,
+                update_dps_text,
+                update_enemy_hp_bars,
+                update_minimap,
+                update_boss_bar,
+                update_music_layers,
+                discover_enemies,
+                toggle_replay_mode,
                 // Transform updates (should run last)
                 update_transforms,
-                // Back to menu on Escape (only when not in outro)
-                return_to_menu.run_if(outro_not_active),
+                // Pause menu on Escape (only when not in outro)
+                (toggle_pause, update_pause_menu)
+                    .chain()
+                    .run_if(outro_not_active),
             )
                 .run_if(in_state(GameState::Playing)),
         )
+        // Battle stats watch every HP change, including the killing blow
+        .add_systems(
+            Update,
+            track_battle_damage
+                .after(check_victory_condition)
+                .run_if(in_state(GameState::Playing)),
+        )
+        // Practice mode: dummies respawn and feed the DPS meter
+        .add_systems(
+            Update,
+            (track_practice_damage, respawn_practice_dummy)
+                .chain()
+                .after(check_victory_condition)
+                .run_if(in_state(GameState::Playing))
+                .run_if(practice_active)
+                .run_if(outro_not_active)
+                .run_if(not_paused),
+        )
         // Victory outro systems
         .add_systems(
             Update,
-            (setup_outro, update_outro, check_outro_complete)
+            (
+                setup_outro,
+                update_outro,
+                toggle_battle_breakdown,
+                release_hit_stop,
+                check_outro_complete,
+            )
                 .chain()
                 .run_if(in_state(GameState::Playing))
                 .run_if(victory_outro_active),
@@ -261,27 +456,92 @@ fn main() {
         )
         .add_systems(
             OnExit(GameState::Playing),
-            (cleanup_arena, cleanup_intro, cleanup_outro),
+            (
+                cleanup_arena,
+                cleanup_intro,
+                cleanup_outro,
+                cleanup_pause,
+                cleanup_tutorial,
+                cleanup_overtime,
+                cleanup_music_layers,
+                finish_replay,
+                persist_discovered_enemies,
+            ),
         )
+        // Enemy zenny drops are banked as enemies are deleted
+        .add_observer(collect_enemy_reward)
+        .add_observer(record_dummy_deletion)
         .run();
 }
 
-/// System to return to main menu when pressing Escape
-fn return_to_menu(
-    keyboard: Res<ButtonInput<KeyCode>>,
-    mut next_state: ResMut<NextState<GameState>>,
-) {
-    if keyboard.just_pressed(KeyCode::Escape) {
-        next_state.set(GameState::MainMenu);
-    }
-}
-
 /// Reset battle timer when entering Playing state
 fn reset_battle_timer(mut timer: ResMut<BattleTimer>) {
     timer.reset();
 }
 
+/// Clear the zenny tally, kill combo and battle stats when entering Playing state
+fn reset_battle_rewards(
+    mut rewards: ResMut<BattleRewards>,
+    mut combo: ResMut<ComboTracker>,
+    mut stats: ResMut<BattleStats>,
+) {
+    rewards.reset();
+    combo.reset();
+    stats.reset();
+}
+
+/// Reseed the battle RNG from today's seed and the selected battle
+fn seed_battle_rng(selected: Res<SelectedBattle>, mut rng: ResMut<GameRng>) {
+    *rng = GameRng::from_seed(battle_seed(daily_seed(), &selected));
+}
+
 /// Tick battle timer during active gameplay
 fn tick_battle_timer(time: Res<Time>, mut timer: ResMut<BattleTimer>) {
     timer.tick(time.delta_secs());
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use systems::pause::{PauseState, not_paused, toggle_pause};
+
+    #[test]
+    fn pausing_stops_the_battle_timer() {
+        let mut world = World::new();
+        world.init_resource::<Time>();
+        world.init_resource::<Time<Virtual>>();
+        world.init_resource::<BattleTimer>();
+        world.init_resource::<PauseState>();
+        world.init_resource::<ButtonInput<KeyCode>>();
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems((toggle_pause, tick_battle_timer.run_if(not_paused)).chain());
+        let mut frame = |world: &mut World, press_pause: bool| {
+            let mut keyboard = world.resource_mut::<ButtonInput<KeyCode>>();
+            keyboard.clear();
+            if press_pause {
+                keyboard.press(KeyCode::Escape);
+            } else {
+                keyboard.release(KeyCode::Escape);
+            }
+            world
+                .resource_mut::<Time>()
+                .advance_by(Duration::from_millis(500));
+            schedule.run(world);
+        };
+
+        frame(&mut world, false);
+        assert_eq!(world.resource::<BattleTimer>().elapsed, 0.5);
+
+        frame(&mut world, true);
+        frame(&mut world, false);
+        assert!(world.resource::<PauseState>().paused);
+        assert_eq!(world.resource::<BattleTimer>().elapsed, 0.5);
+
+        frame(&mut world, true);
+        assert!(!world.resource::<PauseState>().paused);
+        assert_eq!(world.resource::<BattleTimer>().elapsed, 1.0);
+    }
+}
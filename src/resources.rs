@@ -1,14 +1,56 @@
+use bevy::audio::Volume;
 use bevy::prelude::*;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 
 use crate::constants::{
-    ARENA_Y_OFFSET, GRID_HEIGHT, GRID_WIDTH, ROW_SKEW_X, TILE_ASSET_HEIGHT, TILE_ASSET_WIDTH,
-    TILE_LIP_HEIGHT,
+    ARENA_Y_OFFSET, GRID_HEIGHT, GRID_WIDTH, PLAYER_AREA_WIDTH, ROW_SKEW_X, TILE_ASSET_HEIGHT,
+    TILE_ASSET_WIDTH, TILE_LIP_HEIGHT, WAVE_BANNER_TIME,
 };
 
 // ============================================================================
 // Arena Layout Resource (Responsive Scaling)
 // ============================================================================
 
+/// Dimensions of a battle's grid, chosen per battle by `ArenaConfig`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GridSize {
+    pub width: i32,
+    pub height: i32,
+    /// Columns starting out as the player's side
+    pub player_area_width: i32,
+}
+
+impl GridSize {
+    /// A `width` x `height` grid whose first `player_area_width` columns
+    /// start out as the player's
+    pub const fn new(width: i32, height: i32, player_area_width: i32) -> Self {
+        Self {
+            width,
+            height,
+            player_area_width,
+        }
+    }
+
+    /// Whether tile (x, y) lies on the grid
+    pub fn contains(&self, x: i32, y: i32) -> bool {
+        (0..self.width).contains(&x) && (0..self.height).contains(&y)
+    }
+}
+
+impl Default for GridSize {
+    /// The classic 6x3 grid split down the middle
+    fn default() -> Self {
+        Self {
+            width: GRID_WIDTH,
+            height: GRID_HEIGHT,
+            player_area_width: PLAYER_AREA_WIDTH,
+        }
+    }
+}
+
 /// Computed arena layout based on screen dimensions.
 /// Tiles scale to fill screen width while maintaining aspect ratio.
 #[derive(Resource, Debug, Clone)]
@@ -17,7 +59,9 @@ pub struct ArenaLayout {
     pub screen_width: f32,
     /// Current screen height
     pub screen_height: f32,
-    /// Computed tile width (fills screen width with GRID_WIDTH tiles)
+    /// Grid the layout is computed for
+    pub grid: GridSize,
+    /// Computed tile width (fills screen width with `grid.width` tiles)
     pub tile_width: f32,
     /// Computed tile height (maintains aspect ratio with original asset)
     pub tile_height: f32,
@@ -42,11 +86,16 @@ impl Default for ArenaLayout {
 }
 
 impl ArenaLayout {
-    /// Compute arena layout from screen dimensions.
+    /// Compute arena layout from screen dimensions for the default grid.
     /// Tiles fill the full screen width.
     pub fn from_screen_size(screen_width: f32, screen_height: f32) -> Self {
+        Self::for_grid(screen_width, screen_height, GridSize::default())
+    }
+
+    /// Compute arena layout from screen dimensions for a grid of any size
+    pub fn for_grid(screen_width: f32, screen_height: f32, grid: GridSize) -> Self {
         // Tile width = screen width / number of columns
-        let tile_width = screen_width / GRID_WIDTH as f32;
+        let tile_width = screen_width / grid.width as f32;
 
         // Scale factor based on width
         let scale = tile_width / TILE_ASSET_WIDTH;
@@ -59,6 +108,7 @@ impl ArenaLayout {
         Self {
             screen_width,
             screen_height,
+            grid,
             tile_width,
             tile_height,
             lip_height,
@@ -72,8 +122,8 @@ impl ArenaLayout {
 
     /// Get world position for tile sprite center at grid (x, y)
     pub fn tile_sprite_world(&self, x: i32, y: i32) -> Vec2 {
-        let center_x = (GRID_WIDTH as f32 - 1.0) / 2.0;
-        let center_y = (GRID_HEIGHT as f32 - 1.0) / 2.0;
+        let center_x = (self.grid.width as f32 - 1.0) / 2.0;
+        let center_y = (self.grid.height as f32 - 1.0) / 2.0;
 
         let relative_x = (x as f32) - center_x;
         let relative_y = (y as f32) - center_y;
@@ -115,7 +165,7 @@ impl ArenaLayout {
 // ============================================================================
 
 /// Tracks the player's currency
-#[derive(Resource, Debug, Default, Clone, Copy)]
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub struct PlayerCurrency {
     pub zenny: u64,
 }
@@ -134,7 +184,7 @@ impl GameProgress {
 }
 
 /// Persistent stats that can be upgraded
-#[derive(Resource, Debug, Clone, Copy, Default)]
+#[derive(Resource, Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct PlayerUpgrades {
     /// Weapon base damage upgrade count
     pub damage_level: u32,
@@ -144,6 +194,9 @@ pub struct PlayerUpgrades {
     pub fire_rate_level: u32,
     /// Critical chance upgrade count
     pub crit_chance_level: u32,
+    /// Chip (action) cooldown reduction upgrade count
+    #[serde(default)]
+    pub chip_cooldown_level: u32,
 }
 
 #[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq)]
@@ -151,9 +204,153 @@ pub enum WaveState {
     #[default]
     Spawning,
     Active,
+    /// Current wave cleared, next wave waiting behind the "WAVE n" banner
+    Intermission,
     Cleared,
 }
 
+/// Enemy waves of the current battle that have not spawned yet
+#[derive(Resource, Debug, Default)]
+pub struct WaveQueue {
+    pub pending: std::collections::VecDeque<Vec<EnemyConfig>>,
+    /// 1-based number of the wave currently on the field
+    pub current: usize,
+    /// Delay before the next wave spawns (runs while the banner is shown)
+    pub banner_timer: Timer,
+}
+
+impl WaveQueue {
+    /// Queue every wave after the first (which `setup_arena` spawns directly)
+    pub fn new(waves: &[Vec<EnemyConfig>]) -> Self {
+        Self {
+            pending: waves.iter().skip(1).cloned().collect(),
+            current: 1,
+            banner_timer: Timer::from_seconds(WAVE_BANNER_TIME, TimerMode::Once),
+        }
+    }
+
+    pub fn has_next(&self) -> bool {
+        !self.pending.is_empty()
+    }
+
+    /// Take the next wave and restart the banner timer for the one after it
+    pub fn advance(&mut self) -> Option<Vec<EnemyConfig>> {
+        let wave = self.pending.pop_front()?;
+        self.current += 1;
+        self.banner_timer.reset();
+        Some(wave)
+    }
+}
+
+/// Tiles enemies are about to strike, refreshed every frame from charging
+/// enemies and tinted as a warning by `tile_attack_highlight`
+#[derive(Resource, Debug, Default)]
+pub struct TelegraphedTiles(pub Vec<(i32, i32)>);
+
+/// Side a grid tile belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TileOwner {
+    Player,
+    Enemy,
+}
+
+/// Who owns each tile of the grid. Player and enemies may only stand on their
+/// own tiles; panel stealing moves the boundary. Reset at the start of every battle.
+#[derive(Resource, Debug, Clone, PartialEq, Eq)]
+pub struct TileOwnership {
+    width: i32,
+    height: i32,
+    /// Row-major, `width * height` entries
+    owners: Vec<TileOwner>,
+}
+
+impl Default for TileOwnership {
+    fn default() -> Self {
+        Self::new(GridSize::default())
+    }
+}
+
+impl TileOwnership {
+    /// The classic split: the first `player_area_width` columns are the player's
+    pub fn new(grid: GridSize) -> Self {
+        let owners = (0..grid.height)
+            .flat_map(|_| {
+                (0..grid.width).map(|x| {
+                    if x < grid.player_area_width {
+                        TileOwner::Player
+                    } else {
+                        TileOwner::Enemy
+                    }
+                })
+            })
+            .collect();
+        Self {
+            width: grid.width,
+            height: grid.height,
+            owners,
+        }
+    }
+
+    pub fn width(&self) -> i32 {
+        self.width
+    }
+
+    pub fn height(&self) -> i32 {
+        self.height
+    }
+
+    fn index(&self, x: i32, y: i32) -> Option<usize> {
+        ((0..self.width).contains(&x) && (0..self.height).contains(&y))
+            .then(|| (y * self.width + x) as usize)
+    }
+
+    /// Owner of a tile (None outside the grid)
+    pub fn owner(&self, x: i32, y: i32) -> Option<TileOwner> {
+        self.index(x, y).map(|i| self.owners[i])
+    }
+
+    pub fn set(&mut self, x: i32, y: i32, owner: TileOwner) {
+        if let Some(i) = self.index(x, y) {
+            self.owners[i] = owner;
+        }
+    }
+
+    pub fn is_player_tile(&self, x: i32, y: i32) -> bool {
+        self.owner(x, y) == Some(TileOwner::Player)
+    }
+
+    pub fn is_enemy_tile(&self, x: i32, y: i32) -> bool {
+        self.owner(x, y) == Some(TileOwner::Enemy)
+    }
+
+    /// Every tile owned by `owner`, column by column
+    pub fn tiles_owned_by(&self, owner: TileOwner) -> Vec<(i32, i32)> {
+        (0..self.width)
+            .flat_map(|x| (0..self.height).map(move |y| (x, y)))
+            .filter(|&(x, y)| self.owner(x, y) == Some(owner))
+            .collect()
+    }
+
+    /// Tiles a panel steal of `columns` claims: the frontmost `columns` enemy
+    /// tiles of each row. The last enemy tile of a row is never taken, so
+    /// enemies always keep somewhere to stand.
+    pub fn steal_targets(&self, columns: i32) -> Vec<(i32, i32)> {
+        let mut targets = Vec::new();
+        for y in 0..self.height {
+            let row: Vec<i32> = (0..self.width)
+                .filter(|&x| self.is_enemy_tile(x, y))
+                .collect();
+            let stealable = row.len().saturating_sub(1);
+            targets.extend(
+                row.into_iter()
+                    .take((columns.max(0) as usize).min(stealable))
+                    .map(|x| (x, y)),
+            );
+        }
+        targets
+    }
+}
+
 /// Tracks elapsed battle time (for victory screen stats)
 #[derive(Resource, Debug, Default)]
 pub struct BattleTimer {
@@ -170,14 +367,378 @@ impl BattleTimer {
     }
 }
 
+/// Zenny earned from enemies deleted during the current battle
+#[derive(Resource, Debug, Default, Clone, Copy)]
+pub struct BattleRewards {
+    pub zenny: u64,
+}
+
+impl BattleRewards {
+    pub fn reset(&mut self) {
+        self.zenny = 0;
+    }
+
+    pub fn add(&mut self, zenny: u64) {
+        self.zenny += zenny;
+    }
+}
+
+/// How the current battle was fought, broken down on the victory screen
+#[derive(Resource, Debug, Default, Clone)]
+pub struct BattleStats {
+    /// Times each chip was used, in order of first use
+    pub chips_used: Vec<(ActionId, u32)>,
+    pub shots_fired: u32,
+    pub damage_dealt: i32,
+    pub damage_taken: i32,
+    /// HP each entity had when `track_hp` last saw it
+    last_hp: HashMap<Entity, i32>,
+}
+
+impl BattleStats {
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    pub fn record_chip(&mut self, action_id: ActionId) {
+        match self.chips_used.iter_mut().find(|(id, _)| *id == action_id) {
+            Some((_, count)) => *count += 1,
+            None => self.chips_used.push((action_id, 1)),
+        }
+    }
+
+    pub fn chip_uses(&self, action_id: ActionId) -> u32 {
+        self.chips_used
+            .iter()
+            .find(|(id, _)| *id == action_id)
+            .map_or(0, |(_, count)| *count)
+    }
+
+    pub fn record_shot(&mut self) {
+        self.shots_fired += 1;
+    }
+
+    /// Note an entity's current HP. HP lost since it was last seen counts as
+    /// damage taken for the player and damage dealt for anything else.
+    pub fn track_hp(&mut self, entity: Entity, current: i32, is_player: bool) {
+        let Some(last) = self.last_hp.insert(entity, current) else {
+            return;
+        };
+        let lost = (last - current).max(0);
+        if is_player {
+            self.damage_taken += lost;
+        } else {
+            self.damage_dealt += lost;
+        }
+    }
+
+    /// Multi-line summary: totals first, then one line per chip
+    pub fn breakdown(&self) -> String {
+        let mut lines = vec![format!(
+            "SHOTS {}  DEALT {}  TAKEN {}",
+            self.shots_fired, self.damage_dealt, self.damage_taken
+        )];
+        if self.chips_used.is_empty() {
+            lines.push("No chips used".to_string());
+        }
+        for (action_id, count) in &self.chips_used {
+            lines.push(format!(
+                "{} x{}",
+                ActionBlueprint::get(*action_id).name,
+                count
+            ));
+        }
+        lines.join("\n")
+    }
+}
+
+/// Seconds after a kill in which the next kill extends the combo
+pub const COMBO_WINDOW: f32 = 2.0;
+/// Reward bonus added per combo step beyond the first kill
+pub const COMBO_STEP_BONUS: f32 = 0.25;
+/// Highest multiplier a combo can reach
+pub const COMBO_MAX_MULTIPLIER: f32 = 2.0;
+
+/// Reward multiplier for a combo of `count` kills: x1.0, x1.25, x1.5, ... up to the cap
+pub fn combo_multiplier(count: u32) -> f32 {
+    let steps = count.saturating_sub(1) as f32;
+    (1.0 + steps * COMBO_STEP_BONUS).min(COMBO_MAX_MULTIPLIER)
+}
+
+/// Chain of kills landed within `COMBO_WINDOW` of each other (battle-timer seconds)
+#[derive(Resource, Debug, Default, Clone, Copy)]
+pub struct ComboTracker {
+    pub count: u32,
+    pub last_kill_at: Option<f32>,
+}
+
+impl ComboTracker {
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Record a kill at `now`, returning the combo count it lands at
+    pub fn register_kill(&mut self, now: f32) -> u32 {
+        self.count = if self.is_active(now) {
+            self.count + 1
+        } else {
+            1
+        };
+        self.last_kill_at = Some(now);
+        self.count
+    }
+
+    /// Whether a kill at `now` would still extend the combo
+    pub fn is_active(&self, now: f32) -> bool {
+        self.last_kill_at
+            .is_some_and(|last| now - last <= COMBO_WINDOW)
+    }
+
+    /// Apply the combo multiplier for `count` to a base reward
+    pub fn scale_reward(base: u64, count: u32) -> u64 {
+        (base as f32 * combo_multiplier(count)).round() as u64
+    }
+}
+
+// ============================================================================
+// Battle RNG
+// ============================================================================
+
+/// Seeded RNG for everything random in a battle: crit rolls, enemy movement,
+/// summon placement and random chip targeting. Reseeded when a battle starts,
+/// so the same seed replays the same rolls.
+#[derive(Resource)]
+pub struct GameRng(pub StdRng);
+
+impl GameRng {
+    pub fn from_seed(seed: u64) -> Self {
+        Self(StdRng::seed_from_u64(seed))
+    }
+}
+
+impl Default for GameRng {
+    fn default() -> Self {
+        Self::from_seed(daily_seed())
+    }
+}
+
+/// Seed shared by every run on the same (UTC) day
+pub fn daily_seed() -> u64 {
+    // There is no wall clock on the web build, so browsers get a fresh seed per run
+    #[cfg(target_arch = "wasm32")]
+    {
+        rand::random()
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs() / 86_400)
+            .unwrap_or(0)
+    }
+}
+
+/// Mix a base seed with the battle being played, so each battle gets its own sequence
+pub fn battle_seed(base: u64, battle: &SelectedBattle) -> u64 {
+    let id = ((battle.arc as u64) << 32) | battle.battle as u64;
+    base ^ id.wrapping_add(1).wrapping_mul(0x9E37_79B9_7F4A_7C15)
+}
+
+// ============================================================================
+// Audio Settings
+// ============================================================================
+
+/// Amount a volume changes per step on the settings screen
+pub const VOLUME_STEP: f32 = 0.1;
+
+/// Player volume levels (0.0 - 1.0). Every `PlaybackSettings` scales its base
+/// volume through `music_volume` / `sfx_volume`.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AudioSettings {
+    pub master: f32,
+    pub music: f32,
+    pub sfx: f32,
+}
+
+impl Default for AudioSettings {
+    fn default() -> Self {
+        Self {
+            master: 1.0,
+            music: 1.0,
+            sfx: 1.0,
+        }
+    }
+}
+
+impl AudioSettings {
+    /// Volume for background music with the given base loudness
+    pub fn music_volume(&self, base: f32) -> Volume {
+        Volume::Linear(base * self.master * self.music)
+    }
+
+    /// Volume for a sound effect with the given base loudness
+    pub fn sfx_volume(&self, base: f32) -> Volume {
+        Volume::Linear(base * self.master * self.sfx)
+    }
+
+    /// Step a volume level up or down, clamped to 0.0 - 1.0
+    pub fn step(level: &mut f32, steps: i32) {
+        // Round to whole steps so repeated presses never drift
+        let stepped = ((*level / VOLUME_STEP).round() as i32 + steps) as f32 * VOLUME_STEP;
+        *level = stepped.clamp(0.0, 1.0);
+    }
+}
+
+// ============================================================================
+// Difficulty
+// ============================================================================
+
+/// Challenge level picked on the settings screen. Scales enemy HP and damage when
+/// enemies spawn, and the zenny they drop (harder pays more).
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+}
+
+impl Difficulty {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Difficulty::Easy => "Easy",
+            Difficulty::Normal => "Normal",
+            Difficulty::Hard => "Hard",
+        }
+    }
+
+    /// Next level in menu order (wraps around)
+    pub fn next(&self) -> Self {
+        match self {
+            Difficulty::Easy => Difficulty::Normal,
+            Difficulty::Normal => Difficulty::Hard,
+            Difficulty::Hard => Difficulty::Easy,
+        }
+    }
+
+    pub fn hp_multiplier(&self) -> f32 {
+        match self {
+            Difficulty::Easy => 0.7,
+            Difficulty::Normal => 1.0,
+            Difficulty::Hard => 1.4,
+        }
+    }
+
+    pub fn damage_multiplier(&self) -> f32 {
+        match self {
+            Difficulty::Easy => 0.6,
+            Difficulty::Normal => 1.0,
+            Difficulty::Hard => 1.3,
+        }
+    }
+
+    pub fn reward_multiplier(&self) -> f32 {
+        match self {
+            Difficulty::Easy => 0.75,
+            Difficulty::Normal => 1.0,
+            Difficulty::Hard => 1.5,
+        }
+    }
+
+    /// Enemy HP at this difficulty (never below 1)
+    pub fn scale_hp(&self, hp: i32) -> i32 {
+        ((hp as f32 * self.hp_multiplier()).round() as i32).max(1)
+    }
+
+    /// Enemy attack damage at this difficulty (damaging attacks stay at 1 or more)
+    pub fn scale_damage(&self, damage: i32) -> i32 {
+        if damage <= 0 {
+            return damage;
+        }
+        ((damage as f32 * self.damage_multiplier()).round() as i32).max(1)
+    }
+
+    /// Zenny drop at this difficulty
+    pub fn scale_reward(&self, zenny: u64) -> u64 {
+        (zenny as f32 * self.reward_multiplier()).round() as u64
+    }
+}
+
+/// Palette used for element, rarity and panel colors. `Colorblind` swaps the
+/// hue-heavy defaults for a set that stays apart under red-green colorblindness.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ColorScheme {
+    #[default]
+    Default,
+    Colorblind,
+}
+
+impl ColorScheme {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ColorScheme::Default => "Default",
+            ColorScheme::Colorblind => "Colorblind",
+        }
+    }
+
+    /// The other scheme
+    pub fn next(&self) -> Self {
+        match self {
+            ColorScheme::Default => ColorScheme::Colorblind,
+            ColorScheme::Colorblind => ColorScheme::Default,
+        }
+    }
+}
+
+/// Optional assists toggled on the settings screen
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AssistOptions {
+    /// Equipped heal chips fire on their own once HP drops below
+    /// `PANIC_HEAL_THRESHOLD` (still one use per cooldown)
+    pub auto_heal: bool,
+}
+
+/// Accessibility setting: fast flashing (charge telegraphs, hit flashes,
+/// blinking prompts) is swapped for steady or slow color changes
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct ReduceFlashing(pub bool);
+
+impl ReduceFlashing {
+    pub fn label(&self) -> &'static str {
+        if self.0 { "On" } else { "Off" }
+    }
+}
+
+/// Number of save slots offered on the main menu
+pub const SAVE_SLOT_COUNT: usize = 3;
+
+/// Save slot progress is loaded from and auto-saved to, picked on the main menu
+/// (0-based; shown to the player as 1-3). Remembered in settings.json.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct SaveSlot(pub usize);
+
+impl SaveSlot {
+    /// Slot number as shown on the menu
+    pub fn number(&self) -> usize {
+        self.0 + 1
+    }
+
+    /// Next slot in menu order (wraps around)
+    pub fn next(&self) -> Self {
+        Self((self.0 + 1) % SAVE_SLOT_COUNT)
+    }
+}
+
 // ============================================================================
 // Player Loadout Resource
 // ============================================================================
 
-use crate::actions::ActionId;
+use crate::actions::{ActionBlueprint, ActionId};
 
 /// Persistent player loadout - which actions are equipped
-#[derive(Resource, Debug, Clone)]
+#[derive(Resource, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct PlayerLoadout {
     /// 4 action slots (Some = equipped, None = empty)
     pub slots: [Option<ActionId>; 4],
@@ -204,16 +765,28 @@ impl PlayerLoadout {
 
     /// Check if an action is already equipped
     pub fn is_equipped(&self, action_id: ActionId) -> bool {
-        self.slots.iter().any(|s| *s == Some(action_id))
+        self.slots.contains(&Some(action_id))
+    }
+
+    /// Whether `action_id` may go in `slot`: a chip can be equipped twice, but
+    /// only in adjacent slots (see `adjacency_bonus`)
+    pub fn can_equip(&self, slot: usize, action_id: ActionId) -> bool {
+        let copies: Vec<usize> = (0..self.slots.len())
+            .filter(|&other| other != slot && self.slots[other] == Some(action_id))
+            .collect();
+        match copies.as_slice() {
+            [] => true,
+            [other] => other.abs_diff(slot) == 1,
+            _ => false,
+        }
     }
 
-    /// Equip an action to a slot (returns false if already equipped elsewhere)
+    /// Equip an action to a slot (returns false if `can_equip` refuses it)
     pub fn equip(&mut self, slot: usize, action_id: ActionId) -> bool {
         if slot >= 4 {
             return false;
         }
-        // Check if already equipped in another slot
-        if self.is_equipped(action_id) {
+        if !self.can_equip(slot, action_id) {
             return false;
         }
         self.slots[slot] = Some(action_id);
@@ -228,20 +801,112 @@ impl PlayerLoadout {
     }
 }
 
+/// Number of loadout presets the player can save
+pub const LOADOUT_PRESET_COUNT: usize = 3;
+
+/// Saved slot arrangements for quick equipping from the loadout menu
+#[derive(Resource, Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LoadoutPresets {
+    pub presets: [Option<[Option<ActionId>; 4]>; LOADOUT_PRESET_COUNT],
+}
+
+impl LoadoutPresets {
+    /// Store the current slots in a preset (returns false for an invalid index)
+    pub fn save(&mut self, index: usize, loadout: &PlayerLoadout) -> bool {
+        let Some(preset) = self.presets.get_mut(index) else {
+            return false;
+        };
+        *preset = Some(loadout.slots);
+        true
+    }
+
+    /// A preset's slots with chips that are no longer owned left empty
+    pub fn load(&self, index: usize, owned: &OwnedChips) -> Option<[Option<ActionId>; 4]> {
+        let slots = (*self.presets.get(index)?)?;
+        Some(slots.map(|slot| slot.filter(|id| owned.contains(*id))))
+    }
+}
+
+/// Chips the player has earned. The loadout inventory only lists these.
+#[derive(Resource, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OwnedChips(pub HashSet<ActionId>);
+
+impl Default for OwnedChips {
+    /// Starter folder: the default loadout plus a basic buster and sword
+    fn default() -> Self {
+        Self(HashSet::from([
+            ActionId::Recov10,
+            ActionId::Recov50,
+            ActionId::Shield,
+            ActionId::Sword,
+            ActionId::WideSwrd,
+            ActionId::Cannon,
+        ]))
+    }
+}
+
+impl OwnedChips {
+    pub fn contains(&self, action_id: ActionId) -> bool {
+        self.0.contains(&action_id)
+    }
+
+    /// Add a chip (returns false if it was already owned)
+    pub fn add(&mut self, action_id: ActionId) -> bool {
+        self.0.insert(action_id)
+    }
+
+    /// The owned chips out of `catalog`, keeping catalog order
+    pub fn filter(&self, catalog: &[ActionId]) -> Vec<ActionId> {
+        catalog
+            .iter()
+            .copied()
+            .filter(|id| self.contains(*id))
+            .collect()
+    }
+}
+
+/// Enemy types the player has met in battle (saved to bestiary.json). The
+/// encyclopedia screen only describes these.
+#[derive(Resource, Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DiscoveredEnemies(pub HashSet<EnemyId>);
+
+impl DiscoveredEnemies {
+    pub fn contains(&self, enemy_id: EnemyId) -> bool {
+        self.0.contains(&enemy_id)
+    }
+
+    /// Record an enemy type (returns false if it was already discovered)
+    pub fn discover(&mut self, enemy_id: EnemyId) -> bool {
+        self.0.insert(enemy_id)
+    }
+}
+
 // ============================================================================
 // Campaign Resources
 // ============================================================================
 
-use crate::components::EnemyConfig;
-use crate::enemies::EnemyId;
+use crate::components::{EnemyConfig, ObstacleConfig};
+use crate::enemies::{AttackBehavior, EnemyId};
+use crate::weapons::WeaponType;
 
 /// Tracks campaign progress (unlocked arcs, completed battles)
-#[derive(Resource, Debug, Clone)]
+#[derive(Resource, Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct CampaignProgress {
     /// Highest unlocked arc index (0-based)
     pub unlocked_arc: usize,
     /// For each arc, which battles have been completed (true = won)
     pub completed_battles: Vec<Vec<bool>>,
+    /// The first-launch tutorial has been played through. Saves from before
+    /// the tutorial existed count as done.
+    #[serde(default = "tutorial_complete_in_old_saves")]
+    pub tutorial_complete: bool,
+    /// Campaign battle started most recently (the main menu's Continue)
+    #[serde(default)]
+    pub last_battle: Option<SelectedBattle>,
+}
+
+fn tutorial_complete_in_old_saves() -> bool {
+    true
 }
 
 impl Default for CampaignProgress {
@@ -249,6 +914,8 @@ impl Default for CampaignProgress {
         Self {
             unlocked_arc: 0,
             completed_battles: vec![vec![false; 10]], // Arc 1 has 10 battles
+            tutorial_complete: false,
+            last_battle: None,
         }
     }
 }
@@ -275,8 +942,12 @@ impl CampaignProgress {
         }
         self.completed_battles[arc][battle] = true;
 
-        // Check if boss battle (index 9) was completed to unlock next arc
-        if battle == 9 && arc == self.unlocked_arc {
+        // Check if the arc's boss battle was completed to unlock next arc
+        let is_boss = get_all_arcs()
+            .get(arc)
+            .and_then(|a| a.battles.get(battle))
+            .is_some_and(|b| b.is_boss);
+        if is_boss && arc == self.unlocked_arc {
             self.unlocked_arc += 1;
         }
     }
@@ -287,20 +958,97 @@ impl CampaignProgress {
     }
 }
 
+/// Best clear of one campaign battle
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct BattleRecord {
+    pub arc: usize,
+    pub battle: usize,
+    /// Fastest clear, in seconds
+    pub best_time: f32,
+    /// Most zenny earned in one clear (including the time bonus)
+    pub best_reward: u64,
+}
+
+/// Best clear time and reward per campaign battle (saved to records.json)
+#[derive(Resource, Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct BattleRecords {
+    records: Vec<BattleRecord>,
+}
+
+impl BattleRecords {
+    /// Record for a battle that has been cleared at least once
+    pub fn get(&self, arc: usize, battle: usize) -> Option<&BattleRecord> {
+        self.records
+            .iter()
+            .find(|record| record.arc == arc && record.battle == battle)
+    }
+
+    /// Fold a clear into the battle's record; time and reward are kept
+    /// separately, so each only changes when beaten. Returns true if
+    /// anything improved.
+    pub fn record_clear(&mut self, arc: usize, battle: usize, time: f32, reward: u64) -> bool {
+        let Some(record) = self
+            .records
+            .iter_mut()
+            .find(|record| record.arc == arc && record.battle == battle)
+        else {
+            self.records.push(BattleRecord {
+                arc,
+                battle,
+                best_time: time,
+                best_reward: reward,
+            });
+            return true;
+        };
+
+        let improved = time < record.best_time || reward > record.best_reward;
+        record.best_time = record.best_time.min(time);
+        record.best_reward = record.best_reward.max(reward);
+        improved
+    }
+}
+
 /// Currently selected battle to play
-#[derive(Resource, Debug, Clone, Default)]
+#[derive(Resource, Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct SelectedBattle {
     pub arc: usize,
     pub battle: usize,
 }
 
 /// Definition of a single battle encounter
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct BattleDef {
     pub name: &'static str,
     pub description: &'static str,
     pub enemies: Vec<EnemyConfig>,
     pub is_boss: bool,
+    /// Size of the grid the battle is fought on
+    pub grid: GridSize,
+    /// Rocks placed on the field at battle start
+    pub obstacles: Vec<ObstacleConfig>,
+    /// Follow-up waves, each spawned once the previous one is cleared
+    pub reinforcements: Vec<Vec<EnemyConfig>>,
+}
+
+impl BattleDef {
+    /// All waves in spawn order (`enemies` first, then `reinforcements`).
+    /// In a boss battle the first enemy is flagged as the boss.
+    pub fn waves(&self) -> Vec<Vec<EnemyConfig>> {
+        let mut waves: Vec<Vec<EnemyConfig>> = std::iter::once(self.enemies.clone())
+            .chain(self.reinforcements.iter().cloned())
+            .collect();
+        if let Some(boss) = waves[0].first_mut().filter(|_| self.is_boss) {
+            boss.boss = true;
+        }
+        waves
+    }
+
+    /// Every enemy fought in this battle, across all waves
+    pub fn all_enemies(&self) -> impl Iterator<Item = &EnemyConfig> {
+        self.enemies
+            .iter()
+            .chain(self.reinforcements.iter().flatten())
+    }
 }
 
 /// Definition of a campaign arc (10 battles)
@@ -309,11 +1057,13 @@ pub struct ArcDef {
     pub name: &'static str,
     pub description: &'static str,
     pub battles: Vec<BattleDef>,
+    /// Weapon unlocked by beating this arc's boss
+    pub weapon_reward: Option<WeaponType>,
 }
 
 /// Get all arc definitions
 pub fn get_all_arcs() -> Vec<ArcDef> {
-    vec![arc_1_slime_invasion()]
+    vec![arc_1_slime_invasion(), arc_2_royal_court()]
 }
 
 /// Arc 1: Slime Invasion
@@ -328,6 +1078,7 @@ fn arc_1_slime_invasion() -> ArcDef {
                 description: "1x Slime",
                 enemies: vec![EnemyConfig::new(EnemyId::Slime, 4, 1)],
                 is_boss: false,
+                ..Default::default()
             },
             // Battle 2: 2x Slime
             BattleDef {
@@ -338,6 +1089,7 @@ fn arc_1_slime_invasion() -> ArcDef {
                     EnemyConfig::new(EnemyId::Slime, 4, 2),
                 ],
                 is_boss: false,
+                ..Default::default()
             },
             // Battle 3: 3x Slime
             BattleDef {
@@ -349,6 +1101,7 @@ fn arc_1_slime_invasion() -> ArcDef {
                     EnemyConfig::new(EnemyId::Slime, 4, 2),
                 ],
                 is_boss: false,
+                ..Default::default()
             },
             // Battle 4: 1x Slime2
             BattleDef {
@@ -356,6 +1109,7 @@ fn arc_1_slime_invasion() -> ArcDef {
                 description: "1x Slime II",
                 enemies: vec![EnemyConfig::new(EnemyId::Slime2, 4, 1)],
                 is_boss: false,
+                ..Default::default()
             },
             // Battle 5: 1x Slime2, 1x Slime
             BattleDef {
@@ -366,6 +1120,7 @@ fn arc_1_slime_invasion() -> ArcDef {
                     EnemyConfig::new(EnemyId::Slime, 4, 0),
                 ],
                 is_boss: false,
+                ..Default::default()
             },
             // Battle 6: 1x Slime2, 2x Slime
             BattleDef {
@@ -377,18 +1132,21 @@ fn arc_1_slime_invasion() -> ArcDef {
                     EnemyConfig::new(EnemyId::Slime, 4, 2),
                 ],
                 is_boss: false,
+                ..Default::default()
             },
             // Battle 7: 1x Slime2, 3x Slime
             BattleDef {
                 name: "Slime Swarm",
-                description: "1x Slime II, 3x Slime",
+                description: "1x Slime II, 3x Slime on a wide field",
                 enemies: vec![
-                    EnemyConfig::new(EnemyId::Slime2, 5, 1),
-                    EnemyConfig::new(EnemyId::Slime, 4, 0),
-                    EnemyConfig::new(EnemyId::Slime, 4, 2),
-                    EnemyConfig::new(EnemyId::Slime, 3, 1),
+                    EnemyConfig::new(EnemyId::Slime2, 7, 1),
+                    EnemyConfig::new(EnemyId::Slime, 5, 0),
+                    EnemyConfig::new(EnemyId::Slime, 5, 2),
+                    EnemyConfig::new(EnemyId::Slime, 4, 1),
                 ],
                 is_boss: false,
+                grid: GridSize::new(8, 3, 3),
+                ..Default::default()
             },
             // Battle 8: 2x Slime2
             BattleDef {
@@ -399,6 +1157,7 @@ fn arc_1_slime_invasion() -> ArcDef {
                     EnemyConfig::new(EnemyId::Slime2, 4, 2),
                 ],
                 is_boss: false,
+                ..Default::default()
             },
             // Battle 9: 2x Slime2, 1x Slime
             BattleDef {
@@ -410,6 +1169,7 @@ fn arc_1_slime_invasion() -> ArcDef {
                     EnemyConfig::new(EnemyId::Slime, 4, 1),
                 ],
                 is_boss: false,
+                ..Default::default()
             },
             // Battle 10: BOSS - 1x Slime3, 2x Slime2
             BattleDef {
@@ -421,8 +1181,146 @@ fn arc_1_slime_invasion() -> ArcDef {
                     EnemyConfig::new(EnemyId::Slime2, 4, 2),
                 ],
                 is_boss: true,
+                ..Default::default()
+            },
+        ],
+        weapon_reward: Some(WeaponType::Cannon),
+    }
+}
+
+/// Arc 2: Royal Court
+fn arc_2_royal_court() -> ArcDef {
+    ArcDef {
+        name: "Royal Court",
+        description: "The King's guard marches out. Tougher slimes, tighter formations.",
+        battles: vec![
+            // Battle 1: 2x Slime2
+            BattleDef {
+                name: "Palace Gates",
+                description: "2x Slime II behind a rock",
+                enemies: vec![
+                    EnemyConfig::new(EnemyId::Slime2, 4, 0),
+                    EnemyConfig::new(EnemyId::Slime2, 4, 2),
+                ],
+                is_boss: false,
+                obstacles: vec![ObstacleConfig::rock(4, 1)],
+                ..Default::default()
+            },
+            // Battle 2: 3x Slime2
+            BattleDef {
+                name: "Honor Guard",
+                description: "3x Slime II",
+                enemies: vec![
+                    EnemyConfig::new(EnemyId::Slime2, 4, 0),
+                    EnemyConfig::new(EnemyId::Slime2, 5, 1),
+                    EnemyConfig::new(EnemyId::Slime2, 4, 2),
+                ],
+                is_boss: false,
+                ..Default::default()
+            },
+            // Battle 3: 1x Slime3
+            BattleDef {
+                name: "Royal Envoy",
+                description: "1x King Slime",
+                enemies: vec![EnemyConfig::new(EnemyId::Slime3, 4, 1)],
+                is_boss: false,
+                ..Default::default()
+            },
+            // Battle 4: 1x Slime3, 2x Slime
+            BattleDef {
+                name: "Escort Duty",
+                description: "1x King Slime, 2x Slime",
+                enemies: vec![
+                    EnemyConfig::new(EnemyId::Slime3, 5, 1),
+                    EnemyConfig::new(EnemyId::Slime, 4, 0),
+                    EnemyConfig::new(EnemyId::Slime, 4, 2),
+                ],
+                is_boss: false,
+                ..Default::default()
+            },
+            // Battle 5: 4x Slime (tougher)
+            BattleDef {
+                name: "Drill Squad",
+                description: "2 waves of 2x Slime (veteran)",
+                enemies: vec![
+                    EnemyConfig::new(EnemyId::Slime, 3, 0).with_hp(80),
+                    EnemyConfig::new(EnemyId::Slime, 3, 2).with_hp(80),
+                ],
+                is_boss: false,
+                reinforcements: vec![vec![
+                    EnemyConfig::new(EnemyId::Slime, 5, 0).with_hp(80),
+                    EnemyConfig::new(EnemyId::Slime, 5, 2).with_hp(80),
+                ]],
+                ..Default::default()
+            },
+            // Battle 6: 1x Slime3, 1x Slime2
+            BattleDef {
+                name: "Court Wizards",
+                description: "1x King Slime, 1x Slime II",
+                enemies: vec![
+                    EnemyConfig::new(EnemyId::Slime3, 5, 0),
+                    EnemyConfig::new(EnemyId::Slime2, 4, 2),
+                ],
+                is_boss: false,
+                ..Default::default()
+            },
+            // Battle 7: 2x Slime2, 2x Slime
+            BattleDef {
+                name: "Throne Hall",
+                description: "2x Slime II, 2x Slime, 2 rocks",
+                enemies: vec![
+                    EnemyConfig::new(EnemyId::Slime2, 5, 0),
+                    EnemyConfig::new(EnemyId::Slime2, 5, 2),
+                    EnemyConfig::new(EnemyId::Slime, 4, 0),
+                    EnemyConfig::new(EnemyId::Slime, 4, 2),
+                ],
+                is_boss: false,
+                obstacles: vec![ObstacleConfig::rock(4, 1), ObstacleConfig::rock(5, 1)],
+                ..Default::default()
+            },
+            // Battle 8: 2x Slime3
+            BattleDef {
+                name: "Twin Crowns",
+                description: "2x King Slime",
+                enemies: vec![
+                    EnemyConfig::new(EnemyId::Slime3, 4, 0),
+                    EnemyConfig::new(EnemyId::Slime3, 4, 2),
+                ],
+                is_boss: false,
+                ..Default::default()
+            },
+            // Battle 9: 2x Slime3, 1x Slime2
+            BattleDef {
+                name: "Royal Guard",
+                description: "2x King Slime, 1x Slime II",
+                enemies: vec![
+                    EnemyConfig::new(EnemyId::Slime3, 5, 0),
+                    EnemyConfig::new(EnemyId::Slime3, 5, 2),
+                    EnemyConfig::new(EnemyId::Slime2, 4, 1),
+                ],
+                is_boss: false,
+                ..Default::default()
+            },
+            // Battle 10: BOSS - Empowered Slime3 + 2x Slime3
+            BattleDef {
+                name: "Slime Emperor",
+                description: "BOSS: Slime Emperor (summons Slimes) + 2x King Slime",
+                enemies: vec![
+                    EnemyConfig::new(EnemyId::Slime3, 5, 1)
+                        .with_hp(300)
+                        .with_attack(AttackBehavior::Summon {
+                            minion: Box::new(EnemyConfig::new(EnemyId::Slime, 0, 0)),
+                            max_summons: 2,
+                            charge_time: 1.0,
+                        }),
+                    EnemyConfig::new(EnemyId::Slime3, 4, 0),
+                    EnemyConfig::new(EnemyId::Slime3, 4, 2),
+                ],
+                is_boss: true,
+                ..Default::default()
             },
         ],
+        weapon_reward: None,
     }
 }
 
@@ -443,6 +1341,12 @@ impl PlayerUpgrades {
         1.0 - reduction
     }
 
+    pub fn get_chip_cooldown_modifier(&self) -> f32 {
+        // 10% shorter chip cooldowns per level, capped at 50%
+        let reduction = (self.chip_cooldown_level as f32 * 0.1).min(0.5);
+        1.0 - reduction
+    }
+
     pub fn get_crit_chance_bonus(&self) -> f32 {
         self.crit_chance_level as f32 * 0.02 // +2% crit chance per level
     }
@@ -465,3 +1369,166 @@ impl PlayerUpgrades {
         200 * (1.8_f32.powi(self.crit_chance_level as i32) as u64)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn second_arc_unlocks_with_the_first_arc_boss() {
+        let arc_1 = &get_all_arcs()[0];
+        let boss = arc_1.battles.iter().position(|b| b.is_boss).unwrap();
+        let mut progress = CampaignProgress::default();
+        assert!(!progress.is_arc_unlocked(1));
+
+        for battle in (0..arc_1.battles.len()).filter(|b| *b != boss) {
+            progress.complete_battle(0, battle);
+        }
+        assert!(!progress.is_arc_unlocked(1));
+
+        progress.complete_battle(0, boss);
+        assert!(progress.is_arc_unlocked(1));
+    }
+
+    #[test]
+    fn faster_clear_beats_the_record_and_slower_one_does_not() {
+        let mut records = BattleRecords::default();
+        assert!(records.get(0, 2).is_none());
+
+        assert!(records.record_clear(0, 2, 40.0, 300));
+        assert!(records.record_clear(0, 2, 32.5, 300));
+        assert_eq!(records.get(0, 2).unwrap().best_time, 32.5);
+
+        assert!(!records.record_clear(0, 2, 50.0, 200));
+        let record = records.get(0, 2).unwrap();
+        assert_eq!(record.best_time, 32.5);
+        assert_eq!(record.best_reward, 300);
+
+        // A slow clear can still set the reward record without touching the time
+        assert!(records.record_clear(0, 2, 60.0, 450));
+        let record = records.get(0, 2).unwrap();
+        assert_eq!((record.best_time, record.best_reward), (32.5, 450));
+        // Other battles keep their own records
+        assert!(records.get(0, 3).is_none());
+    }
+
+    #[test]
+    fn sfx_level_scales_sound_effect_volume() {
+        let mut audio = AudioSettings::default();
+        assert_eq!(audio.sfx_volume(0.8).to_linear(), 0.8);
+
+        audio.sfx = 0.5;
+        assert_eq!(audio.sfx_volume(0.8).to_linear(), 0.4);
+        assert_eq!(audio.music_volume(0.8).to_linear(), 0.8);
+
+        audio.master = 0.5;
+        assert_eq!(audio.sfx_volume(0.8).to_linear(), 0.2);
+    }
+
+    #[test]
+    fn volume_steps_clamp_to_the_valid_range() {
+        let mut level = 0.9;
+        AudioSettings::step(&mut level, 3);
+        assert_eq!(level, 1.0);
+        AudioSettings::step(&mut level, -20);
+        assert_eq!(level, 0.0);
+    }
+
+    #[test]
+    fn combo_multiplier_steps_up_to_the_cap() {
+        assert_eq!(combo_multiplier(0), 1.0);
+        assert_eq!(combo_multiplier(1), 1.0);
+        assert_eq!(combo_multiplier(2), 1.25);
+        assert_eq!(combo_multiplier(3), 1.5);
+        assert_eq!(combo_multiplier(100), COMBO_MAX_MULTIPLIER);
+    }
+
+    #[test]
+    fn battle_waves_flag_the_boss_and_keep_reinforcement_order() {
+        let battle = BattleDef {
+            enemies: vec![
+                EnemyConfig::new(EnemyId::Slime3, 5, 1),
+                EnemyConfig::new(EnemyId::Slime, 4, 0),
+            ],
+            is_boss: true,
+            reinforcements: vec![vec![EnemyConfig::new(EnemyId::Slime2, 4, 2)]],
+            ..Default::default()
+        };
+
+        let waves = battle.waves();
+        assert_eq!(waves.len(), 2);
+        assert!(waves[0][0].boss);
+        assert!(!waves[0][1].boss);
+        assert_eq!(waves[1][0].enemy_id, EnemyId::Slime2);
+
+        let mut queue = WaveQueue::new(&waves);
+        assert_eq!(queue.current, 1);
+        assert_eq!(queue.advance().unwrap()[0].enemy_id, EnemyId::Slime2);
+        assert_eq!(queue.current, 2);
+        assert!(queue.advance().is_none());
+    }
+
+    #[test]
+    fn difficulty_scales_enemy_hp_and_damage() {
+        assert_eq!(Difficulty::Easy.scale_hp(100), 70);
+        assert_eq!(Difficulty::Normal.scale_hp(100), 100);
+        assert_eq!(Difficulty::Hard.scale_hp(100), 140);
+        assert_eq!(Difficulty::Easy.scale_hp(1), 1);
+
+        assert_eq!(Difficulty::Easy.scale_damage(10), 6);
+        assert_eq!(Difficulty::Normal.scale_damage(10), 10);
+        assert_eq!(Difficulty::Hard.scale_damage(10), 13);
+        assert_eq!(Difficulty::Easy.scale_damage(1), 1);
+        assert_eq!(Difficulty::Easy.scale_damage(0), 0);
+    }
+
+    #[test]
+    fn harder_difficulty_pays_more_zenny() {
+        assert_eq!(Difficulty::Easy.scale_reward(100), 75);
+        assert_eq!(Difficulty::Normal.scale_reward(100), 100);
+        assert_eq!(Difficulty::Hard.scale_reward(100), 150);
+    }
+
+    #[test]
+    fn default_ownership_matches_the_fixed_split() {
+        let ownership = TileOwnership::default();
+        for y in 0..GRID_HEIGHT {
+            for x in 0..GRID_WIDTH {
+                let expected = if x < PLAYER_AREA_WIDTH {
+                    TileOwner::Player
+                } else {
+                    TileOwner::Enemy
+                };
+                assert_eq!(ownership.owner(x, y), Some(expected), "({x}, {y})");
+            }
+        }
+        assert_eq!(ownership.owner(GRID_WIDTH, 0), None);
+        assert_eq!(ownership.owner(0, -1), None);
+    }
+
+    #[test]
+    fn preset_restores_slots_and_drops_unowned_chips() {
+        let loadout = PlayerLoadout {
+            slots: [
+                Some(ActionId::Cannon),
+                None,
+                Some(ActionId::MCannon),
+                Some(ActionId::Recov50),
+            ],
+        };
+        let mut owned = OwnedChips::default();
+        owned.add(ActionId::MCannon);
+        let mut presets = LoadoutPresets::default();
+        assert!(presets.save(1, &loadout));
+        assert!(!presets.save(LOADOUT_PRESET_COUNT, &loadout));
+
+        assert_eq!(presets.load(1, &owned), Some(loadout.slots));
+        assert_eq!(presets.load(0, &owned), None);
+
+        owned.0.remove(&ActionId::MCannon);
+        assert_eq!(
+            presets.load(1, &owned),
+            Some([Some(ActionId::Cannon), None, None, Some(ActionId::Recov50)])
+        );
+    }
+}
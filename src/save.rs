@@ -0,0 +1,472 @@
+//! Save/load of persistent progression.
+//!
+//! Progress lives in `<config dir>/inserta/save.json`, next to the last recorded
+//! battle in `replay.json`, the best clear of each campaign battle in
+//! `records.json` and the enemies met so far in `bestiary.json`. The other save
+//! slots get their own copies of those files (`save2.json`, `records2.json`, ...).
+//! Options (audio, controls, difficulty, colors, the active slot) are shared by
+//! every slot in `settings.json`.
+//! A missing or corrupt file is never fatal: the game just starts from default
+//! resources.
+
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use bevy::prelude::*;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::actions::ChipMode;
+use crate::bindings::KeyBindings;
+use crate::resources::{
+    AssistOptions, AudioSettings, BattleRecords, CampaignProgress, ColorScheme, Difficulty,
+    DiscoveredEnemies, LoadoutPresets, OwnedChips, PlayerCurrency, PlayerLoadout, PlayerUpgrades,
+    ReduceFlashing, SaveSlot,
+};
+use crate::systems::growth::GrowthTreeState;
+use crate::systems::overtime::OvertimeRule;
+use crate::systems::replay::InputLog;
+use crate::weapons::{ChargeMoveRule, PlayerWeapons};
+
+const SAVE_DIR: &str = "inserta";
+const SAVE_FILE: &str = "save.json";
+const SETTINGS_FILE: &str = "settings.json";
+const REPLAY_FILE: &str = "replay.json";
+const RECORDS_FILE: &str = "records.json";
+const DISCOVERED_FILE: &str = "bestiary.json";
+
+/// Options stored in settings.json (audio levels stay top-level for older files)
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct SettingsData {
+    #[serde(flatten)]
+    pub audio: AudioSettings,
+    #[serde(default)]
+    pub bindings: KeyBindings,
+    #[serde(default)]
+    pub difficulty: Difficulty,
+    #[serde(default)]
+    pub color_scheme: ColorScheme,
+    #[serde(default)]
+    pub assist: AssistOptions,
+    #[serde(default)]
+    pub reduce_flashing: ReduceFlashing,
+    #[serde(default)]
+    pub charge_move: ChargeMoveRule,
+    #[serde(default)]
+    pub overtime: OvertimeRule,
+    #[serde(default)]
+    pub chip_mode: ChipMode,
+    #[serde(default)]
+    pub save_slot: SaveSlot,
+}
+
+/// Snapshot of everything that survives a restart
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct SaveData {
+    pub campaign: CampaignProgress,
+    pub currency: PlayerCurrency,
+    pub upgrades: PlayerUpgrades,
+    /// Committed growth tree nodes (pending purchases are never saved)
+    pub unlocked_nodes: Vec<u32>,
+    pub loadout: PlayerLoadout,
+    pub weapons: PlayerWeapons,
+    pub owned_chips: OwnedChips,
+    pub presets: LoadoutPresets,
+}
+
+impl SaveData {
+    pub fn capture(
+        campaign: &CampaignProgress,
+        currency: &PlayerCurrency,
+        upgrades: &PlayerUpgrades,
+        growth: &GrowthTreeState,
+        loadout: &PlayerLoadout,
+        weapons: &PlayerWeapons,
+        owned_chips: &OwnedChips,
+        presets: &LoadoutPresets,
+    ) -> Self {
+        let mut unlocked_nodes: Vec<u32> = growth.unlocked_nodes.iter().copied().collect();
+        unlocked_nodes.sort_unstable();
+
+        Self {
+            campaign: campaign.clone(),
+            currency: *currency,
+            upgrades: *upgrades,
+            unlocked_nodes,
+            loadout: loadout.clone(),
+            weapons: weapons.clone(),
+            owned_chips: owned_chips.clone(),
+            presets: presets.clone(),
+        }
+    }
+
+    /// Growth tree state restored from the saved node list
+    pub fn growth_state(&self) -> GrowthTreeState {
+        GrowthTreeState {
+            unlocked_nodes: self
+                .unlocked_nodes
+                .iter()
+                .copied()
+                .collect::<HashSet<u32>>(),
+            pending: None,
+        }
+    }
+
+    /// Owned chips, including anything equipped (saves made before chip drops
+    /// only stored the loadout)
+    pub fn owned_chips(&self) -> OwnedChips {
+        let mut owned = self.owned_chips.clone();
+        for action_id in self.loadout.equipped_actions() {
+            owned.add(action_id);
+        }
+        owned
+    }
+
+    /// Replace the progression resources with this save's
+    pub fn apply(self, commands: &mut Commands) {
+        commands.insert_resource(self.growth_state());
+        commands.insert_resource(self.owned_chips());
+        commands.insert_resource(self.presets);
+        commands.insert_resource(self.campaign);
+        commands.insert_resource(self.currency);
+        commands.insert_resource(self.upgrades);
+        commands.insert_resource(self.loadout);
+        commands.insert_resource(self.weapons);
+    }
+}
+
+/// Directory holding every save file (None on platforms without a config dir, e.g. wasm)
+#[cfg(not(test))]
+fn save_dir() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join(SAVE_DIR))
+}
+
+/// Tests save under the system temp dir instead of the player's config dir
+#[cfg(test)]
+fn save_dir() -> Option<PathBuf> {
+    Some(std::env::temp_dir().join(format!("{SAVE_DIR}-test-{}", std::process::id())))
+}
+
+/// `file` of `slot` inside `dir` (the first slot keeps the plain name, the others
+/// get their number before the extension: records.json -> records2.json)
+fn slot_path(dir: &Path, file: &str, slot: SaveSlot) -> PathBuf {
+    match (slot.0, file.rsplit_once('.')) {
+        (0, _) | (_, None) => dir.join(file),
+        (_, Some((stem, extension))) => dir.join(format!("{stem}{}.{extension}", slot.number())),
+    }
+}
+
+/// Location of a slot's save file
+pub fn save_path(slot: SaveSlot) -> Option<PathBuf> {
+    save_dir().map(|dir| slot_path(&dir, SAVE_FILE, slot))
+}
+
+/// Location of the settings file (kept separate so options survive a progress reset)
+pub fn settings_path() -> Option<PathBuf> {
+    save_dir().map(|dir| dir.join(SETTINGS_FILE))
+}
+
+/// Write progression to a slot. Failures are logged, never fatal; returns
+/// whether the file was written.
+pub fn save_game(
+    slot: SaveSlot,
+    campaign: &CampaignProgress,
+    currency: &PlayerCurrency,
+    upgrades: &PlayerUpgrades,
+    growth: &GrowthTreeState,
+    loadout: &PlayerLoadout,
+    weapons: &PlayerWeapons,
+    owned_chips: &OwnedChips,
+    presets: &LoadoutPresets,
+) -> bool {
+    let Some(path) = save_path(slot) else {
+        return false;
+    };
+
+    let data = SaveData::capture(
+        campaign,
+        currency,
+        upgrades,
+        growth,
+        loadout,
+        weapons,
+        owned_chips,
+        presets,
+    );
+    write_json(&path, &data)
+}
+
+/// Read a slot's progression. Returns None if there is no usable save.
+pub fn load_game(slot: SaveSlot) -> Option<SaveData> {
+    read_json(&save_path(slot)?)
+}
+
+/// Switch every per-slot resource to `slot`'s files (an empty slot starts fresh)
+pub fn load_slot(slot: SaveSlot, commands: &mut Commands) {
+    load_game(slot).unwrap_or_default().apply(commands);
+    commands.insert_resource(load_records(slot).unwrap_or_default());
+    commands.insert_resource(load_discovered_enemies(slot).unwrap_or_default());
+}
+
+/// Write options to disk. Failures are logged, never fatal.
+pub fn save_settings(settings: &SettingsData) {
+    if let Some(path) = settings_path() {
+        write_json(&path, settings);
+    }
+}
+
+/// Read options from disk. Returns None if there is no usable file.
+pub fn load_settings() -> Option<SettingsData> {
+    read_json(&settings_path()?)
+}
+
+/// Location of a slot's recorded battle inputs raced by the ghost player
+pub fn replay_path(slot: SaveSlot) -> Option<PathBuf> {
+    save_dir().map(|dir| slot_path(&dir, REPLAY_FILE, slot))
+}
+
+/// Write a recorded input log, replacing the slot's previous one. Failures are logged, never fatal.
+pub fn save_replay(slot: SaveSlot, log: &InputLog) {
+    if let Some(path) = replay_path(slot) {
+        write_json(&path, log);
+    }
+}
+
+/// Read a slot's recorded input log. Returns None if there is no usable file.
+pub fn load_replay(slot: SaveSlot) -> Option<InputLog> {
+    read_json(&replay_path(slot)?)
+}
+
+/// Location of a slot's per-battle best clears
+pub fn records_path(slot: SaveSlot) -> Option<PathBuf> {
+    save_dir().map(|dir| slot_path(&dir, RECORDS_FILE, slot))
+}
+
+/// Write a slot's battle records. Failures are logged, never fatal.
+pub fn save_records(slot: SaveSlot, records: &BattleRecords) {
+    if let Some(path) = records_path(slot) {
+        write_json(&path, records);
+    }
+}
+
+/// Read a slot's battle records. Returns None if there is no usable file.
+pub fn load_records(slot: SaveSlot) -> Option<BattleRecords> {
+    read_json(&records_path(slot)?)
+}
+
+/// Location of the enemies a slot has met so far
+pub fn discovered_enemies_path(slot: SaveSlot) -> Option<PathBuf> {
+    save_dir().map(|dir| slot_path(&dir, DISCOVERED_FILE, slot))
+}
+
+/// Write a slot's discovered enemies. Failures are logged, never fatal.
+pub fn save_discovered_enemies(slot: SaveSlot, discovered: &DiscoveredEnemies) {
+    if let Some(path) = discovered_enemies_path(slot) {
+        write_json(&path, discovered);
+    }
+}
+
+/// Read a slot's discovered enemies. Returns None if there is no usable file.
+pub fn load_discovered_enemies(slot: SaveSlot) -> Option<DiscoveredEnemies> {
+    read_json(&discovered_enemies_path(slot)?)
+}
+
+fn write_json<T: Serialize>(path: &Path, value: &T) -> bool {
+    let json = match serde_json::to_string_pretty(value) {
+        Ok(json) => json,
+        Err(err) => {
+            warn!("Failed to serialize {}: {err}", path.display());
+            return false;
+        }
+    };
+
+    if let Some(dir) = path.parent() {
+        if let Err(err) = fs::create_dir_all(dir) {
+            warn!("Failed to create save dir {}: {err}", dir.display());
+            return false;
+        }
+    }
+
+    if let Err(err) = fs::write(path, json) {
+        warn!("Failed to write {}: {err}", path.display());
+        return false;
+    }
+    true
+}
+
+fn read_json<T: DeserializeOwned>(path: &Path) -> Option<T> {
+    let json = fs::read_to_string(path).ok()?;
+
+    match serde_json::from_str(&json) {
+        Ok(data) => Some(data),
+        Err(err) => {
+            warn!("Ignoring corrupt file {}: {err}", path.display());
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::actions::ActionId;
+    use crate::weapons::WeaponType;
+
+    /// Scratch file under the system temp dir, unique per test process
+    fn temp_file(name: &str) -> PathBuf {
+        std::env::temp_dir()
+            .join(format!("inserta-test-{}", std::process::id()))
+            .join(name)
+    }
+
+    fn populated_save() -> SaveData {
+        let mut data = SaveData::default();
+        data.campaign.complete_battle(0, 0);
+        data.campaign.complete_battle(0, 1);
+        data.currency.zenny = 1234;
+        data.upgrades.damage_level = 2;
+        data.upgrades.health_level = 1;
+        data.unlocked_nodes = vec![0, 1, 2];
+        data.loadout.slots[3] = Some(ActionId::Cannon);
+        data.weapons.unlock(WeaponType::Cannon);
+        data.owned_chips.add(ActionId::Recov30);
+        data.presets.save(0, &data.loadout.clone());
+        data
+    }
+
+    #[test]
+    fn save_data_round_trips_through_json() {
+        let path = temp_file("round_trip.json");
+        let data = populated_save();
+
+        assert!(write_json(&path, &data));
+        let loaded: Option<SaveData> = read_json(&path);
+        fs::remove_file(&path).ok();
+
+        assert_eq!(loaded, Some(data));
+    }
+
+    #[test]
+    fn battle_rules_round_trip_through_settings_json() {
+        let path = temp_file("settings.json");
+        let settings = SettingsData {
+            charge_move: ChargeMoveRule::Reset,
+            overtime: OvertimeRule { enabled: true },
+            chip_mode: ChipMode::CustomGauge,
+            ..Default::default()
+        };
+
+        assert!(write_json(&path, &settings));
+        let loaded: Option<SettingsData> = read_json(&path);
+        fs::remove_file(&path).ok();
+        let loaded = loaded.unwrap();
+        assert_eq!(loaded.charge_move, ChargeMoveRule::Reset);
+        assert!(loaded.overtime.enabled);
+        assert_eq!(loaded.chip_mode, ChipMode::CustomGauge);
+
+        // Files written before the rules existed keep the defaults
+        let old: SettingsData = serde_json::from_str(r#"{"master": 0.5}"#).unwrap();
+        assert_eq!(old.charge_move, ChargeMoveRule::Free);
+        assert!(!old.overtime.enabled);
+        assert_eq!(old.chip_mode, ChipMode::Cooldown);
+    }
+
+    #[test]
+    fn corrupt_file_loads_as_none() {
+        let path = temp_file("corrupt.json");
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, "{ \"currency\": ").unwrap();
+
+        let loaded: Option<SaveData> = read_json(&path);
+        fs::remove_file(&path).ok();
+
+        assert_eq!(loaded, None);
+    }
+
+    fn save(slot: SaveSlot, data: &SaveData) -> bool {
+        save_game(
+            slot,
+            &data.campaign,
+            &data.currency,
+            &data.upgrades,
+            &data.growth_state(),
+            &data.loadout,
+            &data.weapons,
+            &data.owned_chips,
+            &data.presets,
+        )
+    }
+
+    #[test]
+    fn save_slots_keep_independent_progress() {
+        // Slots 2 and 3, so nothing else in the test run shares their files
+        let (second, third) = (SaveSlot(1), SaveSlot(2));
+        let data = populated_save();
+        assert!(save(third, &data));
+        assert_eq!(load_game(second), None);
+
+        let mut other = SaveData::default();
+        other.currency.zenny = 7;
+        assert!(save(second, &other));
+        let mut records = BattleRecords::default();
+        records.record_clear(0, 0, 42.0, 100);
+        save_records(third, &records);
+
+        let second_loaded = load_game(second);
+        let third_loaded = load_game(third);
+        let second_records = load_records(second);
+        let third_records = load_records(third);
+        for slot in [second, third] {
+            for path in [save_path(slot), records_path(slot)] {
+                fs::remove_file(path.unwrap()).ok();
+            }
+        }
+
+        assert_eq!(second_loaded, Some(other));
+        assert_eq!(third_loaded, Some(data));
+        assert!(second_records.is_none());
+        assert_eq!(
+            third_records
+                .unwrap()
+                .get(0, 0)
+                .map(|record| record.best_time),
+            Some(42.0)
+        );
+    }
+
+    #[test]
+    fn slot_files_get_the_slot_number() {
+        let dir = Path::new("saves");
+        assert_eq!(
+            slot_path(dir, RECORDS_FILE, SaveSlot(0)),
+            dir.join("records.json")
+        );
+        assert_eq!(
+            slot_path(dir, RECORDS_FILE, SaveSlot(2)),
+            dir.join("records3.json")
+        );
+        assert_eq!(
+            slot_path(dir, DISCOVERED_FILE, SaveSlot(1)),
+            dir.join("bestiary2.json")
+        );
+    }
+
+    #[test]
+    fn active_slot_round_trips_through_settings_json() {
+        let settings = SettingsData {
+            save_slot: SaveSlot(2),
+            ..Default::default()
+        };
+        let json = serde_json::to_string(&settings).unwrap();
+        let loaded: SettingsData = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.save_slot, SaveSlot(2));
+    }
+
+    #[test]
+    fn missing_file_loads_as_none() {
+        let loaded: Option<SaveData> = read_json(&temp_file("missing.json"));
+        assert_eq!(loaded, None);
+    }
+}
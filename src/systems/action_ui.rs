@@ -1,7 +1,7 @@
 use bevy::prelude::*;
 
-use crate::actions::{ActionSlot, ActionState};
-use crate::components::{ActionChargeBar, ActionCooldownOverlay};
+use crate::actions::{ActionBlueprint, ActionSlot, ActionState};
+use crate::components::{ActionChargeBar, ActionCooldownOverlay, ActionTooltip};
 use crate::constants::*;
 use crate::systems::setup::ActionReadyIndicator;
 
@@ -64,3 +64,96 @@ pub fn update_action_bar_ui(
         }
     }
 }
+
+/// Tooltip line for a slot: chip name, element, and charge/cooldown status
+pub fn action_tooltip_text(action: &ActionSlot) -> String {
+    let blueprint = ActionBlueprint::get(action.action_id);
+    let status = match action.state {
+        ActionState::Ready => "READY".to_string(),
+        ActionState::Charging => "CHARGING".to_string(),
+        ActionState::OnCooldown => {
+            format!("{:.1}s", action.cooldown_timer.remaining_secs())
+        }
+    };
+    format!("{} [{:?}] {}", blueprint.name, blueprint.element, status)
+}
+
+/// Show the tooltip for the charging slot, else the most recently used one
+pub fn update_action_tooltip(
+    action_query: Query<&ActionSlot>,
+    mut tooltip_query: Query<(&mut ActionTooltip, &mut Text2d)>,
+) {
+    let charging = action_query
+        .iter()
+        .find(|action| action.state == ActionState::Charging);
+    // The slot that went on cooldown last has the least elapsed cooldown time
+    let last_used = action_query
+        .iter()
+        .filter(|action| action.state == ActionState::OnCooldown)
+        .min_by(|a, b| {
+            a.cooldown_timer
+                .elapsed_secs()
+                .total_cmp(&b.cooldown_timer.elapsed_secs())
+        });
+
+    for (mut tooltip, mut text) in &mut tooltip_query {
+        if let Some(focused) = charging.or(last_used) {
+            tooltip.slot_index = focused.slot_index;
+        }
+
+        let Some(action) = action_query
+            .iter()
+            .find(|action| action.slot_index == tooltip.slot_index)
+        else {
+            continue;
+        };
+        let label = action_tooltip_text(action);
+        if text.0 != label {
+            text.0 = label;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::actions::ActionId;
+
+    #[test]
+    fn tooltip_names_the_slots_chip() {
+        for id in [ActionId::Cannon, ActionId::MiniBomb, ActionId::ShokWave] {
+            let slot = ActionSlot::new(0, id, 2.0, 0.0);
+            let blueprint = ActionBlueprint::get(id);
+            assert_eq!(
+                action_tooltip_text(&slot),
+                format!("{} [{:?}] READY", blueprint.name, blueprint.element)
+            );
+        }
+    }
+
+    #[test]
+    fn tooltip_follows_the_last_used_slot() {
+        let mut world = World::new();
+        let mut cannon = ActionSlot::new(0, ActionId::Cannon, 4.0, 0.0);
+        cannon.start_cooldown();
+        cannon
+            .cooldown_timer
+            .tick(std::time::Duration::from_secs(1));
+        let mut bomb = ActionSlot::new(1, ActionId::MiniBomb, 4.0, 0.0);
+        bomb.start_cooldown();
+        world.spawn(cannon);
+        world.spawn(bomb);
+        let tooltip = world
+            .spawn((ActionTooltip::default(), Text2d::default()))
+            .id();
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(update_action_tooltip);
+        schedule.run(&mut world);
+
+        assert_eq!(world.get::<ActionTooltip>(tooltip).unwrap().slot_index, 1);
+        let text = &world.get::<Text2d>(tooltip).unwrap().0;
+        assert!(text.starts_with("MiniBomb "), "{text}");
+        assert!(text.ends_with("4.0s"), "{text}");
+    }
+}
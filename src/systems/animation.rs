@@ -4,7 +4,8 @@ use bevy::prelude::*;
 use crate::actions::HealFlash;
 use crate::assets::FighterSprites;
 use crate::components::{
-    Enemy, FighterAnim, FighterAnimState, FlashTimer, Player, SlimeAnim, SlimeAnimState,
+    BaseColor, Dying, Enemy, FighterAnim, FighterAnimState, FlashTimer, Player, SlimeAnim,
+    SlimeAnimState, SlimeSprites,
 };
 use crate::enemies::ChargingTelegraph;
 
@@ -100,20 +101,60 @@ fn slime_frames_for_state(state: SlimeAnimState) -> usize {
     }
 }
 
+/// Loop each slime's frames. A `Dying` slime switches to its death sheet,
+/// plays it once and is then despawned.
 pub fn animate_slime(
+    mut commands: Commands,
     time: Res<Time>,
     mut query: Query<
-        (&mut Sprite, &mut SlimeAnim),
-        (With<Enemy>, Without<ChargingTelegraph>, Without<FlashTimer>),
+        (
+            Entity,
+            &mut Sprite,
+            &mut SlimeAnim,
+            &BaseColor,
+            Option<&SlimeSprites>,
+            Has<Dying>,
+        ),
+        (
+            Or<(With<Enemy>, With<Dying>)>,
+            Without<ChargingTelegraph>,
+            Without<FlashTimer>,
+        ),
     >,
 ) {
-    for (mut sprite, mut anim) in &mut query {
+    for (entity, mut sprite, mut anim, base_color, sprites, dying) in &mut query {
+        if dying && anim.state != SlimeAnimState::Dead {
+            let Some(sprites) = sprites else {
+                commands.entity(entity).despawn();
+                continue;
+            };
+            anim.state = SlimeAnimState::Dead;
+            anim.frame = 0;
+            anim.timer = Timer::from_seconds(1.0 / sprites.dead_fps, TimerMode::Repeating);
+            sprite.image = sprites.dead.clone();
+            sprite.texture_atlas = Some(TextureAtlas {
+                layout: sprites.dead_layout.clone(),
+                index: 0,
+            });
+            sprite.color = base_color.0;
+            continue;
+        }
+
         // Tick the animation timer
         anim.timer.tick(time.delta());
 
         if anim.timer.just_finished() {
-            let frame_count = slime_frames_for_state(anim.state);
-            anim.frame = (anim.frame + 1) % frame_count;
+            if anim.state == SlimeAnimState::Dead {
+                let frame_count = sprites.map_or(0, |sprites| sprites.dead_frames);
+                if anim.frame + 1 >= frame_count {
+                    commands.entity(entity).despawn();
+                    continue;
+                }
+                anim.frame += 1;
+            } else {
+                let frame_count = slime_frames_for_state(anim.state);
+                anim.frame = (anim.frame + 1) % frame_count;
+            }
 
             // Update texture atlas index
             if let Some(atlas) = sprite.texture_atlas.as_mut() {
@@ -122,3 +163,70 @@ pub fn animate_slime(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::components::Health;
+    use crate::systems::combat::kill_enemy;
+
+    const DEAD_FRAMES: usize = 3;
+    const DEAD_FPS: f32 = 10.0;
+
+    fn tick(world: &mut World, schedule: &mut Schedule, secs: f32) {
+        world
+            .resource_mut::<Time>()
+            .advance_by(Duration::from_secs_f32(secs));
+        schedule.run(world);
+    }
+
+    #[test]
+    fn killed_enemy_plays_its_death_frames_before_despawning() {
+        let mut world = World::new();
+        world.init_resource::<Time>();
+        let enemy = world
+            .spawn((
+                Enemy,
+                Health {
+                    current: 0,
+                    max: 10,
+                },
+                Sprite::default(),
+                SlimeAnim {
+                    state: SlimeAnimState::Idle,
+                    frame: 0,
+                    timer: Timer::from_seconds(0.1, TimerMode::Repeating),
+                },
+                SlimeSprites {
+                    dead: Handle::default(),
+                    dead_layout: Handle::default(),
+                    dead_frames: DEAD_FRAMES,
+                    dead_fps: DEAD_FPS,
+                },
+                BaseColor(Color::WHITE),
+            ))
+            .id();
+        let mut schedule = Schedule::default();
+        schedule.add_systems(animate_slime);
+
+        kill_enemy(&mut world.commands(), enemy);
+        world.flush();
+        assert!(!world.entity(enemy).contains::<Enemy>());
+
+        tick(&mut world, &mut schedule, 0.0);
+        assert_eq!(
+            world.get::<SlimeAnim>(enemy).unwrap().state,
+            SlimeAnimState::Dead
+        );
+
+        for frame in 1..DEAD_FRAMES {
+            tick(&mut world, &mut schedule, 1.0 / DEAD_FPS);
+            assert_eq!(world.get::<SlimeAnim>(enemy).unwrap().frame, frame);
+        }
+
+        tick(&mut world, &mut schedule, 1.0 / DEAD_FPS);
+        assert!(world.get_entity(enemy).is_err());
+    }
+}
@@ -11,9 +11,11 @@ use bevy::asset::RenderAssetUsages;
 use bevy::mesh::{Indices, PrimitiveTopology};
 use bevy::prelude::*;
 
-use crate::components::{CleanupOnStateExit, GameState, TileAssets, TileHighlightState, TilePanel};
+use crate::components::{
+    CleanupOnStateExit, GameState, PanelStatus, TileAssets, TileHighlightState, TilePanel,
+};
 use crate::constants::*;
-use crate::resources::ArenaLayout;
+use crate::resources::{ArenaLayout, TileOwnership};
 
 // ============================================================================
 // Mesh Helpers
@@ -222,6 +224,7 @@ pub fn spawn_tile_panels(
     commands: &mut Commands,
     asset_server: &Res<AssetServer>,
     layout: &ArenaLayout,
+    ownership: &TileOwnership,
 ) {
     // Load all tile sprite assets (normal and highlighted variants)
     let tile_assets = TileAssets {
@@ -233,9 +236,9 @@ pub fn spawn_tile_panels(
 
     // Spawn grid panels - render from back row (y=2) to front row (y=0)
     // so that front rows overlap back rows correctly
-    for y in (0..GRID_HEIGHT).rev() {
-        for x in 0..GRID_WIDTH {
-            let is_player = x < PLAYER_AREA_WIDTH;
+    for y in (0..ownership.height()).rev() {
+        for x in 0..ownership.width() {
+            let is_player = ownership.is_player_tile(x, y);
             let tile_texture = if is_player {
                 tile_assets.red_normal.clone()
             } else {
@@ -257,6 +260,7 @@ pub fn spawn_tile_panels(
                 Transform::from_xyz(sprite_pos.x, sprite_pos.y, z),
                 TilePanel { x, y },
                 TileHighlightState::new(is_player),
+                PanelStatus::default(),
                 CleanupOnStateExit(GameState::Playing),
             ));
         }
@@ -279,8 +283,9 @@ pub fn spawn_arena_visuals(
     materials: &mut ResMut<Assets<ColorMaterial>>,
     asset_server: &Res<AssetServer>,
     layout: &ArenaLayout,
+    ownership: &TileOwnership,
 ) {
     spawn_background(commands, layout);
     spawn_grid_lines(commands, meshes, materials, layout);
-    spawn_tile_panels(commands, asset_server, layout);
+    spawn_tile_panels(commands, asset_server, layout, ownership);
 }
@@ -0,0 +1,121 @@
+//! Auto-saving to the active save slot, with a brief "Saved" toast.
+//!
+//! Systems that save progress take an [`AutoSave`] param instead of calling
+//! `save_game` directly: it writes to the `SaveSlot` picked on the main menu
+//! and, once the file is written, flashes the toast in the bottom-right corner.
+
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+
+use crate::constants::COLOR_TEXT;
+use crate::resources::{
+    CampaignProgress, LoadoutPresets, OwnedChips, PlayerCurrency, PlayerLoadout, PlayerUpgrades,
+    SaveSlot,
+};
+use crate::save::save_game;
+use crate::systems::growth::GrowthTreeState;
+use crate::weapons::PlayerWeapons;
+
+/// How long the toast stays up after a save
+const SAVE_TOAST_TIME: f32 = 1.5;
+/// Fade-out at the end of the toast
+const SAVE_TOAST_FADE: f32 = 0.5;
+
+/// Time left on the "Saved" toast
+#[derive(Resource, Debug)]
+pub struct SaveToast {
+    pub timer: Timer,
+}
+
+impl Default for SaveToast {
+    fn default() -> Self {
+        let mut timer = Timer::from_seconds(SAVE_TOAST_TIME, TimerMode::Once);
+        timer.finish();
+        Self { timer }
+    }
+}
+
+impl SaveToast {
+    pub fn show(&mut self) {
+        self.timer.reset();
+    }
+
+    /// Toast opacity: solid, then fading out over the last `SAVE_TOAST_FADE` seconds
+    pub fn alpha(&self) -> f32 {
+        (self.timer.remaining_secs() / SAVE_TOAST_FADE).min(1.0)
+    }
+}
+
+/// "Saved" toast text marker
+#[derive(Component)]
+pub struct SaveToastText;
+
+/// Saves progress to the active slot and shows the toast
+#[derive(SystemParam)]
+pub struct AutoSave<'w> {
+    slot: Res<'w, SaveSlot>,
+    toast: ResMut<'w, SaveToast>,
+}
+
+impl AutoSave<'_> {
+    /// The slot progress is saved to
+    pub fn slot(&self) -> SaveSlot {
+        *self.slot
+    }
+
+    pub fn save(
+        &mut self,
+        campaign: &CampaignProgress,
+        currency: &PlayerCurrency,
+        upgrades: &PlayerUpgrades,
+        growth: &GrowthTreeState,
+        loadout: &PlayerLoadout,
+        weapons: &PlayerWeapons,
+        owned_chips: &OwnedChips,
+        presets: &LoadoutPresets,
+    ) {
+        if save_game(
+            *self.slot,
+            campaign,
+            currency,
+            upgrades,
+            growth,
+            loadout,
+            weapons,
+            owned_chips,
+            presets,
+        ) {
+            self.toast.show();
+        }
+    }
+}
+
+/// Spawn the (hidden) toast once; it lives across every screen
+pub fn setup_save_toast(mut commands: Commands) {
+    commands.spawn((
+        Text::new("Saved"),
+        TextFont::from_font_size(22.0),
+        TextColor(COLOR_TEXT.with_alpha(0.0)),
+        Node {
+            position_type: PositionType::Absolute,
+            right: Val::Px(24.0),
+            bottom: Val::Px(20.0),
+            ..default()
+        },
+        GlobalZIndex(100),
+        SaveToastText,
+    ));
+}
+
+/// Run the toast on real time, so it still fades while a battle is paused
+pub fn update_save_toast(
+    time: Res<Time<Real>>,
+    mut toast: ResMut<SaveToast>,
+    mut query: Query<&mut TextColor, With<SaveToastText>>,
+) {
+    toast.timer.tick(time.delta());
+    let alpha = toast.alpha();
+    for mut color in &mut query {
+        color.0 = COLOR_TEXT.with_alpha(alpha);
+    }
+}
@@ -0,0 +1,163 @@
+//! Boss health bar: a large segmented bar across the top of the screen.
+//!
+//! `update_boss_bar` shows the bar while a `Boss` is alive and sizes its fill
+//! to the boss's HP. A pale "chip" overlay trails the fill, draining down to it
+//! after each hit so the damage taken stays readable.
+
+use bevy::prelude::*;
+
+use crate::components::{Boss, CleanupOnStateExit, GameState, Health};
+use crate::constants::*;
+
+/// Size of the boss bar in pixels
+const BOSS_BAR_SIZE: Vec2 = Vec2::new(640.0, 16.0);
+/// Center of the boss bar (top center, just below the battle clock)
+const BOSS_BAR_POS: Vec2 = Vec2::new(0.0, 325.0);
+/// Number of segments the bar is divided into
+const BOSS_BAR_SEGMENTS: usize = 10;
+/// Chip overlay drain speed, in bar fractions per second
+const BOSS_BAR_CHIP_SPEED: f32 = 0.5;
+
+/// Boss bar background; fill, chip and segment dividers are its children
+#[derive(Component)]
+pub struct BossBar;
+
+/// Part of the bar showing the boss's current HP
+#[derive(Component)]
+pub struct BossBarFill;
+
+/// Overlay trailing the fill after damage
+#[derive(Component, Default)]
+pub struct BossBarChip {
+    pub fraction: f32,
+}
+
+/// Size the left-aligned bar piece to `fraction` of the full bar
+fn set_bar_fraction(sprite: &mut Sprite, transform: &mut Transform, fraction: f32) {
+    let width = BOSS_BAR_SIZE.x * fraction;
+    sprite.custom_size = Some(Vec2::new(width, BOSS_BAR_SIZE.y));
+    transform.translation.x = (width - BOSS_BAR_SIZE.x) / 2.0;
+}
+
+/// Spawn the hidden boss bar
+pub fn setup_boss_bar(mut commands: Commands) {
+    commands
+        .spawn((
+            Sprite::from_color(COLOR_HP_BAR_BG, BOSS_BAR_SIZE),
+            Transform::from_translation(BOSS_BAR_POS.extend(Z_UI)),
+            Visibility::Hidden,
+            BossBar,
+            CleanupOnStateExit(GameState::Playing),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Sprite::from_color(COLOR_BOSS_BAR_CHIP, BOSS_BAR_SIZE),
+                Transform::from_xyz(0.0, 0.0, 0.1),
+                BossBarChip { fraction: 1.0 },
+            ));
+            parent.spawn((
+                Sprite::from_color(COLOR_BOSS_BAR_FILL, BOSS_BAR_SIZE),
+                Transform::from_xyz(0.0, 0.0, 0.2),
+                BossBarFill,
+            ));
+
+            let segment = BOSS_BAR_SIZE.x / BOSS_BAR_SEGMENTS as f32;
+            for i in 1..BOSS_BAR_SEGMENTS {
+                parent.spawn((
+                    Sprite::from_color(COLOR_HP_PLATE, Vec2::new(2.0, BOSS_BAR_SIZE.y)),
+                    Transform::from_xyz(i as f32 * segment - BOSS_BAR_SIZE.x / 2.0, 0.0, 0.3),
+                ));
+            }
+        });
+}
+
+/// Show the bar while a boss is alive, track its HP and drain the chip overlay
+pub fn update_boss_bar(
+    time: Res<Time>,
+    boss_query: Query<&Health, With<Boss>>,
+    mut bar_query: Query<&mut Visibility, With<BossBar>>,
+    mut fill_query: Query<(&mut Sprite, &mut Transform), (With<BossBarFill>, Without<BossBarChip>)>,
+    mut chip_query: Query<(&mut Sprite, &mut Transform, &mut BossBarChip), Without<BossBarFill>>,
+) {
+    let boss = boss_query.iter().next();
+    for mut visibility in &mut bar_query {
+        *visibility = if boss.is_some() {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+    }
+    let Some(health) = boss else {
+        return;
+    };
+
+    let fraction = health.fraction();
+    for (mut sprite, mut transform) in &mut fill_query {
+        set_bar_fraction(&mut sprite, &mut transform, fraction);
+    }
+    for (mut sprite, mut transform, mut chip) in &mut chip_query {
+        // Drain toward the fill; heals snap up at once
+        chip.fraction = (chip.fraction - BOSS_BAR_CHIP_SPEED * time.delta_secs()).max(fraction);
+        set_bar_fraction(&mut sprite, &mut transform, chip.fraction);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn width<F: bevy::ecs::query::QueryFilter>(world: &mut World) -> f32 {
+        world
+            .query_filtered::<&Sprite, F>()
+            .single(world)
+            .unwrap()
+            .custom_size
+            .unwrap()
+            .x
+    }
+
+    #[test]
+    fn boss_bar_fill_tracks_the_bosss_hp() {
+        let mut world = World::new();
+        world.init_resource::<Time>();
+        let boss = world
+            .spawn((
+                Boss,
+                Health {
+                    current: 300,
+                    max: 300,
+                },
+            ))
+            .id();
+        let mut setup = Schedule::default();
+        setup.add_systems(setup_boss_bar);
+        setup.run(&mut world);
+        let mut schedule = Schedule::default();
+        schedule.add_systems(update_boss_bar);
+
+        world.get_mut::<Health>(boss).unwrap().current = 150;
+        schedule.run(&mut world);
+        assert_eq!(
+            width::<With<BossBarFill>>(&mut world),
+            BOSS_BAR_SIZE.x * 0.5
+        );
+        // The chip still shows the HP just lost
+        assert_eq!(width::<With<BossBarChip>>(&mut world), BOSS_BAR_SIZE.x);
+
+        world.get_mut::<Health>(boss).unwrap().current = 75;
+        world
+            .resource_mut::<Time>()
+            .advance_by(Duration::from_secs(5));
+        schedule.run(&mut world);
+        assert_eq!(
+            width::<With<BossBarFill>>(&mut world),
+            BOSS_BAR_SIZE.x * 0.25
+        );
+        assert_eq!(
+            width::<With<BossBarChip>>(&mut world),
+            BOSS_BAR_SIZE.x * 0.25
+        );
+    }
+}
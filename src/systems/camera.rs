@@ -0,0 +1,111 @@
+//! Fits the 2D camera to the window.
+//!
+//! The arena and battle HUD are laid out for a fixed `CameraConfig::arena_size`
+//! (1280x800). `fit_camera_to_window` scales the orthographic projection so
+//! that area fills as much of the window as it can, and with letterboxing on,
+//! narrows the viewport to the arena's aspect ratio so the leftover bars stay
+//! empty instead of showing past the arena's edges.
+
+use bevy::camera::Viewport;
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+
+/// Area the camera keeps in view, and how leftover window space is handled
+#[derive(Resource, Debug, Clone, Copy, PartialEq)]
+pub struct CameraConfig {
+    /// World units always visible (the size the arena and HUD are laid out for)
+    pub arena_size: Vec2,
+    /// Bars on the sides (or top and bottom) when the window's aspect ratio
+    /// doesn't match the arena's
+    pub letterbox: bool,
+}
+
+impl Default for CameraConfig {
+    fn default() -> Self {
+        Self {
+            arena_size: Vec2::new(1280.0, 800.0),
+            letterbox: true,
+        }
+    }
+}
+
+/// Orthographic scale (world units per window pixel) that fits all of
+/// `arena_size` into `window_size`
+pub fn fit_scale(window_size: Vec2, arena_size: Vec2) -> f32 {
+    if window_size.x <= 0.0 || window_size.y <= 0.0 {
+        return 1.0;
+    }
+    (arena_size.x / window_size.x).max(arena_size.y / window_size.y)
+}
+
+/// Centered viewport (physical pixels) with the arena's aspect ratio
+fn letterbox_viewport(physical_size: UVec2, arena_size: Vec2) -> Viewport {
+    let window = physical_size.as_vec2();
+    let size = (arena_size / fit_scale(window, arena_size)).min(window);
+    Viewport {
+        physical_position: ((window - size) / 2.0).as_uvec2(),
+        physical_size: size.as_uvec2().max(UVec2::ONE),
+        ..default()
+    }
+}
+
+/// Rescale (and letterbox) the camera whenever the window or config changes
+pub fn fit_camera_to_window(
+    config: Res<CameraConfig>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    mut camera_query: Query<(&mut Camera, &mut Projection), With<Camera2d>>,
+) {
+    let Ok(window) = windows.single() else {
+        return;
+    };
+    let scale = fit_scale(window.size(), config.arena_size);
+    let viewport = config
+        .letterbox
+        .then(|| letterbox_viewport(window.physical_size(), config.arena_size));
+
+    for (mut camera, mut projection) in &mut camera_query {
+        // Only write on change, so the camera isn't marked changed every frame
+        let rescale =
+            matches!(&*projection, Projection::Orthographic(ortho) if ortho.scale != scale);
+        if rescale {
+            if let Projection::Orthographic(ortho) = projection.as_mut() {
+                ortho.scale = scale;
+            }
+        }
+        let current = camera
+            .viewport
+            .as_ref()
+            .map(|viewport| (viewport.physical_position, viewport.physical_size));
+        let wanted = viewport
+            .as_ref()
+            .map(|viewport| (viewport.physical_position, viewport.physical_size));
+        if current != wanted {
+            camera.viewport = viewport.clone();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arena_fits_the_tighter_window_axis() {
+        let arena = Vec2::new(1280.0, 800.0);
+        // Design resolution: no scaling
+        assert_eq!(fit_scale(Vec2::new(1280.0, 800.0), arena), 1.0);
+        // Same aspect, half the size: everything drawn at half size
+        assert_eq!(fit_scale(Vec2::new(640.0, 400.0), arena), 2.0);
+        // Wide window: height is the limit
+        assert_eq!(fit_scale(Vec2::new(2560.0, 1000.0), arena), 0.8);
+        // Tall window: width is the limit
+        assert_eq!(fit_scale(Vec2::new(640.0, 1600.0), arena), 2.0);
+        // Minimized window keeps the last sensible scale
+        assert_eq!(fit_scale(Vec2::ZERO, arena), 1.0);
+
+        // Letterboxing a wide window leaves bars left and right
+        let viewport = letterbox_viewport(UVec2::new(2560, 1000), arena);
+        assert_eq!(viewport.physical_size, UVec2::new(1600, 1000));
+        assert_eq!(viewport.physical_position, UVec2::new(480, 0));
+    }
+}
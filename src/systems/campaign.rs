@@ -1,7 +1,10 @@
 use bevy::prelude::*;
 
 use crate::components::{ArenaConfig, CleanupOnStateExit, FighterConfig, GameState};
-use crate::resources::{CampaignProgress, PlayerLoadout, SelectedBattle, get_all_arcs};
+use crate::resources::{
+    BattleRecords, CampaignProgress, PlayerLoadout, SelectedBattle, get_all_arcs,
+};
+use crate::systems::outro::format_battle_time;
 
 // ============================================================================
 // Campaign UI Components
@@ -36,6 +39,21 @@ pub struct BattleNameText;
 #[derive(Component)]
 pub struct BattleDescText;
 
+/// Marker for the battle's best clear text
+#[derive(Component)]
+pub struct BattleRecordText;
+
+/// Best clear line for the info panel (empty until the battle has been cleared)
+fn record_text(records: &BattleRecords, arc: usize, battle: usize) -> String {
+    records.get(arc, battle).map_or(String::new(), |record| {
+        format!(
+            "Best: {}  |  {} z",
+            format_battle_time(record.best_time),
+            record.best_reward
+        )
+    })
+}
+
 /// Resource for cursor navigation state
 #[derive(Resource, Default)]
 pub struct CampaignCursor {
@@ -58,12 +76,47 @@ const SQUARE_SELECTED: Color = Color::srgb(1.0, 0.9, 0.3);
 // Setup System
 // ============================================================================
 
-pub fn setup_campaign(mut commands: Commands, campaign_progress: Res<CampaignProgress>) {
-    // Initialize cursor resource
-    commands.insert_resource(CampaignCursor::default());
+pub fn setup_campaign(
+    mut commands: Commands,
+    campaign_progress: Res<CampaignProgress>,
+    records: Res<BattleRecords>,
+    selected_battle: Res<SelectedBattle>,
+) {
+    // Open on the arc of the last selected battle (if it is still reachable)
+    let arc_count = get_all_arcs().len();
+    let arc_index = if selected_battle.arc < arc_count
+        && campaign_progress.is_arc_unlocked(selected_battle.arc)
+    {
+        selected_battle.arc
+    } else {
+        0
+    };
+
+    // Initialize cursor resource, on the selected battle if its arc is shown
+    let battle_index = if arc_index == selected_battle.arc {
+        selected_battle.battle
+    } else {
+        0
+    };
+    commands.insert_resource(CampaignCursor {
+        arc_index,
+        battle_index,
+    });
+
+    spawn_campaign_screen(&mut commands, &campaign_progress, &records, arc_index);
+}
 
+/// Build the campaign screen for one arc (rebuilt whenever the arc changes)
+fn spawn_campaign_screen(
+    commands: &mut Commands,
+    campaign_progress: &CampaignProgress,
+    records: &BattleRecords,
+    arc_index: usize,
+) {
     let arcs = get_all_arcs();
-    let current_arc = &arcs[0]; // Start with Arc 1
+    let current_arc = &arcs[arc_index];
+    let next_arc_locked =
+        arc_index + 1 < arcs.len() && !campaign_progress.is_arc_unlocked(arc_index + 1);
 
     // Root container
     commands
@@ -82,6 +135,13 @@ pub fn setup_campaign(mut commands: Commands, campaign_progress: Res<CampaignPro
             CleanupOnStateExit(GameState::Campaign),
         ))
         .with_children(|parent| {
+            // Arc number
+            parent.spawn((
+                Text::new(format!("ARC {} / {}", arc_index + 1, arcs.len())),
+                TextFont::from_font_size(18.0),
+                TextColor(Color::srgba(0.6, 0.6, 0.8, 0.9)),
+            ));
+
             // Title: Arc Name
             parent.spawn((
                 Text::new(current_arc.name),
@@ -99,11 +159,24 @@ pub fn setup_campaign(mut commands: Commands, campaign_progress: Res<CampaignPro
                 TextFont::from_font_size(20.0),
                 TextColor(Color::srgba(0.7, 0.7, 0.7, 0.9)),
                 Node {
-                    margin: UiRect::bottom(Val::Px(40.0)),
+                    margin: UiRect::bottom(Val::Px(if next_arc_locked { 10.0 } else { 40.0 })),
                     ..default()
                 },
             ));
 
+            // Hint for the gate to the next arc
+            if next_arc_locked {
+                parent.spawn((
+                    Text::new("Defeat this arc's boss to unlock the next arc"),
+                    TextFont::from_font_size(16.0),
+                    TextColor(Color::srgba(0.8, 0.4, 0.4, 0.9)),
+                    Node {
+                        margin: UiRect::bottom(Val::Px(24.0)),
+                        ..default()
+                    },
+                ));
+            }
+
             // Battle Grid Container (horizontal row of 10 squares)
             parent
                 .spawn((Node {
@@ -116,9 +189,10 @@ pub fn setup_campaign(mut commands: Commands, campaign_progress: Res<CampaignPro
                 },))
                 .with_children(|grid_parent| {
                     for (battle_idx, battle) in current_arc.battles.iter().enumerate() {
-                        let is_completed = campaign_progress.is_battle_won(0, battle_idx);
+                        let is_completed = campaign_progress.is_battle_won(arc_index, battle_idx);
                         let is_available = battle_idx == 0
-                            || campaign_progress.is_battle_won(0, battle_idx.saturating_sub(1));
+                            || campaign_progress
+                                .is_battle_won(arc_index, battle_idx.saturating_sub(1));
 
                         let base_color = if !is_available {
                             SQUARE_LOCKED
@@ -153,7 +227,7 @@ pub fn setup_campaign(mut commands: Commands, campaign_progress: Res<CampaignPro
                                 }),
                                 BackgroundColor(base_color),
                                 BattleSquare {
-                                    arc_index: 0,
+                                    arc_index,
                                     battle_index: battle_idx,
                                 },
                             ))
@@ -192,7 +266,7 @@ pub fn setup_campaign(mut commands: Commands, campaign_progress: Res<CampaignPro
                             });
 
                         // Connection line (except after last square)
-                        if battle_idx < 9 {
+                        if battle_idx + 1 < current_arc.battles.len() {
                             grid_parent.spawn((
                                 Node {
                                     width: Val::Px(10.0),
@@ -242,12 +316,24 @@ pub fn setup_campaign(mut commands: Commands, campaign_progress: Res<CampaignPro
                         TextColor(Color::srgba(0.8, 0.8, 0.8, 0.9)),
                         BattleDescText,
                     ));
+
+                    // Best clear (once won)
+                    panel.spawn((
+                        Text::new(record_text(records, arc_index, 0)),
+                        TextFont::from_font_size(18.0),
+                        TextColor(SQUARE_SELECTED),
+                        Node {
+                            margin: UiRect::top(Val::Px(10.0)),
+                            ..default()
+                        },
+                        BattleRecordText,
+                    ));
                 });
 
             // Instructions
             parent.spawn((
                 Text::new(
-                    "Arrow Keys / D-Pad: Select Battle  |  Enter / A: Start Battle  |  Esc: Back",
+                    "Left/Right: Select Battle  |  Up/Down / LB/RB: Switch Arc  |  Enter / A: Start Battle  |  Esc: Back",
                 ),
                 TextFont::from_font_size(18.0),
                 TextColor(Color::srgba(0.6, 0.6, 0.6, 0.8)),
@@ -265,8 +351,10 @@ pub fn setup_campaign(mut commands: Commands, campaign_progress: Res<CampaignPro
 
 pub fn update_campaign(
     keyboard: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<&Gamepad>,
     mut cursor: ResMut<CampaignCursor>,
-    campaign_progress: Res<CampaignProgress>,
+    mut campaign_progress: ResMut<CampaignProgress>,
+    records: Res<BattleRecords>,
     player_loadout: Res<PlayerLoadout>,
     mut commands: Commands,
     mut next_state: ResMut<NextState<GameState>>,
@@ -278,8 +366,57 @@ pub fn update_campaign(
     )>,
     mut name_text: Query<&mut Text, (With<BattleNameText>, Without<BattleDescText>)>,
     mut desc_text: Query<&mut Text, (With<BattleDescText>, Without<BattleNameText>)>,
+    mut record_query: Query<
+        &mut Text,
+        (
+            With<BattleRecordText>,
+            Without<BattleNameText>,
+            Without<BattleDescText>,
+        ),
+    >,
+    screen_query: Query<Entity, With<CampaignScreen>>,
 ) {
     let arcs = get_all_arcs();
+
+    // Handle arc switching (Up/Down or shoulder buttons); locked arcs are skipped
+    let mut arc_delta: i32 = 0;
+    if keyboard.just_pressed(KeyCode::ArrowUp) || keyboard.just_pressed(KeyCode::KeyW) {
+        arc_delta -= 1;
+    }
+    if keyboard.just_pressed(KeyCode::ArrowDown) || keyboard.just_pressed(KeyCode::KeyS) {
+        arc_delta += 1;
+    }
+    for gamepad in gamepads.iter() {
+        if gamepad.just_pressed(GamepadButton::LeftTrigger) {
+            arc_delta -= 1;
+        }
+        if gamepad.just_pressed(GamepadButton::RightTrigger) {
+            arc_delta += 1;
+        }
+    }
+    if arc_delta != 0 {
+        let target = cursor.arc_index as i32 + arc_delta;
+        if target >= 0
+            && (target as usize) < arcs.len()
+            && campaign_progress.is_arc_unlocked(target as usize)
+        {
+            cursor.arc_index = target as usize;
+            cursor.battle_index = 0;
+
+            // Rebuild the screen for the new arc
+            for entity in &screen_query {
+                commands.entity(entity).despawn();
+            }
+            spawn_campaign_screen(
+                &mut commands,
+                &campaign_progress,
+                &records,
+                cursor.arc_index,
+            );
+            return;
+        }
+    }
+
     let current_arc = &arcs[cursor.arc_index];
     let old_battle = cursor.battle_index;
 
@@ -322,7 +459,7 @@ pub fn update_campaign(
     }
 
     if keyboard.just_pressed(KeyCode::ArrowRight) || keyboard.just_pressed(KeyCode::KeyD) {
-        if cursor.battle_index < 9 {
+        if cursor.battle_index + 1 < current_arc.battles.len() {
             // Check if next battle is available (current must be completed OR it's battle 0)
             let target = cursor.battle_index + 1;
             if target == 0 || campaign_progress.is_battle_won(cursor.arc_index, cursor.battle_index)
@@ -343,6 +480,9 @@ pub fn update_campaign(
         for mut text in desc_text.iter_mut() {
             **text = battle.description.to_string();
         }
+        for mut text in record_query.iter_mut() {
+            **text = record_text(&records, cursor.arc_index, cursor.battle_index);
+        }
     }
 
     // Always update square visuals (for hover effects and selection)
@@ -395,11 +535,14 @@ pub fn update_campaign(
         if is_available {
             let battle = &current_arc.battles[battle_to_start];
 
-            // Store selected battle for return after victory
-            commands.insert_resource(SelectedBattle {
+            // Store selected battle for return after victory (and the
+            // main menu's Continue, saved along with the battle's outcome)
+            let selected = SelectedBattle {
                 arc: cursor.arc_index,
                 battle: battle_to_start,
-            });
+            };
+            campaign_progress.last_battle = Some(selected);
+            commands.insert_resource(selected);
 
             // Create arena config from battle definition using player's loadout
             let config = ArenaConfig {
@@ -409,7 +552,11 @@ pub fn update_campaign(
                     max_hp: 100,
                     actions: player_loadout.equipped_actions(),
                 },
-                enemies: battle.enemies.clone(),
+                waves: battle.waves(),
+                obstacles: battle.obstacles.clone(),
+                practice: false,
+                tutorial: false,
+                grid: battle.grid,
             };
             commands.insert_resource(config);
 
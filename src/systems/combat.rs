@@ -1,10 +1,25 @@
+use crate::actions::{ActionSlot, ActiveShield, hit_obstacle};
 use crate::components::{
-    BaseColor, Bullet, DefeatOutro, Enemy, EnemyBullet, FlashTimer, GridPosition, Health, Lifetime,
-    MoveTimer, MuzzleFlash, Player, PlayerHealthText, TargetsTiles, TileAssets, TileHighlightState,
-    TilePanel, VictoryOutro,
+    ArenaConfig, BaseColor, BattleRewardText, BattleTimerText, Bullet, CleanupOnStateExit,
+    ComboText, CounterPopup, DefeatOutro, Dying, Enemy, EnemyBullet, EnemyHpBarFill, FlashTimer,
+    GameState, GridPosition, Health, Lifetime, MoveTimer, MuzzleFlash, Obstacle, OverHealth,
+    OverHealthText, PanelStatus, Player, PlayerHealthText, PreviousTile, ProjectileHit,
+    ProjectileImmobile, ProjectileTrail, RenderConfig, Struck, TargetsTiles, TileAssets,
+    TileHighlightState, TilePanel, TrailSegment, VictoryOutro, WaveBanner,
 };
 use crate::constants::*;
-use crate::resources::{BattleTimer, GameProgress, PlayerCurrency, WaveState};
+use crate::enemies::{
+    BehaviorEnemy, ChargingTelegraph, EnemyAnimState, EnemyAttack, EnemyReward, TeleportFx,
+};
+use crate::resources::{
+    ArenaLayout, BattleRewards, BattleStats, BattleTimer, ColorScheme, ComboTracker, GameProgress,
+    PlayerCurrency, ReduceFlashing, TelegraphedTiles, WaveQueue, WaveState, combo_multiplier,
+};
+use crate::systems::outro::{HitStop, format_battle_time, time_bonus};
+use crate::systems::overtime::{Overtime, overtime_damage};
+use crate::systems::rumble::Rumble;
+use crate::systems::shake::ScreenShake;
+use crate::weapons::{EquippedWeapon, FalloffOverlay, Projectile, WeaponState};
 
 /// Speed of highlight fade in/out (intensity units per second)
 const HIGHLIGHT_FADE_SPEED: f32 = 8.0;
@@ -16,8 +31,16 @@ use bevy::prelude::*;
 pub fn bullet_movement(
     mut commands: Commands,
     time: Res<Time>,
+    layout: Res<ArenaLayout>,
     mut query: Query<
-        (Entity, &mut GridPosition, &mut MoveTimer),
+        (
+            Entity,
+            &mut GridPosition,
+            &mut PreviousTile,
+            &mut MoveTimer,
+            Option<&Projectile>,
+            Option<&ProjectileAnimation>,
+        ),
         (
             With<Bullet>,
             Without<EnemyBullet>,
@@ -25,11 +48,27 @@ pub fn bullet_movement(
         ),
     >,
 ) {
-    for (entity, mut pos, mut timer) in &mut query {
+    for (entity, mut pos, mut previous, mut timer, projectile, anim) in &mut query {
         timer.0.tick(time.delta());
         if timer.0.is_finished() {
+            // At max range: fizzle out on the last tile (see projectile_animation_system)
+            if projectile.is_some_and(|p| !p.in_range(pos.x + 1)) {
+                match anim {
+                    Some(anim) => {
+                        commands.entity(entity).insert((
+                            ProjectileAnimation::fizzle(anim.is_charged),
+                            ProjectileHit,
+                            ProjectileImmobile,
+                        ));
+                    }
+                    None => commands.entity(entity).despawn(),
+                }
+                continue;
+            }
+
+            previous.0 = Some(*pos);
             pos.x += 1;
-            if pos.x >= GRID_WIDTH {
+            if pos.x >= layout.grid.width {
                 // Despawn off-screen projectiles (but not hit projectiles in animation)
                 commands.entity(entity).despawn();
             }
@@ -42,16 +81,29 @@ pub fn enemy_bullet_movement(
     mut commands: Commands,
     time: Res<Time>,
     mut query: Query<
-        (Entity, &mut GridPosition, &mut MoveTimer),
+        (
+            Entity,
+            &mut GridPosition,
+            &mut PreviousTile,
+            &mut MoveTimer,
+            Option<&mut crate::components::LaunchDelay>,
+        ),
         (
             With<EnemyBullet>,
             Without<crate::components::ProjectileImmobile>,
         ),
     >,
 ) {
-    for (entity, mut pos, mut timer) in &mut query {
+    for (entity, mut pos, mut previous, mut timer, delay) in &mut query {
+        if let Some(mut delay) = delay {
+            delay.0.tick(time.delta());
+            if !delay.0.is_finished() {
+                continue;
+            }
+        }
         timer.0.tick(time.delta());
         if timer.0.is_finished() {
+            previous.0 = Some(*pos);
             pos.x -= 1;
             if pos.x < 0 {
                 // Despawn off-screen projectiles (but not hit projectiles in animation)
@@ -74,43 +126,280 @@ pub fn muzzle_lifetime(
     }
 }
 
-/// Enemy bullets hit player
+/// Drop a trail segment on the tile a projectile just left
+pub fn spawn_projectile_trails(
+    mut commands: Commands,
+    mut query: Query<(&GridPosition, &RenderConfig, &mut ProjectileTrail)>,
+) {
+    for (pos, render, mut trail) in &mut query {
+        let Some(last) = trail.last.replace(*pos) else {
+            continue;
+        };
+        if last == *pos {
+            continue;
+        }
+        commands.spawn((
+            Sprite::from_color(trail.color, TRAIL_SEGMENT_SIZE),
+            Transform::default(),
+            last,
+            RenderConfig {
+                offset: render.offset,
+                base_z: render.base_z - 0.5,
+            },
+            TrailSegment { color: trail.color },
+            Lifetime(Timer::from_seconds(TRAIL_TIME, TimerMode::Once)),
+            CleanupOnStateExit(GameState::Playing),
+        ));
+    }
+}
+
+/// Fade trail segments out and despawn them when their lifetime ends
+pub fn fade_trail_segments(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut Sprite, &mut Lifetime, &TrailSegment)>,
+) {
+    for (entity, mut sprite, mut lifetime, segment) in &mut query {
+        lifetime.0.tick(time.delta());
+        if lifetime.0.is_finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        let alpha = segment.color.alpha() * lifetime.0.fraction_remaining();
+        sprite.color = segment.color.with_alpha(alpha);
+    }
+}
+
+/// Enemy bullets hit player. An invisible player lets them pass through.
 pub fn enemy_bullet_hit_player(
     mut commands: Commands,
     bullet_query: Query<(Entity, &GridPosition, &EnemyBullet)>,
-    mut player_query: Query<(Entity, &GridPosition, &mut Health), With<Player>>,
+    mut player_query: Query<
+        (
+            Entity,
+            &GridPosition,
+            &mut Health,
+            Option<&ActiveShield>,
+            Option<&mut OverHealth>,
+        ),
+        With<Player>,
+    >,
     mut hp_text_query: Query<&mut Text2d, With<PlayerHealthText>>,
+    mut rumble: Rumble,
+    mut shake: ResMut<ScreenShake>,
+    overtime: Option<Res<Overtime>>,
 ) {
     for (bullet_entity, bullet_pos, enemy_bullet) in &bullet_query {
-        for (player_entity, player_pos, mut health) in &mut player_query {
+        for (player_entity, player_pos, mut health, shield, over_health) in &mut player_query {
+            if shield.is_some_and(ActiveShield::dodges) {
+                continue;
+            }
             if bullet_pos == player_pos {
                 // Use damage from the bullet (defined in enemy blueprint)
-                health.current -= enemy_bullet.damage;
+                let damage = overtime_damage(enemy_bullet.damage, overtime.as_deref());
                 commands.entity(bullet_entity).despawn();
+                damage_player(
+                    &mut commands,
+                    player_entity,
+                    &mut health,
+                    over_health,
+                    damage,
+                    &mut hp_text_query,
+                    &mut rumble,
+                    &mut shake,
+                );
+            }
+        }
+    }
+}
 
-                // Update player HP text
-                for mut text in &mut hp_text_query {
-                    text.0 = format!("HP: {}", health.current.max(0));
-                }
+/// Land an enemy hit on the player. Over-health soaks it first, then HP
+/// drops, the HP text updates and the pad rumbles and the screen shakes.
+/// A fatal hit despawns the player; otherwise it flashes and is marked
+/// `Struck`, which breaks any charge.
+pub fn damage_player(
+    commands: &mut Commands,
+    player_entity: Entity,
+    health: &mut Health,
+    over_health: Option<Mut<OverHealth>>,
+    damage: i32,
+    hp_text_query: &mut Query<&mut Text2d, With<PlayerHealthText>>,
+    rumble: &mut Rumble,
+    shake: &mut ScreenShake,
+) {
+    health.current -= over_health.map_or(damage, |mut pool| pool.absorb(damage));
+    rumble.player_hit(damage);
+    shake.player_hit(damage);
+
+    for mut text in hp_text_query.iter_mut() {
+        text.0 = format!("HP: {}", health.current.max(0));
+    }
+
+    if health.current <= 0 {
+        // Player defeated - could trigger game over
+        commands.entity(player_entity).despawn();
+    } else {
+        commands.entity(player_entity).insert((
+            FlashTimer(Timer::from_seconds(FLASH_TIME, TimerMode::Once)),
+            Struck,
+        ));
+    }
+}
 
-                if health.current <= 0 {
-                    // Player defeated - could trigger game over
-                    commands.entity(player_entity).despawn();
+/// Fade over-health away and show what is left after the player's HP
+pub fn decay_over_health(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut player_query: Query<(Entity, Option<&mut OverHealth>), With<Player>>,
+    mut text_query: Query<&mut TextSpan, With<OverHealthText>>,
+) {
+    for (player_entity, over_health) in &mut player_query {
+        let shown = match over_health {
+            Some(mut pool) => {
+                if pool.decay(time.delta()) {
+                    commands.entity(player_entity).remove::<OverHealth>();
+                    String::new()
                 } else {
-                    // Flash feedback only if still alive
-                    commands
-                        .entity(player_entity)
-                        .insert(FlashTimer(Timer::from_seconds(FLASH_TIME, TimerMode::Once)));
+                    format!(" +{}", pool.current)
+                }
+            }
+            None => String::new(),
+        };
+        for mut text in &mut text_query {
+            if text.0 != shown {
+                text.0 = shown.clone();
+            }
+        }
+    }
+}
+
+/// A hit knocks out whatever the player was charging: the buster charge
+/// (see `WeaponState::interrupt_charge`) and every charging chip slot.
+/// Damage sources mark the player `Struck`; shielded hits never get that far.
+pub fn interrupt_charge_on_hit(
+    mut commands: Commands,
+    mut player_query: Query<(Entity, Option<(&mut WeaponState, &EquippedWeapon)>), With<Struck>>,
+    mut slot_query: Query<&mut ActionSlot>,
+) {
+    for (player_entity, weapon) in &mut player_query {
+        commands.entity(player_entity).remove::<Struck>();
+        if let Some((mut state, weapon)) = weapon {
+            if state.interrupt_charge(weapon.stats.fire_cooldown) {
+                info!("Charge interrupted by a hit");
+            }
+        }
+        for mut slot in &mut slot_query {
+            slot.interrupt_charge();
+        }
+    }
+}
+
+/// Bullets (player and enemy) stop on obstacles and chip away at their HP.
+/// Piercing shots chip the rock once and fly on.
+pub fn bullet_hit_obstacle(
+    mut commands: Commands,
+    mut player_bullets: Query<
+        (Entity, &GridPosition, &mut Projectile, &ProjectileAnimation),
+        (With<Bullet>, Without<EnemyBullet>, Without<ProjectileHit>),
+    >,
+    enemy_bullets: Query<(Entity, &GridPosition, &EnemyBullet)>,
+    mut obstacle_query: Query<(Entity, &GridPosition, &mut Obstacle)>,
+) {
+    for (obstacle_entity, obstacle_pos, mut obstacle) in &mut obstacle_query {
+        for (bullet_entity, bullet_pos, mut projectile, anim) in &mut player_bullets {
+            if bullet_pos == obstacle_pos {
+                if projectile.piercing {
+                    if !projectile.hit_enemies.contains(&obstacle_entity) {
+                        projectile.hit_enemies.push(obstacle_entity);
+                        let damage = projectile.calculate_damage(bullet_pos.x);
+                        hit_obstacle(&mut commands, obstacle_entity, &mut obstacle, damage, false);
+                    }
+                    continue;
                 }
+                let damage = projectile.calculate_damage(bullet_pos.x);
+                hit_obstacle(&mut commands, obstacle_entity, &mut obstacle, damage, false);
+                commands.entity(bullet_entity).insert((
+                    ProjectileAnimation::impact(anim.is_charged),
+                    ProjectileHit,
+                    ProjectileImmobile,
+                ));
+            }
+        }
+
+        for (bullet_entity, bullet_pos, enemy_bullet) in &enemy_bullets {
+            if bullet_pos == obstacle_pos {
+                hit_obstacle(
+                    &mut commands,
+                    obstacle_entity,
+                    &mut obstacle,
+                    enemy_bullet.damage,
+                    false,
+                );
+                commands.entity(bullet_entity).despawn();
+            }
+        }
+    }
+}
+
+/// Player shots and enemy bullets that meet on a tile (or swap tiles in the
+/// same frame) trade off with a spark.
+/// The weaker one is destroyed (both on a tie) and a stronger one flies on.
+pub fn projectile_collision_system(
+    mut commands: Commands,
+    player_bullets: Query<
+        (Entity, &GridPosition, &PreviousTile, &Projectile),
+        (With<Bullet>, Without<EnemyBullet>, Without<ProjectileHit>),
+    >,
+    enemy_bullets: Query<(Entity, &GridPosition, &PreviousTile, &EnemyBullet)>,
+) {
+    let mut destroyed = Vec::new();
+    for (bullet_entity, bullet_pos, bullet_previous, projectile) in &player_bullets {
+        for (enemy_bullet_entity, enemy_bullet_pos, enemy_bullet_previous, enemy_bullet) in
+            &enemy_bullets
+        {
+            let crossed = bullet_previous.0 == Some(*enemy_bullet_pos)
+                && enemy_bullet_previous.0 == Some(*bullet_pos);
+            if (bullet_pos != enemy_bullet_pos && !crossed)
+                || destroyed.contains(&enemy_bullet_entity)
+            {
+                continue;
+            }
+            let damage = projectile.calculate_damage(bullet_pos.x);
+
+            commands.spawn((
+                Sprite {
+                    color: COLOR_SPARK,
+                    custom_size: Some(Vec2::splat(28.0)),
+                    ..default()
+                },
+                Transform::default(),
+                *bullet_pos,
+                RenderConfig {
+                    offset: BULLET_OFFSET,
+                    base_z: Z_BULLET + 1.0,
+                },
+                MuzzleFlash,
+                Lifetime(Timer::from_seconds(SPARK_TIME, TimerMode::Once)),
+                CleanupOnStateExit(GameState::Playing),
+            ));
+
+            if damage >= enemy_bullet.damage {
+                commands.entity(enemy_bullet_entity).despawn();
+                destroyed.push(enemy_bullet_entity);
+            }
+            if damage <= enemy_bullet.damage {
+                commands.entity(bullet_entity).despawn();
+                break;
             }
         }
     }
 }
 
-/// Flash effect for any entity with FlashTimer
+/// Flash effect for any entity with FlashTimer (a softer tint when flashing is reduced)
 pub fn entity_flash(
     mut commands: Commands,
     time: Res<Time>,
+    reduce_flashing: Res<ReduceFlashing>,
     mut query: Query<(Entity, &mut Sprite, &BaseColor, &mut FlashTimer)>,
 ) {
     for (entity, mut sprite, base, mut flash) in &mut query {
@@ -119,8 +408,10 @@ pub fn entity_flash(
         if flash.0.is_finished() {
             sprite.color = base.0;
             commands.entity(entity).remove::<FlashTimer>();
+        } else if reduce_flashing.0 {
+            sprite.color = base.0.mix(&COLOR_DAMAGE_FLASH, REDUCED_FLASH_TINT);
         } else {
-            sprite.color = Color::srgb(1.0, 0.3, 0.3); // Red flash for damage
+            sprite.color = COLOR_DAMAGE_FLASH; // Red flash for damage
         }
     }
 }
@@ -135,8 +426,17 @@ pub fn entity_flash(
 pub fn tile_attack_highlight(
     time: Res<Time>,
     tile_assets: Option<Res<TileAssets>>,
+    color_scheme: Res<ColorScheme>,
+    reduce_flashing: Res<ReduceFlashing>,
+    telegraphed: Res<TelegraphedTiles>,
+    falloff: Res<FalloffOverlay>,
     targeting_query: Query<(&TargetsTiles, Option<&GridPosition>)>,
-    mut tile_query: Query<(&TilePanel, &mut TileHighlightState, &mut Sprite)>,
+    mut tile_query: Query<(
+        &TilePanel,
+        &mut TileHighlightState,
+        &mut Sprite,
+        Option<&PanelStatus>,
+    )>,
 ) {
     // Skip if tile assets aren't loaded yet
     let Some(assets) = tile_assets else {
@@ -161,7 +461,7 @@ pub fn tile_attack_highlight(
     let dt = time.delta_secs();
 
     // Update each tile's highlight state and texture
-    for (tile, mut highlight, mut sprite) in &mut tile_query {
+    for (tile, mut highlight, mut sprite, status) in &mut tile_query {
         let is_targeted = targeted_positions.contains(&(tile.x, tile.y));
 
         // Set target based on whether tile is being attacked
@@ -218,7 +518,41 @@ pub fn tile_attack_highlight(
             1.0
         };
 
-        sprite.color = Color::srgba(1.0, 1.0, 1.0, alpha);
+        // Incoming enemy attacks warn first, then the falloff overlay;
+        // otherwise panel status (e.g. burning) tints the panel
+        let is_telegraphed = telegraphed.0.contains(&(tile.x, tile.y));
+        let tint = if is_telegraphed {
+            telegraph_tint(*color_scheme)
+        } else if let Some(multiplier) = falloff.multiplier_at(tile.x, tile.y) {
+            falloff_tint(multiplier, *color_scheme)
+        } else {
+            status.map_or(Color::WHITE, |status| status.tint(*color_scheme))
+        };
+        let blink =
+            if is_telegraphed && *color_scheme == ColorScheme::Colorblind && !reduce_flashing.0 {
+                0.6 + 0.4 * (time.elapsed_secs() * TELEGRAPH_BLINK_SPEED).sin().abs()
+            } else {
+                1.0
+            };
+        sprite.color = tint.with_alpha(alpha * blink);
+    }
+}
+
+/// Tint over tiles a charging enemy is about to hit
+fn telegraph_tint(scheme: ColorScheme) -> Color {
+    match scheme {
+        ColorScheme::Default => COLOR_PANEL_TELEGRAPH,
+        ColorScheme::Colorblind => COLOR_CB_PANEL_TELEGRAPH,
+    }
+}
+
+/// Falloff overlay tint: green (blue when colorblind) at full damage shading
+/// to orange at half or less
+pub fn falloff_tint(multiplier: f32, scheme: ColorScheme) -> Color {
+    let t = ((1.0 - multiplier) * 2.0).clamp(0.0, 1.0);
+    match scheme {
+        ColorScheme::Default => COLOR_FALLOFF_FULL.mix(&COLOR_FALLOFF_MIN, t),
+        ColorScheme::Colorblind => COLOR_CB_FALLOFF_FULL.mix(&COLOR_CB_FALLOFF_MIN, t),
     }
 }
 
@@ -245,13 +579,37 @@ pub fn check_victory_condition(
     mut currency: ResMut<PlayerCurrency>,
     mut progress: ResMut<GameProgress>,
     battle_timer: Res<BattleTimer>,
+    rewards: Res<BattleRewards>,
+    queue: Res<WaveQueue>,
+    config: Res<ArenaConfig>,
+    mut virtual_time: ResMut<Time<Virtual>>,
 ) {
+    // Practice arenas never end (see respawn_practice_dummy)
+    if config.practice {
+        return;
+    }
+
     if *wave_state == WaveState::Active && enemy_query.is_empty() {
+        // More waves to come: hand over to advance_wave
+        if queue.has_next() {
+            *wave_state = WaveState::Intermission;
+            info!("Wave {} cleared!", queue.current);
+            commands.spawn((
+                Text2d::new(format!("WAVE {}", queue.current + 1)),
+                TextFont::from_font_size(72.0),
+                TextColor(COLOR_TEXT),
+                Transform::from_xyz(0.0, 60.0, Z_UI + 50.0),
+                WaveBanner,
+                CleanupOnStateExit(GameState::Playing),
+            ));
+            return;
+        }
+
         // Victory!
         *wave_state = WaveState::Cleared;
 
-        // Award currency (base + scaling)
-        let reward = 100 + (progress.current_level as u64 * 50);
+        // Award the zenny dropped by deleted enemies
+        let reward = rewards.zenny;
         currency.zenny += reward;
         info!("Wave Cleared! Reward: {} Zenny", reward);
 
@@ -260,7 +618,190 @@ pub fn check_victory_condition(
 
         // Trigger the victory outro instead of immediate state transition
         // The outro system will detect this resource and set up the UI
-        commands.insert_resource(VictoryOutro::new(battle_timer.elapsed, reward));
+        let bonus = time_bonus(battle_timer.elapsed, config.par_time());
+        commands.insert_resource(VictoryOutro::new(battle_timer.elapsed, reward, bonus));
+        // The killing blow plays out in slow motion during the outro's HitStop phase
+        commands.insert_resource(HitStop::start(&mut virtual_time));
+    }
+}
+
+/// Delete an enemy that ran out of HP. It stops being an `Enemy` right away,
+/// so rewards, targeting and the victory check count it as gone, and lingers
+/// as `Dying` while `animate_slime` plays its death frames.
+pub fn kill_enemy(commands: &mut Commands, entity: Entity) {
+    commands
+        .entity(entity)
+        .remove::<(
+            Enemy,
+            BehaviorEnemy,
+            ChargingTelegraph,
+            FlashTimer,
+            TeleportFx,
+        )>()
+        .insert(Dying)
+        .despawn_related::<Children>();
+}
+
+/// Counter hit: an enemy struck while charging loses its attack, takes
+/// `COUNTER_HIT_MULTIPLIER` times the damage and gets a "COUNTER" pop-up.
+/// Returns the damage to deal (unchanged if the enemy wasn't charging).
+pub fn counter_hit(
+    commands: &mut Commands,
+    enemy_entity: Entity,
+    enemy_pos: GridPosition,
+    attack: Option<&mut EnemyAttack>,
+    damage: i32,
+) -> i32 {
+    if !attack.is_some_and(|attack| attack.cancel_charge()) {
+        return damage;
+    }
+    commands
+        .entity(enemy_entity)
+        .remove::<ChargingTelegraph>()
+        .insert(EnemyAnimState::Idle);
+    commands.spawn((
+        Text2d::new("COUNTER"),
+        TextFont::from_font_size(26.0),
+        TextColor(COLOR_COUNTER),
+        Transform::default(),
+        enemy_pos,
+        RenderConfig {
+            offset: Vec2::new(0.0, 150.0),
+            base_z: Z_UI,
+        },
+        CounterPopup,
+        Lifetime(Timer::from_seconds(COUNTER_POPUP_TIME, TimerMode::Once)),
+        CleanupOnStateExit(GameState::Playing),
+    ));
+    (damage as f32 * COUNTER_HIT_MULTIPLIER).round() as i32
+}
+
+/// Float "COUNTER" pop-ups upward as they fade, then despawn them
+pub fn update_counter_popups(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<
+        (Entity, &mut Lifetime, &mut RenderConfig, &mut TextColor),
+        With<CounterPopup>,
+    >,
+) {
+    for (entity, mut lifetime, mut render, mut color) in &mut query {
+        lifetime.0.tick(time.delta());
+        if lifetime.0.is_finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        render.offset.y += 60.0 * time.delta_secs();
+        color.0 = COLOR_COUNTER.with_alpha(lifetime.0.fraction_remaining());
+    }
+}
+
+/// Bank an enemy's zenny drop when it is deleted after losing all its HP,
+/// scaled by the current kill combo.
+/// Despawns from state cleanup leave HP above zero and are ignored.
+pub fn collect_enemy_reward(
+    removed: On<Remove, Enemy>,
+    query: Query<(&Health, &EnemyReward)>,
+    battle_timer: Res<BattleTimer>,
+    mut combo: ResMut<ComboTracker>,
+    mut rewards: ResMut<BattleRewards>,
+) {
+    let Ok((health, reward)) = query.get(removed.entity) else {
+        return;
+    };
+    if health.current <= 0 {
+        let count = combo.register_kill(battle_timer.elapsed);
+        rewards.add(ComboTracker::scale_reward(reward.0, count));
+    }
+}
+
+/// Feed HP changes into the battle stats: HP lost by the player is damage
+/// taken, HP lost by anything else is damage dealt
+pub fn track_battle_damage(
+    mut stats: ResMut<BattleStats>,
+    query: Query<(Entity, &Health, Has<Player>), Changed<Health>>,
+) {
+    for (entity, health, is_player) in &query {
+        stats.track_hp(entity, health.current, is_player);
+    }
+}
+
+/// Show the running kill combo, pulsing while it can still be extended
+pub fn update_combo_text(
+    time: Res<Time>,
+    battle_timer: Res<BattleTimer>,
+    combo: Res<ComboTracker>,
+    mut query: Query<(&mut Text2d, &mut Transform), With<ComboText>>,
+) {
+    let active = combo.count >= 2 && combo.is_active(battle_timer.elapsed);
+    for (mut text, mut transform) in &mut query {
+        if active {
+            text.0 = format!(
+                "COMBO {} x{:.2}",
+                combo.count,
+                combo_multiplier(combo.count)
+            );
+            let pulse = 1.0 + 0.1 * (time.elapsed_secs() * 10.0).sin();
+            transform.scale = Vec3::splat(pulse);
+        } else if !text.0.is_empty() {
+            text.0.clear();
+            transform.scale = Vec3::ONE;
+        }
+    }
+}
+
+/// Keep the in-battle zenny tally in sync with `BattleRewards`
+pub fn update_reward_text(
+    rewards: Res<BattleRewards>,
+    mut query: Query<&mut Text2d, With<BattleRewardText>>,
+) {
+    if !rewards.is_changed() {
+        return;
+    }
+    for mut text in &mut query {
+        text.0 = format!("Z: {}", rewards.zenny);
+    }
+}
+
+/// Show the running battle clock
+pub fn update_battle_timer_text(
+    battle_timer: Res<BattleTimer>,
+    mut query: Query<&mut Text2d, With<BattleTimerText>>,
+) {
+    if !battle_timer.is_changed() {
+        return;
+    }
+    for mut text in &mut query {
+        text.0 = format_battle_time(battle_timer.elapsed);
+    }
+}
+
+/// HP bar color: green when healthy, yellow past half, red when low
+pub fn hp_bar_color(fraction: f32) -> Color {
+    if fraction > 0.5 {
+        COLOR_HP_BAR_HIGH
+    } else if fraction > 0.25 {
+        COLOR_HP_BAR_MID
+    } else {
+        COLOR_HP_BAR_LOW
+    }
+}
+
+/// Shrink each enemy's HP bar fill to its remaining HP, keeping it left-aligned
+pub fn update_enemy_hp_bars(
+    enemy_query: Query<(&Health, &Children), (With<Enemy>, Changed<Health>)>,
+    mut fill_query: Query<(&mut Sprite, &mut Transform), With<EnemyHpBarFill>>,
+) {
+    for (health, children) in &enemy_query {
+        let fraction = health.fraction();
+        let width = ENEMY_HP_BAR_SIZE.x * fraction;
+        for child in children.iter() {
+            if let Ok((mut sprite, mut transform)) = fill_query.get_mut(child) {
+                sprite.custom_size = Some(Vec2::new(width, ENEMY_HP_BAR_SIZE.y));
+                sprite.color = hp_bar_color(fraction);
+                transform.translation.x = (width - ENEMY_HP_BAR_SIZE.x) / 2.0;
+            }
+        }
     }
 }
 
@@ -320,8 +861,8 @@ pub fn projectile_animation_system(
             )
         };
 
+        // Size stays as spawned: charged shots are drawn larger
         sprite.image = sprite_image;
-        sprite.custom_size = Some(BULLET_DRAW_SIZE);
         sprite.texture_atlas = Some(TextureAtlas {
             layout: sprite_layout,
             index: frame_index,
@@ -335,9 +876,11 @@ pub fn check_defeat_condition(
     mut wave_state: ResMut<WaveState>,
     player_query: Query<&Health, With<Player>>,
     battle_timer: Res<BattleTimer>,
+    mut rumble: Rumble,
+    config: Res<ArenaConfig>,
 ) {
-    // Only check during active battle
-    if *wave_state != WaveState::Active {
+    // Only check during active battle (practice arenas never end)
+    if *wave_state != WaveState::Active || config.practice {
         return;
     }
 
@@ -353,8 +896,418 @@ pub fn check_defeat_condition(
         *wave_state = WaveState::Cleared; // Reuse Cleared state to stop gameplay
 
         info!("Player Defeated! No reward earned.");
+        rumble.defeat();
 
         // Trigger the defeat outro
         commands.insert_resource(DefeatOutro::new(battle_timer.elapsed));
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::actions::{ShieldType, process_shield_effects, update_active_shields};
+    use crate::resources::COMBO_WINDOW;
+    use crate::systems::rumble::init_hit_feedback;
+    use crate::weapons::DamageType;
+
+    #[test]
+    fn moving_projectile_leaves_a_fading_trail() {
+        let mut world = World::new();
+        world.init_resource::<Time>();
+        let color = DamageType::Fire.tint();
+        let shot = world
+            .spawn((
+                GridPosition { x: 1, y: 1 },
+                RenderConfig {
+                    offset: BULLET_OFFSET,
+                    base_z: Z_BULLET,
+                },
+                ProjectileTrail::new(color),
+            ))
+            .id();
+        let mut schedule = Schedule::default();
+        schedule.add_systems((spawn_projectile_trails, fade_trail_segments).chain());
+        let segments = |world: &mut World| {
+            world
+                .query::<(&GridPosition, &TrailSegment)>()
+                .iter(world)
+                .map(|(pos, segment)| (*pos, segment.color))
+                .collect::<Vec<_>>()
+        };
+
+        // Standing still leaves no trail
+        schedule.run(&mut world);
+        schedule.run(&mut world);
+        assert!(segments(&mut world).is_empty());
+
+        world.get_mut::<GridPosition>(shot).unwrap().x = 2;
+        schedule.run(&mut world);
+        assert_eq!(
+            segments(&mut world),
+            vec![(GridPosition { x: 1, y: 1 }, color)]
+        );
+
+        world
+            .resource_mut::<Time>()
+            .advance_by(Duration::from_secs_f32(TRAIL_TIME + 0.01));
+        schedule.run(&mut world);
+        assert!(segments(&mut world).is_empty());
+    }
+
+    fn reward_world() -> World {
+        let mut world = World::new();
+        world.init_resource::<BattleTimer>();
+        world.init_resource::<ComboTracker>();
+        world.init_resource::<BattleRewards>();
+        world.add_observer(collect_enemy_reward);
+        world
+    }
+
+    /// Kill an enemy worth `zenny` at `now` seconds of battle time
+    fn kill_at(world: &mut World, zenny: u64, now: f32) {
+        world.resource_mut::<BattleTimer>().elapsed = now;
+        let enemy = world
+            .spawn((
+                Enemy,
+                Health {
+                    current: 0,
+                    max: 10,
+                },
+                EnemyReward(zenny),
+            ))
+            .id();
+        world.despawn(enemy);
+    }
+
+    #[test]
+    fn shot_at_max_range_fizzles_out_before_despawning() {
+        let mut world = World::new();
+        world.init_resource::<Time>();
+        world.init_resource::<ArenaLayout>();
+        let shot = world
+            .spawn((
+                Bullet,
+                GridPosition { x: 1, y: 1 },
+                MoveTimer(Timer::from_seconds(BULLET_MOVE_TIMER, TimerMode::Repeating)),
+                Projectile::test_shot(10, 2),
+                ProjectileAnimation::blaster(false),
+            ))
+            .id();
+        let mut schedule = Schedule::default();
+        schedule.add_systems(bullet_movement);
+        let tick = |world: &mut World, schedule: &mut Schedule| {
+            world
+                .resource_mut::<Time>()
+                .advance_by(Duration::from_secs_f32(BULLET_MOVE_TIMER));
+            schedule.run(world);
+        };
+
+        tick(&mut world, &mut schedule);
+        tick(&mut world, &mut schedule);
+        assert_eq!(world.get::<GridPosition>(shot).unwrap().x, 3);
+        assert!(!world.entity(shot).contains::<ProjectileHit>());
+
+        // The next step would leave its range: it stays put and plays its finish frames
+        tick(&mut world, &mut schedule);
+        assert_eq!(world.get::<GridPosition>(shot).unwrap().x, 3);
+        assert_eq!(
+            world.get::<ProjectileAnimation>(shot).unwrap().state,
+            crate::assets::ProjectileAnimationState::Finish
+        );
+        assert!(world.entity(shot).contains::<ProjectileImmobile>());
+        assert!(world.entity(shot).contains::<ProjectileHit>());
+    }
+
+    /// Fire an equal-damage player shot from column `shot_x` and enemy shot from
+    /// `enemy_shot_x` along row 1, step both once, and return which survived
+    fn shots_after_one_step(shot_x: i32, enemy_shot_x: i32) -> (bool, bool) {
+        let mut world = World::new();
+        world.init_resource::<Time>();
+        world.init_resource::<ArenaLayout>();
+        let move_timer = || MoveTimer(Timer::from_seconds(BULLET_MOVE_TIMER, TimerMode::Repeating));
+        let shot = world
+            .spawn((
+                Bullet,
+                GridPosition { x: shot_x, y: 1 },
+                move_timer(),
+                Projectile::test_shot(10, 6),
+            ))
+            .id();
+        let enemy_shot = world
+            .spawn((
+                GridPosition {
+                    x: enemy_shot_x,
+                    y: 1,
+                },
+                move_timer(),
+                EnemyBullet::new(10),
+            ))
+            .id();
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(
+            (
+                bullet_movement,
+                enemy_bullet_movement,
+                projectile_collision_system,
+            )
+                .chain(),
+        );
+        world
+            .resource_mut::<Time>()
+            .advance_by(Duration::from_secs_f32(BULLET_MOVE_TIMER));
+        schedule.run(&mut world);
+
+        (
+            world.get_entity(shot).is_ok(),
+            world.get_entity(enemy_shot).is_ok(),
+        )
+    }
+
+    #[test]
+    fn converging_shots_cancel_each_other_out() {
+        // Meeting on the tile between them
+        assert_eq!(shots_after_one_step(1, 3), (false, false));
+        // Side by side: they swap tiles in the same step
+        assert_eq!(shots_after_one_step(1, 2), (false, false));
+        // Already past each other: both fly on
+        assert_eq!(shots_after_one_step(2, 1), (true, true));
+    }
+
+    /// Player standing on (1, 1) with an Invis shield, and a bullet on their tile
+    fn invisible_player_world(invis_secs: f32) -> (World, Entity) {
+        let mut world = World::new();
+        world.init_resource::<Time>();
+        init_hit_feedback(&mut world);
+        let player = world
+            .spawn((
+                Player,
+                GridPosition { x: 1, y: 1 },
+                Health {
+                    current: 100,
+                    max: 100,
+                },
+                ActiveShield {
+                    duration_timer: Timer::from_seconds(invis_secs, TimerMode::Once),
+                    damage_threshold: None,
+                    shield_type: ShieldType::Invis,
+                },
+            ))
+            .id();
+        world.spawn((GridPosition { x: 1, y: 1 }, EnemyBullet::new(30)));
+        (world, player)
+    }
+
+    #[test]
+    fn bullets_pass_through_an_invisible_player() {
+        let (mut world, player) = invisible_player_world(1.0);
+        let mut schedule = Schedule::default();
+        schedule.add_systems(
+            (
+                process_shield_effects,
+                update_active_shields,
+                enemy_bullet_hit_player,
+            )
+                .chain(),
+        );
+
+        schedule.run(&mut world);
+        assert_eq!(world.get::<Health>(player).unwrap().current, 100);
+        assert!(world.get::<ActiveShield>(player).is_some());
+        let bullets = world.query::<&EnemyBullet>().iter(&world).count();
+        assert_eq!(bullets, 1);
+
+        // Once invisibility wears off the same bullet lands
+        world
+            .resource_mut::<Time>()
+            .advance_by(Duration::from_secs_f32(1.0));
+        schedule.run(&mut world);
+        assert!(world.get::<ActiveShield>(player).is_none());
+        assert_eq!(world.get::<Health>(player).unwrap().current, 70);
+    }
+
+    #[test]
+    fn getting_hit_interrupts_the_players_charges() {
+        use crate::actions::{ActionId, ActionState};
+        use crate::weapons::{WeaponFiringState, WeaponType};
+
+        let mut world = World::new();
+        world.init_resource::<Time>();
+        init_hit_feedback(&mut world);
+        let weapon = EquippedWeapon::new(WeaponType::Blaster);
+        let mut state = WeaponState::new(weapon.stats.fire_cooldown);
+        state.start_charging(1.0);
+        let player = world
+            .spawn((
+                Player,
+                GridPosition { x: 1, y: 1 },
+                Health {
+                    current: 100,
+                    max: 100,
+                },
+                weapon,
+                state,
+            ))
+            .id();
+        let mut slot = ActionSlot::new(0, ActionId::WideSwrd, 3.0, 0.5);
+        slot.start_charging();
+        let slot = world.spawn(slot).id();
+        let mut schedule = Schedule::default();
+        schedule.add_systems((enemy_bullet_hit_player, interrupt_charge_on_hit).chain());
+
+        // Nothing hits: both charges carry on
+        schedule.run(&mut world);
+        let state = world.get::<WeaponState>(player).unwrap();
+        assert_eq!(state.firing_state, WeaponFiringState::Charging);
+        assert!(state.charge_timer.is_some());
+        assert_eq!(
+            world.get::<ActionSlot>(slot).unwrap().state,
+            ActionState::Charging
+        );
+
+        world.spawn((GridPosition { x: 1, y: 1 }, EnemyBullet::new(10)));
+        schedule.run(&mut world);
+        assert_eq!(world.get::<Health>(player).unwrap().current, 90);
+        let state = world.get::<WeaponState>(player).unwrap();
+        assert_eq!(state.firing_state, WeaponFiringState::OnCooldown);
+        assert!(state.charge_timer.is_none());
+        assert!(!state.charge_ready);
+        let slot = world.get::<ActionSlot>(slot).unwrap();
+        assert_eq!(slot.state, ActionState::Ready);
+        assert!(slot.charge_timer.is_none());
+        assert!(world.get::<Struck>(player).is_none());
+    }
+
+    #[test]
+    fn over_health_soaks_hits_before_base_hp() {
+        let mut world = World::new();
+        world.init_resource::<Time>();
+        init_hit_feedback(&mut world);
+        let player = world
+            .spawn((
+                Player,
+                GridPosition { x: 1, y: 1 },
+                Health {
+                    current: 100,
+                    max: 100,
+                },
+                OverHealth::new(30, 8.0),
+            ))
+            .id();
+        let mut schedule = Schedule::default();
+        schedule.add_systems(enemy_bullet_hit_player);
+        let mut hit = |world: &mut World, damage: i32| {
+            world.spawn((GridPosition { x: 1, y: 1 }, EnemyBullet::new(damage)));
+            schedule.run(world);
+        };
+
+        // Fully absorbed
+        hit(&mut world, 20);
+        assert_eq!(world.get::<Health>(player).unwrap().current, 100);
+        assert_eq!(world.get::<OverHealth>(player).unwrap().current, 10);
+
+        // The pool takes what it can, the rest reaches base HP
+        hit(&mut world, 25);
+        assert_eq!(world.get::<OverHealth>(player).unwrap().current, 0);
+        assert_eq!(world.get::<Health>(player).unwrap().current, 85);
+
+        // Fading never refills it, and it runs out with its timer
+        let mut pool = OverHealth::new(60, 8.0);
+        assert!(!pool.decay(Duration::from_secs(4)));
+        assert_eq!(pool.current, 30);
+        pool.absorb(25);
+        assert!(!pool.decay(Duration::from_secs(2)));
+        assert_eq!(pool.current, 5);
+        assert!(pool.decay(Duration::from_secs(2)));
+    }
+
+    #[test]
+    fn kills_bank_their_zenny_drops() {
+        let mut world = reward_world();
+        kill_at(&mut world, 50, 1.0);
+        kill_at(&mut world, 30, 1.0 + COMBO_WINDOW + 1.0);
+        assert_eq!(world.resource::<BattleRewards>().zenny, 80);
+    }
+
+    #[test]
+    fn quick_kills_pay_more_than_spaced_ones() {
+        let mut quick = reward_world();
+        kill_at(&mut quick, 50, 1.0);
+        kill_at(&mut quick, 50, 1.5);
+
+        let mut spaced = reward_world();
+        kill_at(&mut spaced, 50, 1.0);
+        kill_at(&mut spaced, 50, 1.0 + COMBO_WINDOW + 1.0);
+
+        assert!(quick.resource::<BattleRewards>().zenny > spaced.resource::<BattleRewards>().zenny);
+    }
+
+    #[test]
+    fn hp_bar_fill_matches_remaining_hp() {
+        let mut world = World::new();
+        let fill = world
+            .spawn((Sprite::default(), Transform::default(), EnemyHpBarFill))
+            .id();
+        let enemy = world
+            .spawn((
+                Enemy,
+                Health {
+                    current: 200,
+                    max: 200,
+                },
+            ))
+            .add_child(fill)
+            .id();
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(update_enemy_hp_bars);
+        let fill_width = |world: &World| {
+            world.get::<Sprite>(fill).unwrap().custom_size.unwrap().x / ENEMY_HP_BAR_SIZE.x
+        };
+
+        schedule.run(&mut world);
+        assert_eq!(fill_width(&world), 1.0);
+
+        world.get_mut::<Health>(enemy).unwrap().current -= 150;
+        schedule.run(&mut world);
+        assert_eq!(fill_width(&world), 0.25);
+        assert_eq!(world.get::<Sprite>(fill).unwrap().color, COLOR_HP_BAR_LOW);
+    }
+
+    #[test]
+    fn piercing_shots_chip_a_rock_once_and_fly_on() {
+        let mut world = World::new();
+        let shot = |world: &mut World, piercing: bool| {
+            world
+                .spawn((
+                    Bullet,
+                    GridPosition { x: 3, y: 1 },
+                    Projectile {
+                        piercing,
+                        ..Projectile::test_shot(10, 6)
+                    },
+                    ProjectileAnimation::blaster(false),
+                ))
+                .id()
+        };
+        let rock = world
+            .spawn((GridPosition { x: 3, y: 1 }, Obstacle { hp: 100 }))
+            .id();
+        let pierce = shot(&mut world, true);
+        let mut schedule = Schedule::default();
+        schedule.add_systems(bullet_hit_obstacle);
+
+        // Two frames on the rock's tile: one hit, and the shot keeps going
+        schedule.run(&mut world);
+        schedule.run(&mut world);
+        assert_eq!(world.get::<Obstacle>(rock).unwrap().hp, 90);
+        assert!(world.get::<ProjectileHit>(pierce).is_none());
+
+        let plain = shot(&mut world, false);
+        schedule.run(&mut world);
+        assert!(world.get::<ProjectileHit>(plain).is_some());
+    }
+}
@@ -0,0 +1,232 @@
+// ============================================================================
+// Enemy Encyclopedia - Enemies met so far and how they fight
+// ============================================================================
+//
+// Reached from the main menu. Every enemy type gets a row; the ones in
+// `DiscoveredEnemies` show their HP, movement and attack (summarized from the
+// blueprint), the rest stay "???". `discover_enemies` records each type as it
+// spawns in battle, and the set is written to disk when the battle ends.
+
+use bevy::prelude::*;
+
+use crate::components::{CleanupOnStateExit, GameState};
+use crate::enemies::{AttackBehavior, EnemyBlueprint, EnemyId, EnemyKind, MovementBehavior};
+use crate::resources::{DiscoveredEnemies, SaveSlot};
+use crate::save::save_discovered_enemies;
+
+const ENTRY_NAME: Color = Color::srgb(1.0, 0.9, 0.4);
+const ENTRY_DETAILS: Color = Color::srgb(0.75, 0.8, 0.9);
+const ENTRY_UNKNOWN: Color = Color::srgb(0.4, 0.4, 0.5);
+
+/// One line on how an enemy moves
+pub fn movement_summary(movement: &MovementBehavior) -> &'static str {
+    match movement {
+        MovementBehavior::Stationary => "Stays put",
+        MovementBehavior::Random { .. } => "Wanders its area",
+        MovementBehavior::ChaseRow => "Follows your row",
+        MovementBehavior::ChasePlayer => "Closes in on you",
+        MovementBehavior::PatrolHorizontal => "Patrols left and right",
+        MovementBehavior::PatrolVertical => "Patrols up and down",
+        MovementBehavior::HideAndPeek { .. } => "Hides, then peeks out to attack",
+        MovementBehavior::Teleport { .. } => "Teleports around its area",
+        MovementBehavior::BackRowOnly => "Holds the back row",
+        MovementBehavior::MirrorPlayer => "Mirrors your row",
+        MovementBehavior::Advance { .. } => "Advances toward you",
+        MovementBehavior::Flee { .. } => "Retreats to the back when hurt",
+    }
+}
+
+/// One line on how an enemy attacks, with its base damage
+pub fn attack_summary(attack: &AttackBehavior) -> String {
+    match attack {
+        AttackBehavior::None => "None".to_string(),
+        AttackBehavior::Projectile { damage, speed, .. } => {
+            format!("Shot, {damage} dmg at {speed} tiles/s")
+        }
+        AttackBehavior::ProjectileSpread {
+            damage,
+            row_offsets,
+            ..
+        } => format!("{}-way shot, {damage} dmg", row_offsets.len()),
+        AttackBehavior::ShockWave { damage, .. } => format!("Shockwave, {damage} dmg"),
+        AttackBehavior::Melee { damage, range, .. } => {
+            format!("Melee, {damage} dmg at {range} tile reach")
+        }
+        AttackBehavior::AreaAttack {
+            damage, pattern, ..
+        } => format!("Area blast, {damage} dmg on {} tiles", pattern.len()),
+        AttackBehavior::Bomb { damage, radius, .. } => {
+            format!("Bomb, {damage} dmg in a {radius} tile radius")
+        }
+        AttackBehavior::LaserBeam { damage, .. } => format!("Laser, {damage} dmg down the row"),
+        AttackBehavior::Summon { minion, .. } => {
+            format!("Summons {}", EnemyBlueprint::get(minion.enemy_id).name)
+        }
+    }
+}
+
+/// Record every enemy type as it spawns
+pub fn discover_enemies(
+    mut discovered: ResMut<DiscoveredEnemies>,
+    query: Query<&EnemyKind, Added<EnemyKind>>,
+) {
+    for kind in &query {
+        if !discovered.contains(kind.0) {
+            discovered.discover(kind.0);
+            info!("Discovered {:?}", kind.0);
+        }
+    }
+}
+
+/// Write the discovered enemies when a battle that found new ones ends
+pub fn persist_discovered_enemies(discovered: Res<DiscoveredEnemies>, slot: Res<SaveSlot>) {
+    if discovered.is_changed() {
+        save_discovered_enemies(*slot, &discovered);
+    }
+}
+
+pub fn setup_encyclopedia(mut commands: Commands, discovered: Res<DiscoveredEnemies>) {
+    commands
+        .spawn((
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(6.0),
+                ..default()
+            },
+            BackgroundColor(Color::srgb(0.03, 0.03, 0.1)),
+            CleanupOnStateExit(GameState::Encyclopedia),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new("ENEMIES"),
+                TextFont::from_font_size(60.0),
+                TextColor(Color::srgb(0.9, 0.4, 0.3)),
+            ));
+            let found = EnemyId::ALL
+                .iter()
+                .filter(|id| discovered.contains(**id))
+                .count();
+            parent.spawn((
+                Text::new(format!("{found} / {} discovered", EnemyId::ALL.len())),
+                TextFont::from_font_size(22.0),
+                TextColor(ENTRY_DETAILS),
+                Node {
+                    margin: UiRect::bottom(Val::Px(20.0)),
+                    ..default()
+                },
+            ));
+
+            for id in EnemyId::ALL {
+                if !discovered.contains(id) {
+                    parent.spawn((
+                        Text::new("???"),
+                        TextFont::from_font_size(28.0),
+                        TextColor(ENTRY_UNKNOWN),
+                        Node {
+                            margin: UiRect::top(Val::Px(10.0)),
+                            ..default()
+                        },
+                    ));
+                    continue;
+                }
+                let blueprint = EnemyBlueprint::get(id);
+                parent.spawn((
+                    Text::new(format!(
+                        "{}  -  HP {}",
+                        blueprint.name, blueprint.stats.base_hp
+                    )),
+                    TextFont::from_font_size(28.0),
+                    TextColor(ENTRY_NAME),
+                    Node {
+                        margin: UiRect::top(Val::Px(10.0)),
+                        ..default()
+                    },
+                ));
+                parent.spawn((
+                    Text::new(format!(
+                        "Move: {}  |  Attack: {}",
+                        movement_summary(&blueprint.movement),
+                        attack_summary(&blueprint.attack)
+                    )),
+                    TextFont::from_font_size(20.0),
+                    TextColor(ENTRY_DETAILS),
+                ));
+            }
+
+            parent.spawn((
+                Text::new("Esc / B: Back"),
+                TextFont::from_font_size(18.0),
+                TextColor(Color::srgba(0.6, 0.6, 0.6, 0.8)),
+                Node {
+                    margin: UiRect::top(Val::Px(30.0)),
+                    ..default()
+                },
+            ));
+        });
+}
+
+/// Back to the main menu
+pub fn update_encyclopedia(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<&Gamepad>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    let back = keyboard.any_just_pressed([KeyCode::Escape, KeyCode::Enter])
+        || gamepads.iter().any(|gamepad| {
+            gamepad.just_pressed(GamepadButton::East) || gamepad.just_pressed(GamepadButton::South)
+        });
+    if back {
+        next_state.set(GameState::MainMenu);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::EnemyConfig;
+    use crate::resources::{ArenaLayout, Difficulty};
+    use crate::systems::setup::spawn_enemy;
+
+    fn spawn_mettaur(
+        mut commands: Commands,
+        asset_server: Res<AssetServer>,
+        mut atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
+        arena_layout: Res<ArenaLayout>,
+    ) {
+        spawn_enemy(
+            &mut commands,
+            &asset_server,
+            &mut atlas_layouts,
+            &EnemyConfig::new(EnemyId::Mettaur, 4, 1),
+            0,
+            &arena_layout,
+            Difficulty::default(),
+        );
+    }
+
+    #[test]
+    fn spawning_an_enemy_discovers_its_type() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+            .init_asset::<Image>()
+            .init_asset::<TextureAtlasLayout>()
+            .init_resource::<ArenaLayout>()
+            .init_resource::<DiscoveredEnemies>()
+            .add_systems(Update, (spawn_mettaur, discover_enemies).chain());
+        assert!(
+            !app.world()
+                .resource::<DiscoveredEnemies>()
+                .contains(EnemyId::Mettaur)
+        );
+
+        app.update();
+        let discovered = app.world().resource::<DiscoveredEnemies>();
+        assert!(discovered.contains(EnemyId::Mettaur));
+        assert!(!discovered.contains(EnemyId::Slime));
+        assert_eq!(discovered.0.len(), 1);
+    }
+}
@@ -1,9 +1,14 @@
+use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
 use std::collections::HashSet;
 
 use crate::components::{CleanupOnStateExit, GameState};
-use crate::resources::{PlayerCurrency, PlayerUpgrades};
+use crate::resources::{
+    CampaignProgress, LoadoutPresets, OwnedChips, PlayerCurrency, PlayerLoadout, PlayerUpgrades,
+};
+use crate::systems::autosave::AutoSave;
 use crate::systems::shop::{ShopAction, ShopButtonAction}; // Import from shop for reuse
+use crate::weapons::PlayerWeapons;
 
 // ============================================================================
 // Growth Tree Data
@@ -15,6 +20,7 @@ pub enum UpgradeType {
     Health,
     FireRate,
     CritChance,
+    ChipCooldown,
     Core, // Starting point
 }
 
@@ -125,15 +131,108 @@ pub const GROWTH_NODES: &[GrowthNodeData] = &[
         label: "CRT +2%",
         description: "Further increases critical chance.",
     },
+    GrowthNodeData {
+        id: 9,
+        upgrade_type: UpgradeType::ChipCooldown,
+        cost: 350,
+        parent_id: Some(4),
+        x: 120.0,
+        y: 120.0, // Right-Down
+        label: "CHIP -10%",
+        description: "Reduces all chip cooldowns by 10%.",
+    },
 ];
 
 // ============================================================================
 // Resources & Components
 // ============================================================================
 
+/// Seconds a purchase can still be undone before it is committed
+pub const PURCHASE_UNDO_WINDOW: f32 = 3.0;
+
 #[derive(Resource, Default)]
 pub struct GrowthTreeState {
     pub unlocked_nodes: HashSet<u32>,
+    /// Purchase that has been paid for but can still be undone
+    pub pending: Option<PendingPurchase>,
+}
+
+/// A node purchase inside its undo grace window
+pub struct PendingPurchase {
+    pub node_id: u32,
+    pub timer: Timer,
+}
+
+impl GrowthTreeState {
+    /// Lock in the pending purchase: unlock the node and apply its upgrade.
+    /// Returns true if a purchase was committed.
+    pub fn commit_pending(&mut self, upgrades: &mut PlayerUpgrades) -> bool {
+        let Some(pending) = self.pending.take() else {
+            return false;
+        };
+        let Some(node) = GROWTH_NODES.iter().find(|n| n.id == pending.node_id) else {
+            return false;
+        };
+
+        self.unlocked_nodes.insert(node.id);
+        match node.upgrade_type {
+            UpgradeType::Damage => upgrades.damage_level += 1,
+            UpgradeType::Health => upgrades.health_level += 1,
+            UpgradeType::FireRate => upgrades.fire_rate_level += 1,
+            UpgradeType::CritChance => upgrades.crit_chance_level += 1,
+            UpgradeType::ChipCooldown => upgrades.chip_cooldown_level += 1,
+            UpgradeType::Core => {}
+        }
+        true
+    }
+
+    /// Pay for a node now; it unlocks once the undo window closes
+    pub fn begin_purchase(&mut self, node: &GrowthNodeData, currency: &mut PlayerCurrency) {
+        currency.zenny -= node.cost;
+        self.pending = Some(PendingPurchase {
+            node_id: node.id,
+            timer: Timer::from_seconds(PURCHASE_UNDO_WINDOW, TimerMode::Once),
+        });
+    }
+
+    /// Undo the pending purchase and refund its full cost
+    pub fn cancel_pending(&mut self, currency: &mut PlayerCurrency) {
+        let Some(pending) = self.pending.take() else {
+            return;
+        };
+        if let Some(node) = GROWTH_NODES.iter().find(|n| n.id == pending.node_id) {
+            currency.zenny += node.cost;
+        }
+    }
+
+    pub fn is_pending(&self, node_id: u32) -> bool {
+        self.pending.as_ref().is_some_and(|p| p.node_id == node_id)
+    }
+}
+
+/// Node focused by keyboard/gamepad (or last hovered); drives the info panel
+#[derive(Resource, Debug, Default, Clone, Copy)]
+pub struct GrowthCursor {
+    pub focused: u32,
+}
+
+/// Closest node from `from_id` in screen direction `dir` (UI space: -y is up).
+/// Nodes off to the side count double, so straight lines win over diagonals.
+pub fn neighbor_in_direction(from_id: u32, dir: Vec2) -> Option<u32> {
+    let from = GROWTH_NODES.iter().find(|n| n.id == from_id)?;
+    GROWTH_NODES
+        .iter()
+        .filter_map(|node| {
+            let delta = Vec2::new(node.x - from.x, node.y - from.y);
+            let along = delta.dot(dir);
+            if along <= 0.0 {
+                return None;
+            }
+            let across = (delta - dir * along).length();
+            Some((node.id, along + 2.0 * across))
+        })
+        .min_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(id, _)| id)
 }
 
 #[derive(Component)]
@@ -159,6 +258,7 @@ pub fn setup_growth_tree(
 ) {
     // Ensure core is unlocked
     tree_state.unlocked_nodes.insert(0);
+    commands.insert_resource(GrowthCursor::default());
 
     // Root Container (Row)
     commands
@@ -221,6 +321,7 @@ pub fn setup_growth_tree(
                                         UpgradeType::Health => "HP",
                                         UpgradeType::FireRate => "SPD",
                                         UpgradeType::CritChance => "CRT",
+                                        UpgradeType::ChipCooldown => "CHP",
                                     }),
                                     TextFont::from_font_size(20.0),
                                     TextColor(Color::WHITE),
@@ -322,7 +423,7 @@ pub fn setup_growth_tree(
 
                     // Controller Hints
                     panel.spawn((
-                        Text::new("[D-Pad] Navigate  [A] Unlock  [Esc] Back"),
+                        Text::new("[D-Pad] Navigate  [A] Unlock  [X/Bksp] Undo  [Esc] Back"),
                         TextFont::from_font_size(16.0),
                         TextColor(Color::srgba(1.0, 1.0, 1.0, 0.5)),
                         Node {
@@ -334,8 +435,18 @@ pub fn setup_growth_tree(
         });
 }
 
+/// Loadout-side progress the growth tree only reads to autosave
+#[derive(SystemParam)]
+pub struct LoadoutSaveData<'w> {
+    loadout: Res<'w, PlayerLoadout>,
+    presets: Res<'w, LoadoutPresets>,
+    player_weapons: Res<'w, PlayerWeapons>,
+    owned_chips: Res<'w, OwnedChips>,
+}
+
 pub fn update_growth_tree(
     keyboard: Res<ButtonInput<KeyCode>>,
+    time: Res<Time>,
     gamepads: Query<&Gamepad>,
     mut node_query: Query<
         (
@@ -352,37 +463,59 @@ pub fn update_growth_tree(
         (With<Button>, With<ShopButtonAction>),
     >,
 
-    // Note: Text component in 0.18 has public field `.0` which is the String
-    mut title_query: Query<
-        &mut Text,
-        (
-            With<InfoPanelTitle>,
-            Without<InfoPanelDesc>,
-            Without<InfoPanelCost>,
-        ),
-    >,
-    mut desc_query: Query<
-        &mut Text,
-        (
-            With<InfoPanelDesc>,
-            Without<InfoPanelTitle>,
-            Without<InfoPanelCost>,
-        ),
-    >,
-    mut cost_query: Query<
-        &mut Text,
-        (
-            With<InfoPanelCost>,
-            Without<InfoPanelTitle>,
-            Without<InfoPanelDesc>,
-        ),
-    >,
+    // Info panel texts: title, description, cost
+    mut info_texts: ParamSet<(
+        Query<&mut Text, With<InfoPanelTitle>>,
+        Query<&mut Text, With<InfoPanelDesc>>,
+        Query<&mut Text, With<InfoPanelCost>>,
+    )>,
+    cursor: Res<GrowthCursor>,
 
     mut currency: ResMut<PlayerCurrency>,
     mut upgrades: ResMut<PlayerUpgrades>,
     mut tree_state: ResMut<GrowthTreeState>,
     mut next_state: ResMut<NextState<GameState>>,
+    campaign: Res<CampaignProgress>,
+    saved: LoadoutSaveData,
+    mut autosave: AutoSave,
 ) {
+    // Tick the undo window; commit once it runs out
+    if let Some(pending) = tree_state.pending.as_mut() {
+        pending.timer.tick(time.delta());
+        if pending.timer.is_finished() && tree_state.commit_pending(&mut upgrades) {
+            autosave.save(
+                &campaign,
+                &currency,
+                &upgrades,
+                &tree_state,
+                &saved.loadout,
+                &saved.player_weapons,
+                &saved.owned_chips,
+                &saved.presets,
+            );
+        }
+    }
+
+    // Undo the pending purchase
+    let mut undo = keyboard.just_pressed(KeyCode::Backspace);
+    for gamepad in gamepads.iter() {
+        if gamepad.just_pressed(GamepadButton::West) {
+            undo = true;
+        }
+    }
+    if undo {
+        tree_state.cancel_pending(&mut currency);
+    }
+
+    // Confirm buys the focused node
+    let mut confirm =
+        keyboard.just_pressed(KeyCode::Enter) || keyboard.just_pressed(KeyCode::Space);
+    for gamepad in gamepads.iter() {
+        if gamepad.just_pressed(GamepadButton::South) {
+            confirm = true;
+        }
+    }
+
     // Handle back to menu via keyboard/gamepad
     let mut back = keyboard.just_pressed(KeyCode::Escape);
     for gamepad in gamepads.iter() {
@@ -415,14 +548,18 @@ pub fn update_growth_tree(
     // 2. Handle Tree Nodes
     for (interaction, data, mut bg, mut border) in &mut node_query {
         let is_unlocked = tree_state.unlocked_nodes.contains(&data.id);
+        let is_pending = tree_state.is_pending(data.id);
         let is_parent_unlocked = data
             .parent_id
             .is_none_or(|pid| tree_state.unlocked_nodes.contains(&pid));
         let can_afford = currency.zenny >= data.cost;
-        let is_purchasable = !is_unlocked && is_parent_unlocked;
+        let is_purchasable = !is_unlocked && !is_pending && is_parent_unlocked;
 
         // Visuals
-        if is_unlocked {
+        if is_pending {
+            bg.0 = Color::srgb(0.8, 0.7, 0.2); // Yellow (Pending, can undo)
+            *border = BorderColor::all(Color::srgb(1.0, 0.9, 0.4));
+        } else if is_unlocked {
             bg.0 = Color::srgb(0.3, 0.8, 0.4); // Green (Unlocked)
             *border = BorderColor::all(Color::srgb(0.6, 1.0, 0.7));
         } else if is_purchasable {
@@ -438,21 +575,24 @@ pub fn update_growth_tree(
             *border = BorderColor::all(Color::srgb(0.3, 0.3, 0.3));
         }
 
-        // Interaction (Hover/Focus updates Info Panel)
-        if *interaction == Interaction::Hovered {
+        // Focus updates Info Panel
+        let is_focused = data.id == cursor.focused;
+        if is_focused {
             // Highlight
             *border = BorderColor::all(Color::WHITE);
 
             // Update Info - using iter_mut().next() safely
-            if let Some(mut text) = title_query.iter_mut().next() {
+            if let Some(mut text) = info_texts.p0().iter_mut().next() {
                 text.0 = data.label.to_string();
             }
-            if let Some(mut text) = desc_query.iter_mut().next() {
+            if let Some(mut text) = info_texts.p1().iter_mut().next() {
                 text.0 = data.description.to_string();
             }
 
-            if let Some(mut text) = cost_query.iter_mut().next() {
-                if is_unlocked {
+            if let Some(mut text) = info_texts.p2().iter_mut().next() {
+                if let Some(pending) = tree_state.pending.as_ref().filter(|_| is_pending) {
+                    text.0 = format!("UNDO? {:.1}s [Bksp]", pending.timer.remaining_secs());
+                } else if is_unlocked {
                     text.0 = "LEARNED!".to_string();
                 } else if !is_parent_unlocked {
                     text.0 = "LOCKED".to_string();
@@ -462,26 +602,164 @@ pub fn update_growth_tree(
             }
         }
 
-        // Interaction (Pressed buys)
-        if *interaction == Interaction::Pressed && is_purchasable && can_afford {
-            // Deduct cost
-            currency.zenny -= data.cost;
-            // Unlock node
-            tree_state.unlocked_nodes.insert(data.id);
-            // Apply stats
-            match data.upgrade_type {
-                UpgradeType::Damage => upgrades.damage_level += 1,
-                UpgradeType::Health => upgrades.health_level += 1,
-                UpgradeType::FireRate => upgrades.fire_rate_level += 1,
-                UpgradeType::CritChance => upgrades.crit_chance_level += 1,
-                UpgradeType::Core => {}
+        // Click or confirm on the focused node buys
+        let buy = *interaction == Interaction::Pressed || (confirm && is_focused);
+        if buy && is_purchasable && can_afford {
+            // Only one purchase can be undone at a time
+            if tree_state.commit_pending(&mut upgrades) {
+                autosave.save(
+                    &campaign,
+                    &currency,
+                    &upgrades,
+                    &tree_state,
+                    &saved.loadout,
+                    &saved.player_weapons,
+                    &saved.owned_chips,
+                    &saved.presets,
+                );
             }
+            tree_state.begin_purchase(data, &mut currency);
+        }
+    }
+}
+
+/// Move the growth cursor with arrows/WASD/D-pad; hovering a node with the mouse focuses it
+pub fn navigate_growth_tree(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<&Gamepad>,
+    mut cursor: ResMut<GrowthCursor>,
+    node_query: Query<(&Interaction, &GrowthNodeData), Changed<Interaction>>,
+) {
+    let mut up = keyboard.just_pressed(KeyCode::ArrowUp) || keyboard.just_pressed(KeyCode::KeyW);
+    let mut down =
+        keyboard.just_pressed(KeyCode::ArrowDown) || keyboard.just_pressed(KeyCode::KeyS);
+    let mut left =
+        keyboard.just_pressed(KeyCode::ArrowLeft) || keyboard.just_pressed(KeyCode::KeyA);
+    let mut right =
+        keyboard.just_pressed(KeyCode::ArrowRight) || keyboard.just_pressed(KeyCode::KeyD);
+    for gamepad in gamepads.iter() {
+        up |= gamepad.just_pressed(GamepadButton::DPadUp);
+        down |= gamepad.just_pressed(GamepadButton::DPadDown);
+        left |= gamepad.just_pressed(GamepadButton::DPadLeft);
+        right |= gamepad.just_pressed(GamepadButton::DPadRight);
+    }
+
+    let dir = if up {
+        Vec2::NEG_Y
+    } else if down {
+        Vec2::Y
+    } else if left {
+        Vec2::NEG_X
+    } else if right {
+        Vec2::X
+    } else {
+        Vec2::ZERO
+    };
+    if dir != Vec2::ZERO {
+        if let Some(id) = neighbor_in_direction(cursor.focused, dir) {
+            cursor.focused = id;
+        }
+    }
+
+    for (interaction, data) in &node_query {
+        if *interaction == Interaction::Hovered {
+            cursor.focused = data.id;
         }
     }
 }
 
+/// Leaving the screen commits any purchase still in its undo window
+pub fn commit_pending_purchase(
+    mut tree_state: ResMut<GrowthTreeState>,
+    mut upgrades: ResMut<PlayerUpgrades>,
+    currency: Res<PlayerCurrency>,
+    campaign: Res<CampaignProgress>,
+    loadout: Res<PlayerLoadout>,
+    player_weapons: Res<PlayerWeapons>,
+    owned_chips: Res<OwnedChips>,
+    presets: Res<LoadoutPresets>,
+    mut autosave: AutoSave,
+) {
+    if tree_state.commit_pending(&mut upgrades) {
+        autosave.save(
+            &campaign,
+            &currency,
+            &upgrades,
+            &tree_state,
+            &loadout,
+            &player_weapons,
+            &owned_chips,
+            &presets,
+        );
+    }
+}
+
 pub fn cleanup_growth(mut commands: Commands, query: Query<Entity, With<GrowthMenu>>) {
     for entity in &query {
         commands.entity(entity).despawn();
     }
+    commands.remove_resource::<GrowthCursor>();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(id: u32) -> &'static GrowthNodeData {
+        GROWTH_NODES.iter().find(|n| n.id == id).unwrap()
+    }
+
+    #[test]
+    fn cancel_within_window_refunds_in_full() {
+        let mut state = GrowthTreeState::default();
+        let mut currency = PlayerCurrency { zenny: 500 };
+
+        state.begin_purchase(node(1), &mut currency);
+        assert_eq!(currency.zenny, 500 - node(1).cost);
+        assert!(state.is_pending(1));
+
+        state.cancel_pending(&mut currency);
+        assert_eq!(currency.zenny, 500);
+        assert!(state.pending.is_none());
+        assert!(!state.unlocked_nodes.contains(&1));
+    }
+
+    #[test]
+    fn commit_unlocks_node_and_applies_upgrade() {
+        let mut state = GrowthTreeState::default();
+        let mut currency = PlayerCurrency { zenny: 500 };
+        let mut upgrades = PlayerUpgrades::default();
+
+        state.begin_purchase(node(1), &mut currency);
+        assert!(state.commit_pending(&mut upgrades));
+
+        assert!(state.unlocked_nodes.contains(&1));
+        assert_eq!(upgrades.damage_level, 1);
+        assert_eq!(currency.zenny, 500 - node(1).cost);
+        assert!(!state.commit_pending(&mut upgrades));
+    }
+
+    #[test]
+    fn pressing_up_from_the_core_focuses_the_node_above() {
+        let mut world = World::new();
+        world.init_resource::<ButtonInput<KeyCode>>();
+        world.insert_resource(GrowthCursor { focused: 0 });
+        world
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(KeyCode::ArrowUp);
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(navigate_growth_tree);
+        schedule.run(&mut world);
+
+        assert_eq!(world.resource::<GrowthCursor>().focused, 1);
+    }
+
+    #[test]
+    fn navigation_prefers_straight_lines_over_diagonals() {
+        assert_eq!(neighbor_in_direction(1, Vec2::NEG_Y), Some(5));
+        assert_eq!(neighbor_in_direction(1, Vec2::NEG_X), Some(3));
+        assert_eq!(neighbor_in_direction(0, Vec2::X), Some(4));
+        assert_eq!(neighbor_in_direction(8, Vec2::X), None);
+    }
 }
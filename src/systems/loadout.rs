@@ -10,9 +10,16 @@
 
 use bevy::prelude::*;
 
-use crate::actions::{ActionBlueprint, ActionId, Element, Rarity};
+use crate::actions::{ActionBlueprint, ActionEffect, ActionId, Element, Rarity};
 use crate::components::{CleanupOnStateExit, GameState};
-use crate::resources::PlayerLoadout;
+use crate::enemies::EnemyBlueprint;
+use crate::resources::{
+    BattleDef, CampaignProgress, ColorScheme, LOADOUT_PRESET_COUNT, LoadoutPresets, OwnedChips,
+    PlayerCurrency, PlayerLoadout, PlayerUpgrades, SelectedBattle, get_all_arcs,
+};
+use crate::systems::autosave::AutoSave;
+use crate::systems::growth::GrowthTreeState;
+use crate::weapons::PlayerWeapons;
 
 // ============================================================================
 // Constants - Beautiful MMBN-inspired color palette
@@ -130,19 +137,24 @@ impl LoadoutState {
 // Helper Functions
 // ============================================================================
 
-/// Get color for element
-fn element_color(element: Element) -> Color {
-    match element {
-        Element::None => Color::srgb(0.7, 0.7, 0.7),
-        Element::Fire => Color::srgb(1.0, 0.4, 0.2),
-        Element::Aqua => Color::srgb(0.3, 0.6, 1.0),
-        Element::Elec => Color::srgb(1.0, 0.9, 0.2),
-        Element::Wood => Color::srgb(0.3, 0.8, 0.3),
+/// Get color for element. The colorblind palette keeps the four elements
+/// apart by lightness as well as hue.
+pub fn element_color(element: Element, scheme: ColorScheme) -> Color {
+    match (scheme, element) {
+        (_, Element::None) => Color::srgb(0.7, 0.7, 0.7),
+        (ColorScheme::Default, Element::Fire) => Color::srgb(1.0, 0.4, 0.2),
+        (ColorScheme::Default, Element::Aqua) => Color::srgb(0.3, 0.6, 1.0),
+        (ColorScheme::Default, Element::Elec) => Color::srgb(1.0, 0.9, 0.2),
+        (ColorScheme::Default, Element::Wood) => Color::srgb(0.3, 0.8, 0.3),
+        (ColorScheme::Colorblind, Element::Fire) => Color::srgb(0.84, 0.37, 0.0),
+        (ColorScheme::Colorblind, Element::Aqua) => Color::srgb(0.0, 0.45, 0.7),
+        (ColorScheme::Colorblind, Element::Elec) => Color::srgb(0.94, 0.89, 0.26),
+        (ColorScheme::Colorblind, Element::Wood) => Color::srgb(0.8, 0.6, 0.7),
     }
 }
 
 /// Get rarity stars string
-fn rarity_stars(rarity: Rarity) -> &'static str {
+pub fn rarity_stars(rarity: Rarity) -> &'static str {
     match rarity {
         Rarity::Common => "*",
         Rarity::Uncommon => "**",
@@ -152,19 +164,29 @@ fn rarity_stars(rarity: Rarity) -> &'static str {
     }
 }
 
-/// Get color for rarity
-fn rarity_color(rarity: Rarity) -> Color {
-    match rarity {
-        Rarity::Common => Color::srgb(0.7, 0.7, 0.7),
-        Rarity::Uncommon => Color::srgb(0.4, 0.8, 0.4),
-        Rarity::Rare => Color::srgb(0.4, 0.6, 1.0),
-        Rarity::SuperRare => Color::srgb(0.8, 0.4, 1.0),
-        Rarity::UltraRare => Color::srgb(1.0, 0.8, 0.2),
+/// Get color for rarity. The colorblind palette climbs in lightness with
+/// rarity so the star count is not the only cue.
+pub fn rarity_color(rarity: Rarity, scheme: ColorScheme) -> Color {
+    match scheme {
+        ColorScheme::Default => match rarity {
+            Rarity::Common => Color::srgb(0.7, 0.7, 0.7),
+            Rarity::Uncommon => Color::srgb(0.4, 0.8, 0.4),
+            Rarity::Rare => Color::srgb(0.4, 0.6, 1.0),
+            Rarity::SuperRare => Color::srgb(0.8, 0.4, 1.0),
+            Rarity::UltraRare => Color::srgb(1.0, 0.8, 0.2),
+        },
+        ColorScheme::Colorblind => match rarity {
+            Rarity::Common => Color::srgb(0.55, 0.55, 0.55),
+            Rarity::Uncommon => Color::srgb(0.0, 0.45, 0.7),
+            Rarity::Rare => Color::srgb(0.34, 0.71, 0.91),
+            Rarity::SuperRare => Color::srgb(0.9, 0.6, 0.0),
+            Rarity::UltraRare => Color::srgb(0.94, 0.89, 0.26),
+        },
     }
 }
 
-/// Get all available actions for inventory
-fn get_all_actions() -> Vec<ActionId> {
+/// Every chip in the game, in inventory order
+pub fn get_all_actions() -> Vec<ActionId> {
     vec![
         // Recovery
         ActionId::Recov10,
@@ -183,6 +205,7 @@ fn get_all_actions() -> Vec<ActionId> {
         ActionId::Invis2,
         ActionId::Invis3,
         ActionId::LifeAura,
+        ActionId::OverHP,
         // Swords
         ActionId::Sword,
         ActionId::WideSwrd,
@@ -229,6 +252,7 @@ fn get_all_actions() -> Vec<ActionId> {
         ActionId::Dash,
         ActionId::GutsPnch,
         ActionId::IcePunch,
+        ActionId::Mine,
         // Panel
         ActionId::Steal,
         ActionId::Geddon1,
@@ -237,11 +261,146 @@ fn get_all_actions() -> Vec<ActionId> {
     ]
 }
 
+/// Chips the inventory lists: the owned ones, in catalog order
+pub fn owned_actions(owned: &OwnedChips) -> Vec<ActionId> {
+    owned.filter(&get_all_actions())
+}
+
+// ============================================================================
+// Loadout Recommendation
+// ============================================================================
+
+/// Rough role a chip plays in a loadout
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ChipRole {
+    Attack,
+    Recovery,
+    Defense,
+    Utility,
+}
+
+fn chip_role(effect: &ActionEffect) -> ChipRole {
+    match effect {
+        ActionEffect::Heal { .. } => ChipRole::Recovery,
+        ActionEffect::Shield { .. }
+        | ActionEffect::Invisibility { .. }
+        | ActionEffect::Guard { .. }
+        | ActionEffect::OverHealth { .. } => ChipRole::Defense,
+        _ if effect_damage(effect) > 0 => ChipRole::Attack,
+        _ => ChipRole::Utility,
+    }
+}
+
+/// Total damage an effect deals (0 for non-damaging effects)
+fn effect_damage(effect: &ActionEffect) -> i32 {
+    match effect {
+        ActionEffect::Damage { amount, .. } | ActionEffect::Drain { amount } => *amount,
+        ActionEffect::MultiHit {
+            damage_per_hit,
+            hit_count,
+            ..
+        } => damage_per_hit * hit_count,
+        ActionEffect::Delayed { effect, .. } => effect_damage(effect),
+        ActionEffect::Combo { effects } => effects.iter().map(effect_damage).sum(),
+        _ => 0,
+    }
+}
+
+/// Highest-rated owned chip for a role
+fn best_for_role(
+    chips: &[(ActionId, ActionBlueprint)],
+    role: ChipRole,
+    rating: impl Fn(&ActionBlueprint) -> f32,
+) -> Option<ActionId> {
+    chips
+        .iter()
+        .filter(|(_, bp)| chip_role(&bp.effect) == role)
+        .max_by(|(_, a), (_, b)| rating(a).total_cmp(&rating(b)))
+        .map(|(id, _)| *id)
+}
+
+/// Recommend a loadout for a battle using only chips from `inventory`.
+///
+/// Slots 1-2 get attacks (enemy weakness element first, then damage per second
+/// of cooldown), slot 3 recovery, slot 4 defense. Boss battles favour the
+/// biggest heal and longest defense; regular battles favour the fastest ones.
+/// Roles with no owned chip are backfilled with further attacks.
+pub fn recommend_loadout(battle: &BattleDef, inventory: &[ActionId]) -> [Option<ActionId>; 4] {
+    let weaknesses: Vec<Element> = battle
+        .all_enemies()
+        .filter_map(|enemy| EnemyBlueprint::get(enemy.enemy_id).element.weak_to())
+        .collect();
+
+    let chips: Vec<(ActionId, ActionBlueprint)> = inventory
+        .iter()
+        .map(|id| (*id, ActionBlueprint::get(*id)))
+        .collect();
+
+    let attack_rating = |bp: &ActionBlueprint| effect_damage(&bp.effect) as f32 / bp.cooldown;
+    let mut attacks: Vec<&(ActionId, ActionBlueprint)> = chips
+        .iter()
+        .filter(|(_, bp)| chip_role(&bp.effect) == ChipRole::Attack)
+        .collect();
+    attacks.sort_by(|(_, a), (_, b)| {
+        let a_weak = weaknesses.contains(&a.element);
+        let b_weak = weaknesses.contains(&b.element);
+        b_weak
+            .cmp(&a_weak)
+            .then_with(|| attack_rating(b).total_cmp(&attack_rating(a)))
+    });
+
+    let recovery = best_for_role(&chips, ChipRole::Recovery, |bp| {
+        let amount = match bp.effect {
+            ActionEffect::Heal { amount } => amount as f32,
+            _ => 0.0,
+        };
+        if battle.is_boss {
+            amount
+        } else {
+            amount / bp.cooldown
+        }
+    });
+    let defense = best_for_role(&chips, ChipRole::Defense, |bp| {
+        if battle.is_boss {
+            match bp.effect {
+                ActionEffect::Shield { duration, .. }
+                | ActionEffect::Invisibility { duration }
+                | ActionEffect::Guard { duration }
+                | ActionEffect::OverHealth { duration, .. } => duration,
+                _ => 0.0,
+            }
+        } else {
+            -bp.cooldown
+        }
+    });
+
+    let mut picks: Vec<ActionId> = attacks.iter().take(2).map(|(id, _)| *id).collect();
+    picks.extend(recovery);
+    picks.extend(defense);
+    for (id, _) in attacks.iter().skip(2) {
+        if picks.len() >= 4 {
+            break;
+        }
+        picks.push(*id);
+    }
+
+    let mut slots = [None; 4];
+    for (slot, id) in slots.iter_mut().zip(picks) {
+        *slot = Some(id);
+    }
+    slots
+}
+
 // ============================================================================
 // Setup System
 // ============================================================================
 
-pub fn setup_loadout(mut commands: Commands, loadout: Res<PlayerLoadout>) {
+pub fn setup_loadout(
+    mut commands: Commands,
+    loadout: Res<PlayerLoadout>,
+    owned: Res<OwnedChips>,
+    color_scheme: Res<ColorScheme>,
+) {
     // Initialize state
     commands.insert_resource(LoadoutState::default());
 
@@ -396,7 +555,9 @@ pub fn setup_loadout(mut commands: Commands, loadout: Res<PlayerLoadout>) {
 
             // Instructions at bottom
             parent.spawn((
-                Text::new("[Arrow Keys/D-Pad] Navigate  |  [Enter/A] Select  |  [Esc/B] Back"),
+                Text::new(
+                    "[Arrow Keys/D-Pad] Navigate  |  [Enter/A] Select  |  [R/Y] Recommend  |  [F1-F3] Load preset  |  [Shift+F1-F3] Save preset  |  [Esc/B] Back",
+                ),
                 TextFont::from_font_size(16.0),
                 TextColor(TEXT_MUTED),
                 Node {
@@ -407,7 +568,7 @@ pub fn setup_loadout(mut commands: Commands, loadout: Res<PlayerLoadout>) {
         });
 
     // Spawn inventory panel (initially hidden)
-    spawn_inventory_panel(&mut commands, &loadout);
+    spawn_inventory_panel(&mut commands, &loadout, &owned, *color_scheme);
 }
 
 /// Spawn a single action slot
@@ -473,8 +634,13 @@ fn spawn_slot(parent: &mut ChildSpawnerCommands, index: usize, action: Option<Ac
 }
 
 /// Spawn the inventory panel (hidden initially)
-fn spawn_inventory_panel(commands: &mut Commands, loadout: &PlayerLoadout) {
-    let all_actions = get_all_actions();
+fn spawn_inventory_panel(
+    commands: &mut Commands,
+    loadout: &PlayerLoadout,
+    owned: &OwnedChips,
+    color_scheme: ColorScheme,
+) {
+    let all_actions = owned_actions(owned);
 
     // Create a full-screen overlay container for proper centering
     commands
@@ -564,7 +730,13 @@ fn spawn_inventory_panel(commands: &mut Commands, loadout: &PlayerLoadout) {
                                 // Add all actions (index 1+)
                                 for (i, action_id) in all_actions.iter().enumerate() {
                                     let is_equipped = loadout.is_equipped(*action_id);
-                                    spawn_inventory_item(list, *action_id, is_equipped, i + 1);
+                                    spawn_inventory_item(
+                                        list,
+                                        *action_id,
+                                        is_equipped,
+                                        i + 1,
+                                        color_scheme,
+                                    );
                                 }
                             });
                         });
@@ -680,6 +852,7 @@ fn spawn_inventory_item(
     action_id: ActionId,
     is_equipped: bool,
     index: usize,
+    color_scheme: ColorScheme,
 ) {
     let blueprint = ActionBlueprint::get(action_id);
 
@@ -735,7 +908,7 @@ fn spawn_inventory_item(
                 parent.spawn((
                     Text::new(format!("{:?}", blueprint.element)),
                     TextFont::from_font_size(12.0),
-                    TextColor(element_color(blueprint.element)),
+                    TextColor(element_color(blueprint.element, color_scheme)),
                 ));
             }
 
@@ -760,6 +933,7 @@ pub fn update_loadout_input(
     gamepads: Query<&Gamepad>,
     time: Res<Time>,
     mut state: ResMut<LoadoutState>,
+    owned: Res<OwnedChips>,
     mut next_state: ResMut<NextState<GameState>>,
     mut inventory_visibility: Query<&mut Visibility, With<InventoryPanel>>,
 ) {
@@ -793,7 +967,7 @@ pub fn update_loadout_input(
         || gp_confirm;
     let back = keyboard.just_pressed(KeyCode::Escape) || gp_back;
 
-    let all_actions = get_all_actions();
+    let all_actions = owned_actions(&owned);
     let total_inventory_items = all_actions.len() + 1; // +1 for "Clear Slot"
 
     // Input cooldown
@@ -879,6 +1053,7 @@ pub fn handle_inventory_selection(
     keyboard: Res<ButtonInput<KeyCode>>,
     gamepads: Query<&Gamepad>,
     mut state: ResMut<LoadoutState>,
+    owned: Res<OwnedChips>,
     mut loadout: ResMut<PlayerLoadout>,
     mut inventory_visibility: Query<&mut Visibility, With<InventoryPanel>>,
 ) {
@@ -903,7 +1078,7 @@ pub fn handle_inventory_selection(
 
     if confirm {
         if let Some(slot) = state.editing_slot {
-            let all_actions = get_all_actions();
+            let all_actions = owned_actions(&owned);
 
             if state.inventory_cursor == 0 {
                 // "Clear Slot" selected
@@ -913,8 +1088,8 @@ pub fn handle_inventory_selection(
                 let action_index = state.inventory_cursor - 1;
                 if action_index < all_actions.len() {
                     let action_id = all_actions[action_index];
-                    // Only equip if not already equipped elsewhere
-                    if !loadout.is_equipped(action_id) {
+                    // A second copy only fits next to the first
+                    if loadout.can_equip(slot, action_id) {
                         loadout.slots[slot] = Some(action_id);
                     }
                 }
@@ -930,6 +1105,91 @@ pub fn handle_inventory_selection(
     }
 }
 
+/// Auto-fill slots with a recommended build for the selected campaign battle
+pub fn handle_recommend_loadout(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<&Gamepad>,
+    state: Res<LoadoutState>,
+    owned: Res<OwnedChips>,
+    selected: Res<SelectedBattle>,
+    mut loadout: ResMut<PlayerLoadout>,
+) {
+    if state.inventory_open {
+        return;
+    }
+
+    let mut recommend = keyboard.just_pressed(KeyCode::KeyR);
+    for gamepad in gamepads.iter() {
+        if gamepad.just_pressed(GamepadButton::North) {
+            recommend = true;
+        }
+    }
+    if !recommend {
+        return;
+    }
+
+    let arcs = get_all_arcs();
+    let Some(battle) = arcs
+        .get(selected.arc)
+        .and_then(|arc| arc.battles.get(selected.battle))
+    else {
+        return;
+    };
+
+    loadout.slots = recommend_loadout(battle, &owned_actions(&owned));
+}
+
+/// Keys for preset 1-3: press to load, hold Shift to save the current slots
+const PRESET_KEYS: [KeyCode; LOADOUT_PRESET_COUNT] = [KeyCode::F1, KeyCode::F2, KeyCode::F3];
+
+/// Save the current slots into a preset, or load one back (skipping unowned chips)
+pub fn handle_loadout_presets(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    state: Res<LoadoutState>,
+    owned: Res<OwnedChips>,
+    mut loadout: ResMut<PlayerLoadout>,
+    mut presets: ResMut<LoadoutPresets>,
+    campaign: Res<CampaignProgress>,
+    currency: Res<PlayerCurrency>,
+    upgrades: Res<PlayerUpgrades>,
+    growth: Res<GrowthTreeState>,
+    player_weapons: Res<PlayerWeapons>,
+    mut autosave: AutoSave,
+) {
+    if state.inventory_open {
+        return;
+    }
+
+    let Some(index) = PRESET_KEYS
+        .iter()
+        .position(|key| keyboard.just_pressed(*key))
+    else {
+        return;
+    };
+    let saving = keyboard.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+
+    if saving {
+        presets.save(index, &loadout);
+        info!("Saved loadout preset {}", index + 1);
+    } else if let Some(slots) = presets.load(index, &owned) {
+        loadout.slots = slots;
+        info!("Loaded loadout preset {}", index + 1);
+    } else {
+        return;
+    }
+
+    autosave.save(
+        &campaign,
+        &currency,
+        &upgrades,
+        &growth,
+        &loadout,
+        &player_weapons,
+        &owned,
+        &presets,
+    );
+}
+
 /// Update slot visuals based on selection
 pub fn update_slot_visuals(
     state: Res<LoadoutState>,
@@ -979,7 +1239,9 @@ pub fn update_slot_visuals(
 /// Update details panel based on selection
 pub fn update_details_panel(
     state: Res<LoadoutState>,
+    owned: Res<OwnedChips>,
     loadout: Res<PlayerLoadout>,
+    color_scheme: Res<ColorScheme>,
     mut name_query: Query<(&mut Text, &mut TextColor), With<DetailsName>>,
     mut desc_query: Query<&mut Text, (With<DetailsDescription>, Without<DetailsName>)>,
     mut stats_query: Query<
@@ -1005,7 +1267,7 @@ pub fn update_details_panel(
         if state.inventory_cursor == 0 {
             None // Clear slot
         } else {
-            let all_actions = get_all_actions();
+            let all_actions = owned_actions(&owned);
             all_actions.get(state.inventory_cursor - 1).copied()
         }
     } else {
@@ -1019,14 +1281,14 @@ pub fn update_details_panel(
         // Name
         if let Ok((mut text, mut color)) = name_query.single_mut() {
             text.0 = format!("{} {}", blueprint.name, rarity_stars(blueprint.rarity));
-            color.0 = rarity_color(blueprint.rarity);
+            color.0 = rarity_color(blueprint.rarity, *color_scheme);
         }
 
         // Element
         if let Ok((mut text, mut color)) = elem_query.single_mut() {
             if blueprint.element != Element::None {
                 text.0 = format!("Element: {:?}", blueprint.element);
-                color.0 = element_color(blueprint.element);
+                color.0 = element_color(blueprint.element, *color_scheme);
             } else {
                 text.0 = "Element: None".to_string();
                 color.0 = TEXT_MUTED;
@@ -1147,7 +1409,9 @@ pub fn update_inventory_visuals(
 /// Update the details panel inside the inventory overlay
 pub fn update_inventory_details(
     state: Res<LoadoutState>,
+    owned: Res<OwnedChips>,
     loadout: Res<PlayerLoadout>,
+    color_scheme: Res<ColorScheme>,
     mut name_query: Query<(&mut Text, &mut TextColor), With<InventoryDetailsName>>,
     mut desc_query: Query<&mut Text, (With<InventoryDetailsDesc>, Without<InventoryDetailsName>)>,
     mut stats_query: Query<
@@ -1166,7 +1430,7 @@ pub fn update_inventory_details(
     let action_opt = if state.inventory_cursor == 0 {
         None // Clear slot
     } else {
-        let all_actions = get_all_actions();
+        let all_actions = owned_actions(&owned);
         all_actions.get(state.inventory_cursor - 1).copied()
     };
 
@@ -1186,7 +1450,7 @@ pub fn update_inventory_details(
             color.0 = if is_equipped {
                 TEXT_MUTED
             } else {
-                rarity_color(blueprint.rarity)
+                rarity_color(blueprint.rarity, *color_scheme)
             };
         }
 
@@ -1231,3 +1495,67 @@ pub fn update_inventory_details(
 pub fn cleanup_loadout(mut commands: Commands) {
     commands.remove_resource::<LoadoutState>();
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::EnemyConfig;
+    use crate::enemies::EnemyId;
+
+    fn battle(enemy_id: EnemyId) -> BattleDef {
+        BattleDef {
+            enemies: vec![EnemyConfig::new(enemy_id, 4, 1)],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn recommendation_prefers_the_enemy_weakness() {
+        // Slimes are Fire, so the weaker Aqua Bubbler beats a stronger neutral cannon
+        let inventory = [ActionId::MCannon, ActionId::Bubbler, ActionId::Recov50];
+        let slots = recommend_loadout(&battle(EnemyId::Slime), &inventory);
+        assert_eq!(slots[0], Some(ActionId::Bubbler));
+        assert_eq!(slots[1], Some(ActionId::MCannon));
+
+        let slots = recommend_loadout(&battle(EnemyId::Slime3), &inventory);
+        assert_eq!(slots[0], Some(ActionId::MCannon));
+    }
+
+    #[test]
+    fn recommendation_only_uses_owned_chips() {
+        let owned = OwnedChips::default();
+        let slots = recommend_loadout(&battle(EnemyId::Slime), &owned_actions(&owned));
+
+        assert!(slots.iter().any(Option::is_some));
+        for id in slots.into_iter().flatten() {
+            assert!(owned.contains(id), "{id:?} is not owned");
+        }
+    }
+
+    #[test]
+    fn inventory_lists_only_owned_chips() {
+        let mut owned = OwnedChips::default();
+        assert!(!owned.contains(ActionId::MCannon));
+        assert!(!owned_actions(&owned).contains(&ActionId::MCannon));
+
+        owned.add(ActionId::MCannon);
+        let inventory = owned_actions(&owned);
+        assert!(inventory.contains(&ActionId::MCannon));
+        assert_eq!(inventory.len(), owned.0.len());
+    }
+
+    #[test]
+    fn colorblind_element_colors_are_distinct_from_the_default_palette() {
+        let elements = [Element::Fire, Element::Aqua, Element::Elec, Element::Wood];
+        let palette = |scheme| elements.map(|element| element_color(element, scheme));
+        let default = palette(ColorScheme::Default);
+        let colorblind = palette(ColorScheme::Colorblind);
+
+        for colors in [default, colorblind] {
+            for (i, a) in colors.iter().enumerate() {
+                assert!(colors[i + 1..].iter().all(|b| a != b));
+            }
+        }
+        assert!(default.iter().all(|color| !colorblind.contains(color)));
+    }
+}
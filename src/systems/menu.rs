@@ -1,6 +1,10 @@
 use bevy::prelude::*;
 
-use crate::components::{CleanupOnStateExit, GameState};
+use crate::bindings::KeyBindings;
+use crate::components::{ArenaConfig, CleanupOnStateExit, GameState};
+use crate::resources::{AudioSettings, CampaignProgress, PlayerLoadout, SaveSlot, SelectedBattle};
+use crate::save::{load_slot, save_settings};
+use crate::systems::settings::GameOptions;
 
 /// Marker for the main menu container
 #[derive(Component)]
@@ -10,16 +14,39 @@ pub struct MainMenu;
 #[derive(Component)]
 pub struct MenuButtonAction(pub MenuAction);
 
+/// Marker for the save slot button label
+#[derive(Component)]
+pub struct SaveSlotText;
+
 /// Available menu actions
 #[derive(Clone, Debug, Copy)]
 pub enum MenuAction {
+    Continue,
     Campaign,
+    Practice,
     Loadout,
     Shop,
+    Settings,
+    Encyclopedia,
+    SaveSlot,
+}
+
+/// Greyed-out Continue when there is no battle to return to
+const CONTINUE_DISABLED_BG: Color = Color::srgb(0.2, 0.2, 0.25);
+
+/// Campaign battle Continue returns to (None without a saved campaign battle)
+pub fn continue_battle(campaign: &CampaignProgress) -> Option<SelectedBattle> {
+    campaign
+        .last_battle
+        .filter(|battle| campaign.is_arc_unlocked(battle.arc))
+}
+
+fn save_slot_label(slot: SaveSlot) -> String {
+    format!("Save Slot: {}", slot.number())
 }
 
 /// Setup the main menu using Bevy UI
-pub fn setup_menu(mut commands: Commands) {
+pub fn setup_menu(mut commands: Commands, save_slot: Res<SaveSlot>) {
     // Root Node (Full Screen)
     commands
         .spawn((
@@ -58,6 +85,31 @@ pub fn setup_menu(mut commands: Commands) {
                 },
             ));
 
+            // Continue Button (back to the last campaign battle's arc)
+            parent
+                .spawn((
+                    Button,
+                    Node {
+                        width: Val::Px(300.0),
+                        height: Val::Px(65.0),
+                        justify_content: JustifyContent::Center,
+                        align_items: AlignItems::Center,
+                        border: UiRect::all(Val::Px(2.0)),
+                        margin: UiRect::bottom(Val::Px(15.0)),
+                        ..default()
+                    },
+                    BorderColor::all(Color::WHITE),
+                    BackgroundColor(Color::srgb(0.3, 0.5, 0.8)),
+                    MenuButtonAction(MenuAction::Continue),
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        Text::new("Continue"),
+                        TextFont::from_font_size(30.0),
+                        TextColor(Color::WHITE),
+                    ));
+                });
+
             // Campaign Button
             parent
                 .spawn((
@@ -83,6 +135,31 @@ pub fn setup_menu(mut commands: Commands) {
                     ));
                 });
 
+            // Practice Button
+            parent
+                .spawn((
+                    Button,
+                    Node {
+                        width: Val::Px(300.0),
+                        height: Val::Px(65.0),
+                        justify_content: JustifyContent::Center,
+                        align_items: AlignItems::Center,
+                        border: UiRect::all(Val::Px(2.0)),
+                        margin: UiRect::bottom(Val::Px(15.0)),
+                        ..default()
+                    },
+                    BorderColor::all(Color::WHITE),
+                    BackgroundColor(Color::srgb(0.3, 0.6, 0.5)),
+                    MenuButtonAction(MenuAction::Practice),
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        Text::new("Practice"),
+                        TextFont::from_font_size(30.0),
+                        TextColor(Color::WHITE),
+                    ));
+                });
+
             // Loadout Button
             parent
                 .spawn((
@@ -132,6 +209,82 @@ pub fn setup_menu(mut commands: Commands) {
                     ));
                 });
 
+            // Settings Button
+            parent
+                .spawn((
+                    Button,
+                    Node {
+                        width: Val::Px(300.0),
+                        height: Val::Px(65.0),
+                        justify_content: JustifyContent::Center,
+                        align_items: AlignItems::Center,
+                        border: UiRect::all(Val::Px(2.0)),
+                        margin: UiRect::top(Val::Px(15.0)),
+                        ..default()
+                    },
+                    BorderColor::all(Color::WHITE),
+                    BackgroundColor(Color::srgb(0.4, 0.4, 0.5)),
+                    MenuButtonAction(MenuAction::Settings),
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        Text::new("Settings"),
+                        TextFont::from_font_size(30.0),
+                        TextColor(Color::WHITE),
+                    ));
+                });
+
+            // Encyclopedia Button
+            parent
+                .spawn((
+                    Button,
+                    Node {
+                        width: Val::Px(300.0),
+                        height: Val::Px(65.0),
+                        justify_content: JustifyContent::Center,
+                        align_items: AlignItems::Center,
+                        border: UiRect::all(Val::Px(2.0)),
+                        margin: UiRect::top(Val::Px(15.0)),
+                        ..default()
+                    },
+                    BorderColor::all(Color::WHITE),
+                    BackgroundColor(Color::srgb(0.4, 0.4, 0.5)),
+                    MenuButtonAction(MenuAction::Encyclopedia),
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        Text::new("Enemies"),
+                        TextFont::from_font_size(30.0),
+                        TextColor(Color::WHITE),
+                    ));
+                });
+
+            // Save Slot Button (cycles slots 1-3, loading the chosen slot's progress)
+            parent
+                .spawn((
+                    Button,
+                    Node {
+                        width: Val::Px(300.0),
+                        height: Val::Px(65.0),
+                        justify_content: JustifyContent::Center,
+                        align_items: AlignItems::Center,
+                        border: UiRect::all(Val::Px(2.0)),
+                        margin: UiRect::top(Val::Px(15.0)),
+                        ..default()
+                    },
+                    BorderColor::all(Color::WHITE),
+                    BackgroundColor(Color::srgb(0.4, 0.4, 0.5)),
+                    MenuButtonAction(MenuAction::SaveSlot),
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        Text::new(save_slot_label(*save_slot)),
+                        TextFont::from_font_size(30.0),
+                        TextColor(Color::WHITE),
+                        SaveSlotText,
+                    ));
+                });
+
             // Instructions
             parent.spawn((
                 Text::new("Navigation: D-Pad / Arrow Keys | Select: A / Enter"),
@@ -151,30 +304,80 @@ pub fn handle_menu_selection(
         (&Interaction, &MenuButtonAction),
         (Changed<Interaction>, With<Button>),
     >,
+    mut commands: Commands,
+    loadout: Res<PlayerLoadout>,
+    campaign: Res<CampaignProgress>,
+    mut save_slot: ResMut<SaveSlot>,
+    options: GameOptions,
+    audio: Res<AudioSettings>,
+    bindings: Res<KeyBindings>,
+    mut slot_texts: Query<&mut Text, With<SaveSlotText>>,
     mut next_state: ResMut<NextState<GameState>>,
 ) {
     for (interaction, action) in &interaction_query {
         if *interaction == Interaction::Pressed {
             match action.0 {
+                MenuAction::Continue => {
+                    // The campaign screen opens on the selected battle
+                    if let Some(battle) = continue_battle(&campaign) {
+                        commands.insert_resource(battle);
+                        next_state.set(GameState::Campaign);
+                    }
+                }
                 MenuAction::Campaign => {
                     next_state.set(GameState::Campaign);
                 }
+                MenuAction::Practice => {
+                    commands.insert_resource(ArenaConfig::practice(loadout.equipped_actions()));
+                    next_state.set(GameState::Playing);
+                }
                 MenuAction::Loadout => {
                     next_state.set(GameState::Loadout);
                 }
                 MenuAction::Shop => {
                     next_state.set(GameState::Shop);
                 }
+                MenuAction::Settings => {
+                    next_state.set(GameState::Settings);
+                }
+                MenuAction::Encyclopedia => {
+                    next_state.set(GameState::Encyclopedia);
+                }
+                MenuAction::SaveSlot => {
+                    *save_slot = save_slot.next();
+                    load_slot(*save_slot, &mut commands);
+                    for mut text in &mut slot_texts {
+                        text.0 = save_slot_label(*save_slot);
+                    }
+                    // Remember the slot for the next launch
+                    save_settings(&options.settings_data(&audio, &bindings, *save_slot));
+                }
             }
         }
     }
 }
 
-/// Update visual state of menu buttons (highlight hovered/pressed)
+/// Update visual state of menu buttons (highlight hovered/pressed); Continue
+/// stays greyed out while there is no battle to return to
 pub fn update_menu_visuals(
-    mut query: Query<(&Interaction, &mut BackgroundColor, &mut BorderColor), With<Button>>,
+    campaign: Res<CampaignProgress>,
+    mut query: Query<
+        (
+            &Interaction,
+            &mut BackgroundColor,
+            &mut BorderColor,
+            Option<&MenuButtonAction>,
+        ),
+        With<Button>,
+    >,
 ) {
-    for (interaction, mut bg, mut border) in &mut query {
+    let can_continue = continue_battle(&campaign).is_some();
+    for (interaction, mut bg, mut border, action) in &mut query {
+        if !can_continue && action.is_some_and(|action| matches!(action.0, MenuAction::Continue)) {
+            bg.0 = CONTINUE_DISABLED_BG;
+            *border = BorderColor::all(Color::NONE);
+            continue;
+        }
         match interaction {
             Interaction::Pressed => {
                 bg.0 = Color::srgb(0.2, 0.4, 0.7);
@@ -196,3 +399,79 @@ pub fn update_menu_visuals(
 pub fn cleanup_menu() {
     // No resources to remove in this version
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::actions::ChipMode;
+    use crate::resources::{AssistOptions, ColorScheme, Difficulty, ReduceFlashing};
+    use crate::systems::overtime::OvertimeRule;
+    use crate::weapons::ChargeMoveRule;
+
+    /// World with what `handle_menu_selection` reads and `action`'s button pressed
+    fn menu_world(campaign: CampaignProgress, action: MenuAction) -> World {
+        let mut world = World::new();
+        world.insert_resource(campaign);
+        world.init_resource::<PlayerLoadout>();
+        world.init_resource::<SelectedBattle>();
+        world.init_resource::<SaveSlot>();
+        world.init_resource::<AudioSettings>();
+        world.init_resource::<KeyBindings>();
+        world.init_resource::<ReduceFlashing>();
+        world.init_resource::<Difficulty>();
+        world.init_resource::<ColorScheme>();
+        world.init_resource::<AssistOptions>();
+        world.init_resource::<ChargeMoveRule>();
+        world.init_resource::<OvertimeRule>();
+        world.init_resource::<ChipMode>();
+        world.init_resource::<NextState<GameState>>();
+        world.spawn((Button, Interaction::Pressed, MenuButtonAction(action)));
+        world
+    }
+
+    fn press_continue(campaign: CampaignProgress) -> World {
+        let mut world = menu_world(campaign, MenuAction::Continue);
+        world.run_system_cached(handle_menu_selection).unwrap();
+        world
+    }
+
+    #[test]
+    fn continue_routes_to_the_saved_battles_arc() {
+        let last = SelectedBattle { arc: 1, battle: 3 };
+        let world = press_continue(CampaignProgress {
+            unlocked_arc: 1,
+            last_battle: Some(last),
+            ..Default::default()
+        });
+        assert_eq!(*world.resource::<SelectedBattle>(), last);
+        assert!(matches!(
+            world.resource::<NextState<GameState>>(),
+            NextState::Pending(GameState::Campaign)
+        ));
+
+        // Nothing saved yet: Continue is greyed out and does nothing
+        let world = press_continue(CampaignProgress::default());
+        assert_eq!(
+            *world.resource::<SelectedBattle>(),
+            SelectedBattle::default()
+        );
+        assert!(matches!(
+            world.resource::<NextState<GameState>>(),
+            NextState::Unchanged
+        ));
+    }
+
+    #[test]
+    fn save_slot_button_cycles_the_active_slot() {
+        let mut world = menu_world(CampaignProgress::default(), MenuAction::SaveSlot);
+        let label = world
+            .spawn((Text::new(save_slot_label(SaveSlot(0))), SaveSlotText))
+            .id();
+
+        world.run_system_cached(handle_menu_selection).unwrap();
+        assert_eq!(*world.resource::<SaveSlot>(), SaveSlot(1));
+        assert_eq!(world.get::<Text>(label).unwrap().0, "Save Slot: 2");
+        // Remembered for the next launch
+        assert_eq!(crate::save::load_settings().unwrap().save_slot, SaveSlot(1));
+    }
+}
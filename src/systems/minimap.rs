@@ -0,0 +1,153 @@
+//! Minimap: a compact overview of the grid in the top-right corner.
+//!
+//! `update_minimap` keeps one dot per enemy and one for the player, placed by
+//! `minimap_local` so the map fits whatever grid the battle is fought on.
+
+use bevy::prelude::*;
+
+use crate::components::{CleanupOnStateExit, Enemy, GameState, GridPosition, Player};
+use crate::constants::*;
+use crate::resources::{ArenaLayout, GridSize};
+
+/// Size of the minimap panel in pixels
+const MINIMAP_SIZE: Vec2 = Vec2::new(132.0, 66.0);
+/// Gap between the minimap panel and the top-right corner of the screen
+const MINIMAP_MARGIN: Vec2 = Vec2::splat(16.0);
+/// Dot diameter as a fraction of one minimap cell
+const MINIMAP_DOT_SCALE: f32 = 0.6;
+
+/// Minimap background panel; dots are spawned as its children
+#[derive(Component)]
+pub struct Minimap;
+
+/// Minimap dot tracking one enemy or the player
+#[derive(Component)]
+pub struct MinimapDot {
+    pub target: Entity,
+}
+
+/// Size of one grid tile on the minimap
+fn minimap_cell(grid: GridSize) -> Vec2 {
+    MINIMAP_SIZE / Vec2::new(grid.width as f32, grid.height as f32)
+}
+
+/// Center of the tile at `pos`, relative to the minimap center
+pub fn minimap_local(pos: &GridPosition, grid: GridSize) -> Vec2 {
+    let cell = minimap_cell(grid);
+    Vec2::new(pos.x as f32 + 0.5, pos.y as f32 + 0.5) * cell - MINIMAP_SIZE / 2.0
+}
+
+/// Center of the minimap panel, tucked into the top-right corner of the screen
+fn minimap_pos(layout: &ArenaLayout) -> Vec2 {
+    Vec2::new(layout.screen_width, layout.screen_height) / 2.0 - MINIMAP_SIZE / 2.0 - MINIMAP_MARGIN
+}
+
+/// Spawn the empty minimap panel
+pub fn setup_minimap(mut commands: Commands, layout: Res<ArenaLayout>) {
+    commands.spawn((
+        Sprite::from_color(COLOR_MINIMAP_BG, MINIMAP_SIZE),
+        Transform::from_translation(minimap_pos(&layout).extend(Z_UI)),
+        Minimap,
+        CleanupOnStateExit(GameState::Playing),
+    ));
+}
+
+/// Move each dot to its target, drop dots whose target is gone and add dots
+/// for new enemies
+pub fn update_minimap(
+    mut commands: Commands,
+    layout: Res<ArenaLayout>,
+    minimap_query: Query<Entity, With<Minimap>>,
+    mut dot_query: Query<(Entity, &MinimapDot, &mut Transform)>,
+    target_query: Query<(Entity, &GridPosition, Has<Player>), Or<(With<Enemy>, With<Player>)>>,
+) {
+    let Ok(minimap) = minimap_query.single() else {
+        return;
+    };
+
+    let mut tracked = Vec::new();
+    for (dot_entity, dot, mut transform) in &mut dot_query {
+        match target_query.get(dot.target) {
+            Ok((_, pos, _)) => {
+                transform.translation = minimap_local(pos, layout.grid).extend(0.1);
+                tracked.push(dot.target);
+            }
+            Err(_) => commands.entity(dot_entity).despawn(),
+        }
+    }
+
+    let dot_size = Vec2::splat(minimap_cell(layout.grid).min_element() * MINIMAP_DOT_SCALE);
+    for (target, pos, is_player) in &target_query {
+        if tracked.contains(&target) {
+            continue;
+        }
+        let color = if is_player {
+            COLOR_MINIMAP_PLAYER
+        } else {
+            COLOR_MINIMAP_ENEMY
+        };
+        commands.entity(minimap).with_children(|parent| {
+            parent.spawn((
+                Sprite::from_color(color, dot_size),
+                Transform::from_translation(minimap_local(pos, layout.grid).extend(0.1)),
+                MinimapDot { target },
+            ));
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn grid_corners_and_center_map_onto_the_minimap() {
+        let grid = GridSize::default();
+        let cell = minimap_cell(grid);
+        let half = MINIMAP_SIZE / 2.0;
+
+        let bottom_left = minimap_local(&GridPosition { x: 0, y: 0 }, grid);
+        assert_eq!(bottom_left, -half + cell / 2.0);
+
+        let top_right = minimap_local(
+            &GridPosition {
+                x: grid.width - 1,
+                y: grid.height - 1,
+            },
+            grid,
+        );
+        assert_eq!(top_right, half - cell / 2.0);
+
+        let top_left = minimap_local(
+            &GridPosition {
+                x: 0,
+                y: grid.height - 1,
+            },
+            grid,
+        );
+        assert_eq!(top_left, Vec2::new(bottom_left.x, top_right.y));
+
+        // The grid's center lands on the minimap's center
+        assert_eq!((bottom_left + top_right) / 2.0, Vec2::ZERO);
+    }
+
+    #[test]
+    fn wide_grids_squeeze_into_the_same_panel() {
+        let wide = GridSize::new(8, 3, 3);
+        let far_right = minimap_local(&GridPosition { x: 7, y: 1 }, wide);
+        assert_eq!(
+            far_right.x,
+            MINIMAP_SIZE.x / 2.0 - minimap_cell(wide).x / 2.0
+        );
+        assert!(minimap_cell(wide).x < minimap_cell(GridSize::default()).x);
+    }
+
+    #[test]
+    fn panel_stays_in_the_top_right_corner_of_any_screen() {
+        for (width, height) in [(1280.0, 800.0), (1920.0, 1080.0)] {
+            let layout = ArenaLayout::for_grid(width, height, GridSize::new(8, 3, 3));
+            let top_right = minimap_pos(&layout) + MINIMAP_SIZE / 2.0;
+            assert_eq!(top_right, Vec2::new(width, height) / 2.0 - MINIMAP_MARGIN);
+        }
+    }
+}
@@ -2,16 +2,31 @@ pub mod action_ui;
 pub mod actions;
 pub mod animation;
 pub mod arena;
+pub mod autosave;
+pub mod boss_bar;
+pub mod camera;
 pub mod campaign;
 pub mod combat;
 pub mod common;
+pub mod encyclopedia;
 pub mod grid_utils;
 pub mod growth;
 pub mod intro;
 pub mod loadout;
 pub mod menu;
+pub mod minimap;
+pub mod music;
 pub mod outro;
+pub mod overtime;
+pub mod pause;
 pub mod player;
+pub mod practice;
+pub mod replay;
+pub mod reward;
+pub mod rumble;
+pub mod settings;
 pub mod setup;
+pub mod shake;
 pub mod shop;
 pub mod splash;
+pub mod tutorial;
@@ -0,0 +1,145 @@
+//! Battle music that builds as the fight goes on.
+//!
+//! The base loop plays throughout at a steady volume. A drum layer of the same
+//! length loops alongside it, starting silent; `update_music_layers` fades it
+//! in as the battle's enemies are deleted (see `intensity_volume`), so the
+//! music builds toward the last kill.
+
+use bevy::prelude::*;
+
+use crate::components::{ArenaConfig, CleanupOnStateExit, Enemy, GameState};
+use crate::resources::{AudioSettings, WaveQueue, WaveState};
+
+/// Base battle loop
+const BGM_BATTLE: &str = "audio/bgm/battle.mp3";
+/// Intensity layer, on the base loop's beat and just as long so both wrap together
+const BGM_INTENSITY: &str = "audio/bgm/battle-intensity.mp3";
+/// Loudness of the base loop (scaled by `AudioSettings::music_volume`)
+const BGM_BASE_VOLUME: f32 = 0.4;
+/// Loudness of the intensity layer once the last enemy is deleted
+const BGM_INTENSITY_VOLUME: f32 = 0.5;
+
+/// The base loop's `AudioPlayer`
+#[derive(Component, Debug)]
+pub struct BattleMusic;
+
+/// The intensity layer's `AudioPlayer`
+#[derive(Component, Debug)]
+pub struct IntensityLayer;
+
+/// Enemy count the music's intensity is measured against
+#[derive(Resource, Debug, Default)]
+pub struct MusicLayers {
+    /// Enemies across every wave of the battle
+    pub total: usize,
+}
+
+/// Intensity layer volume (0.0 - 1.0): silent with every enemy alive, full
+/// once the last one is deleted
+pub fn intensity_volume(enemies_remaining: usize, total: usize) -> f32 {
+    if total == 0 {
+        return 0.0;
+    }
+    1.0 - (enemies_remaining as f32 / total as f32).min(1.0)
+}
+
+/// Start the base loop, and the intensity layer silent beside it
+pub fn setup_music_layers(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    config: Res<ArenaConfig>,
+    audio_settings: Res<AudioSettings>,
+) {
+    commands.insert_resource(MusicLayers {
+        total: config.waves.iter().map(Vec::len).sum(),
+    });
+    commands.spawn((
+        AudioPlayer::new(asset_server.load(BGM_BATTLE)),
+        PlaybackSettings::LOOP.with_volume(audio_settings.music_volume(BGM_BASE_VOLUME)),
+        BattleMusic,
+        CleanupOnStateExit(GameState::Playing),
+    ));
+    commands.spawn((
+        AudioPlayer::new(asset_server.load(BGM_INTENSITY)),
+        PlaybackSettings::LOOP.with_volume(audio_settings.music_volume(0.0)),
+        IntensityLayer,
+        CleanupOnStateExit(GameState::Playing),
+    ));
+}
+
+/// Follow the music setting with both loops, and the enemies left (on the
+/// field and in later waves) with the intensity layer
+pub fn update_music_layers(
+    layers: Option<Res<MusicLayers>>,
+    wave_state: Res<WaveState>,
+    queue: Res<WaveQueue>,
+    audio_settings: Res<AudioSettings>,
+    enemy_query: Query<(), With<Enemy>>,
+    mut base_query: Query<&mut AudioSink, (With<BattleMusic>, Without<IntensityLayer>)>,
+    mut layer_query: Query<&mut AudioSink, (With<IntensityLayer>, Without<BattleMusic>)>,
+) {
+    let Some(layers) = layers else {
+        return;
+    };
+    // Nothing has spawned yet: keep the current mix
+    if *wave_state == WaveState::Spawning {
+        return;
+    }
+
+    let remaining = enemy_query.iter().count() + queue.pending.iter().map(Vec::len).sum::<usize>();
+    let intensity = intensity_volume(remaining, layers.total);
+    for mut sink in &mut base_query {
+        sink.set_volume(audio_settings.music_volume(BGM_BASE_VOLUME));
+    }
+    for mut sink in &mut layer_query {
+        sink.set_volume(audio_settings.music_volume(BGM_INTENSITY_VOLUME * intensity));
+    }
+}
+
+/// Drop the enemy count when the battle ends
+pub fn cleanup_music_layers(mut commands: Commands) {
+    commands.remove_resource::<MusicLayers>();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn intensity_rises_as_enemies_are_deleted() {
+        assert_eq!(intensity_volume(4, 4), 0.0);
+        assert_eq!(intensity_volume(3, 4), 0.25);
+        assert_eq!(intensity_volume(1, 4), 0.75);
+        assert_eq!(intensity_volume(0, 4), 1.0);
+
+        // Summoned minions can push the count past the battle's total
+        assert_eq!(intensity_volume(6, 4), 0.0);
+        // An empty battle has nothing to build toward
+        assert_eq!(intensity_volume(0, 0), 0.0);
+    }
+
+    #[test]
+    fn intensity_layer_starts_silent_beside_the_base_loop() {
+        let mut assets = App::new();
+        assets
+            .add_plugins((MinimalPlugins, AssetPlugin::default()))
+            .init_asset::<AudioSource>();
+        let mut world = World::new();
+        world.insert_resource(assets.world().resource::<AssetServer>().clone());
+        world.init_resource::<ArenaConfig>();
+        world.init_resource::<AudioSettings>();
+        world.run_system_cached(setup_music_layers).unwrap();
+
+        let volume = |world: &mut World, path: &str| {
+            let mut query = world.query::<(&AudioPlayer, &PlaybackSettings)>();
+            let (_, settings) = query
+                .iter(world)
+                .find(|(player, _)| player.0.path().unwrap().to_string() == path)
+                .unwrap();
+            settings.volume.to_linear()
+        };
+        let base = AudioSettings::default().music_volume(BGM_BASE_VOLUME);
+        assert_eq!(volume(&mut world, BGM_BATTLE), base.to_linear());
+        assert_eq!(volume(&mut world, BGM_INTENSITY), 0.0);
+    }
+}
@@ -2,16 +2,27 @@
 // Post-Battle Outro Systems (Victory & Defeat)
 // ============================================================================
 
-use bevy::audio::{AudioPlayer, AudioSource, PlaybackSettings, Volume};
+use bevy::audio::{AudioPlayer, AudioSource, PlaybackSettings};
 use bevy::prelude::*;
+use bevy::sprite::Anchor;
 
 use crate::components::{
-    CleanupOnStateExit, DefeatContinueText, DefeatGameOverText, DefeatNoRewardText, DefeatOutro,
-    DefeatPhase, DefeatStatsPanel, DefeatTimeText, GameState, OutroPhase, VictoryClearText,
-    VictoryContinueText, VictoryOutro, VictoryRewardText, VictoryStatsPanel, VictoryTimeText,
+    CleanupOnStateExit, DefeatChoice, DefeatContinueText, DefeatGameOverText, DefeatNoRewardText,
+    DefeatOutro, DefeatPhase, DefeatStatsPanel, DefeatTimeText, GameState, OutroPhase,
+    VictoryBonusText, VictoryBreakdownText, VictoryClearText, VictoryContinueText, VictoryOutro,
+    VictoryRewardText, VictoryStatsPanel, VictoryTimeText,
 };
-use crate::constants::Z_UI;
-use crate::resources::{CampaignProgress, SelectedBattle};
+use crate::constants::{TIME_BONUS_MAX, Z_UI};
+use crate::resources::{
+    AudioSettings, BattleRecords, BattleStats, CampaignProgress, GameRng, LoadoutPresets,
+    OwnedChips, PlayerCurrency, PlayerLoadout, PlayerUpgrades, ReduceFlashing, SelectedBattle,
+    get_all_arcs,
+};
+use crate::save::save_records;
+use crate::systems::autosave::AutoSave;
+use crate::systems::growth::GrowthTreeState;
+use crate::systems::reward::{RewardOffer, roll_reward_choices};
+use crate::weapons::PlayerWeapons;
 
 // Timing constants (in seconds)
 const HITSTOP_DURATION: f32 = 0.1;
@@ -21,6 +32,55 @@ const STATS_START: f32 = 0.5;
 const STATS_DURATION: f32 = 1.0;
 const WAIT_CONFIRM_START: f32 = 1.5;
 
+/// Virtual time speed during the HitStop phase, so the killing blow plays out
+/// in slow motion (HITSTOP_DURATION / HITSTOP_TIME_SCALE ≈ 0.33s of real time)
+const HITSTOP_TIME_SCALE: f32 = 0.3;
+
+/// Clear time (as a fraction of par) at which the time bonus reaches zero
+const TIME_BONUS_CUTOFF: f32 = 1.5;
+
+/// Zenny for clearing in `battle_time` seconds: the full `TIME_BONUS_MAX` at
+/// half par or faster, half at par, tapering linearly to zero at 1.5x par.
+pub fn time_bonus(battle_time: f32, par_time: f32) -> u64 {
+    if par_time <= 0.0 {
+        return 0;
+    }
+    let ratio = battle_time / par_time;
+    let share = ((TIME_BONUS_CUTOFF - ratio) / (TIME_BONUS_CUTOFF - 0.5)).clamp(0.0, 1.0);
+    (TIME_BONUS_MAX as f32 * share).round() as u64
+}
+
+/// Slow motion on the killing blow. While present, `Time<Virtual>` runs at
+/// `HITSTOP_TIME_SCALE`; `release_hit_stop` restores the previous speed once the
+/// outro leaves its HitStop phase.
+#[derive(Resource, Debug)]
+pub struct HitStop {
+    restore_speed: f32,
+}
+
+impl HitStop {
+    pub fn start(virtual_time: &mut Time<Virtual>) -> Self {
+        let restore_speed = virtual_time.relative_speed();
+        virtual_time.set_relative_speed(HITSTOP_TIME_SCALE);
+        Self { restore_speed }
+    }
+
+    pub fn end(&self, virtual_time: &mut Time<Virtual>) {
+        virtual_time.set_relative_speed(self.restore_speed);
+    }
+}
+
+/// Battle time as `mm:ss.cc` (minutes, seconds, centiseconds)
+pub fn format_battle_time(seconds: f32) -> String {
+    let centis = (seconds.max(0.0) * 100.0) as u32;
+    format!(
+        "{:02}:{:02}.{:02}",
+        centis / 6000,
+        centis / 100 % 60,
+        centis % 100
+    )
+}
+
 /// Marker to track if outro UI has been spawned
 #[derive(Component)]
 struct OutroUISpawned;
@@ -33,6 +93,8 @@ struct OutroUISpawned;
 pub fn setup_outro(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
+    audio_settings: Res<AudioSettings>,
+    stats: Res<BattleStats>,
     outro: Option<Res<VictoryOutro>>,
     existing_ui: Query<(), With<VictoryClearText>>,
 ) {
@@ -44,7 +106,7 @@ pub fn setup_outro(
     let victory_sound: Handle<AudioSource> = asset_server.load("audio/sound/victory.mp3");
     commands.spawn((
         AudioPlayer::new(victory_sound),
-        PlaybackSettings::ONCE.with_volume(Volume::Linear(0.8)),
+        PlaybackSettings::ONCE.with_volume(audio_settings.sfx_volume(0.8)),
         CleanupOnStateExit(GameState::Playing),
     ));
 
@@ -89,14 +151,34 @@ pub fn setup_outro(
                 VictoryRewardText,
             ));
 
+            // Time bonus line
+            parent.spawn((
+                Text2d::new("TIME BONUS: +0 Z"),
+                TextFont::from_font_size(24.0),
+                TextColor(Color::srgba(0.5, 1.0, 0.6, 0.0)), // Start invisible
+                Transform::from_xyz(0.0, -45.0, 1.0),
+                VictoryBonusText,
+            ));
+
             // Continue prompt
             parent.spawn((
-                Text2d::new("Press SPACE to continue"),
+                Text2d::new("Press SPACE to continue  |  TAB: details"),
                 TextFont::from_font_size(20.0),
                 TextColor(Color::srgba(0.7, 0.7, 0.7, 0.0)), // Start invisible
-                Transform::from_xyz(0.0, -70.0, 1.0),
+                Transform::from_xyz(0.0, -80.0, 1.0),
                 VictoryContinueText,
             ));
+
+            // Stats breakdown below the panel (hidden until toggled)
+            parent.spawn((
+                Text2d::new(stats.breakdown()),
+                TextFont::from_font_size(18.0),
+                TextColor(Color::srgb(0.85, 0.85, 0.9)),
+                Anchor::TOP_CENTER,
+                Transform::from_xyz(0.0, -110.0, 1.0),
+                Visibility::Hidden,
+                VictoryBreakdownText,
+            ));
         });
 }
 
@@ -104,9 +186,20 @@ pub fn setup_outro(
 // Update System
 // ============================================================================
 
+/// Alpha of a blinking continue prompt: a slow pulse, held steady when
+/// flashing is reduced
+fn prompt_alpha(elapsed: f32, reduce_flashing: ReduceFlashing) -> f32 {
+    if reduce_flashing.0 {
+        0.85
+    } else {
+        (elapsed * 2.0).sin() * 0.3 + 0.7
+    }
+}
+
 /// Update the victory outro sequence
 pub fn update_outro(
     time: Res<Time>,
+    reduce_flashing: Res<ReduceFlashing>,
     keyboard: Res<ButtonInput<KeyCode>>,
     gamepads: Query<&Gamepad>,
     mut outro: ResMut<VictoryOutro>,
@@ -117,6 +210,7 @@ pub fn update_outro(
             Without<VictoryStatsPanel>,
             Without<VictoryTimeText>,
             Without<VictoryRewardText>,
+            Without<VictoryBonusText>,
             Without<VictoryContinueText>,
         ),
     >,
@@ -136,6 +230,7 @@ pub fn update_outro(
             With<VictoryTimeText>,
             Without<VictoryClearText>,
             Without<VictoryRewardText>,
+            Without<VictoryBonusText>,
             Without<VictoryContinueText>,
         ),
     >,
@@ -145,6 +240,17 @@ pub fn update_outro(
             With<VictoryRewardText>,
             Without<VictoryClearText>,
             Without<VictoryTimeText>,
+            Without<VictoryBonusText>,
+            Without<VictoryContinueText>,
+        ),
+    >,
+    mut bonus_text: Query<
+        (&mut Text2d, &mut TextColor),
+        (
+            With<VictoryBonusText>,
+            Without<VictoryClearText>,
+            Without<VictoryTimeText>,
+            Without<VictoryRewardText>,
             Without<VictoryContinueText>,
         ),
     >,
@@ -155,6 +261,7 @@ pub fn update_outro(
             Without<VictoryClearText>,
             Without<VictoryTimeText>,
             Without<VictoryRewardText>,
+            Without<VictoryBonusText>,
         ),
     >,
 ) {
@@ -225,10 +332,7 @@ pub fn update_outro(
 
             // Count up effect for time
             let displayed_time = outro.battle_time * phase_progress;
-            let minutes = (displayed_time / 60.0) as u32;
-            let seconds = (displayed_time % 60.0) as u32;
-            let centis = ((displayed_time % 1.0) * 100.0) as u32;
-            text.0 = format!("TIME: {:02}:{:02}.{:02}", minutes, seconds, centis);
+            text.0 = format!("TIME: {}", format_battle_time(displayed_time));
         }
     }
 
@@ -245,12 +349,22 @@ pub fn update_outro(
         }
     }
 
+    // Handle time bonus line (appears after the reward has counted up)
+    for (mut text, mut color) in &mut bonus_text {
+        if outro.phase == OutroPhase::Stats || outro.phase == OutroPhase::WaitConfirm {
+            let phase_progress =
+                ((outro.elapsed - STATS_START - 0.4) / (STATS_DURATION - 0.4)).clamp(0.0, 1.0);
+            color.0 = Color::srgba(0.5, 1.0, 0.6, phase_progress);
+
+            let displayed_bonus = (outro.time_bonus as f32 * phase_progress) as u64;
+            text.0 = format!("TIME BONUS: +{} Z", displayed_bonus);
+        }
+    }
+
     // Handle continue prompt (blink effect when waiting)
     for mut color in &mut continue_text {
         if outro.phase == OutroPhase::WaitConfirm {
-            // Blink effect
-            let blink = (outro.elapsed * 2.0).sin() * 0.3 + 0.7;
-            color.0 = Color::srgba(0.7, 0.7, 0.7, blink);
+            color.0 = Color::srgba(0.7, 0.7, 0.7, prompt_alpha(outro.elapsed, *reduce_flashing));
         }
     }
 
@@ -269,41 +383,140 @@ pub fn update_outro(
     }
 }
 
+/// Show or hide the battle stats breakdown with Tab / Y once the stats are up
+pub fn toggle_battle_breakdown(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<&Gamepad>,
+    outro: Res<VictoryOutro>,
+    mut query: Query<&mut Visibility, With<VictoryBreakdownText>>,
+) {
+    if outro.phase != OutroPhase::WaitConfirm {
+        return;
+    }
+    let toggled = keyboard.just_pressed(KeyCode::Tab)
+        || gamepads
+            .iter()
+            .any(|gp| gp.just_pressed(GamepadButton::North));
+    if !toggled {
+        return;
+    }
+    for mut visibility in &mut query {
+        visibility.toggle_visible_hidden();
+    }
+}
+
 // ============================================================================
 // Transition System - Handle state change after outro
 // ============================================================================
 
 /// Check if outro is complete and transition to next state
 pub fn check_outro_complete(
+    mut commands: Commands,
     outro: Option<Res<VictoryOutro>>,
     mut next_state: ResMut<NextState<GameState>>,
     mut campaign_progress: ResMut<CampaignProgress>,
     selected_battle: Option<Res<SelectedBattle>>,
+    mut currency: ResMut<PlayerCurrency>,
+    upgrades: Res<PlayerUpgrades>,
+    growth: Res<GrowthTreeState>,
+    loadout: Res<PlayerLoadout>,
+    mut player_weapons: ResMut<PlayerWeapons>,
+    owned_chips: Res<OwnedChips>,
+    presets: Res<LoadoutPresets>,
+    mut records: ResMut<BattleRecords>,
+    mut rng: ResMut<GameRng>,
+    mut autosave: AutoSave,
 ) {
     let Some(outro) = outro else { return };
 
     if outro.is_done() {
+        // Pay out the fast-clear bonus on top of the zenny banked at victory
+        currency.zenny += outro.time_bonus;
+
         // Mark battle complete and transition
         if let Some(selected) = selected_battle {
             campaign_progress.complete_battle(selected.arc, selected.battle);
+            if records.record_clear(
+                selected.arc,
+                selected.battle,
+                outro.battle_time,
+                outro.reward + outro.time_bonus,
+            ) {
+                save_records(autosave.slot(), &records);
+            }
+            let mut boss = false;
+
+            // Beating an arc's boss hands out that arc's weapon reward
+            if let Some(arc) = get_all_arcs().get(selected.arc) {
+                let is_boss = arc.battles.get(selected.battle).is_some_and(|b| b.is_boss);
+                if let Some(weapon) = arc.weapon_reward.filter(|_| is_boss) {
+                    player_weapons.unlock(weapon);
+                }
+
+                boss = is_boss;
+            }
+
+            autosave.save(
+                &campaign_progress,
+                &currency,
+                &upgrades,
+                &growth,
+                &loadout,
+                &player_weapons,
+                &owned_chips,
+                &presets,
+            );
             info!(
                 "Battle {} of Arc {} completed!",
                 selected.battle + 1,
                 selected.arc + 1
             );
-            next_state.set(GameState::Campaign);
+
+            // Offer a chip pick before heading back to the map
+            let choices = roll_reward_choices(&owned_chips, boss, &mut rng.0);
+            if choices.is_empty() {
+                next_state.set(GameState::Campaign);
+            } else {
+                commands.insert_resource(RewardOffer::new(choices));
+                next_state.set(GameState::Reward);
+            }
         } else {
             next_state.set(GameState::Shop);
         }
     }
 }
 
+/// Return to normal speed once the killing blow's slow motion has played out
+pub fn release_hit_stop(
+    mut commands: Commands,
+    outro: Res<VictoryOutro>,
+    hit_stop: Option<Res<HitStop>>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+) {
+    let Some(hit_stop) = hit_stop else {
+        return;
+    };
+    if outro.phase != OutroPhase::HitStop {
+        hit_stop.end(&mut virtual_time);
+        commands.remove_resource::<HitStop>();
+    }
+}
+
 // ============================================================================
 // Cleanup System
 // ============================================================================
 
 /// Cleanup outro resources when leaving Playing state
-pub fn cleanup_outro(mut commands: Commands) {
+pub fn cleanup_outro(
+    mut commands: Commands,
+    hit_stop: Option<Res<HitStop>>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+) {
+    // Never leave the next battle in slow motion
+    if let Some(hit_stop) = hit_stop {
+        hit_stop.end(&mut virtual_time);
+        commands.remove_resource::<HitStop>();
+    }
     commands.remove_resource::<VictoryOutro>();
     commands.remove_resource::<DefeatOutro>();
 }
@@ -349,6 +562,22 @@ const DEFEAT_STATS_START: f32 = 0.8;
 const DEFEAT_STATS_DURATION: f32 = 0.7;
 const DEFEAT_WAIT_CONFIRM_START: f32 = 1.5;
 
+/// Retry / Quit prompt with the highlighted option bracketed
+fn defeat_prompt(choice: DefeatChoice) -> String {
+    let option = |label: &str, selected: bool| {
+        if selected {
+            format!("> {label} <")
+        } else {
+            format!("  {label}  ")
+        }
+    };
+    format!(
+        "{}      {}",
+        option("RETRY", choice == DefeatChoice::Retry),
+        option("QUIT", choice == DefeatChoice::Quit)
+    )
+}
+
 // ============================================================================
 // Defeat Setup System
 // ============================================================================
@@ -357,6 +586,7 @@ const DEFEAT_WAIT_CONFIRM_START: f32 = 1.5;
 pub fn setup_defeat_outro(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
+    audio_settings: Res<AudioSettings>,
     outro: Option<Res<DefeatOutro>>,
     existing_ui: Query<(), With<DefeatGameOverText>>,
 ) {
@@ -369,7 +599,7 @@ pub fn setup_defeat_outro(
     let gameover_sound: Handle<AudioSource> = asset_server.load("audio/sound/game-over.mp3");
     commands.spawn((
         AudioPlayer::new(gameover_sound),
-        PlaybackSettings::ONCE.with_volume(Volume::Linear(0.8)),
+        PlaybackSettings::ONCE.with_volume(audio_settings.sfx_volume(0.8)),
         CleanupOnStateExit(GameState::Playing),
     ));
 
@@ -414,9 +644,9 @@ pub fn setup_defeat_outro(
                 DefeatNoRewardText,
             ));
 
-            // Continue prompt
+            // Retry / Quit prompt
             parent.spawn((
-                Text2d::new("Press SPACE to continue"),
+                Text2d::new(defeat_prompt(DefeatChoice::default())),
                 TextFont::from_font_size(20.0),
                 TextColor(Color::srgba(0.7, 0.7, 0.7, 0.0)), // Start invisible
                 Transform::from_xyz(0.0, -60.0, 1.0),
@@ -432,6 +662,7 @@ pub fn setup_defeat_outro(
 /// Update the defeat outro sequence
 pub fn update_defeat_outro(
     time: Res<Time>,
+    reduce_flashing: Res<ReduceFlashing>,
     keyboard: Res<ButtonInput<KeyCode>>,
     gamepads: Query<&Gamepad>,
     mut outro: ResMut<DefeatOutro>,
@@ -474,7 +705,7 @@ pub fn update_defeat_outro(
         ),
     >,
     mut continue_text: Query<
-        &mut TextColor,
+        (&mut Text2d, &mut TextColor),
         (
             With<DefeatContinueText>,
             Without<DefeatGameOverText>,
@@ -555,11 +786,7 @@ pub fn update_defeat_outro(
             color.0 = Color::srgba(1.0, 1.0, 1.0, phase_progress);
 
             // Show final time (no count-up for defeat, just reveal)
-            let displayed_time = outro.battle_time;
-            let minutes = (displayed_time / 60.0) as u32;
-            let seconds = (displayed_time % 60.0) as u32;
-            let centis = ((displayed_time % 1.0) * 100.0) as u32;
-            text.0 = format!("TIME: {:02}:{:02}.{:02}", minutes, seconds, centis);
+            text.0 = format!("TIME: {}", format_battle_time(outro.battle_time));
         }
     }
 
@@ -573,12 +800,31 @@ pub fn update_defeat_outro(
         }
     }
 
-    // Handle continue prompt (blink effect when waiting)
-    for mut color in &mut continue_text {
+    // Left / Right switches between Retry and Quit
+    if outro.phase == DefeatPhase::WaitConfirm {
+        let keyboard_switch = [
+            KeyCode::ArrowLeft,
+            KeyCode::ArrowRight,
+            KeyCode::KeyA,
+            KeyCode::KeyD,
+        ]
+        .into_iter()
+        .any(|key| keyboard.just_pressed(key));
+
+        let gamepad_switch = gamepads.iter().any(|gp| {
+            gp.just_pressed(GamepadButton::DPadLeft) || gp.just_pressed(GamepadButton::DPadRight)
+        });
+
+        if keyboard_switch || gamepad_switch {
+            outro.choice = outro.choice.toggle();
+        }
+    }
+
+    // Handle Retry / Quit prompt (blink effect when waiting)
+    for (mut text, mut color) in &mut continue_text {
         if outro.phase == DefeatPhase::WaitConfirm {
-            // Blink effect
-            let blink = (outro.elapsed * 2.0).sin() * 0.3 + 0.7;
-            color.0 = Color::srgba(0.7, 0.7, 0.7, blink);
+            color.0 = Color::srgba(0.7, 0.7, 0.7, prompt_alpha(outro.elapsed, *reduce_flashing));
+            text.0 = defeat_prompt(outro.choice);
         }
     }
 
@@ -601,7 +847,8 @@ pub fn update_defeat_outro(
 // Defeat Transition System
 // ============================================================================
 
-/// Check if defeat outro is complete and transition to campaign (no battle marked complete)
+/// Check if defeat outro is complete and retry the battle or return to the
+/// campaign (no battle marked complete)
 pub fn check_defeat_outro_complete(
     outro: Option<Res<DefeatOutro>>,
     mut next_state: ResMut<NextState<GameState>>,
@@ -609,7 +856,11 @@ pub fn check_defeat_outro_complete(
 ) {
     let Some(outro) = outro else { return };
 
-    if outro.is_done() {
+    if outro.is_done() && outro.choice == DefeatChoice::Retry {
+        // Re-entering Playing re-runs arena setup with the same ArenaConfig
+        info!("Retrying the battle...");
+        next_state.set(GameState::Playing);
+    } else if outro.is_done() {
         // Don't mark battle complete - player lost!
         if selected_battle.is_some() {
             info!("Returning to campaign after defeat...");
@@ -620,3 +871,107 @@ pub fn check_defeat_outro_complete(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn battle_time_formats_as_minutes_seconds_centis() {
+        assert_eq!(format_battle_time(0.0), "00:00.00");
+        assert_eq!(format_battle_time(0.25), "00:00.25");
+        assert_eq!(format_battle_time(9.5), "00:09.50");
+        assert_eq!(format_battle_time(61.75), "01:01.75");
+        assert_eq!(format_battle_time(754.5), "12:34.50");
+    }
+
+    #[test]
+    fn fast_clears_earn_the_full_time_bonus() {
+        assert_eq!(time_bonus(10.0, 60.0), TIME_BONUS_MAX);
+        assert_eq!(time_bonus(30.0, 60.0), TIME_BONUS_MAX);
+    }
+
+    #[test]
+    fn clearing_at_par_earns_half_the_time_bonus() {
+        assert_eq!(time_bonus(60.0, 60.0), TIME_BONUS_MAX / 2);
+        assert!(time_bonus(75.0, 60.0) < TIME_BONUS_MAX / 2);
+    }
+
+    #[test]
+    fn slow_clears_earn_no_time_bonus() {
+        assert_eq!(time_bonus(90.0, 60.0), 0);
+        assert_eq!(time_bonus(600.0, 60.0), 0);
+        assert_eq!(time_bonus(10.0, 0.0), 0);
+    }
+
+    #[test]
+    fn hit_stop_slows_time_until_the_outro_leaves_hit_stop() {
+        let mut world = World::new();
+        world.init_resource::<Time<Virtual>>();
+        world.insert_resource(VictoryOutro::new(30.0, 100, 0));
+        let hit_stop = HitStop::start(&mut world.resource_mut::<Time<Virtual>>());
+        world.insert_resource(hit_stop);
+        assert_eq!(
+            world.resource::<Time<Virtual>>().relative_speed(),
+            HITSTOP_TIME_SCALE
+        );
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(release_hit_stop);
+
+        // Still in the HitStop phase: stay slowed
+        schedule.run(&mut world);
+        assert_eq!(
+            world.resource::<Time<Virtual>>().relative_speed(),
+            HITSTOP_TIME_SCALE
+        );
+
+        world.resource_mut::<VictoryOutro>().phase = OutroPhase::Clear;
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<Time<Virtual>>().relative_speed(), 1.0);
+        assert!(!world.contains_resource::<HitStop>());
+    }
+
+    #[test]
+    fn retrying_after_defeat_replays_the_same_battle() {
+        use crate::components::{ArenaConfig, EnemyConfig};
+        use crate::enemies::EnemyId;
+
+        let mut world = World::new();
+        world.init_resource::<NextState<GameState>>();
+        world.insert_resource(ArenaConfig {
+            waves: vec![vec![EnemyConfig::new(EnemyId::Slime2, 5, 2)]],
+            ..default()
+        });
+        world.insert_resource(SelectedBattle { arc: 1, battle: 4 });
+        let mut outro = DefeatOutro::new(42.0);
+        outro.confirmed = true;
+        world.insert_resource(outro);
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(check_defeat_outro_complete);
+        schedule.run(&mut world);
+
+        assert!(matches!(
+            *world.resource::<NextState<GameState>>(),
+            NextState::Pending(GameState::Playing)
+        ));
+        let config = world.resource::<ArenaConfig>();
+        assert_eq!(config.waves.len(), 1);
+        assert_eq!(config.waves[0][0].enemy_id, EnemyId::Slime2);
+        assert_eq!(
+            (config.waves[0][0].start_x, config.waves[0][0].start_y),
+            (5, 2)
+        );
+        let selected = world.resource::<SelectedBattle>();
+        assert_eq!((selected.arc, selected.battle), (1, 4));
+
+        // Quit still heads back to the campaign
+        world.resource_mut::<DefeatOutro>().choice = DefeatChoice::Quit;
+        schedule.run(&mut world);
+        assert!(matches!(
+            *world.resource::<NextState<GameState>>(),
+            NextState::Pending(GameState::Campaign)
+        ));
+    }
+}
@@ -0,0 +1,177 @@
+//! Sudden-death overtime for battles that drag on.
+//!
+//! With the `OvertimeRule` on (picked on the settings screen), a battle still
+//! running after `OVERTIME_THRESHOLD` seconds on the `BattleTimer` goes into
+//! overtime: an "OVERTIME" banner flashes up, the player's HP drains a little
+//! every second (never below 1, so enemies still have to land the last hit),
+//! and enemy hits land harder (see `overtime_damage`). Practice and tutorial
+//! arenas never go into overtime.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::components::{
+    ArenaConfig, CleanupOnStateExit, GameState, Health, Player, PlayerHealthText,
+};
+use crate::constants::*;
+use crate::resources::BattleTimer;
+
+/// Battle time (seconds) after which overtime starts
+pub const OVERTIME_THRESHOLD: f32 = 120.0;
+/// HP the player loses per drain tick
+pub const OVERTIME_DRAIN: i32 = 2;
+/// Seconds between drain ticks
+const OVERTIME_DRAIN_INTERVAL: f32 = 1.0;
+/// Enemy damage multiplier during overtime
+const OVERTIME_DAMAGE_SCALE: f32 = 1.5;
+/// How long the banner stays up
+const OVERTIME_BANNER_TIME: f32 = 2.0;
+const COLOR_OVERTIME_BANNER: Color = Color::srgb(1.0, 0.3, 0.25);
+
+/// Whether long battles go into overtime
+#[derive(Resource, Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OvertimeRule {
+    pub enabled: bool,
+}
+
+/// Present once the current battle is in overtime
+#[derive(Resource, Debug)]
+pub struct Overtime {
+    pub drain: Timer,
+    pub banner: Timer,
+}
+
+impl Default for Overtime {
+    fn default() -> Self {
+        Self {
+            drain: Timer::from_seconds(OVERTIME_DRAIN_INTERVAL, TimerMode::Repeating),
+            banner: Timer::from_seconds(OVERTIME_BANNER_TIME, TimerMode::Once),
+        }
+    }
+}
+
+/// "OVERTIME" banner marker
+#[derive(Component)]
+pub struct OvertimeBanner;
+
+/// Damage an enemy hit deals, raised while the battle is in overtime
+pub fn overtime_damage(damage: i32, overtime: Option<&Overtime>) -> i32 {
+    match overtime {
+        Some(_) => (damage as f32 * OVERTIME_DAMAGE_SCALE).round() as i32,
+        None => damage,
+    }
+}
+
+/// Start overtime once the battle runs past the threshold, then drain the
+/// player's HP on every tick
+pub fn process_overtime(
+    mut commands: Commands,
+    time: Res<Time>,
+    rule: Res<OvertimeRule>,
+    config: Res<ArenaConfig>,
+    battle_timer: Res<BattleTimer>,
+    overtime: Option<ResMut<Overtime>>,
+    mut player_query: Query<&mut Health, With<Player>>,
+    mut hp_text_query: Query<&mut Text2d, With<PlayerHealthText>>,
+    banner_query: Query<Entity, With<OvertimeBanner>>,
+) {
+    let Some(mut overtime) = overtime else {
+        let starts = rule.enabled
+            && !config.practice
+            && !config.tutorial
+            && battle_timer.elapsed >= OVERTIME_THRESHOLD;
+        if starts {
+            info!("Overtime!");
+            commands.insert_resource(Overtime::default());
+            commands.spawn((
+                Text2d::new("OVERTIME"),
+                TextFont::from_font_size(72.0),
+                TextColor(COLOR_OVERTIME_BANNER),
+                Transform::from_xyz(0.0, 60.0, Z_UI + 50.0),
+                OvertimeBanner,
+                CleanupOnStateExit(GameState::Playing),
+            ));
+        }
+        return;
+    };
+
+    overtime.banner.tick(time.delta());
+    if overtime.banner.just_finished() {
+        for banner in &banner_query {
+            commands.entity(banner).despawn();
+        }
+    }
+
+    overtime.drain.tick(time.delta());
+    if !overtime.drain.just_finished() {
+        return;
+    }
+    for mut health in &mut player_query {
+        let drained = (health.current - OVERTIME_DRAIN).max(1);
+        if drained == health.current {
+            continue;
+        }
+        health.current = drained;
+        for mut text in &mut hp_text_query {
+            text.0 = format!("HP: {}", health.current);
+        }
+    }
+}
+
+/// Drop overtime when the battle ends
+pub fn cleanup_overtime(mut commands: Commands) {
+    commands.remove_resource::<Overtime>();
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn hp_only_drains_once_the_battle_runs_past_the_threshold() {
+        let mut world = World::new();
+        world.init_resource::<Time>();
+        world.init_resource::<BattleTimer>();
+        world.init_resource::<ArenaConfig>();
+        world.insert_resource(OvertimeRule { enabled: true });
+        let player = world
+            .spawn((
+                Player,
+                Health {
+                    current: 100,
+                    max: 100,
+                },
+            ))
+            .id();
+        let mut schedule = Schedule::default();
+        schedule.add_systems(process_overtime);
+        let mut step = |world: &mut World, elapsed: f32| {
+            world.resource_mut::<BattleTimer>().elapsed = elapsed;
+            world
+                .resource_mut::<Time>()
+                .advance_by(Duration::from_secs_f32(1.0));
+            schedule.run(world);
+            world.get::<Health>(player).unwrap().current
+        };
+
+        for elapsed in [1.0, 60.0, OVERTIME_THRESHOLD - 1.0] {
+            assert_eq!(step(&mut world, elapsed), 100);
+        }
+        assert!(!world.contains_resource::<Overtime>());
+
+        // Crossing the threshold starts overtime; the drain follows a tick later
+        assert_eq!(step(&mut world, OVERTIME_THRESHOLD), 100);
+        assert!(world.contains_resource::<Overtime>());
+        assert_eq!(world.query::<&OvertimeBanner>().iter(&world).count(), 1);
+        assert_eq!(
+            step(&mut world, OVERTIME_THRESHOLD + 1.0),
+            100 - OVERTIME_DRAIN
+        );
+        assert_eq!(
+            step(&mut world, OVERTIME_THRESHOLD + 2.0),
+            100 - 2 * OVERTIME_DRAIN
+        );
+    }
+}
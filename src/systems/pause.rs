@@ -0,0 +1,254 @@
+// ============================================================================
+// Pause Menu - Freezes the battle in place
+// ============================================================================
+//
+// Escape / Start toggles the pause overlay during a battle. While paused:
+// - `Time<Virtual>` is paused so every gameplay timer stops exactly where it was
+// - gameplay systems are gated by the `not_paused` run condition (no input),
+//   which also holds them while the custom gauge's chip select is open
+// - a dimmed overlay offers Resume / Restart / Quit to Menu
+
+use bevy::prelude::*;
+
+use crate::actions::CustomGauge;
+use crate::components::{CleanupOnStateExit, GameState};
+
+const OVERLAY_COLOR: Color = Color::srgba(0.0, 0.0, 0.0, 0.6);
+const PANEL_BG: Color = Color::srgba(0.08, 0.08, 0.15, 0.95);
+const PANEL_BORDER: Color = Color::srgb(0.3, 0.4, 0.6);
+const OPTION_NORMAL: Color = Color::srgb(0.6, 0.6, 0.7);
+const OPTION_SELECTED: Color = Color::srgb(1.0, 0.9, 0.4);
+
+/// Options listed in the pause panel (in display order)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PauseOption {
+    Resume,
+    Restart,
+    QuitToMenu,
+}
+
+impl PauseOption {
+    pub const ALL: [PauseOption; 3] = [
+        PauseOption::Resume,
+        PauseOption::Restart,
+        PauseOption::QuitToMenu,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            PauseOption::Resume => "Resume",
+            PauseOption::Restart => "Restart",
+            PauseOption::QuitToMenu => "Quit to Menu",
+        }
+    }
+}
+
+/// Whether the battle is paused, and which option is highlighted
+#[derive(Resource, Debug, Default)]
+pub struct PauseState {
+    pub paused: bool,
+    pub cursor: usize,
+}
+
+/// Marker for the pause overlay root
+#[derive(Component)]
+pub struct PauseMenu;
+
+/// Marker for a pause option label
+#[derive(Component)]
+pub struct PauseOptionText {
+    pub index: usize,
+}
+
+// ============================================================================
+// Systems
+// ============================================================================
+
+/// Toggle pause with Escape / gamepad Start
+pub fn toggle_pause(
+    mut commands: Commands,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<&Gamepad>,
+    mut pause: ResMut<PauseState>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+    menu_query: Query<Entity, With<PauseMenu>>,
+) {
+    let mut toggle = keyboard.just_pressed(KeyCode::Escape);
+    for gamepad in gamepads.iter() {
+        if gamepad.just_pressed(GamepadButton::Start) {
+            toggle = true;
+        }
+    }
+    if !toggle {
+        return;
+    }
+
+    if pause.paused {
+        resume(&mut commands, &mut pause, &mut virtual_time, &menu_query);
+    } else {
+        pause.paused = true;
+        pause.cursor = 0;
+        virtual_time.pause();
+        spawn_pause_menu(&mut commands);
+    }
+}
+
+/// Navigate and confirm pause options
+pub fn update_pause_menu(
+    mut commands: Commands,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<&Gamepad>,
+    mut pause: ResMut<PauseState>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+    mut next_state: ResMut<NextState<GameState>>,
+    menu_query: Query<Entity, With<PauseMenu>>,
+    mut option_query: Query<(&PauseOptionText, &mut TextColor)>,
+) {
+    if !pause.paused {
+        return;
+    }
+
+    let mut up = keyboard.just_pressed(KeyCode::ArrowUp) || keyboard.just_pressed(KeyCode::KeyW);
+    let mut down =
+        keyboard.just_pressed(KeyCode::ArrowDown) || keyboard.just_pressed(KeyCode::KeyS);
+    let mut confirm =
+        keyboard.just_pressed(KeyCode::Enter) || keyboard.just_pressed(KeyCode::Space);
+    for gamepad in gamepads.iter() {
+        up |= gamepad.just_pressed(GamepadButton::DPadUp);
+        down |= gamepad.just_pressed(GamepadButton::DPadDown);
+        confirm |= gamepad.just_pressed(GamepadButton::South);
+    }
+
+    let count = PauseOption::ALL.len();
+    if up {
+        pause.cursor = (pause.cursor + count - 1) % count;
+    }
+    if down {
+        pause.cursor = (pause.cursor + 1) % count;
+    }
+
+    for (option, mut color) in &mut option_query {
+        color.0 = if option.index == pause.cursor {
+            OPTION_SELECTED
+        } else {
+            OPTION_NORMAL
+        };
+    }
+
+    if !confirm {
+        return;
+    }
+
+    match PauseOption::ALL[pause.cursor] {
+        PauseOption::Resume => {
+            resume(&mut commands, &mut pause, &mut virtual_time, &menu_query);
+        }
+        PauseOption::Restart => {
+            // Re-entering Playing re-runs arena setup with the same ArenaConfig
+            resume(&mut commands, &mut pause, &mut virtual_time, &menu_query);
+            next_state.set(GameState::Playing);
+        }
+        PauseOption::QuitToMenu => {
+            resume(&mut commands, &mut pause, &mut virtual_time, &menu_query);
+            next_state.set(GameState::MainMenu);
+        }
+    }
+}
+
+/// Make sure time is never left paused when the battle ends
+pub fn cleanup_pause(mut pause: ResMut<PauseState>, mut virtual_time: ResMut<Time<Virtual>>) {
+    pause.paused = false;
+    pause.cursor = 0;
+    virtual_time.unpause();
+}
+
+/// Run condition: gameplay only runs while the battle is not paused, nor frozen
+/// on the custom gauge's chip select
+pub fn not_paused(pause: Option<Res<PauseState>>, gauge: Option<Res<CustomGauge>>) -> bool {
+    pause.map(|p| !p.paused).unwrap_or(true) && !gauge.is_some_and(|gauge| gauge.selecting)
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+fn resume(
+    commands: &mut Commands,
+    pause: &mut PauseState,
+    virtual_time: &mut Time<Virtual>,
+    menu_query: &Query<Entity, With<PauseMenu>>,
+) {
+    pause.paused = false;
+    virtual_time.unpause();
+    for entity in menu_query.iter() {
+        commands.entity(entity).despawn();
+    }
+}
+
+fn spawn_pause_menu(commands: &mut Commands) {
+    commands
+        .spawn((
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                position_type: PositionType::Absolute,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(OVERLAY_COLOR),
+            GlobalZIndex(100),
+            PauseMenu,
+            CleanupOnStateExit(GameState::Playing),
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn((
+                    Node {
+                        width: Val::Px(360.0),
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        padding: UiRect::all(Val::Px(30.0)),
+                        row_gap: Val::Px(18.0),
+                        border: UiRect::all(Val::Px(3.0)),
+                        ..default()
+                    },
+                    BackgroundColor(PANEL_BG),
+                    BorderColor::all(PANEL_BORDER),
+                ))
+                .with_children(|panel| {
+                    panel.spawn((
+                        Text::new("PAUSED"),
+                        TextFont::from_font_size(48.0),
+                        TextColor(Color::WHITE),
+                        Node {
+                            margin: UiRect::bottom(Val::Px(10.0)),
+                            ..default()
+                        },
+                    ));
+
+                    for (index, option) in PauseOption::ALL.iter().enumerate() {
+                        panel.spawn((
+                            Text::new(option.label()),
+                            TextFont::from_font_size(30.0),
+                            TextColor(if index == 0 {
+                                OPTION_SELECTED
+                            } else {
+                                OPTION_NORMAL
+                            }),
+                            PauseOptionText { index },
+                        ));
+                    }
+
+                    panel.spawn((
+                        Text::new("[Up/Down] Select  [Enter/A] Confirm  [Esc/Start] Resume"),
+                        TextFont::from_font_size(14.0),
+                        TextColor(Color::srgba(1.0, 1.0, 1.0, 0.5)),
+                        Node {
+                            margin: UiRect::top(Val::Px(10.0)),
+                            ..default()
+                        },
+                    ));
+                });
+        });
+}
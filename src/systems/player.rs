@@ -1,72 +1,418 @@
+use std::time::Duration;
+
+use bevy::ecs::query::QueryFilter;
 use bevy::prelude::*;
 
+use crate::actions::{ActiveShield, ShieldType};
+use crate::bindings::{InputAction, KeyBindings};
 use crate::components::*;
-use crate::constants::*;
+use crate::constants::{DASH_COOLDOWN, DASH_WINDOW, DODGE_COOLDOWN, DODGE_IFRAMES, MOVE_COOLDOWN};
+use crate::resources::TileOwnership;
+
+/// Double-tap detection for dashing: when each direction was last tapped
+#[derive(Debug, Default)]
+pub struct DashInput {
+    last_tap: Vec<(IVec2, f32)>,
+    /// Direction double-tapped, waiting for the move cooldown
+    pending: Option<IVec2>,
+}
+
+impl DashInput {
+    /// Note a tap of `direction` at `now`; a second tap within `DASH_WINDOW`
+    /// queues a dash that way
+    pub fn tap(&mut self, direction: IVec2, now: f32) {
+        let previous = self
+            .last_tap
+            .iter()
+            .position(|(dir, _)| *dir == direction)
+            .map(|index| self.last_tap.swap_remove(index).1);
+        if previous.is_some_and(|at| now - at <= DASH_WINDOW) {
+            // A third tap starts a new pair instead of dashing again
+            self.pending = Some(direction);
+        } else {
+            self.last_tap.push((direction, now));
+        }
+    }
+
+    /// Whether a move in `direction` is the queued dash (consumes it)
+    pub fn take_dash(&mut self, direction: IVec2) -> bool {
+        self.pending.take() == Some(direction)
+    }
+
+    /// Once `cooldown` has run out, move `pos` toward the `held` direction:
+    /// two tiles for a queued dash, else one. Restarts `cooldown` for the
+    /// move that was made. Shared by the player and the replay ghost.
+    pub fn walk<F: QueryFilter>(
+        &mut self,
+        pos: &mut GridPosition,
+        held: Option<IVec2>,
+        cooldown: &mut Timer,
+        ownership: &TileOwnership,
+        obstacles: &Query<&GridPosition, F>,
+    ) {
+        if !cooldown.is_finished() {
+            return;
+        }
+        let Some(direction) = held else {
+            // Let go before the cooldown ran out: the dash is dropped
+            self.pending = None;
+            return;
+        };
+        let tiles = if self.take_dash(direction) { 2 } else { 1 };
+
+        // A dash stops early at the edge of the player area
+        let moved = (0..tiles)
+            .take_while(|_| step(pos, direction, ownership, obstacles))
+            .count();
+        if moved > 0 {
+            let secs = if moved > 1 {
+                DASH_COOLDOWN
+            } else {
+                MOVE_COOLDOWN
+            };
+            cooldown.set_duration(Duration::from_secs_f32(secs));
+            cooldown.reset();
+        }
+    }
+}
+
+/// When the next dodge is allowed (seconds since startup)
+#[derive(Debug, Default)]
+pub struct DodgeCooldown {
+    ready_at: f32,
+}
 
-/// Player movement system - handles WASD/Arrow key input and Gamepad
+impl DodgeCooldown {
+    /// Sidestep `pos` (in the `held` direction, else to a free row) if the
+    /// last dodge has cooled down by `now`. Dodging ignores the move cooldown,
+    /// but restarts it. Returns whether it dodged.
+    pub fn dodge<F: QueryFilter>(
+        &mut self,
+        now: f32,
+        pos: &mut GridPosition,
+        held: Option<IVec2>,
+        cooldown: &mut Timer,
+        ownership: &TileOwnership,
+        obstacles: &Query<&GridPosition, F>,
+    ) -> bool {
+        if now < self.ready_at {
+            return false;
+        }
+        self.ready_at = now + DODGE_COOLDOWN;
+        let directions = match held {
+            Some(direction) => vec![direction],
+            None => vec![IVec2::Y, IVec2::NEG_Y],
+        };
+        for direction in directions {
+            if step(pos, direction, ownership, obstacles) {
+                break;
+            }
+        }
+        cooldown.set_duration(Duration::from_secs_f32(MOVE_COOLDOWN));
+        cooldown.reset();
+        true
+    }
+}
+
+/// Player movement system - handles the bound movement keys/buttons.
+/// Double-tapping a direction dashes two tiles, followed by `DASH_COOLDOWN`.
+/// Dodge sidesteps (in the held direction, else to a free row) with
+/// `DODGE_IFRAMES` of invisibility, once per `DODGE_COOLDOWN`.
 pub fn move_player(
+    mut commands: Commands,
     keyboard_input: Res<ButtonInput<KeyCode>>,
     gamepads: Query<&Gamepad>,
+    bindings: Res<KeyBindings>,
     time: Res<Time>,
     mut cooldown: ResMut<InputCooldown>,
-    mut query: Query<&mut GridPosition, With<Player>>,
+    mut dash: Local<DashInput>,
+    mut dodge: Local<DodgeCooldown>,
+    ownership: Res<TileOwnership>,
+    mut query: Query<(Entity, &mut GridPosition, Option<&ActiveShield>), With<Player>>,
+    obstacle_query: Query<&GridPosition, (With<Obstacle>, Without<Player>)>,
 ) {
     cooldown.0.tick(time.delta());
 
-    if !cooldown.0.is_finished() {
-        return;
+    // Taps count even during the cooldown, so a quick double-tap still dashes
+    let just_pressed = |action| bindings.just_pressed(action, &keyboard_input, &gamepads);
+    if let Some(direction) = move_direction(just_pressed) {
+        dash.tap(direction, time.elapsed_secs());
     }
 
-    let mut moved = false;
-    let mut direction = IVec2::ZERO;
-
-    // Keyboard Input
-    if keyboard_input.pressed(KeyCode::KeyW) || keyboard_input.pressed(KeyCode::ArrowUp) {
-        direction.y += 1;
-        moved = true;
-    } else if keyboard_input.pressed(KeyCode::KeyS) || keyboard_input.pressed(KeyCode::ArrowDown) {
-        direction.y -= 1;
-        moved = true;
-    } else if keyboard_input.pressed(KeyCode::KeyA) || keyboard_input.pressed(KeyCode::ArrowLeft) {
-        direction.x -= 1;
-        moved = true;
-    } else if keyboard_input.pressed(KeyCode::KeyD) || keyboard_input.pressed(KeyCode::ArrowRight) {
-        direction.x += 1;
-        moved = true;
-    }
+    // Keyboard and gamepad input (first bound direction wins)
+    let pressed = |action| bindings.pressed(action, &keyboard_input, &gamepads);
 
-    // Gamepad Input (override/add to keyboard)
-    if !moved {
-        for gamepad in gamepads.iter() {
-            if gamepad.pressed(GamepadButton::DPadUp) {
-                direction.y += 1;
-                moved = true;
-            } else if gamepad.pressed(GamepadButton::DPadDown) {
-                direction.y -= 1;
-                moved = true;
-            } else if gamepad.pressed(GamepadButton::DPadLeft) {
-                direction.x -= 1;
-                moved = true;
-            } else if gamepad.pressed(GamepadButton::DPadRight) {
-                direction.x += 1;
-                moved = true;
+    let held = move_direction(pressed);
+    if just_pressed(InputAction::Dodge) {
+        let now = time.elapsed_secs();
+        let mut dodged = false;
+        for (entity, mut pos, shield) in &mut query {
+            if !dodge.dodge(
+                now,
+                &mut pos,
+                held,
+                &mut cooldown.0,
+                &ownership,
+                &obstacle_query,
+            ) {
+                continue;
+            }
+            dodged = true;
+            // A chip shield that is already up is kept, as is a longer invisibility
+            let keep = shield.is_some_and(|shield| {
+                !shield.dodges() || shield.duration_timer.remaining_secs() > DODGE_IFRAMES
+            });
+            if !keep {
+                commands.entity(entity).insert(ActiveShield {
+                    duration_timer: Timer::from_seconds(DODGE_IFRAMES, TimerMode::Once),
+                    damage_threshold: None,
+                    shield_type: ShieldType::Invis,
+                });
             }
         }
+        if dodged {
+            return;
+        }
     }
 
-    if moved {
-        for mut pos in &mut query {
-            let new_x = pos.x + direction.x;
-            let new_y = pos.y + direction.y;
+    for (_, mut pos, _) in &mut query {
+        dash.walk(&mut pos, held, &mut cooldown.0, &ownership, &obstacle_query);
+    }
+}
 
-            if (0..GRID_HEIGHT).contains(&new_y) && (0..PLAYER_AREA_WIDTH).contains(&new_x) {
-                pos.x = new_x;
-                pos.y = new_y;
-                cooldown.0.reset();
-            }
-        }
+/// Direction of the first held movement action, if any
+pub fn move_direction(pressed: impl Fn(InputAction) -> bool) -> Option<IVec2> {
+    if pressed(InputAction::MoveUp) {
+        Some(IVec2::Y)
+    } else if pressed(InputAction::MoveDown) {
+        Some(IVec2::NEG_Y)
+    } else if pressed(InputAction::MoveLeft) {
+        Some(IVec2::NEG_X)
+    } else if pressed(InputAction::MoveRight) {
+        Some(IVec2::X)
+    } else {
+        None
+    }
+}
+
+/// Step `pos` one tile in `direction` if it lands on a free player tile.
+/// Returns whether it moved.
+pub fn step<'a>(
+    pos: &mut GridPosition,
+    direction: IVec2,
+    ownership: &TileOwnership,
+    obstacles: impl IntoIterator<Item = &'a GridPosition>,
+) -> bool {
+    let new_x = pos.x + direction.x;
+    let new_y = pos.y + direction.y;
+
+    let blocked = obstacles
+        .into_iter()
+        .any(|obstacle| obstacle.x == new_x && obstacle.y == new_y);
+
+    if ownership.is_player_tile(new_x, new_y) && !blocked {
+        pos.x = new_x;
+        pos.y = new_y;
+        true
+    } else {
+        false
     }
 }
 
 // NOTE: Shooting is now handled by the weapon system in src/weapons/mod.rs
 // The player_shoot function has been removed and replaced with weapon_input_system
+
+/// World with the resources `move_player` reads: time, keyboard, default
+/// bindings, tile ownership and a move cooldown
+#[cfg(test)]
+pub fn player_test_world() -> World {
+    let mut world = World::new();
+    world.init_resource::<Time>();
+    world.init_resource::<ButtonInput<KeyCode>>();
+    world.init_resource::<KeyBindings>();
+    world.init_resource::<TileOwnership>();
+    world.insert_resource(InputCooldown(Timer::from_seconds(
+        MOVE_COOLDOWN,
+        TimerMode::Once,
+    )));
+    world
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resources::{GridSize, TileOwner};
+
+    /// Tap `key` once the move cooldown has run out and return the player's tile
+    fn tap(world: &mut World, schedule: &mut Schedule, key: KeyCode) -> GridPosition {
+        let mut keyboard = world.resource_mut::<ButtonInput<KeyCode>>();
+        keyboard.reset_all();
+        keyboard.press(key);
+        world
+            .resource_mut::<Time>()
+            .advance_by(Duration::from_secs_f32(MOVE_COOLDOWN));
+        schedule.run(world);
+        *world
+            .query_filtered::<&GridPosition, With<Player>>()
+            .single(world)
+            .unwrap()
+    }
+
+    #[test]
+    fn player_can_step_onto_a_tile_it_gains() {
+        let mut world = player_test_world();
+        world.spawn((Player, GridPosition { x: 2, y: 1 }));
+        let mut schedule = Schedule::default();
+        schedule.add_systems(move_player);
+
+        assert_eq!(
+            tap(&mut world, &mut schedule, KeyCode::KeyD),
+            GridPosition { x: 2, y: 1 }
+        );
+
+        let mut ownership = world.resource_mut::<TileOwnership>();
+        ownership.set(3, 1, TileOwner::Player);
+        assert_eq!(
+            tap(&mut world, &mut schedule, KeyCode::KeyD),
+            GridPosition { x: 3, y: 1 }
+        );
+
+        // Losing a tile closes it again
+        let mut ownership = world.resource_mut::<TileOwnership>();
+        ownership.set(2, 1, TileOwner::Enemy);
+        assert_eq!(
+            tap(&mut world, &mut schedule, KeyCode::KeyA),
+            GridPosition { x: 3, y: 1 }
+        );
+    }
+
+    /// Player x after pressing left twice, `gap` seconds apart, on a wide player area
+    fn left_twice(gap: f32) -> Vec<i32> {
+        let mut world = player_test_world();
+        world.insert_resource(TileOwnership::new(GridSize {
+            width: 8,
+            height: 3,
+            player_area_width: 6,
+        }));
+        world.spawn((Player, GridPosition { x: 5, y: 1 }));
+        let mut schedule = Schedule::default();
+        schedule.add_systems(move_player);
+
+        let mut frame = |world: &mut World, held: bool, secs: f32| {
+            let mut keyboard = world.resource_mut::<ButtonInput<KeyCode>>();
+            keyboard.reset_all();
+            if held {
+                keyboard.press(KeyCode::KeyA);
+            }
+            world
+                .resource_mut::<Time>()
+                .advance_by(Duration::from_secs_f32(secs));
+            schedule.run(world);
+            world
+                .query_filtered::<&GridPosition, With<Player>>()
+                .single(world)
+                .unwrap()
+                .x
+        };
+
+        vec![
+            frame(&mut world, true, MOVE_COOLDOWN),
+            frame(&mut world, false, 0.05),
+            frame(&mut world, true, gap - 0.05),
+        ]
+    }
+
+    #[test]
+    fn double_tapping_a_direction_dashes_two_tiles() {
+        assert_eq!(left_twice(0.2), vec![4, 4, 2]);
+        assert_eq!(left_twice(0.5), vec![4, 4, 3]);
+    }
+
+    #[test]
+    fn dodge_dodges_bullets_and_waits_for_its_cooldown() {
+        let mut world = player_test_world();
+        crate::systems::rumble::init_hit_feedback(&mut world);
+        let player = world
+            .spawn((
+                Player,
+                GridPosition { x: 1, y: 1 },
+                Health {
+                    current: 100,
+                    max: 100,
+                },
+            ))
+            .id();
+        let mut schedule = Schedule::default();
+        schedule
+            .add_systems((move_player, crate::systems::combat::enemy_bullet_hit_player).chain());
+        let dodge_after = |world: &mut World, schedule: &mut Schedule, secs: f32| {
+            let mut keyboard = world.resource_mut::<ButtonInput<KeyCode>>();
+            keyboard.reset_all();
+            keyboard.press(KeyCode::ShiftLeft);
+            world
+                .resource_mut::<Time>()
+                .advance_by(Duration::from_secs_f32(secs));
+            schedule.run(world);
+            *world.get::<GridPosition>(player).unwrap()
+        };
+
+        // No direction held: sidestep to a free row
+        assert_eq!(
+            dodge_after(&mut world, &mut schedule, 0.0),
+            GridPosition { x: 1, y: 2 }
+        );
+        let shield = world.get::<ActiveShield>(player).unwrap();
+        assert!(shield.dodges());
+
+        // A bullet on the player's tile passes through during the i-frames
+        world.spawn((GridPosition { x: 1, y: 2 }, EnemyBullet::new(30)));
+        schedule.run(&mut world);
+        assert_eq!(world.get::<Health>(player).unwrap().current, 100);
+
+        // Still cooling down: no sidestep
+        assert_eq!(
+            dodge_after(&mut world, &mut schedule, DODGE_COOLDOWN / 2.0),
+            GridPosition { x: 1, y: 2 }
+        );
+
+        // Without the i-frames the same bullet lands
+        world.entity_mut(player).remove::<ActiveShield>();
+        schedule.run(&mut world);
+        assert_eq!(world.get::<Health>(player).unwrap().current, 70);
+
+        assert_eq!(
+            dodge_after(&mut world, &mut schedule, DODGE_COOLDOWN / 2.0),
+            GridPosition { x: 1, y: 1 }
+        );
+        assert!(world.get::<ActiveShield>(player).is_some());
+    }
+
+    #[test]
+    fn dodging_keeps_a_longer_invisibility() {
+        let mut world = player_test_world();
+        let player = world
+            .spawn((
+                Player,
+                GridPosition { x: 1, y: 1 },
+                ActiveShield {
+                    duration_timer: Timer::from_seconds(3.0, TimerMode::Once),
+                    damage_threshold: None,
+                    shield_type: ShieldType::Invis,
+                },
+            ))
+            .id();
+        world
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(KeyCode::ShiftLeft);
+        let mut schedule = Schedule::default();
+        schedule.add_systems(move_player);
+        schedule.run(&mut world);
+
+        assert_eq!(
+            *world.get::<GridPosition>(player).unwrap(),
+            GridPosition { x: 1, y: 2 }
+        );
+        let shield = world.get::<ActiveShield>(player).unwrap();
+        assert_eq!(shield.duration_timer.remaining_secs(), 3.0);
+    }
+}
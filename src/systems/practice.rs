@@ -0,0 +1,214 @@
+//! Practice mode: a sandbox battle against a respawning dummy with a DPS readout.
+//!
+//! Started from the main menu with `ArenaConfig::practice`. Victory and defeat
+//! checks skip practice arenas; `respawn_practice_dummy` puts the dummy back as
+//! soon as it is deleted, and damage taken by dummies feeds the `DpsMeter`.
+
+use bevy::prelude::*;
+
+use crate::components::{ArenaConfig, CleanupOnStateExit, Enemy, GameState, Health};
+use crate::constants::*;
+use crate::enemies::EnemyReward;
+use crate::resources::{ArenaLayout, BattleTimer, Difficulty, WaveState};
+use crate::systems::setup::spawn_enemy;
+
+/// Shortest window the average is taken over, so the first hit doesn't read as a spike
+const DPS_MIN_WINDOW: f32 = 1.0;
+
+/// Damage dealt to practice dummies, averaged over battle time
+#[derive(Resource, Debug, Default, Clone, Copy)]
+pub struct DpsMeter {
+    pub total_damage: i32,
+    /// Battle time of the first recorded hit
+    pub started_at: Option<f32>,
+}
+
+impl DpsMeter {
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Record a hit landing at battle time `now`
+    pub fn record(&mut self, damage: i32, now: f32) {
+        if damage <= 0 {
+            return;
+        }
+        self.started_at.get_or_insert(now);
+        self.total_damage += damage;
+    }
+
+    /// Average damage per second between the first hit and `now`
+    pub fn dps(&self, now: f32) -> f32 {
+        match self.started_at {
+            Some(start) => self.total_damage as f32 / (now - start).max(DPS_MIN_WINDOW),
+            None => 0.0,
+        }
+    }
+}
+
+/// Enemy in a practice arena; remembers its HP to measure the damage it takes
+#[derive(Component)]
+pub struct PracticeDummy {
+    pub last_hp: i32,
+}
+
+/// DPS readout text marker
+#[derive(Component)]
+pub struct DpsText;
+
+/// Run condition: the current arena is a practice sandbox
+pub fn practice_active(config: Option<Res<ArenaConfig>>) -> bool {
+    config.is_some_and(|config| config.practice)
+}
+
+/// Clear the meter and, in practice arenas, spawn the DPS readout
+pub fn setup_practice(
+    mut commands: Commands,
+    config: Res<ArenaConfig>,
+    mut meter: ResMut<DpsMeter>,
+) {
+    meter.reset();
+    if !config.practice {
+        return;
+    }
+
+    commands.spawn((
+        Text2d::new("DPS: 0.0"),
+        TextLayout::new_with_justify(Justify::Left),
+        TextFont::from_font_size(24.0),
+        TextColor(COLOR_TEXT),
+        Transform::from_xyz(-580.0, 270.0, Z_UI),
+        DpsText,
+        CleanupOnStateExit(GameState::Playing),
+    ));
+}
+
+/// Tag new enemies as dummies (they drop no zenny) and record the HP they lost this frame
+pub fn track_practice_damage(
+    mut commands: Commands,
+    battle_timer: Res<BattleTimer>,
+    mut meter: ResMut<DpsMeter>,
+    new_query: Query<(Entity, &Health), (With<Enemy>, Without<PracticeDummy>)>,
+    mut dummy_query: Query<(&Health, &mut PracticeDummy)>,
+) {
+    for (entity, health) in &new_query {
+        commands.entity(entity).insert((
+            PracticeDummy {
+                last_hp: health.current,
+            },
+            EnemyReward(0),
+        ));
+    }
+
+    for (health, mut dummy) in &mut dummy_query {
+        if health.current != dummy.last_hp {
+            meter.record(dummy.last_hp - health.current, battle_timer.elapsed);
+            dummy.last_hp = health.current;
+        }
+    }
+}
+
+/// Record the killing blow, which despawns the dummy before `track_practice_damage` sees it
+pub fn record_dummy_deletion(
+    despawn: On<Despawn, PracticeDummy>,
+    dummy_query: Query<(&Health, &PracticeDummy)>,
+    battle_timer: Res<BattleTimer>,
+    mut meter: ResMut<DpsMeter>,
+) {
+    if let Ok((health, dummy)) = dummy_query.get(despawn.entity) {
+        meter.record(dummy.last_hp - health.current, battle_timer.elapsed);
+    }
+}
+
+/// Bring the dummies back as soon as the field is empty
+pub fn respawn_practice_dummy(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
+    layout: Res<ArenaLayout>,
+    config: Res<ArenaConfig>,
+    wave_state: Res<WaveState>,
+    enemy_query: Query<Entity, With<Enemy>>,
+) {
+    if *wave_state != WaveState::Active || !enemy_query.is_empty() {
+        return;
+    }
+
+    for enemy_config in config.waves.first().into_iter().flatten() {
+        spawn_enemy(
+            &mut commands,
+            &asset_server,
+            &mut atlas_layouts,
+            enemy_config,
+            0,
+            &layout,
+            // Dummies ignore difficulty so DPS numbers stay comparable
+            Difficulty::Normal,
+        );
+    }
+}
+
+/// Keep the DPS readout in sync with the meter
+pub fn update_dps_text(
+    battle_timer: Res<BattleTimer>,
+    meter: Res<DpsMeter>,
+    mut query: Query<&mut Text2d, With<DpsText>>,
+) {
+    for mut text in &mut query {
+        text.0 = format!(
+            "DPS: {:.1}  (total {})",
+            meter.dps(battle_timer.elapsed),
+            meter.total_damage
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dps_averages_hits_over_elapsed_time() {
+        let mut meter = DpsMeter::default();
+        assert_eq!(meter.dps(5.0), 0.0);
+
+        meter.record(30, 1.0);
+        // The first hit alone is spread over the minimum window
+        assert_eq!(meter.dps(1.0), 30.0 / DPS_MIN_WINDOW);
+
+        meter.record(50, 3.0);
+        meter.record(0, 4.0);
+        assert_eq!(meter.total_damage, 80);
+        assert_eq!(meter.dps(5.0), 20.0);
+    }
+
+    #[test]
+    fn empty_practice_arena_respawns_the_dummy() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+            .init_asset::<Image>()
+            .init_asset::<TextureAtlasLayout>()
+            .init_resource::<ArenaLayout>()
+            .insert_resource(WaveState::Active)
+            .insert_resource(ArenaConfig::practice(Vec::new()))
+            .add_systems(Update, respawn_practice_dummy);
+
+        let dummies = |app: &mut App| {
+            let world = app.world_mut();
+            world
+                .query_filtered::<Entity, With<Enemy>>()
+                .iter(world)
+                .collect::<Vec<_>>()
+        };
+
+        app.update();
+        let first = dummies(&mut app);
+        assert_eq!(first.len(), 1);
+        app.update();
+        assert_eq!(dummies(&mut app), first);
+
+        app.world_mut().despawn(first[0]);
+        app.update();
+        assert_eq!(dummies(&mut app).len(), 1);
+    }
+}
@@ -0,0 +1,376 @@
+// ============================================================================
+// Input Replay - Record a battle's inputs and race a ghost against them
+// ============================================================================
+//
+// F5 during a battle cycles the replay mode (Off -> Record -> Ghost) for the
+// next battle:
+// - Record: `InputRecorder` logs the player's held inputs with timestamps, and
+//   a cleared battle's log is written to replay.json (see `save::save_replay`)
+// - Ghost: the saved log drives a translucent `GhostPlayer` around the
+//   player's side of the field for speedrun comparison
+//
+// A log only stores the frames where the held inputs changed; playback holds
+// each snapshot until the next one is due.
+
+use bevy::prelude::*;
+use bevy::sprite::Anchor;
+use serde::{Deserialize, Serialize};
+
+use crate::assets::FighterSprites;
+use crate::bindings::{InputAction, KeyBindings};
+use crate::components::{
+    ArenaConfig, CleanupOnStateExit, GameState, GridPosition, Obstacle, RenderConfig,
+};
+use crate::constants::{
+    CHARACTER_OFFSET, FIGHTER_ANCHOR, FIGHTER_DRAW_SIZE, MOVE_COOLDOWN, Z_CHARACTER,
+};
+use crate::resources::{ArenaLayout, SaveSlot, TileOwnership, WaveState};
+use crate::save::{load_replay, save_replay};
+use crate::systems::player::{DashInput, DodgeCooldown, move_direction};
+
+/// Sprite tint of the ghost player
+const GHOST_COLOR: Color = Color::srgba(0.6, 0.9, 1.0, 0.4);
+
+/// Player inputs held during one frame
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct InputSnapshot {
+    pub pressed: Vec<InputAction>,
+}
+
+impl InputSnapshot {
+    pub fn capture(
+        bindings: &KeyBindings,
+        keyboard: &ButtonInput<KeyCode>,
+        gamepads: &Query<&Gamepad>,
+    ) -> Self {
+        Self {
+            pressed: InputAction::ALL
+                .into_iter()
+                .filter(|&action| bindings.pressed(action, keyboard, gamepads))
+                .collect(),
+        }
+    }
+
+    pub fn is_pressed(&self, action: InputAction) -> bool {
+        self.pressed.contains(&action)
+    }
+}
+
+/// Recorded inputs: seconds since the battle started, and what was held from then on
+pub type InputLog = Vec<(f32, InputSnapshot)>;
+
+/// What the next battle does with replays
+#[derive(Resource, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ReplayMode {
+    #[default]
+    Off,
+    Record,
+    Ghost,
+}
+
+impl ReplayMode {
+    fn next(self) -> Self {
+        match self {
+            ReplayMode::Off => ReplayMode::Record,
+            ReplayMode::Record => ReplayMode::Ghost,
+            ReplayMode::Ghost => ReplayMode::Off,
+        }
+    }
+}
+
+/// Present while the current battle's inputs are being recorded
+#[derive(Resource, Debug, Default)]
+pub struct InputRecorder {
+    pub elapsed: f32,
+    pub log: InputLog,
+}
+
+impl InputRecorder {
+    /// Log `snapshot` at the current time if it differs from the last one
+    pub fn record(&mut self, snapshot: InputSnapshot) {
+        let unchanged = match self.log.last() {
+            Some((_, last)) => *last == snapshot,
+            None => snapshot == InputSnapshot::default(),
+        };
+        if !unchanged {
+            self.log.push((self.elapsed, snapshot));
+        }
+    }
+}
+
+/// Present while a ghost is replaying a recorded log
+#[derive(Resource, Debug, Default)]
+pub struct GhostPlayback {
+    pub elapsed: f32,
+    pub log: InputLog,
+    /// Number of log entries already due
+    cursor: usize,
+}
+
+impl GhostPlayback {
+    pub fn new(log: InputLog) -> Self {
+        Self { log, ..default() }
+    }
+
+    /// Inputs held at the current time
+    pub fn current(&mut self) -> InputSnapshot {
+        while self
+            .log
+            .get(self.cursor)
+            .is_some_and(|(at, _)| *at <= self.elapsed)
+        {
+            self.cursor += 1;
+        }
+        self.cursor
+            .checked_sub(1)
+            .map(|index| self.log[index].1.clone())
+            .unwrap_or_default()
+    }
+}
+
+/// Translucent player driven by `GhostPlayback`, with its own move cooldown
+#[derive(Component, Debug)]
+pub struct GhostPlayer {
+    pub cooldown: Timer,
+    dash: DashInput,
+    dodge: DodgeCooldown,
+    /// Inputs held last frame, to tell fresh taps from held keys
+    previous: InputSnapshot,
+}
+
+impl Default for GhostPlayer {
+    fn default() -> Self {
+        Self {
+            cooldown: Timer::from_seconds(MOVE_COOLDOWN, TimerMode::Once),
+            dash: DashInput::default(),
+            dodge: DodgeCooldown::default(),
+            previous: InputSnapshot::default(),
+        }
+    }
+}
+
+// ============================================================================
+// Systems
+// ============================================================================
+
+/// Cycle the replay mode with F5 (applies from the next battle)
+pub fn toggle_replay_mode(keyboard: Res<ButtonInput<KeyCode>>, mut mode: ResMut<ReplayMode>) {
+    if keyboard.just_pressed(KeyCode::F5) {
+        *mode = mode.next();
+        info!("Replay mode for the next battle: {:?}", *mode);
+    }
+}
+
+/// Start recording, or spawn the ghost from the saved replay
+pub fn start_replay(
+    mut commands: Commands,
+    mode: Res<ReplayMode>,
+    config: Res<ArenaConfig>,
+    layout: Res<ArenaLayout>,
+    sprites: Res<FighterSprites>,
+    slot: Res<SaveSlot>,
+) {
+    match *mode {
+        ReplayMode::Off => {}
+        ReplayMode::Record => commands.insert_resource(InputRecorder::default()),
+        ReplayMode::Ghost => {
+            let Some(log) = load_replay(*slot) else {
+                warn!("No saved replay to race against");
+                return;
+            };
+            commands.insert_resource(GhostPlayback::new(log));
+            commands.spawn((
+                Sprite {
+                    image: sprites.idle.clone(),
+                    texture_atlas: Some(sprites.layout.clone().into()),
+                    color: GHOST_COLOR,
+                    custom_size: Some(layout.scale_vec2(FIGHTER_DRAW_SIZE)),
+                    ..default()
+                },
+                Anchor(FIGHTER_ANCHOR),
+                Transform::default(),
+                GridPosition {
+                    x: config.fighter.start_x,
+                    y: config.fighter.start_y,
+                },
+                RenderConfig {
+                    offset: CHARACTER_OFFSET,
+                    base_z: Z_CHARACTER - 0.5,
+                },
+                GhostPlayer::default(),
+                CleanupOnStateExit(GameState::Playing),
+            ));
+        }
+    }
+}
+
+/// Log this frame's held inputs
+pub fn record_player_input(
+    time: Res<Time>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<&Gamepad>,
+    bindings: Res<KeyBindings>,
+    recorder: Option<ResMut<InputRecorder>>,
+) {
+    let Some(mut recorder) = recorder else {
+        return;
+    };
+    recorder.elapsed += time.delta_secs();
+    recorder.record(InputSnapshot::capture(&bindings, &keyboard, &gamepads));
+}
+
+/// Move the ghost with the recorded inputs, under the same rules as `move_player`
+pub fn drive_ghost(
+    time: Res<Time>,
+    playback: Option<ResMut<GhostPlayback>>,
+    ownership: Res<TileOwnership>,
+    mut ghost_query: Query<(&mut GridPosition, &mut GhostPlayer)>,
+    obstacle_query: Query<&GridPosition, (With<Obstacle>, Without<GhostPlayer>)>,
+) {
+    let Some(mut playback) = playback else {
+        return;
+    };
+    playback.elapsed += time.delta_secs();
+    let snapshot = playback.current();
+
+    for (mut pos, mut ghost) in &mut ghost_query {
+        let ghost = &mut *ghost;
+        ghost.cooldown.tick(time.delta());
+
+        let just_pressed =
+            |action| snapshot.is_pressed(action) && !ghost.previous.is_pressed(action);
+        let dodging = just_pressed(InputAction::Dodge);
+        if let Some(direction) = move_direction(just_pressed) {
+            ghost.dash.tap(direction, time.elapsed_secs());
+        }
+        ghost.previous = snapshot.clone();
+
+        let held = move_direction(|action| snapshot.is_pressed(action));
+        let dodged = dodging
+            && ghost.dodge.dodge(
+                time.elapsed_secs(),
+                &mut pos,
+                held,
+                &mut ghost.cooldown,
+                &ownership,
+                &obstacle_query,
+            );
+        if !dodged {
+            ghost.dash.walk(
+                &mut pos,
+                held,
+                &mut ghost.cooldown,
+                &ownership,
+                &obstacle_query,
+            );
+        }
+    }
+}
+
+/// Save a cleared battle's recording and stop any replay
+pub fn finish_replay(
+    mut commands: Commands,
+    recorder: Option<Res<InputRecorder>>,
+    wave_state: Res<WaveState>,
+    slot: Res<SaveSlot>,
+) {
+    if let Some(recorder) = recorder {
+        if *wave_state == WaveState::Cleared {
+            save_replay(*slot, &recorder.log);
+        }
+    }
+    commands.remove_resource::<InputRecorder>();
+    commands.remove_resource::<GhostPlayback>();
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::components::Player;
+    use crate::systems::player::{move_player, player_test_world};
+
+    const FRAME: f32 = 1.0 / 20.0;
+
+    /// Held key per frame: right, a pause, up, then left, with gaps between,
+    /// a dash back to the right and a dodge
+    fn script() -> Vec<Option<KeyCode>> {
+        let hold = |key, frames| vec![key; frames];
+        [
+            hold(None, 4),
+            hold(Some(KeyCode::KeyD), 2),
+            hold(None, 5),
+            hold(Some(KeyCode::KeyW), 1),
+            hold(None, 3),
+            hold(Some(KeyCode::KeyA), 6),
+            // Double-tap right (the first tap lands during the move cooldown)
+            hold(Some(KeyCode::KeyD), 1),
+            hold(None, 1),
+            hold(Some(KeyCode::KeyD), 3),
+            hold(None, 2),
+            // Dodge with no direction held
+            hold(Some(KeyCode::ShiftLeft), 2),
+            hold(None, 2),
+        ]
+        .concat()
+    }
+
+    fn advance(world: &mut World) {
+        world
+            .resource_mut::<Time>()
+            .advance_by(Duration::from_secs_f32(FRAME));
+    }
+
+    fn position<F: bevy::ecs::query::QueryFilter>(world: &mut World) -> GridPosition {
+        *world
+            .query_filtered::<&GridPosition, F>()
+            .single(world)
+            .unwrap()
+    }
+
+    #[test]
+    fn replaying_a_recording_retraces_the_players_path() {
+        let mut world = player_test_world();
+        world.init_resource::<InputRecorder>();
+        world.spawn((Player, GridPosition { x: 1, y: 1 }));
+        let mut schedule = Schedule::default();
+        schedule.add_systems((record_player_input, move_player));
+
+        let mut recorded = Vec::new();
+        for key in script() {
+            let mut keyboard = world.resource_mut::<ButtonInput<KeyCode>>();
+            keyboard.reset_all();
+            if let Some(key) = key {
+                keyboard.press(key);
+            }
+            advance(&mut world);
+            schedule.run(&mut world);
+            recorded.push(position::<With<Player>>(&mut world));
+        }
+        assert_ne!(recorded.first(), recorded.last());
+        let dashed = recorded
+            .windows(2)
+            .any(|pair| (pair[1].x - pair[0].x).abs() == 2);
+        assert!(dashed, "the script should dash: {recorded:?}");
+        // The dodge sidesteps from the dash's tile to a free row
+        assert_eq!(recorded.last(), Some(&GridPosition { x: 2, y: 1 }));
+
+        let log = world.resource::<InputRecorder>().log.clone();
+        let mut world = World::new();
+        world.init_resource::<Time>();
+        world.init_resource::<TileOwnership>();
+        world.insert_resource(GhostPlayback::new(log));
+        world.spawn((GhostPlayer::default(), GridPosition { x: 1, y: 1 }));
+        let mut schedule = Schedule::default();
+        schedule.add_systems(drive_ghost);
+
+        let mut replayed = Vec::new();
+        for _ in script() {
+            advance(&mut world);
+            schedule.run(&mut world);
+            replayed.push(position::<With<GhostPlayer>>(&mut world));
+        }
+        assert_eq!(replayed, recorded);
+    }
+}
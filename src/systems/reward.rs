@@ -0,0 +1,320 @@
+// ============================================================================
+// Reward Screen - Pick one of three chips after a campaign win
+// ============================================================================
+//
+// `check_outro_complete` rolls the offer with `roll_reward_choices` and moves
+// to `GameState::Reward`. Left/Right picks a card, confirm adds the chip to
+// `OwnedChips`, saves, and returns to the campaign map.
+
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::actions::{ActionBlueprint, ActionId, Element, Rarity};
+use crate::components::{CleanupOnStateExit, GameState};
+use crate::resources::{
+    CampaignProgress, ColorScheme, LoadoutPresets, OwnedChips, PlayerCurrency, PlayerLoadout,
+    PlayerUpgrades,
+};
+use crate::systems::autosave::AutoSave;
+use crate::systems::growth::GrowthTreeState;
+use crate::systems::loadout::{element_color, get_all_actions, rarity_color, rarity_stars};
+use crate::weapons::PlayerWeapons;
+
+/// Number of chips offered after a win
+pub const REWARD_CHOICES: usize = 3;
+
+const CARD_BG: Color = Color::srgba(0.08, 0.08, 0.15, 0.95);
+const CARD_BORDER_NORMAL: Color = Color::srgb(0.3, 0.4, 0.6);
+const CARD_BORDER_SELECTED: Color = Color::srgb(1.0, 0.85, 0.3);
+
+/// Relative drop weight of a rarity. Normal battles never drop
+/// SuperRare or UltraRare chips; bosses lean towards the rare end.
+pub fn reward_weight(rarity: Rarity, boss: bool) -> u32 {
+    match (rarity, boss) {
+        (Rarity::Common, false) => 60,
+        (Rarity::Uncommon, false) => 30,
+        (Rarity::Rare, false) => 10,
+        (Rarity::SuperRare | Rarity::UltraRare, false) => 0,
+        (Rarity::Common, true) => 10,
+        (Rarity::Uncommon, true) => 30,
+        (Rarity::Rare, true) => 35,
+        (Rarity::SuperRare, true) => 20,
+        (Rarity::UltraRare, true) => 5,
+    }
+}
+
+/// Roll up to `REWARD_CHOICES` distinct unowned chips, weighted by rarity.
+/// Returns fewer (or none) once the eligible pool runs dry.
+pub fn roll_reward_choices(owned: &OwnedChips, boss: bool, rng: &mut impl Rng) -> Vec<ActionId> {
+    let mut pool: Vec<(ActionId, u32)> = get_all_actions()
+        .into_iter()
+        .filter(|id| !owned.contains(*id))
+        .map(|id| (id, reward_weight(ActionBlueprint::get(id).rarity, boss)))
+        .filter(|(_, weight)| *weight > 0)
+        .collect();
+
+    let mut choices = Vec::with_capacity(REWARD_CHOICES);
+    while choices.len() < REWARD_CHOICES && !pool.is_empty() {
+        let total: u32 = pool.iter().map(|(_, weight)| weight).sum();
+        let mut roll = rng.random_range(0..total);
+        let index = pool
+            .iter()
+            .position(|(_, weight)| {
+                if roll < *weight {
+                    true
+                } else {
+                    roll -= weight;
+                    false
+                }
+            })
+            .unwrap_or(0);
+        choices.push(pool.swap_remove(index).0);
+    }
+    choices
+}
+
+/// Chips offered on the reward screen
+#[derive(Resource, Debug, Clone)]
+pub struct RewardOffer {
+    pub choices: Vec<ActionId>,
+    pub cursor: usize,
+}
+
+impl RewardOffer {
+    pub fn new(choices: Vec<ActionId>) -> Self {
+        Self { choices, cursor: 0 }
+    }
+}
+
+/// Marker for a reward card (index into `RewardOffer::choices`)
+#[derive(Component)]
+pub struct RewardCard {
+    pub index: usize,
+}
+
+// ============================================================================
+// Systems
+// ============================================================================
+
+pub fn setup_reward(
+    mut commands: Commands,
+    offer: Res<RewardOffer>,
+    color_scheme: Res<ColorScheme>,
+) {
+    commands
+        .spawn((
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(24.0),
+                ..default()
+            },
+            BackgroundColor(Color::srgb(0.02, 0.02, 0.08)),
+            CleanupOnStateExit(GameState::Reward),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new("CHIP REWARD"),
+                TextFont::from_font_size(60.0),
+                TextColor(Color::srgb(0.3, 0.8, 1.0)),
+            ));
+
+            parent
+                .spawn(Node {
+                    flex_direction: FlexDirection::Row,
+                    column_gap: Val::Px(24.0),
+                    ..default()
+                })
+                .with_children(|row| {
+                    for (index, action_id) in offer.choices.iter().enumerate() {
+                        spawn_reward_card(row, index, *action_id, *color_scheme);
+                    }
+                });
+
+            parent.spawn((
+                Text::new("Left/Right: Select  |  Enter / A: Take chip"),
+                TextFont::from_font_size(18.0),
+                TextColor(Color::srgba(0.6, 0.6, 0.6, 0.8)),
+            ));
+        });
+}
+
+fn spawn_reward_card(
+    parent: &mut ChildSpawnerCommands,
+    index: usize,
+    action_id: ActionId,
+    color_scheme: ColorScheme,
+) {
+    let blueprint = ActionBlueprint::get(action_id);
+
+    parent
+        .spawn((
+            Node {
+                width: Val::Px(260.0),
+                min_height: Val::Px(240.0),
+                flex_direction: FlexDirection::Column,
+                padding: UiRect::all(Val::Px(16.0)),
+                row_gap: Val::Px(10.0),
+                border: UiRect::all(Val::Px(3.0)),
+                ..default()
+            },
+            BackgroundColor(CARD_BG),
+            BorderColor::all(if index == 0 {
+                CARD_BORDER_SELECTED
+            } else {
+                CARD_BORDER_NORMAL
+            }),
+            RewardCard { index },
+        ))
+        .with_children(|card| {
+            card.spawn((
+                Text::new(blueprint.name),
+                TextFont::from_font_size(28.0),
+                TextColor(rarity_color(blueprint.rarity, color_scheme)),
+            ));
+            card.spawn((
+                Text::new(rarity_stars(blueprint.rarity)),
+                TextFont::from_font_size(20.0),
+                TextColor(rarity_color(blueprint.rarity, color_scheme)),
+            ));
+            if blueprint.element != Element::None {
+                card.spawn((
+                    Text::new(format!("{:?}", blueprint.element)),
+                    TextFont::from_font_size(18.0),
+                    TextColor(element_color(blueprint.element, color_scheme)),
+                ));
+            }
+            card.spawn((
+                Text::new(blueprint.description),
+                TextFont::from_font_size(16.0),
+                TextColor(Color::srgb(0.85, 0.85, 0.9)),
+            ));
+            card.spawn((
+                Text::new(format!(
+                    "Cooldown: {:.1}s\nCharge: {:.1}s",
+                    blueprint.cooldown, blueprint.charge_time
+                )),
+                TextFont::from_font_size(16.0),
+                TextColor(Color::srgb(0.6, 0.6, 0.7)),
+            ));
+        });
+}
+
+pub fn update_reward(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<&Gamepad>,
+    mut offer: ResMut<RewardOffer>,
+    mut owned_chips: ResMut<OwnedChips>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut card_query: Query<(&RewardCard, &mut BorderColor)>,
+    campaign: Res<CampaignProgress>,
+    currency: Res<PlayerCurrency>,
+    upgrades: Res<PlayerUpgrades>,
+    growth: Res<GrowthTreeState>,
+    loadout: Res<PlayerLoadout>,
+    player_weapons: Res<PlayerWeapons>,
+    presets: Res<LoadoutPresets>,
+    mut autosave: AutoSave,
+) {
+    let mut left =
+        keyboard.just_pressed(KeyCode::ArrowLeft) || keyboard.just_pressed(KeyCode::KeyA);
+    let mut right =
+        keyboard.just_pressed(KeyCode::ArrowRight) || keyboard.just_pressed(KeyCode::KeyD);
+    let mut confirm =
+        keyboard.just_pressed(KeyCode::Enter) || keyboard.just_pressed(KeyCode::Space);
+    for gamepad in gamepads.iter() {
+        left |= gamepad.just_pressed(GamepadButton::DPadLeft);
+        right |= gamepad.just_pressed(GamepadButton::DPadRight);
+        confirm |= gamepad.just_pressed(GamepadButton::South);
+    }
+
+    let count = offer.choices.len();
+    if count == 0 {
+        next_state.set(GameState::Campaign);
+        return;
+    }
+    if left {
+        offer.cursor = (offer.cursor + count - 1) % count;
+    }
+    if right {
+        offer.cursor = (offer.cursor + 1) % count;
+    }
+
+    for (card, mut border) in &mut card_query {
+        *border = BorderColor::all(if card.index == offer.cursor {
+            CARD_BORDER_SELECTED
+        } else {
+            CARD_BORDER_NORMAL
+        });
+    }
+
+    if confirm {
+        let chip = offer.choices[offer.cursor];
+        owned_chips.add(chip);
+        info!("Chip reward: {}", ActionBlueprint::get(chip).name);
+        autosave.save(
+            &campaign,
+            &currency,
+            &upgrades,
+            &growth,
+            &loadout,
+            &player_weapons,
+            &owned_chips,
+            &presets,
+        );
+        next_state.set(GameState::Campaign);
+    }
+}
+
+pub fn cleanup_reward(mut commands: Commands) {
+    commands.remove_resource::<RewardOffer>();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resources::GameRng;
+
+    fn rarity(id: ActionId) -> Rarity {
+        ActionBlueprint::get(id).rarity
+    }
+
+    #[test]
+    fn normal_battles_never_offer_super_or_ultra_rare_chips() {
+        let owned = OwnedChips::default();
+        for seed in 0..200 {
+            let mut rng = GameRng::from_seed(seed);
+            let choices = roll_reward_choices(&owned, false, &mut rng.0);
+
+            assert_eq!(choices.len(), REWARD_CHOICES);
+            for id in &choices {
+                assert!(
+                    !matches!(rarity(*id), Rarity::SuperRare | Rarity::UltraRare),
+                    "seed {seed} offered {id:?}"
+                );
+                assert!(!owned.contains(*id));
+            }
+            assert!(
+                choices
+                    .iter()
+                    .enumerate()
+                    .all(|(i, id)| !choices[..i].contains(id))
+            );
+        }
+    }
+
+    #[test]
+    fn boss_battles_can_offer_ultra_rare_chips() {
+        let owned = OwnedChips::default();
+        let offered_ultra_rare = (0..200).any(|seed| {
+            let mut rng = GameRng::from_seed(seed);
+            roll_reward_choices(&owned, true, &mut rng.0)
+                .into_iter()
+                .any(|id| rarity(id) == Rarity::UltraRare)
+        });
+        assert!(offered_ultra_rare);
+    }
+}
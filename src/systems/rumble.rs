@@ -0,0 +1,100 @@
+//! Gamepad rumble feedback for hits, player damage and defeat.
+//!
+//! Systems take a [`Rumble`] param and call one of its event methods; every
+//! connected gamepad gets the same rumble.
+
+use bevy::ecs::system::SystemParam;
+use bevy::input::gamepad::{GamepadRumbleIntensity, GamepadRumbleRequest};
+use bevy::prelude::*;
+use std::time::Duration;
+
+/// Damage at which a hit rumbles at full strength
+const RUMBLE_FULL_DAMAGE: f32 = 50.0;
+/// Weakest rumble any damaging hit produces
+const RUMBLE_MIN_INTENSITY: f32 = 0.15;
+
+/// Rumble length when a player shot lands on an enemy
+const RUMBLE_ENEMY_HIT_SECS: f32 = 0.08;
+/// Rumble length when the player takes damage
+const RUMBLE_PLAYER_HIT_SECS: f32 = 0.25;
+/// Rumble length on defeat
+const RUMBLE_DEFEAT_SECS: f32 = 1.0;
+
+/// Map damage dealt or taken to a rumble strength in `0.0..=1.0`
+pub fn rumble_intensity(damage: i32) -> f32 {
+    if damage <= 0 {
+        return 0.0;
+    }
+    (damage as f32 / RUMBLE_FULL_DAMAGE).clamp(RUMBLE_MIN_INTENSITY, 1.0)
+}
+
+/// Sends rumble requests to every connected gamepad
+#[derive(SystemParam)]
+pub struct Rumble<'w, 's> {
+    requests: MessageWriter<'w, GamepadRumbleRequest>,
+    gamepads: Query<'w, 's, Entity, With<Gamepad>>,
+}
+
+impl Rumble<'_, '_> {
+    /// Short, light buzz when a player projectile hits an enemy
+    pub fn enemy_hit(&mut self, damage: i32) {
+        let intensity = rumble_intensity(damage) * 0.5;
+        self.send(
+            GamepadRumbleIntensity::weak_motor(intensity),
+            RUMBLE_ENEMY_HIT_SECS,
+        );
+    }
+
+    /// Stronger jolt when the player takes damage
+    pub fn player_hit(&mut self, damage: i32) {
+        let intensity = rumble_intensity(damage);
+        self.send(
+            GamepadRumbleIntensity {
+                strong_motor: intensity,
+                weak_motor: intensity * 0.5,
+            },
+            RUMBLE_PLAYER_HIT_SECS,
+        );
+    }
+
+    /// Long full-strength rumble when the player is deleted
+    pub fn defeat(&mut self) {
+        self.send(GamepadRumbleIntensity::MAX, RUMBLE_DEFEAT_SECS);
+    }
+
+    fn send(&mut self, intensity: GamepadRumbleIntensity, secs: f32) {
+        if intensity.strong_motor <= 0.0 && intensity.weak_motor <= 0.0 {
+            return;
+        }
+        for gamepad in &self.gamepads {
+            self.requests.write(GamepadRumbleRequest::Add {
+                duration: Duration::from_secs_f32(secs),
+                intensity,
+                gamepad,
+            });
+        }
+    }
+}
+
+/// Set up what hit feedback writes to: rumble requests and screen shake
+#[cfg(test)]
+pub fn init_hit_feedback(world: &mut World) {
+    world.init_resource::<Messages<GamepadRumbleRequest>>();
+    world.init_resource::<crate::systems::shake::ScreenShake>();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn intensity_scales_with_damage() {
+        assert_eq!(rumble_intensity(0), 0.0);
+        assert_eq!(rumble_intensity(-10), 0.0);
+        assert_eq!(rumble_intensity(1), RUMBLE_MIN_INTENSITY);
+        assert_eq!(rumble_intensity(25), 0.5);
+        assert_eq!(rumble_intensity(50), 1.0);
+        assert_eq!(rumble_intensity(400), 1.0);
+        assert!(rumble_intensity(20) < rumble_intensity(40));
+    }
+}
@@ -0,0 +1,477 @@
+// ============================================================================
+// Settings Screen - Audio volume, game options and controls
+// ============================================================================
+//
+// Reached from the main menu. Up/Down picks a row, Left/Right steps the
+// volume by `VOLUME_STEP` (or cycles a game option, see `GameOptions`).
+// Confirming a control row waits for the next key press and binds it (Esc
+// cancels). Leaving the screen writes settings.json.
+
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+
+use crate::actions::ChipMode;
+use crate::bindings::{BINDABLE_KEYS, InputAction, KeyBindings};
+use crate::components::{CleanupOnStateExit, GameState};
+use crate::resources::{
+    AssistOptions, AudioSettings, ColorScheme, Difficulty, ReduceFlashing, SaveSlot,
+};
+use crate::save::{SettingsData, save_settings};
+use crate::systems::overtime::OvertimeRule;
+use crate::weapons::ChargeMoveRule;
+
+const OPTION_NORMAL: Color = Color::srgb(0.6, 0.6, 0.7);
+const OPTION_SELECTED: Color = Color::srgb(1.0, 0.9, 0.4);
+
+/// Rows on the settings screen (in display order)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettingsOption {
+    Master,
+    Music,
+    Effects,
+    ReduceFlashing,
+    Difficulty,
+    ColorScheme,
+    AutoHeal,
+    ChargeMove,
+    Overtime,
+    ChipMode,
+    Bind(InputAction),
+    Back,
+}
+
+impl SettingsOption {
+    /// Every row: volumes, game options, one per control, then Back
+    pub fn all() -> Vec<SettingsOption> {
+        let mut options = vec![
+            SettingsOption::Master,
+            SettingsOption::Music,
+            SettingsOption::Effects,
+            SettingsOption::ReduceFlashing,
+            SettingsOption::Difficulty,
+            SettingsOption::ColorScheme,
+            SettingsOption::AutoHeal,
+            SettingsOption::ChargeMove,
+            SettingsOption::Overtime,
+            SettingsOption::ChipMode,
+        ];
+        options.extend(InputAction::ALL.into_iter().map(SettingsOption::Bind));
+        options.push(SettingsOption::Back);
+        options
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            SettingsOption::Master => "Master",
+            SettingsOption::Music => "Music",
+            SettingsOption::Effects => "Effects",
+            SettingsOption::ReduceFlashing => "Reduce Flashing",
+            SettingsOption::Difficulty => "Difficulty",
+            SettingsOption::ColorScheme => "Colors",
+            SettingsOption::AutoHeal => "Panic Heal",
+            SettingsOption::ChargeMove => "Charge While Moving",
+            SettingsOption::Overtime => "Sudden Death",
+            SettingsOption::ChipMode => "Chip Mode",
+            SettingsOption::Bind(action) => action.label(),
+            SettingsOption::Back => "Back",
+        }
+    }
+
+    /// The volume level this row controls (None for the other rows)
+    fn level_mut<'a>(&self, settings: &'a mut AudioSettings) -> Option<&'a mut f32> {
+        match self {
+            SettingsOption::Master => Some(&mut settings.master),
+            SettingsOption::Music => Some(&mut settings.music),
+            SettingsOption::Effects => Some(&mut settings.sfx),
+            _ => None,
+        }
+    }
+
+    fn row_text(
+        &self,
+        settings: &AudioSettings,
+        options: &GameOptions,
+        bindings: &KeyBindings,
+        capturing: Option<InputAction>,
+    ) -> String {
+        if let Some(value) = options.value(*self) {
+            return format!("{} < {} >", self.label(), value);
+        }
+        let level = match self {
+            SettingsOption::Master => settings.master,
+            SettingsOption::Music => settings.music,
+            SettingsOption::Effects => settings.sfx,
+            SettingsOption::Bind(action) if capturing == Some(*action) => {
+                return format!("{:<14} [press a key]", self.label());
+            }
+            SettingsOption::Bind(action) => {
+                return format!("{:<14} [{}]", self.label(), bindings.keys_label(*action));
+            }
+            _ => return self.label().to_string(),
+        };
+        format!(
+            "{:<8} < {:>3}% >",
+            self.label(),
+            (level * 100.0).round() as i32
+        )
+    }
+}
+
+/// Game options shown as `< value >` rows, cycled with Left/Right or confirm
+#[derive(SystemParam)]
+pub struct GameOptions<'w> {
+    reduce_flashing: ResMut<'w, ReduceFlashing>,
+    difficulty: ResMut<'w, Difficulty>,
+    color_scheme: ResMut<'w, ColorScheme>,
+    assist: ResMut<'w, AssistOptions>,
+    charge_move: ResMut<'w, ChargeMoveRule>,
+    overtime: ResMut<'w, OvertimeRule>,
+    chip_mode: ResMut<'w, ChipMode>,
+}
+
+impl GameOptions<'_> {
+    /// Current value of an option row (None for the other rows)
+    fn value(&self, option: SettingsOption) -> Option<String> {
+        let value = match option {
+            SettingsOption::ReduceFlashing => self.reduce_flashing.label().to_string(),
+            SettingsOption::Difficulty => self.difficulty.label().to_string(),
+            SettingsOption::ColorScheme => self.color_scheme.label().to_string(),
+            SettingsOption::AutoHeal => on_off(self.assist.auto_heal).to_string(),
+            SettingsOption::ChargeMove => self.charge_move.label().to_string(),
+            SettingsOption::Overtime => on_off(self.overtime.enabled).to_string(),
+            SettingsOption::ChipMode => self.chip_mode.label().to_string(),
+            _ => return None,
+        };
+        Some(value)
+    }
+
+    /// Step an option row to its next value (other rows are left alone)
+    fn cycle(&mut self, option: SettingsOption) {
+        match option {
+            SettingsOption::ReduceFlashing => self.reduce_flashing.0 = !self.reduce_flashing.0,
+            SettingsOption::Difficulty => *self.difficulty = self.difficulty.next(),
+            SettingsOption::ColorScheme => *self.color_scheme = self.color_scheme.next(),
+            SettingsOption::AutoHeal => self.assist.auto_heal = !self.assist.auto_heal,
+            SettingsOption::ChargeMove => *self.charge_move = self.charge_move.next(),
+            SettingsOption::Overtime => self.overtime.enabled = !self.overtime.enabled,
+            SettingsOption::ChipMode => *self.chip_mode = self.chip_mode.next(),
+            _ => {}
+        }
+    }
+
+    /// Everything settings.json stores, with the given audio levels, controls
+    /// and active save slot
+    pub fn settings_data(
+        &self,
+        audio: &AudioSettings,
+        bindings: &KeyBindings,
+        save_slot: SaveSlot,
+    ) -> SettingsData {
+        SettingsData {
+            audio: *audio,
+            bindings: bindings.clone(),
+            difficulty: *self.difficulty,
+            color_scheme: *self.color_scheme,
+            assist: *self.assist,
+            reduce_flashing: *self.reduce_flashing,
+            charge_move: *self.charge_move,
+            overtime: *self.overtime,
+            chip_mode: *self.chip_mode,
+            save_slot,
+        }
+    }
+}
+
+fn on_off(enabled: bool) -> &'static str {
+    if enabled { "On" } else { "Off" }
+}
+
+/// Which row is highlighted, and which control is waiting for a key press
+#[derive(Resource, Debug, Default)]
+pub struct SettingsCursor {
+    pub index: usize,
+    pub capturing: Option<InputAction>,
+}
+
+/// Marker for a settings row label
+#[derive(Component)]
+pub struct SettingsOptionText {
+    pub index: usize,
+}
+
+// ============================================================================
+// Systems
+// ============================================================================
+
+pub fn setup_settings(
+    mut commands: Commands,
+    settings: Res<AudioSettings>,
+    options: GameOptions,
+    bindings: Res<KeyBindings>,
+) {
+    commands.insert_resource(SettingsCursor::default());
+
+    let column = || Node {
+        flex_direction: FlexDirection::Column,
+        row_gap: Val::Px(8.0),
+        ..default()
+    };
+    let row = |index: usize, option: &SettingsOption, font_size: f32| {
+        (
+            Text::new(option.row_text(&settings, &options, &bindings, None)),
+            TextFont::from_font_size(font_size),
+            TextColor(if index == 0 {
+                OPTION_SELECTED
+            } else {
+                OPTION_NORMAL
+            }),
+            SettingsOptionText { index },
+        )
+    };
+    // Controls get their own column so every row fits on screen
+    let rows: Vec<(usize, SettingsOption)> =
+        SettingsOption::all().into_iter().enumerate().collect();
+    let is_control = |option: &SettingsOption| matches!(option, SettingsOption::Bind(_));
+
+    commands
+        .spawn((
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(8.0),
+                ..default()
+            },
+            BackgroundColor(Color::srgb(0.03, 0.03, 0.1)),
+            CleanupOnStateExit(GameState::Settings),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new("SETTINGS"),
+                TextFont::from_font_size(60.0),
+                TextColor(Color::srgb(0.9, 0.4, 0.3)),
+                Node {
+                    margin: UiRect::bottom(Val::Px(20.0)),
+                    ..default()
+                },
+            ));
+
+            parent
+                .spawn(Node {
+                    column_gap: Val::Px(60.0),
+                    ..default()
+                })
+                .with_children(|parent| {
+                    parent.spawn(column()).with_children(|parent| {
+                        for (index, option) in rows.iter().filter(|(_, option)| !is_control(option))
+                        {
+                            parent.spawn(row(*index, option, 30.0));
+                        }
+                    });
+                    parent.spawn(column()).with_children(|parent| {
+                        for (index, option) in rows.iter().filter(|(_, option)| is_control(option))
+                        {
+                            parent.spawn(row(*index, option, 22.0));
+                        }
+                    });
+                });
+
+            parent.spawn((
+                Text::new(
+                    "Up/Down: Select  |  Left/Right: Adjust  |  Enter: Rebind  |  Esc / B: Back",
+                ),
+                TextFont::from_font_size(18.0),
+                TextColor(Color::srgba(0.6, 0.6, 0.6, 0.8)),
+                Node {
+                    margin: UiRect::top(Val::Px(30.0)),
+                    ..default()
+                },
+            ));
+        });
+}
+
+pub fn update_settings(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<&Gamepad>,
+    mut cursor: ResMut<SettingsCursor>,
+    mut settings: ResMut<AudioSettings>,
+    mut options: GameOptions,
+    mut bindings: ResMut<KeyBindings>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut option_query: Query<(&SettingsOptionText, &mut Text, &mut TextColor)>,
+) {
+    let rows = SettingsOption::all();
+
+    // Waiting for a key: the next bindable press is taken, Esc cancels
+    if let Some(action) = cursor.capturing {
+        if keyboard.just_pressed(KeyCode::Escape) {
+            cursor.capturing = None;
+        } else if let Some(key) = keyboard
+            .get_just_pressed()
+            .find(|key| BINDABLE_KEYS.contains(key))
+        {
+            bindings.rebind_key(action, *key);
+            cursor.capturing = None;
+        }
+        refresh_rows(
+            &rows,
+            &cursor,
+            &settings,
+            &options,
+            &bindings,
+            &mut option_query,
+        );
+        return;
+    }
+
+    let mut up = keyboard.just_pressed(KeyCode::ArrowUp) || keyboard.just_pressed(KeyCode::KeyW);
+    let mut down =
+        keyboard.just_pressed(KeyCode::ArrowDown) || keyboard.just_pressed(KeyCode::KeyS);
+    let mut left =
+        keyboard.just_pressed(KeyCode::ArrowLeft) || keyboard.just_pressed(KeyCode::KeyA);
+    let mut right =
+        keyboard.just_pressed(KeyCode::ArrowRight) || keyboard.just_pressed(KeyCode::KeyD);
+    let mut confirm =
+        keyboard.just_pressed(KeyCode::Enter) || keyboard.just_pressed(KeyCode::Space);
+    let mut back = keyboard.just_pressed(KeyCode::Escape);
+    for gamepad in gamepads.iter() {
+        up |= gamepad.just_pressed(GamepadButton::DPadUp);
+        down |= gamepad.just_pressed(GamepadButton::DPadDown);
+        left |= gamepad.just_pressed(GamepadButton::DPadLeft);
+        right |= gamepad.just_pressed(GamepadButton::DPadRight);
+        confirm |= gamepad.just_pressed(GamepadButton::South);
+        back |= gamepad.just_pressed(GamepadButton::East);
+    }
+
+    let count = rows.len();
+    if up {
+        cursor.index = (cursor.index + count - 1) % count;
+    }
+    if down {
+        cursor.index = (cursor.index + 1) % count;
+    }
+
+    let selected = rows[cursor.index];
+    let steps = right as i32 - left as i32;
+    if steps != 0 {
+        if let Some(level) = selected.level_mut(&mut settings) {
+            AudioSettings::step(level, steps);
+        }
+    }
+    if options.value(selected).is_some() && (steps != 0 || confirm) {
+        options.cycle(selected);
+    }
+
+    // Only keyboard confirm starts a rebind, so the key that started it is never captured
+    let keyboard_confirm =
+        keyboard.just_pressed(KeyCode::Enter) || keyboard.just_pressed(KeyCode::Space);
+    if let SettingsOption::Bind(action) = selected {
+        if keyboard_confirm {
+            cursor.capturing = Some(action);
+        }
+    }
+
+    refresh_rows(
+        &rows,
+        &cursor,
+        &settings,
+        &options,
+        &bindings,
+        &mut option_query,
+    );
+
+    if back || (confirm && selected == SettingsOption::Back) {
+        next_state.set(GameState::MainMenu);
+    }
+}
+
+fn refresh_rows(
+    rows: &[SettingsOption],
+    cursor: &SettingsCursor,
+    settings: &AudioSettings,
+    options: &GameOptions,
+    bindings: &KeyBindings,
+    option_query: &mut Query<(&SettingsOptionText, &mut Text, &mut TextColor)>,
+) {
+    for (option, mut text, mut color) in option_query.iter_mut() {
+        color.0 = if option.index == cursor.index {
+            OPTION_SELECTED
+        } else {
+            OPTION_NORMAL
+        };
+        let row = rows[option.index].row_text(settings, options, bindings, cursor.capturing);
+        if text.0 != row {
+            text.0 = row;
+        }
+    }
+}
+
+/// Persist settings when leaving the screen
+pub fn cleanup_settings(
+    mut commands: Commands,
+    settings: Res<AudioSettings>,
+    bindings: Res<KeyBindings>,
+    save_slot: Res<SaveSlot>,
+    options: GameOptions,
+) {
+    save_settings(&options.settings_data(&settings, &bindings, *save_slot));
+    commands.remove_resource::<SettingsCursor>();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Run the settings screen for one frame with `key` pressed on `option`'s row
+    fn press_on(world: &mut World, option: SettingsOption, key: KeyCode) {
+        let index = SettingsOption::all()
+            .iter()
+            .position(|row| *row == option)
+            .unwrap();
+        world.resource_mut::<SettingsCursor>().index = index;
+        let mut keyboard = world.resource_mut::<ButtonInput<KeyCode>>();
+        keyboard.reset_all();
+        keyboard.press(key);
+        world.run_system_cached(update_settings).unwrap();
+    }
+
+    #[test]
+    fn option_rows_cycle_their_values() {
+        let mut world = World::new();
+        world.init_resource::<ButtonInput<KeyCode>>();
+        world.init_resource::<SettingsCursor>();
+        world.init_resource::<AudioSettings>();
+        world.init_resource::<KeyBindings>();
+        world.init_resource::<ReduceFlashing>();
+        world.init_resource::<Difficulty>();
+        world.init_resource::<ColorScheme>();
+        world.init_resource::<AssistOptions>();
+        world.init_resource::<ChargeMoveRule>();
+        world.init_resource::<OvertimeRule>();
+        world.init_resource::<ChipMode>();
+        world.init_resource::<NextState<GameState>>();
+
+        press_on(&mut world, SettingsOption::Difficulty, KeyCode::ArrowRight);
+        assert_eq!(*world.resource::<Difficulty>(), Difficulty::Hard);
+        press_on(&mut world, SettingsOption::ColorScheme, KeyCode::Enter);
+        assert_eq!(*world.resource::<ColorScheme>(), ColorScheme::Colorblind);
+        press_on(&mut world, SettingsOption::AutoHeal, KeyCode::ArrowLeft);
+        assert!(world.resource::<AssistOptions>().auto_heal);
+        press_on(&mut world, SettingsOption::ChargeMove, KeyCode::ArrowRight);
+        assert_eq!(*world.resource::<ChargeMoveRule>(), ChargeMoveRule::Slow);
+        press_on(&mut world, SettingsOption::Overtime, KeyCode::Enter);
+        assert!(world.resource::<OvertimeRule>().enabled);
+        press_on(&mut world, SettingsOption::ChipMode, KeyCode::ArrowRight);
+        assert_eq!(*world.resource::<ChipMode>(), ChipMode::CustomGauge);
+
+        // Volume rows still step their level rather than cycling anything
+        press_on(&mut world, SettingsOption::Music, KeyCode::ArrowRight);
+        assert_eq!(*world.resource::<Difficulty>(), Difficulty::Hard);
+        assert!(matches!(
+            world.resource::<NextState<GameState>>(),
+            NextState::Unchanged
+        ));
+    }
+}
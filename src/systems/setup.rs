@@ -1,4 +1,3 @@
-use bevy::audio::{AudioPlayer, AudioSource, PlaybackSettings, Volume};
 use bevy::image::TextureAtlas;
 use bevy::prelude::*;
 use bevy::sprite::Anchor;
@@ -7,34 +6,62 @@ use bevy::text::Justify;
 use crate::actions::{ActionBlueprint, ActionId, ActionSlot};
 use crate::assets::{FighterSprites, ProjectileSprites};
 use crate::components::{
-    ActionBar, ActionChargeBar, ActionCooldownOverlay, ActionKeyText, ActionSlotUI, ArenaConfig,
-    BaseColor, CleanupOnStateExit, Enemy, EnemyConfig, FighterAnim, FighterAnimState, GameState,
-    GridPosition, Health, HealthText, Player, PlayerHealthText, RenderConfig, SlimeAnim,
-    SlimeAnimState,
+    ActionBar, ActionChargeBar, ActionCooldownOverlay, ActionKeyText, ActionSlotUI, ActionTooltip,
+    ArenaConfig, BaseColor, BattleRewardText, BattleTimerText, Boss, ChargeBar, ChargeBarFill,
+    CleanupOnStateExit, ComboText, Enemy, EnemyConfig, EnemyHpBarFill, FighterAnim,
+    FighterAnimState, GameState, GridPosition, Health, HealthText, Obstacle, ObstacleConfig,
+    OverHealthText, Player, PlayerHealthText, RenderConfig, SlimeAnim, SlimeAnimState,
+    SlimeSprites, WaveBanner,
 };
 use crate::constants::*;
 use crate::enemies::{
-    BehaviorEnemy, EnemyAnimState, EnemyAttack, EnemyBlueprint, EnemyMovement, EnemyStats,
-    EnemyTraitContainer,
+    AttackBehavior, BehaviorEnemy, EnemyAnimState, EnemyAttack, EnemyBlueprint, EnemyGuard,
+    EnemyIntent, EnemyIntentIcon, EnemyKind, EnemyMovement, EnemyReward, EnemyStats,
+    EnemyTraitContainer, GuardPlate, Summoner,
 };
-use crate::resources::{ArenaLayout, PlayerUpgrades, WaveState};
+use crate::resources::{
+    ArenaLayout, Difficulty, PlayerUpgrades, TileOwner, TileOwnership, WaveQueue, WaveState,
+};
+use crate::save::{load_settings, load_slot};
 use crate::systems::arena::spawn_arena_visuals;
-use crate::weapons::{EquippedWeapon, WeaponState, WeaponType};
+use crate::systems::camera::CameraConfig;
+use crate::systems::outro::format_battle_time;
+use crate::weapons::{EquippedWeapon, PlayerWeapons, WeaponState};
 
 // ============================================================================
 // Global Setup (runs once at app startup)
 // ============================================================================
 
-/// Setup that runs once at app start - camera only
+/// Setup that runs once at app start - camera, saved progress and settings
 pub fn setup_global(mut commands: Commands) {
     commands.spawn(Camera2d);
+
+    let settings = load_settings();
+    if let Some(settings) = &settings {
+        commands.insert_resource(settings.audio);
+        commands.insert_resource(settings.bindings.clone());
+        commands.insert_resource(settings.difficulty);
+        commands.insert_resource(settings.color_scheme);
+        commands.insert_resource(settings.assist);
+        commands.insert_resource(settings.reduce_flashing);
+        commands.insert_resource(settings.charge_move);
+        commands.insert_resource(settings.overtime);
+        commands.insert_resource(settings.chip_mode);
+        commands.insert_resource(settings.save_slot);
+    }
+
+    // Restore the last used slot's progression (an empty slot keeps the defaults)
+    let slot = settings
+        .map(|settings| settings.save_slot)
+        .unwrap_or_default();
+    load_slot(slot, &mut commands);
 }
 
 // ============================================================================
 // Arena Setup (runs when entering Playing state)
 // ============================================================================
 
-/// Setup the arena background, grid, BGM, and spawn entities based on ArenaConfig
+/// Setup the arena background, grid, and spawn entities based on ArenaConfig
 pub fn setup_arena(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
@@ -42,43 +69,35 @@ pub fn setup_arena(
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<ColorMaterial>>,
     config: Res<ArenaConfig>,
+    difficulty: Res<Difficulty>,
     upgrades: Res<PlayerUpgrades>,
+    player_weapons: Res<PlayerWeapons>,
     mut wave_state: ResMut<WaveState>,
-    windows: Query<&Window>,
+    camera: Res<CameraConfig>,
 ) {
     *wave_state = WaveState::Spawning;
 
     // ========================================================================
-    // Compute Arena Layout from window size
+    // Compute Arena Layout for the camera's arena size (the camera scales it
+    // to the window, see fit_camera_to_window)
     // ========================================================================
-    let layout = windows
-        .iter()
-        .next()
-        .map(|window| ArenaLayout::from_screen_size(window.width(), window.height()))
-        .unwrap_or_default();
+    let layout = ArenaLayout::for_grid(camera.arena_size.x, camera.arena_size.y, config.grid);
     commands.insert_resource(layout.clone());
 
     // ========================================================================
     // Arena Visuals (background, grid lines, tile panels)
     // ========================================================================
+    // Panels stolen last battle go back to their side
+    let ownership = TileOwnership::new(config.grid);
     spawn_arena_visuals(
         &mut commands,
         &mut meshes,
         &mut materials,
         &asset_server,
         &layout,
+        &ownership,
     );
 
-    // ========================================================================
-    // BGM
-    // ========================================================================
-    let bgm: Handle<AudioSource> = asset_server.load("audio/bgm/battle.mp3");
-    commands.spawn((
-        AudioPlayer::new(bgm),
-        PlaybackSettings::LOOP.with_volume(Volume::Linear(0.45)),
-        CleanupOnStateExit(GameState::Playing),
-    ));
-
     // ========================================================================
     // Fighter sprite sheets
     // ========================================================================
@@ -109,57 +128,128 @@ pub fn setup_arena(
     // ========================================================================
     let fighter_config = &config.fighter;
 
-    // Create equipped weapon and its state
-    let mut equipped_weapon = EquippedWeapon::new(WeaponType::Blaster);
-    equipped_weapon.stats.apply_upgrades(&upgrades);
+    // Create equipped weapon (last one the player switched to) and its state
+    let equipped_weapon = EquippedWeapon::with_upgrades(player_weapons.equipped, &upgrades);
 
     let weapon_state = WeaponState::new(equipped_weapon.stats.fire_cooldown);
 
     let max_hp = upgrades.get_max_hp();
 
+    commands
+        .spawn((
+            Sprite {
+                image: fighter_idle,
+                texture_atlas: Some(fighter_layout.into()),
+                color: Color::WHITE,
+                custom_size: Some(layout.scale_vec2(FIGHTER_DRAW_SIZE)),
+                ..default()
+            },
+            Anchor(FIGHTER_ANCHOR),
+            Transform::default(),
+            GridPosition {
+                x: fighter_config.start_x,
+                y: fighter_config.start_y,
+            },
+            RenderConfig {
+                offset: CHARACTER_OFFSET,
+                base_z: Z_CHARACTER,
+            },
+            FighterAnim {
+                state: FighterAnimState::Idle,
+                frame: 0,
+                timer: Timer::from_seconds(0.1, TimerMode::Repeating),
+            },
+            Player,
+            Health {
+                current: max_hp,
+                max: max_hp,
+            },
+            BaseColor(Color::WHITE),
+            // Weapon system components
+            equipped_weapon,
+            weapon_state,
+            CleanupOnStateExit(GameState::Playing),
+        ))
+        .with_children(|parent| {
+            // Weapon charge bar (see update_charge_bar)
+            let bar_size = layout.scale_vec2(CHARGE_BAR_SIZE);
+            parent
+                .spawn((
+                    Transform::from_xyz(0.0, layout.scale_val(CHARGE_BAR_OFFSET_Y), 1.0),
+                    Visibility::Hidden,
+                    ChargeBar,
+                ))
+                .with_children(|bar| {
+                    bar.spawn((
+                        Sprite {
+                            color: COLOR_CHARGE_BAR_BG,
+                            custom_size: Some(bar_size + Vec2::splat(4.0)),
+                            ..default()
+                        },
+                        Transform::default(),
+                    ));
+                    bar.spawn((
+                        Sprite {
+                            color: COLOR_ACTION_CHARGE,
+                            custom_size: Some(Vec2::new(0.0, bar_size.y)),
+                            ..default()
+                        },
+                        Anchor::CENTER_LEFT,
+                        Transform::from_xyz(-bar_size.x / 2.0, 0.0, 0.1),
+                        ChargeBarFill,
+                    ));
+                });
+        });
+
+    // Player HP display (top-left area, above arena), with any over-health
+    // in its own color after it
+    commands
+        .spawn((
+            Text2d::new(format!("HP: {}", max_hp)),
+            TextLayout::new_with_justify(Justify::Left),
+            TextFont::from_font_size(28.0),
+            TextColor(COLOR_TEXT),
+            Transform::from_xyz(-580.0, 360.0, Z_UI),
+            PlayerHealthText,
+            CleanupOnStateExit(GameState::Playing),
+        ))
+        .with_child((
+            TextSpan::new(""),
+            TextFont::from_font_size(28.0),
+            TextColor(COLOR_OVER_HEALTH),
+            OverHealthText,
+        ));
+
+    // Zenny earned this battle (kept small and dim under the HP display)
     commands.spawn((
-        Sprite {
-            image: fighter_idle,
-            texture_atlas: Some(fighter_layout.into()),
-            color: Color::WHITE,
-            custom_size: Some(layout.scale_vec2(FIGHTER_DRAW_SIZE)),
-            ..default()
-        },
-        Anchor(FIGHTER_ANCHOR),
-        Transform::default(),
-        GridPosition {
-            x: fighter_config.start_x,
-            y: fighter_config.start_y,
-        },
-        RenderConfig {
-            offset: CHARACTER_OFFSET,
-            base_z: Z_CHARACTER,
-        },
-        FighterAnim {
-            state: FighterAnimState::Idle,
-            frame: 0,
-            timer: Timer::from_seconds(0.1, TimerMode::Repeating),
-        },
-        Player,
-        Health {
-            current: max_hp,
-            max: max_hp,
-        },
-        BaseColor(Color::WHITE),
-        // Weapon system components
-        equipped_weapon,
-        weapon_state,
+        Text2d::new("Z: 0"),
+        TextLayout::new_with_justify(Justify::Left),
+        TextFont::from_font_size(18.0),
+        TextColor(COLOR_TEXT.with_alpha(0.6)),
+        Transform::from_xyz(-580.0, 330.0, Z_UI),
+        BattleRewardText,
         CleanupOnStateExit(GameState::Playing),
     ));
 
-    // Player HP display (top-left area, above arena)
+    // Battle clock (top center)
     commands.spawn((
-        Text2d::new(format!("HP: {}", max_hp)),
-        TextLayout::new_with_justify(Justify::Left),
-        TextFont::from_font_size(28.0),
+        Text2d::new(format_battle_time(0.0)),
+        TextLayout::new_with_justify(Justify::Center),
+        TextFont::from_font_size(24.0),
         TextColor(COLOR_TEXT),
-        Transform::from_xyz(-580.0, 360.0, Z_UI),
-        PlayerHealthText,
+        Transform::from_xyz(0.0, 360.0, Z_UI),
+        BattleTimerText,
+        CleanupOnStateExit(GameState::Playing),
+    ));
+
+    // Kill combo (empty until two enemies fall in quick succession)
+    commands.spawn((
+        Text2d::new(""),
+        TextLayout::new_with_justify(Justify::Left),
+        TextFont::from_font_size(24.0),
+        TextColor(COLOR_ACTION_CHARGE),
+        Transform::from_xyz(-580.0, 300.0, Z_UI),
+        ComboText,
         CleanupOnStateExit(GameState::Playing),
     ));
 
@@ -196,35 +286,171 @@ pub fn setup_arena(
     // ========================================================================
     // Enemies (from config) - using the new blueprint system
     // ========================================================================
-    for enemy_config in &config.enemies {
+    // Only the first wave spawns now; the rest wait in the WaveQueue
+    let rocks: Vec<(i32, i32)> = config
+        .obstacles
+        .iter()
+        .map(|rock| (rock.x, rock.y))
+        .collect();
+    let first_wave = config.waves.first().map_or(&[][..], Vec::as_slice);
+    for enemy_config in place_enemies(first_wave, &ownership, &rocks) {
         spawn_enemy(
             &mut commands,
             &asset_server,
             &mut atlas_layouts,
-            enemy_config,
+            &enemy_config,
             0, // TODO: Pass wave level for HP scaling
             &layout,
+            *difficulty,
         );
     }
+    commands.insert_resource(ownership);
+    commands.insert_resource(WaveQueue::new(&config.waves));
+
+    // ========================================================================
+    // Obstacles (from config)
+    // ========================================================================
+    for obstacle_config in &config.obstacles {
+        spawn_obstacle(&mut commands, obstacle_config, &layout);
+    }
+}
+
+/// Between waves: once the "WAVE n" banner has shown long enough, spawn the next queued wave
+pub fn advance_wave(
+    mut commands: Commands,
+    time: Res<Time>,
+    asset_server: Res<AssetServer>,
+    mut atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
+    layout: Res<ArenaLayout>,
+    ownership: Res<TileOwnership>,
+    mut wave_state: ResMut<WaveState>,
+    mut queue: ResMut<WaveQueue>,
+    difficulty: Res<Difficulty>,
+    banner_query: Query<Entity, With<WaveBanner>>,
+    obstacle_query: Query<&GridPosition, With<Obstacle>>,
+) {
+    if *wave_state != WaveState::Intermission {
+        return;
+    }
+
+    queue.banner_timer.tick(time.delta());
+    if !queue.banner_timer.is_finished() {
+        return;
+    }
+
+    for banner in &banner_query {
+        commands.entity(banner).despawn();
+    }
+
+    if let Some(wave) = queue.advance() {
+        let rocks: Vec<(i32, i32)> = obstacle_query.iter().map(|pos| (pos.x, pos.y)).collect();
+        for enemy_config in place_enemies(&wave, &ownership, &rocks) {
+            spawn_enemy(
+                &mut commands,
+                &asset_server,
+                &mut atlas_layouts,
+                &enemy_config,
+                0,
+                &layout,
+                *difficulty,
+            );
+        }
+        info!("Wave {} incoming!", queue.current);
+    }
+    *wave_state = WaveState::Spawning;
+}
+
+/// Settle a wave onto distinct tiles, in config order: an enemy whose tile is
+/// taken (or blocked by a rock, or not on the enemy side) is nudged to the
+/// nearest free enemy tile. Enemies left without a tile are dropped with a
+/// warning.
+pub fn place_enemies(
+    wave: &[EnemyConfig],
+    ownership: &TileOwnership,
+    blocked: &[(i32, i32)],
+) -> Vec<EnemyConfig> {
+    let mut taken = blocked.to_vec();
+    let mut placed = Vec::with_capacity(wave.len());
+    for config in wave {
+        let wanted = (config.start_x, config.start_y);
+        let tile = if ownership.is_enemy_tile(wanted.0, wanted.1) && !taken.contains(&wanted) {
+            Some(wanted)
+        } else {
+            // Nearest by grid distance; ties go to the earlier tile column by column
+            ownership
+                .tiles_owned_by(TileOwner::Enemy)
+                .into_iter()
+                .filter(|tile| !taken.contains(tile))
+                .min_by_key(|&(x, y)| (x - wanted.0).abs() + (y - wanted.1).abs())
+        };
+        let Some((x, y)) = tile else {
+            warn!(
+                "Arena overbooked: no free tile for {:?}, dropping it",
+                config.enemy_id
+            );
+            continue;
+        };
+        if (x, y) != wanted {
+            info!(
+                "{:?} nudged from {:?} to {:?} (tile taken)",
+                config.enemy_id,
+                wanted,
+                (x, y)
+            );
+        }
+        taken.push((x, y));
+        placed.push(EnemyConfig {
+            start_x: x,
+            start_y: y,
+            ..config.clone()
+        });
+    }
+    placed
+}
+
+/// Spawn a rock that occupies a tile until it is destroyed
+fn spawn_obstacle(commands: &mut Commands, config: &ObstacleConfig, arena_layout: &ArenaLayout) {
+    commands.spawn((
+        Sprite {
+            color: COLOR_OBSTACLE,
+            custom_size: Some(arena_layout.scale_vec2(OBSTACLE_DRAW_SIZE)),
+            ..default()
+        },
+        Transform::default(),
+        GridPosition {
+            x: config.x,
+            y: config.y,
+        },
+        RenderConfig {
+            offset: OBSTACLE_OFFSET,
+            base_z: Z_CHARACTER,
+        },
+        Obstacle { hp: config.hp },
+        BaseColor(COLOR_OBSTACLE),
+        CleanupOnStateExit(GameState::Playing),
+    ));
 }
 
 /// Spawn an enemy using the blueprint system
 /// This is the unified spawn function for all enemy types
-fn spawn_enemy(
+pub fn spawn_enemy(
     commands: &mut Commands,
     asset_server: &AssetServer,
     atlas_layouts: &mut Assets<TextureAtlasLayout>,
     config: &EnemyConfig,
     wave_level: i32,
     arena_layout: &ArenaLayout,
-) {
+    difficulty: Difficulty,
+) -> Entity {
     // Get the blueprint for this enemy type
     let blueprint = EnemyBlueprint::get(config.enemy_id);
 
-    // Calculate HP (use override or scaled from blueprint)
-    let hp = config
-        .hp_override
-        .unwrap_or_else(|| blueprint.scaled_hp(wave_level));
+    // Calculate HP (use override or scaled from blueprint), then apply difficulty
+    let hp = difficulty.scale_hp(
+        config
+            .hp_override
+            .unwrap_or_else(|| blueprint.scaled_hp(wave_level)),
+    );
 
     // Get visuals from blueprint
     let visuals = &blueprint.visuals;
@@ -255,7 +481,7 @@ fn spawn_enemy(
                     layout: atlas_layout,
                     index: 0,
                 }),
-                color: Color::WHITE,
+                color: visuals.tint,
                 custom_size: Some(arena_layout.scale_vec2(visuals.draw_size)),
                 flip_x: visuals.flip_x,
                 ..default()
@@ -283,25 +509,75 @@ fn spawn_enemy(
                 current: hp,
                 max: hp,
             },
-            BaseColor(Color::WHITE),
+            BaseColor(visuals.tint),
             CleanupOnStateExit(GameState::Playing),
         ))
         .id();
 
+    let attack = config
+        .attack_override
+        .as_ref()
+        .unwrap_or(&blueprint.attack)
+        .with_damage(|damage| difficulty.scale_damage(damage));
+    if matches!(attack, AttackBehavior::Summon { .. }) {
+        commands.entity(enemy_entity).insert(Summoner::default());
+    }
+    if config.boss {
+        commands.entity(enemy_entity).insert(Boss);
+    }
+    if let Some(cycle) = &blueprint.traits.guard {
+        commands
+            .entity(enemy_entity)
+            .insert(EnemyGuard::new(cycle.clone()))
+            .with_children(|parent| {
+                let tile = Vec2::new(arena_layout.tile_width, arena_layout.visible_height);
+                parent.spawn((
+                    Sprite::from_color(COLOR_GUARD_PLATE, GUARD_PLATE_SIZE * tile),
+                    Transform::from_xyz(0.0, GUARD_PLATE_RISE * tile.y, 0.3),
+                    Visibility::Hidden,
+                    GuardPlate,
+                ));
+            });
+    }
+
     // Add behavior components separately (to avoid tuple size limits)
     commands.entity(enemy_entity).insert((
         EnemyStats {
             base_hp: blueprint.stats.base_hp,
-            contact_damage: blueprint.stats.contact_damage,
+            contact_damage: difficulty.scale_damage(blueprint.stats.contact_damage),
             move_speed: blueprint.stats.move_speed,
             attack_speed: blueprint.stats.attack_speed,
         },
-        EnemyMovement::new(blueprint.movement.clone(), blueprint.stats.move_speed),
-        EnemyAttack::new(blueprint.attack.clone(), blueprint.stats.attack_speed),
+        EnemyMovement::new(
+            config
+                .movement_override
+                .clone()
+                .unwrap_or_else(|| blueprint.movement.clone()),
+            blueprint.stats.move_speed,
+        ),
+        EnemyAttack::new(attack, blueprint.stats.attack_speed),
         EnemyTraitContainer::new(blueprint.traits.clone()),
         EnemyAnimState::default(),
+        EnemyReward(difficulty.scale_reward(blueprint.zenny_reward)),
+        EnemyKind(config.enemy_id),
     ));
 
+    // Death sheet, played by animate_slime before the enemy despawns
+    if let (Some(dead_file), Some(dead_grid)) = (&anims.dead_file, anims.dead_grid) {
+        commands.entity(enemy_entity).insert(SlimeSprites {
+            dead: asset_server.load(format!("{}/{}", visuals.sprite_path, dead_file)),
+            dead_layout: atlas_layouts.add(TextureAtlasLayout::from_grid(
+                UVec2::new(16, 16),
+                dead_grid.0,
+                dead_grid.1,
+                None,
+                None,
+            )),
+            dead_frames: anims.dead_frames,
+            dead_fps: anims.dead_fps,
+        });
+    }
+
     // Spawn HP display as children
     commands.entity(enemy_entity).with_children(|parent| {
         // HP plate background
@@ -333,7 +609,33 @@ fn spawn_enemy(
             Transform::from_xyz(0.0, 80.0, 0.2),
             HealthText,
         ));
+
+        // HP bar background
+        parent.spawn((
+            Sprite::from_color(COLOR_HP_BAR_BG, ENEMY_HP_BAR_SIZE),
+            Transform::from_xyz(0.0, 60.0, 0.0),
+        ));
+
+        // HP bar fill (resized by update_enemy_hp_bars)
+        parent.spawn((
+            Sprite::from_color(COLOR_HP_BAR_HIGH, ENEMY_HP_BAR_SIZE),
+            Transform::from_xyz(0.0, 60.0, 0.1),
+            EnemyHpBarFill,
+        ));
+
+        // Intent icon (updated by update_enemy_intent)
+        let intent = EnemyIntent::Idle;
+        parent.spawn((
+            Text2d::new(intent.glyph()),
+            TextLayout::new_with_justify(Justify::Center),
+            TextFont::from_font_size(24.0),
+            TextColor(intent.color()),
+            Transform::from_xyz(0.0, 108.0, 0.2),
+            EnemyIntentIcon,
+        ));
     });
+
+    enemy_entity
 }
 
 // ============================================================================
@@ -459,6 +761,15 @@ pub fn setup_action_bar(mut commands: Commands, config: Res<ArenaConfig>) {
                         ));
                     });
             }
+
+            // Tooltip for the focused slot
+            parent.spawn((
+                Text2d::new(""),
+                TextColor(COLOR_ACTION_KEY_TEXT),
+                TextFont::from_font_size(16.0),
+                Transform::from_xyz(0.0, ACTION_SLOT_SIZE / 2.0 + 18.0, 0.1),
+                ActionTooltip::default(),
+            ));
         });
 }
 
@@ -483,11 +794,21 @@ pub struct ActionReadyIndicator {
 }
 
 /// Spawn the actual ActionSlot components based on config
-pub fn spawn_player_actions(mut commands: Commands, config: Res<ArenaConfig>) {
+pub fn spawn_player_actions(
+    mut commands: Commands,
+    config: Res<ArenaConfig>,
+    upgrades: Res<PlayerUpgrades>,
+) {
+    let cooldown_modifier = upgrades.get_chip_cooldown_modifier();
     for (i, action_id) in config.fighter.actions.iter().enumerate() {
         let blueprint = ActionBlueprint::get(*action_id);
         commands.spawn((
-            ActionSlot::new(i, *action_id, blueprint.cooldown, blueprint.charge_time),
+            ActionSlot::new(
+                i,
+                *action_id,
+                blueprint.cooldown * cooldown_modifier,
+                blueprint.charge_time,
+            ),
             CleanupOnStateExit(GameState::Playing),
         ));
     }
@@ -539,6 +860,18 @@ pub fn cleanup_campaign_entities(
     }
 }
 
+/// Cleanup for when leaving Settings state
+pub fn cleanup_settings_entities(
+    mut commands: Commands,
+    query: Query<(Entity, &CleanupOnStateExit)>,
+) {
+    for (entity, scoped) in &query {
+        if scoped.0 == GameState::Settings {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
 /// Cleanup for when leaving Loadout state
 pub fn cleanup_loadout_entities(
     mut commands: Commands,
@@ -550,3 +883,252 @@ pub fn cleanup_loadout_entities(
         }
     }
 }
+
+/// Cleanup for when leaving Encyclopedia state
+pub fn cleanup_encyclopedia_entities(
+    mut commands: Commands,
+    query: Query<(Entity, &CleanupOnStateExit)>,
+) {
+    for (entity, scoped) in &query {
+        if scoped.0 == GameState::Encyclopedia {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+/// Cleanup for when leaving Reward state
+pub fn cleanup_reward_entities(
+    mut commands: Commands,
+    query: Query<(Entity, &CleanupOnStateExit)>,
+) {
+    for (entity, scoped) in &query {
+        if scoped.0 == GameState::Reward {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enemies::{EnemyId, MovementBehavior};
+    use crate::resources::{BattleRewards, BattleTimer, GameProgress, PlayerCurrency};
+    use crate::systems::combat::{check_victory_condition, update_wave_state};
+
+    /// Headless app with asset storage, so enemies can be spawned without a window
+    fn arena_app(waves: Vec<Vec<EnemyConfig>>) -> App {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+            .init_asset::<Image>()
+            .init_asset::<TextureAtlasLayout>()
+            .init_resource::<ArenaLayout>()
+            .init_resource::<TileOwnership>()
+            .init_resource::<Difficulty>()
+            .init_resource::<WaveState>()
+            .init_resource::<PlayerCurrency>()
+            .init_resource::<GameProgress>()
+            .init_resource::<BattleTimer>()
+            .init_resource::<BattleRewards>()
+            .insert_resource(WaveQueue::new(&waves))
+            .insert_resource(ArenaConfig { waves, ..default() });
+        app
+    }
+
+    /// Spawn enemies the way `setup_arena` does
+    fn spawn_wave(app: &mut App, wave: &[EnemyConfig]) {
+        let world = app.world_mut();
+        world.resource_scope(
+            |world, mut atlas_layouts: Mut<Assets<TextureAtlasLayout>>| {
+                let asset_server = world.resource::<AssetServer>().clone();
+                let layout = world.resource::<ArenaLayout>().clone();
+                let mut commands = world.commands();
+                for config in wave {
+                    spawn_enemy(
+                        &mut commands,
+                        &asset_server,
+                        &mut atlas_layouts,
+                        config,
+                        0,
+                        &layout,
+                        Difficulty::default(),
+                    );
+                }
+            },
+        );
+        world.flush();
+    }
+
+    fn enemies(app: &mut App) -> Vec<(i32, i32)> {
+        let world = app.world_mut();
+        let mut tiles: Vec<(i32, i32)> = world
+            .query_filtered::<&GridPosition, With<Enemy>>()
+            .iter(world)
+            .map(|pos| (pos.x, pos.y))
+            .collect();
+        tiles.sort_unstable();
+        tiles
+    }
+
+    #[test]
+    fn next_wave_spawns_only_once_the_field_is_clear() {
+        let first = vec![EnemyConfig::new(EnemyId::Slime, 4, 1)];
+        let second = vec![
+            EnemyConfig::new(EnemyId::Slime2, 5, 0),
+            EnemyConfig::new(EnemyId::Slime2, 5, 2),
+        ];
+        let mut app = arena_app(vec![first.clone(), second]);
+        app.add_systems(
+            Update,
+            (update_wave_state, check_victory_condition, advance_wave).chain(),
+        );
+        spawn_wave(&mut app, &first);
+
+        for _ in 0..3 {
+            app.update();
+        }
+        assert_eq!(enemies(&mut app), vec![(4, 1)]);
+        assert_eq!(*app.world().resource::<WaveState>(), WaveState::Active);
+
+        let slime = app
+            .world_mut()
+            .query_filtered::<Entity, With<Enemy>>()
+            .single(app.world())
+            .unwrap();
+        app.world_mut().despawn(slime);
+        app.update();
+        assert_eq!(
+            *app.world().resource::<WaveState>(),
+            WaveState::Intermission
+        );
+        assert!(enemies(&mut app).is_empty());
+
+        let banner = app.world().resource::<WaveQueue>().banner_timer.duration();
+        app.world_mut()
+            .resource_mut::<WaveQueue>()
+            .banner_timer
+            .tick(banner);
+        app.update();
+        assert_eq!(enemies(&mut app), vec![(5, 0), (5, 2)]);
+        assert_eq!(app.world().resource::<WaveQueue>().current, 2);
+    }
+
+    #[test]
+    fn enemies_sharing_a_tile_are_nudged_apart() {
+        let ownership = TileOwnership::default();
+        let wave = vec![
+            EnemyConfig::new(EnemyId::Mettaur, 4, 1),
+            EnemyConfig::new(EnemyId::Slime, 4, 1),
+            EnemyConfig::new(EnemyId::Slime2, 4, 1),
+        ];
+        let placed: Vec<(i32, i32)> = place_enemies(&wave, &ownership, &[(3, 1)])
+            .iter()
+            .map(|config| (config.start_x, config.start_y))
+            .collect();
+        // The first keeps its tile, the rest move to the nearest free ones
+        // (never onto the rock)
+        assert_eq!(placed, vec![(4, 1), (4, 0), (4, 2)]);
+
+        // More enemies than free enemy tiles: the extras are dropped
+        let crowd = vec![EnemyConfig::new(EnemyId::Slime, 5, 1); 12];
+        let placed = place_enemies(&crowd, &ownership, &[(3, 1)]);
+        let free_tiles = ownership.tiles_owned_by(TileOwner::Enemy).len() - 1;
+        assert_eq!(placed.len(), free_tiles);
+        let mut tiles: Vec<(i32, i32)> = placed
+            .iter()
+            .map(|config| (config.start_x, config.start_y))
+            .collect();
+        tiles.sort_unstable();
+        tiles.dedup();
+        assert_eq!(tiles.len(), free_tiles);
+    }
+
+    #[test]
+    fn mixed_wave_spawns_each_enemy_type_with_its_behaviors() {
+        let wave = vec![
+            EnemyConfig::new(EnemyId::Mettaur, 3, 0),
+            EnemyConfig::new(EnemyId::Canodumb, 5, 2),
+        ];
+        let mut app = arena_app(vec![wave.clone()]);
+        spawn_wave(&mut app, &wave);
+
+        let world = app.world_mut();
+        let mut spawned: Vec<_> = world
+            .query::<(&GridPosition, &EnemyMovement, &EnemyAttack)>()
+            .iter(world)
+            .map(|(pos, movement, attack)| {
+                (
+                    (pos.x, pos.y),
+                    movement.behavior.clone(),
+                    attack.behavior.clone(),
+                )
+            })
+            .collect();
+        spawned.sort_unstable_by_key(|(tile, _, _)| *tile);
+        assert_eq!(spawned.len(), 2);
+
+        let (tile, movement, attack) = &spawned[0];
+        assert_eq!(*tile, (3, 0));
+        assert!(matches!(movement, MovementBehavior::Stationary));
+        assert!(matches!(attack, AttackBehavior::ShockWave { .. }));
+
+        let (tile, movement, attack) = &spawned[1];
+        assert_eq!(*tile, (5, 2));
+        assert!(matches!(movement, MovementBehavior::BackRowOnly));
+        assert!(matches!(attack, AttackBehavior::Projectile { .. }));
+    }
+
+    #[test]
+    fn guard_plate_is_sized_from_the_tile() {
+        let plate = |layout: ArenaLayout| {
+            let wave = vec![EnemyConfig::new(EnemyId::Mettaur, 4, 1)];
+            let mut app = arena_app(vec![wave.clone()]);
+            app.insert_resource(layout);
+            spawn_wave(&mut app, &wave);
+            let world = app.world_mut();
+            let (sprite, transform) = world
+                .query_filtered::<(&Sprite, &Transform), With<GuardPlate>>()
+                .single(world)
+                .unwrap();
+            (sprite.custom_size.unwrap(), transform.translation.y)
+        };
+
+        let (size, rise) = plate(ArenaLayout::default());
+        let (wide_size, wide_rise) = plate(ArenaLayout::from_screen_size(2560.0, 1600.0));
+        assert!((wide_size - size * 2.0).length() < 1e-3);
+        assert!((wide_rise - rise * 2.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn chip_cooldown_upgrade_shortens_slot_cooldowns() {
+        let cooldown_with = |chip_cooldown_level| {
+            let mut world = World::new();
+            world.insert_resource(ArenaConfig {
+                fighter: crate::components::FighterConfig {
+                    actions: vec![ActionId::Cannon],
+                    ..default()
+                },
+                ..default()
+            });
+            world.insert_resource(PlayerUpgrades {
+                chip_cooldown_level,
+                ..default()
+            });
+            let mut schedule = Schedule::default();
+            schedule.add_systems(spawn_player_actions);
+            schedule.run(&mut world);
+            world
+                .query::<&ActionSlot>()
+                .single(&world)
+                .unwrap()
+                .cooldown_timer
+                .duration()
+                .as_secs_f32()
+        };
+
+        let base = ActionBlueprint::get(ActionId::Cannon).cooldown;
+        assert!((cooldown_with(0) - base).abs() < 1e-4);
+        assert!((cooldown_with(2) - base * 0.8).abs() < 1e-4);
+        // The reduction is capped at half the cooldown
+        assert!((cooldown_with(10) - base * 0.5).abs() < 1e-4);
+    }
+}
@@ -0,0 +1,111 @@
+//! Camera shake for impactful moments (explosions, charged hits, heavy damage).
+//!
+//! Systems add trauma to the [`ScreenShake`] resource; `apply_screen_shake`
+//! offsets the camera by trauma² scaled noise and lets trauma decay back to zero.
+
+use bevy::prelude::*;
+
+/// Largest camera offset in pixels, reached at full trauma
+const SHAKE_MAX_OFFSET: f32 = 14.0;
+/// Trauma lost per second
+const SHAKE_DECAY: f32 = 1.8;
+
+/// Trauma from a bomb explosion
+pub const TRAUMA_EXPLOSION: f32 = 0.5;
+/// Trauma when a charged shot lands
+pub const TRAUMA_CHARGED_HIT: f32 = 0.3;
+/// Damage taken at or above which the player's hit shakes the screen
+const HEAVY_HIT_DAMAGE: i32 = 30;
+/// Trauma from a heavy hit on the player
+const TRAUMA_HEAVY_HIT: f32 = 0.45;
+
+/// Accumulated camera trauma in `0.0..=1.0`
+#[derive(Resource, Debug, Default, Clone, Copy)]
+pub struct ScreenShake {
+    pub trauma: f32,
+}
+
+impl ScreenShake {
+    pub fn add_trauma(&mut self, amount: f32) {
+        self.trauma = (self.trauma + amount).clamp(0.0, 1.0);
+    }
+
+    /// Shake only for hits big enough to matter
+    pub fn player_hit(&mut self, damage: i32) {
+        if damage >= HEAVY_HIT_DAMAGE {
+            self.add_trauma(TRAUMA_HEAVY_HIT);
+        }
+    }
+
+    pub fn decay(&mut self, dt: f32) {
+        self.trauma = (self.trauma - SHAKE_DECAY * dt).max(0.0);
+    }
+
+    /// Camera offset at time `t`. Squaring trauma keeps small shakes subtle.
+    pub fn offset(&self, t: f32) -> Vec2 {
+        let strength = self.trauma * self.trauma * SHAKE_MAX_OFFSET;
+        Vec2::new(shake_noise(t, 0.0), shake_noise(t, 1.7)) * strength
+    }
+}
+
+/// Smooth pseudo-noise in `-1.0..=1.0` from mismatched sine waves
+fn shake_noise(t: f32, seed: f32) -> f32 {
+    ((t * 37.0 + seed).sin() * 0.6 + (t * 59.0 + seed * 2.3).sin() * 0.4).clamp(-1.0, 1.0)
+}
+
+/// Offset the camera by the current shake and decay trauma
+pub fn apply_screen_shake(
+    time: Res<Time>,
+    mut shake: ResMut<ScreenShake>,
+    mut camera_query: Query<&mut Transform, With<Camera2d>>,
+) {
+    let offset = shake.offset(time.elapsed_secs());
+    for mut transform in &mut camera_query {
+        transform.translation.x = offset.x;
+        transform.translation.y = offset.y;
+    }
+    shake.decay(time.delta_secs());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trauma_decays_to_zero() {
+        let mut shake = ScreenShake::default();
+        shake.add_trauma(TRAUMA_EXPLOSION);
+        shake.decay(0.1);
+        assert!(shake.trauma > 0.0 && shake.trauma < TRAUMA_EXPLOSION);
+
+        for _ in 0..60 {
+            shake.decay(1.0 / 60.0);
+        }
+        assert_eq!(shake.trauma, 0.0);
+        assert_eq!(shake.offset(0.4), Vec2::ZERO);
+    }
+
+    #[test]
+    fn more_trauma_shakes_harder() {
+        let t = 0.4;
+        let mut shake = ScreenShake::default();
+        shake.add_trauma(0.3);
+        let small = shake.offset(t).length();
+        shake.add_trauma(0.4);
+        let large = shake.offset(t).length();
+        assert!(small > 0.0);
+        assert!(large > small);
+
+        shake.add_trauma(5.0);
+        assert_eq!(shake.trauma, 1.0);
+    }
+
+    #[test]
+    fn only_heavy_hits_add_trauma() {
+        let mut shake = ScreenShake::default();
+        shake.player_hit(HEAVY_HIT_DAMAGE - 1);
+        assert_eq!(shake.trauma, 0.0);
+        shake.player_hit(HEAVY_HIT_DAMAGE);
+        assert_eq!(shake.trauma, TRAUMA_HEAVY_HIT);
+    }
+}
@@ -2,6 +2,8 @@ use bevy::prelude::*;
 
 use crate::components::{CleanupOnStateExit, GameState};
 use crate::constants::*;
+use crate::resources::CampaignProgress;
+use crate::systems::tutorial::start_tutorial;
 
 /// Marker for the splash screen container
 #[derive(Component)]
@@ -77,7 +79,9 @@ pub fn setup_splash(mut commands: Commands) {
 
 /// Handle splash screen input and timing
 pub fn update_splash(
+    mut commands: Commands,
     mut next_state: ResMut<NextState<GameState>>,
+    campaign: Res<CampaignProgress>,
     keyboard: Res<ButtonInput<KeyCode>>,
     gamepads: Query<&Gamepad>,
     mut timer: ResMut<SplashTimer>,
@@ -105,9 +109,13 @@ pub fn update_splash(
         }
     }
 
-    // Advance on timer completion or input
+    // Advance on timer completion or input; first launch goes to the tutorial
     if timer.0.is_finished() || input_detected {
-        next_state.set(GameState::MainMenu);
+        if campaign.tutorial_complete {
+            next_state.set(GameState::MainMenu);
+        } else {
+            start_tutorial(&mut commands, &mut next_state);
+        }
     }
 }
 
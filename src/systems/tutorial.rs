@@ -0,0 +1,251 @@
+//! First-launch tutorial: a scripted battle against a harmless slime.
+//!
+//! The splash screen starts `ArenaConfig::tutorial` instead of the main menu
+//! until `CampaignProgress::tutorial_complete` is saved. `update_tutorial`
+//! freezes the battle at each beat of the `TutorialScript` and shows its
+//! prompt, naming the keys currently bound to the beat's input; that input
+//! resumes play, and the next prompt follows after a
+//! short stretch of free play.
+
+use bevy::prelude::*;
+
+use crate::bindings::{InputAction, KeyBindings};
+use crate::components::{ArenaConfig, CleanupOnStateExit, GameState};
+use crate::constants::*;
+use crate::resources::{
+    CampaignProgress, LoadoutPresets, OwnedChips, PlayerCurrency, PlayerLoadout, PlayerUpgrades,
+};
+use crate::systems::autosave::AutoSave;
+use crate::systems::growth::GrowthTreeState;
+use crate::weapons::PlayerWeapons;
+
+/// Prompt shown once every beat has been played
+const TUTORIAL_FINISH_PROMPT: &str = "Now delete the slime!";
+
+/// One beat of the tutorial: the input that clears it and what it does
+#[derive(Debug, Clone, Copy)]
+pub struct TutorialStep {
+    pub input: InputAction,
+    /// Rest of the prompt after "Press <keys>"
+    pub hint: &'static str,
+}
+
+impl TutorialStep {
+    /// "Press <bound keys> <hint>", so rebound controls show up in the prompt
+    pub fn prompt(&self, bindings: &KeyBindings) -> String {
+        format!("Press {} {}", bindings.keys_label(self.input), self.hint)
+    }
+}
+
+/// Tutorial beats in order, and how far the player has got
+#[derive(Resource, Debug)]
+pub struct TutorialScript {
+    steps: Vec<TutorialStep>,
+    current: usize,
+    /// The battle is frozen on the current step's prompt
+    pub waiting: bool,
+    /// Free play before the next prompt appears
+    pub gap: Timer,
+}
+
+impl Default for TutorialScript {
+    fn default() -> Self {
+        Self::new(vec![
+            TutorialStep {
+                input: InputAction::MoveUp,
+                hint: "to move up",
+            },
+            TutorialStep {
+                input: InputAction::Fire,
+                hint: "to fire your buster",
+            },
+            TutorialStep {
+                input: InputAction::Action1,
+                hint: "to use a chip",
+            },
+        ])
+    }
+}
+
+impl TutorialScript {
+    pub fn new(steps: Vec<TutorialStep>) -> Self {
+        Self {
+            steps,
+            current: 0,
+            waiting: false,
+            gap: Timer::from_seconds(TUTORIAL_BEAT_GAP, TimerMode::Once),
+        }
+    }
+
+    /// Step waiting to be cleared (None once the script is done)
+    pub fn current_step(&self) -> Option<&TutorialStep> {
+        self.steps.get(self.current)
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.current >= self.steps.len()
+    }
+
+    /// Move past the current step if its input is pressed; other inputs are ignored
+    pub fn advance(&mut self, pressed: impl Fn(InputAction) -> bool) -> bool {
+        let cleared = self.current_step().is_some_and(|step| pressed(step.input));
+        if cleared {
+            self.current += 1;
+        }
+        cleared
+    }
+}
+
+/// Tutorial prompt text marker
+#[derive(Component)]
+pub struct TutorialPrompt;
+
+/// Run condition: the current arena is the tutorial
+pub fn tutorial_active(script: Option<Res<TutorialScript>>) -> bool {
+    script.is_some()
+}
+
+/// Start the tutorial battle (first launch)
+pub fn start_tutorial(commands: &mut Commands, next_state: &mut NextState<GameState>) {
+    commands.insert_resource(ArenaConfig::tutorial());
+    next_state.set(GameState::Playing);
+}
+
+/// In the tutorial arena, load the script and spawn the (empty) prompt
+pub fn setup_tutorial(mut commands: Commands, config: Res<ArenaConfig>) {
+    if !config.tutorial {
+        return;
+    }
+    commands.insert_resource(TutorialScript::default());
+    commands.spawn((
+        Text2d::new(""),
+        TextLayout::new_with_justify(Justify::Center),
+        TextFont::from_font_size(32.0),
+        TextColor(COLOR_TEXT),
+        Transform::from_xyz(0.0, 200.0, Z_UI),
+        TutorialPrompt,
+        CleanupOnStateExit(GameState::Playing),
+    ));
+}
+
+/// Freeze on each beat's prompt until its input is pressed
+pub fn update_tutorial(
+    real_time: Res<Time<Real>>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<&Gamepad>,
+    bindings: Res<KeyBindings>,
+    mut script: ResMut<TutorialScript>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+    mut prompt_query: Query<&mut Text2d, With<TutorialPrompt>>,
+) {
+    let prompt = if script.waiting {
+        if !script.advance(|action| bindings.just_pressed(action, &keyboard, &gamepads)) {
+            return;
+        }
+        script.waiting = false;
+        script.gap.reset();
+        virtual_time.unpause();
+        if script.is_complete() {
+            TUTORIAL_FINISH_PROMPT.to_string()
+        } else {
+            String::new()
+        }
+    } else {
+        if script.is_complete() {
+            return;
+        }
+        script.gap.tick(real_time.delta());
+        if !script.gap.is_finished() {
+            return;
+        }
+        script.waiting = true;
+        virtual_time.pause();
+        script
+            .current_step()
+            .map_or(String::new(), |step| step.prompt(&bindings))
+    };
+
+    for mut text in &mut prompt_query {
+        text.0 = prompt.clone();
+    }
+}
+
+/// Save the tutorial as done once every beat has been cleared
+pub fn finish_tutorial(
+    script: Res<TutorialScript>,
+    mut campaign: ResMut<CampaignProgress>,
+    currency: Res<PlayerCurrency>,
+    upgrades: Res<PlayerUpgrades>,
+    growth: Res<GrowthTreeState>,
+    loadout: Res<PlayerLoadout>,
+    weapons: Res<PlayerWeapons>,
+    owned_chips: Res<OwnedChips>,
+    presets: Res<LoadoutPresets>,
+    mut autosave: AutoSave,
+) {
+    if !script.is_complete() || campaign.tutorial_complete {
+        return;
+    }
+    campaign.tutorial_complete = true;
+    autosave.save(
+        &campaign,
+        &currency,
+        &upgrades,
+        &growth,
+        &loadout,
+        &weapons,
+        &owned_chips,
+        &presets,
+    );
+}
+
+/// Drop the script when the battle ends
+pub fn cleanup_tutorial(mut commands: Commands) {
+    commands.remove_resource::<TutorialScript>();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn script_only_advances_on_the_current_steps_input() {
+        let mut script = TutorialScript::default();
+        let only = |expected: InputAction| move |action: InputAction| action == expected;
+
+        assert_eq!(script.current_step().unwrap().input, InputAction::MoveUp);
+        assert!(!script.advance(only(InputAction::Fire)));
+        assert!(!script.advance(only(InputAction::Action1)));
+        assert!(!script.advance(|_| false));
+        assert_eq!(script.current_step().unwrap().input, InputAction::MoveUp);
+
+        assert!(script.advance(only(InputAction::MoveUp)));
+        assert_eq!(script.current_step().unwrap().input, InputAction::Fire);
+        // The step just cleared no longer counts
+        assert!(!script.advance(only(InputAction::MoveUp)));
+
+        assert!(script.advance(only(InputAction::Fire)));
+        assert!(!script.is_complete());
+        assert!(script.advance(only(InputAction::Action1)));
+        assert!(script.is_complete());
+        assert!(script.current_step().is_none());
+        assert!(!script.advance(|_| true));
+    }
+
+    #[test]
+    fn prompts_name_the_keys_currently_bound() {
+        let script = TutorialScript::default();
+        let step = script.current_step().unwrap();
+        let mut bindings = KeyBindings::default();
+        assert_eq!(
+            step.prompt(&bindings),
+            format!(
+                "Press {} to move up",
+                bindings.keys_label(InputAction::MoveUp)
+            )
+        );
+
+        bindings.rebind_key(InputAction::MoveUp, KeyCode::KeyI);
+        assert_eq!(step.prompt(&bindings), "Press KeyI to move up");
+    }
+}
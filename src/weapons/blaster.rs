@@ -4,7 +4,8 @@
 //!
 //! ## Characteristics
 //! - **Single Shot**: Tap to fire one projectile. No automatic fire.
-//! - **Charged Shot**: Hold to charge, release to fire a powerful shot.
+//! - **Charged Shot**: Hold to charge, release to fire a bigger, faster shot
+//!   that pierces every enemy in the row.
 //! - **Balanced**: Not spammable, but consistent damage output.
 //!
 //! ## Strategy
@@ -34,6 +35,7 @@ pub mod constants {
     // Projectile
     pub const BLASTER_RANGE: i32 = 6; // Full arena width
     pub const BLASTER_PROJECTILE_SPEED: f32 = 8.33; // ~120ms per tile (matches BULLET_MOVE_TIMER)
+    pub const BLASTER_CHARGED_SPEED: f32 = 12.5; // ~80ms per tile
     pub const BLASTER_PROJECTILE_SIZE: Vec2 = Vec2::new(16.0, 16.0);
     pub const BLASTER_CHARGED_SIZE: Vec2 = Vec2::new(28.0, 28.0);
 
@@ -52,13 +54,13 @@ pub fn blaster_stats() -> WeaponStats {
         // Normal shot: 1 damage, filler shots
         damage: DamageConfig {
             amount: BLASTER_DAMAGE,
-            damage_type: DamageType::Physical,
+            damage_type: DamageType::Electric,
         },
 
         // Charged shot: 5 damage, rewarding timing mastery
         charged_damage: Some(DamageConfig {
             amount: BLASTER_CHARGED_DAMAGE,
-            damage_type: DamageType::Physical,
+            damage_type: DamageType::Electric,
         }),
 
         // Charge time - not too long, but requires commitment
@@ -78,11 +80,13 @@ pub fn blaster_stats() -> WeaponStats {
         // No falloff for blaster - consistent damage at all ranges
         falloff: FalloffConfig::none(),
 
-        // Full arena range
+        // Full arena range, stops at the first enemy
         range: BLASTER_RANGE,
+        piercing: false,
 
-        // Fast projectile
+        // Fast projectile; the charged shot is faster still and pierces
         projectile_speed: BLASTER_PROJECTILE_SPEED,
+        charged_projectile_speed: BLASTER_CHARGED_SPEED,
 
         // Visual configuration
         projectile_size: BLASTER_PROJECTILE_SIZE,
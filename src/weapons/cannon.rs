@@ -0,0 +1,90 @@
+//! Cannon - A heavy, slow-firing alternative to the Blaster
+//!
+//! Trades the Blaster's charge mechanic for raw per-shot damage.
+//!
+//! ## Characteristics
+//! - **Heavy Shot**: Every shot hits hard, but there is no charged shot.
+//! - **Slow**: Long cooldown between shots punishes missed timing.
+//! - **Falloff**: Loses damage against enemies in the back columns.
+//!
+//! ## Strategy
+//! - Step forward to fire at close range for full damage
+//! - Line up shots carefully; a miss costs almost a second of output
+
+use super::{CriticalConfig, DamageConfig, DamageType, FalloffConfig, WeaponStats};
+use bevy::prelude::*;
+
+/// Cannon weapon constants
+pub mod constants {
+    use bevy::prelude::*;
+
+    // Damage
+    pub const CANNON_DAMAGE: i32 = 3;
+
+    // Timing
+    pub const CANNON_FIRE_COOLDOWN: f32 = 0.8; // Cooldown after each shot
+
+    // Critical hits
+    pub const CANNON_CRIT_CHANCE: f32 = 0.12; // 12% crit chance
+    pub const CANNON_CRIT_MULTIPLIER: f32 = 2.0; // 2x on crit
+
+    // Falloff
+    pub const CANNON_FALLOFF_START: i32 = 3; // Full damage up to 3 tiles
+    pub const CANNON_FALLOFF_END: i32 = 5; // Minimum damage from 5 tiles
+    pub const CANNON_FALLOFF_MIN: f32 = 0.5; // 50% damage at max range
+
+    // Projectile
+    pub const CANNON_RANGE: i32 = 6; // Full arena width
+    pub const CANNON_PROJECTILE_SPEED: f32 = 8.33; // Same travel time as the Blaster
+    pub const CANNON_PROJECTILE_SIZE: Vec2 = Vec2::new(24.0, 24.0);
+
+    // Colors
+    pub const CANNON_COLOR: Color = Color::srgb(1.0, 0.6, 0.2); // Orange shell
+}
+
+use constants::*;
+
+/// Create the stats for the Cannon weapon
+pub fn cannon_stats() -> WeaponStats {
+    WeaponStats {
+        name: "Cannon".to_string(),
+
+        // Normal shot: 3 damage, no charge
+        damage: DamageConfig {
+            amount: CANNON_DAMAGE,
+            damage_type: DamageType::Fire,
+        },
+        charged_damage: None,
+        charge_time: 0.0,
+
+        // Rarer than damage, but crits hit very hard
+        critical: CriticalConfig {
+            chance: CANNON_CRIT_CHANCE,
+            multiplier: CANNON_CRIT_MULTIPLIER,
+            orange_multiplier: 3.0,
+            red_multiplier: 4.0,
+        },
+
+        // Slow cooldown - every shot should count
+        fire_cooldown: CANNON_FIRE_COOLDOWN,
+
+        // Weaker against the back columns
+        falloff: FalloffConfig {
+            start_range: CANNON_FALLOFF_START,
+            end_range: CANNON_FALLOFF_END,
+            min_multiplier: CANNON_FALLOFF_MIN,
+        },
+
+        // Full arena range, stops at the first enemy
+        range: CANNON_RANGE,
+        piercing: false,
+        projectile_speed: CANNON_PROJECTILE_SPEED,
+        charged_projectile_speed: CANNON_PROJECTILE_SPEED,
+
+        // Visual configuration (no charged shot, so both look the same)
+        projectile_size: CANNON_PROJECTILE_SIZE,
+        projectile_color: CANNON_COLOR,
+        charged_projectile_size: CANNON_PROJECTILE_SIZE,
+        charged_projectile_color: CANNON_COLOR,
+    }
+}
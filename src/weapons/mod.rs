@@ -9,11 +9,15 @@
 //! - Range: Maximum distance the weapon can hit
 
 pub mod blaster;
+pub mod cannon;
 
 use crate::assets::{ProjectileAnimation, ProjectileSprites};
-use crate::resources::PlayerUpgrades;
+use crate::bindings::{InputAction, KeyBindings};
+use crate::resources::{ArenaLayout, BattleStats, GameRng, PlayerUpgrades, ReduceFlashing};
 use bevy::image::TextureAtlas;
 use bevy::prelude::*;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
 
 // ============================================================================
 // Weapon Stats & Types
@@ -30,6 +34,19 @@ pub enum DamageType {
     Void,
 }
 
+impl DamageType {
+    /// Tint for projectiles and their trails (Physical keeps the sprite's own colors)
+    pub fn tint(&self) -> Color {
+        match self {
+            DamageType::Physical => Color::WHITE,
+            DamageType::Fire => Color::srgb(1.0, 0.5, 0.25),
+            DamageType::Ice => Color::srgb(0.55, 0.85, 1.0),
+            DamageType::Electric => Color::srgb(1.0, 0.95, 0.35),
+            DamageType::Void => Color::srgb(0.7, 0.4, 1.0),
+        }
+    }
+}
+
 /// Damage configuration for a weapon
 #[derive(Debug, Clone)]
 pub struct DamageConfig {
@@ -85,8 +102,8 @@ pub enum CritResult {
 
 impl CriticalConfig {
     /// Roll for a critical hit and return the result
-    pub fn roll(&self) -> CritResult {
-        let roll: f32 = rand::random();
+    pub fn roll(&self, rng: &mut impl Rng) -> CritResult {
+        let roll: f32 = rng.random();
 
         if self.chance >= 2.0 {
             // Guaranteed orange crit, chance for red
@@ -165,6 +182,11 @@ impl FalloffConfig {
             1.0 - (1.0 - self.min_multiplier) * progress
         }
     }
+
+    /// Multiplier for each distance from 1 to `max_range` tiles (index 0 = 1 tile away)
+    pub fn multipliers(&self, max_range: i32) -> Vec<f32> {
+        (1..=max_range).map(|d| self.get_multiplier(d)).collect()
+    }
 }
 
 /// Complete weapon statistics
@@ -186,8 +208,12 @@ pub struct WeaponStats {
     pub falloff: FalloffConfig,
     /// Maximum range in tiles (projectile despawns after this)
     pub range: i32,
+    /// Shots pass through enemies (hitting each once) instead of stopping at the first
+    pub piercing: bool,
     /// Projectile speed (tiles per second)
     pub projectile_speed: f32,
+    /// Charged projectile speed (tiles per second)
+    pub charged_projectile_speed: f32,
     /// Visual: projectile size
     pub projectile_size: Vec2,
     /// Visual: projectile color (normal shot)
@@ -227,7 +253,9 @@ impl Default for WeaponStats {
             fire_cooldown: 0.35,
             falloff: FalloffConfig::default(),
             range: 6,
+            piercing: false,
             projectile_speed: 8.33, // tiles per second (matches 0.12s move timer)
+            charged_projectile_speed: 12.5,
             projectile_size: Vec2::new(18.0, 18.0),
             projectile_color: Color::srgb(1.0, 0.95, 0.2), // Yellow
             charged_projectile_color: Color::srgb(1.0, 0.5, 0.1), // Orange
@@ -241,10 +269,11 @@ impl Default for WeaponStats {
 // ============================================================================
 
 /// Enum of all available weapon types
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
 pub enum WeaponType {
     #[default]
     Blaster,
+    Cannon,
     // Future weapons:
     // Spreader,     // Multiple projectiles in a cone
     // Railgun,      // Instant hit, high damage, long charge
@@ -253,14 +282,60 @@ pub enum WeaponType {
 }
 
 impl WeaponType {
+    /// All weapon types, in cycling order
+    pub const ALL: [WeaponType; 2] = [WeaponType::Blaster, WeaponType::Cannon];
+
     /// Get the stats for this weapon type
     pub fn stats(&self) -> WeaponStats {
         match self {
             WeaponType::Blaster => blaster::blaster_stats(),
+            WeaponType::Cannon => cannon::cannon_stats(),
         }
     }
 }
 
+/// Resource tracking which weapons the player owns and which one is equipped
+#[derive(Resource, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PlayerWeapons {
+    pub unlocked: Vec<WeaponType>,
+    pub equipped: WeaponType,
+}
+
+impl Default for PlayerWeapons {
+    fn default() -> Self {
+        Self {
+            unlocked: vec![WeaponType::Blaster],
+            equipped: WeaponType::Blaster,
+        }
+    }
+}
+
+impl PlayerWeapons {
+    pub fn is_unlocked(&self, weapon: WeaponType) -> bool {
+        self.unlocked.contains(&weapon)
+    }
+
+    /// Add a weapon to the owned list (no-op if already owned)
+    pub fn unlock(&mut self, weapon: WeaponType) {
+        if !self.is_unlocked(weapon) {
+            self.unlocked.push(weapon);
+        }
+    }
+
+    /// Next unlocked weapon after `current` in `WeaponType::ALL` order (wraps around)
+    pub fn next_after(&self, current: WeaponType) -> WeaponType {
+        let start = WeaponType::ALL
+            .iter()
+            .position(|w| *w == current)
+            .unwrap_or(0);
+        (1..=WeaponType::ALL.len())
+            .map(|offset| WeaponType::ALL[(start + offset) % WeaponType::ALL.len()])
+            .find(|w| self.is_unlocked(*w))
+            .unwrap_or(current)
+    }
+}
+
 // ============================================================================
 // Weapon Components
 // ============================================================================
@@ -289,6 +364,13 @@ impl EquippedWeapon {
             weapon_type,
         }
     }
+
+    /// Create a weapon with player upgrades already applied to its stats
+    pub fn with_upgrades(weapon_type: WeaponType, upgrades: &PlayerUpgrades) -> Self {
+        let mut weapon = Self::new(weapon_type);
+        weapon.stats.apply_upgrades(upgrades);
+        weapon
+    }
 }
 
 /// State of weapon firing/charging
@@ -312,6 +394,11 @@ pub struct WeaponState {
     pub fire_held: bool,
     /// Whether a charged shot is ready to release
     pub charge_ready: bool,
+    /// A fire press made during cooldown fires once the cooldown ends, if it
+    /// ends before this time (seconds since startup)
+    pub buffered_until: Option<f32>,
+    /// Tile the player stood on last frame (to spot moves while charging)
+    pub last_tile: Option<(i32, i32)>,
 }
 
 impl Default for WeaponState {
@@ -322,6 +409,8 @@ impl Default for WeaponState {
             charge_timer: None,
             fire_held: false,
             charge_ready: false,
+            buffered_until: None,
+            last_tile: None,
         }
     }
 }
@@ -336,6 +425,8 @@ impl WeaponState {
             charge_timer: None,
             fire_held: false,
             charge_ready: false,
+            buffered_until: None,
+            last_tile: None,
         }
     }
 
@@ -343,6 +434,18 @@ impl WeaponState {
         self.firing_state == WeaponFiringState::Ready && self.cooldown_timer.is_finished()
     }
 
+    /// Remember a press made at `now` during cooldown for `INPUT_BUFFER_TIME`
+    pub fn buffer_press(&mut self, now: f32) {
+        if self.firing_state == WeaponFiringState::OnCooldown {
+            self.buffered_until = Some(now + INPUT_BUFFER_TIME);
+        }
+    }
+
+    /// Consume a buffered press that is still fresh at `now`
+    pub fn take_buffered(&mut self, now: f32) -> bool {
+        self.buffered_until.take().is_some_and(|until| now <= until)
+    }
+
     pub fn start_cooldown(&mut self, duration: f32) {
         self.firing_state = WeaponFiringState::OnCooldown;
         self.cooldown_timer = Timer::from_seconds(duration, TimerMode::Once);
@@ -362,6 +465,83 @@ impl WeaponState {
             .map(|t| t.fraction())
             .unwrap_or(0.0)
     }
+
+    /// Take back charge for a move made while charging, per `rule`
+    pub fn lose_charge(&mut self, rule: ChargeMoveRule) {
+        let Some(timer) = self.charge_timer.as_mut() else {
+            return;
+        };
+        match rule {
+            ChargeMoveRule::Free => {}
+            ChargeMoveRule::Slow => {
+                let penalty = timer.duration().mul_f32(CHARGE_MOVE_PENALTY);
+                let elapsed = timer.elapsed().saturating_sub(penalty);
+                timer.set_elapsed(elapsed);
+            }
+            ChargeMoveRule::Reset => timer.reset(),
+        }
+        self.charge_ready = timer.is_finished();
+    }
+
+    /// Knock out a charge in progress (the player was hit): no shot, and the
+    /// cooldown a release at this point would have left. Returns false if the
+    /// weapon wasn't charging.
+    pub fn interrupt_charge(&mut self, full_cooldown: f32) -> bool {
+        if self.firing_state != WeaponFiringState::Charging {
+            return false;
+        }
+        self.start_cooldown(cancel_cooldown(self.charge_progress(), full_cooldown));
+        true
+    }
+
+    /// Fill fraction of the charge bar over the player (None while not charging)
+    pub fn charge_bar_fill(&self) -> Option<f32> {
+        (self.firing_state == WeaponFiringState::Charging).then(|| self.charge_progress())
+    }
+}
+
+/// How moving affects a weapon charge in progress (picked on the settings screen)
+#[derive(Resource, Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ChargeMoveRule {
+    /// Charge keeps filling while moving
+    #[default]
+    Free,
+    /// Each move knocks back `CHARGE_MOVE_PENALTY` of the full charge
+    Slow,
+    /// Each move starts the charge over
+    Reset,
+}
+
+impl ChargeMoveRule {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ChargeMoveRule::Free => "Free",
+            ChargeMoveRule::Slow => "Slow",
+            ChargeMoveRule::Reset => "Reset",
+        }
+    }
+
+    /// Next rule in settings order (wraps around)
+    pub fn next(self) -> Self {
+        match self {
+            ChargeMoveRule::Free => ChargeMoveRule::Slow,
+            ChargeMoveRule::Slow => ChargeMoveRule::Reset,
+            ChargeMoveRule::Reset => ChargeMoveRule::Free,
+        }
+    }
+}
+
+/// Fraction of the full charge lost per tile moved under `ChargeMoveRule::Slow`
+const CHARGE_MOVE_PENALTY: f32 = 0.5;
+
+/// Shortest cooldown a cancelled charge can leave, as a fraction of the full one
+const MIN_CANCEL_COOLDOWN: f32 = 0.1;
+
+/// Cooldown after releasing a charge early: it grows with how far the charge
+/// got, so a quick tap refunds almost all of `full_cooldown` and a nearly
+/// finished charge refunds little.
+pub fn cancel_cooldown(charge_fraction: f32, full_cooldown: f32) -> f32 {
+    full_cooldown * charge_fraction.clamp(MIN_CANCEL_COOLDOWN, 1.0)
 }
 
 /// Marker component for projectiles fired from weapons
@@ -383,9 +563,35 @@ pub struct Projectile {
     pub falloff: FalloffConfig,
     /// Maximum range
     pub max_range: i32,
+    /// Passes through enemies instead of stopping at the first one
+    pub piercing: bool,
+    /// Enemies (and rocks) already damaged by this (piercing) projectile
+    pub hit_enemies: Vec<Entity>,
 }
 
 impl Projectile {
+    /// Plain physical shot fired from column 1, for tests
+    #[cfg(test)]
+    pub fn test_shot(damage: i32, max_range: i32) -> Self {
+        Self {
+            damage,
+            damage_type: DamageType::Physical,
+            is_charged: false,
+            origin_x: 1,
+            crit_result: CritResult::Normal,
+            crit_multiplier: 1.0,
+            falloff: FalloffConfig::none(),
+            max_range,
+            piercing: false,
+            hit_enemies: Vec::new(),
+        }
+    }
+
+    /// Whether the projectile at `current_x` is still within its maximum range
+    pub fn in_range(&self, current_x: i32) -> bool {
+        (current_x - self.origin_x).abs() <= self.max_range
+    }
+
     /// Calculate final damage based on distance traveled and crit
     pub fn calculate_damage(&self, current_x: i32) -> i32 {
         let distance = (current_x - self.origin_x).abs();
@@ -403,15 +609,20 @@ pub struct WeaponPlugin;
 
 impl Plugin for WeaponPlugin {
     fn build(&self, app: &mut App) {
+        app.init_resource::<FalloffOverlay>();
+        app.init_resource::<ChargeMoveRule>();
         app.add_systems(
             Update,
             (
                 weapon_input_system,
                 weapon_cooldown_system,
+                update_charge_bar.after(weapon_input_system),
                 projectile_hit_system,
+                update_falloff_overlay,
             )
                 .run_if(in_state(crate::components::GameState::Playing))
-                .run_if(crate::systems::intro::intro_complete),
+                .run_if(crate::systems::intro::intro_complete)
+                .run_if(crate::systems::pause::not_paused),
         );
     }
 }
@@ -421,43 +632,89 @@ impl Plugin for WeaponPlugin {
 // ============================================================================
 
 use crate::components::{
-    Bullet, Enemy, EnemyBullet, FlashTimer, GridPosition, Health, HealthText, Lifetime, MoveTimer,
-    MuzzleFlash, Player, ProjectileHit, ProjectileImmobile, RenderConfig, TargetsTiles,
+    Bullet, ChargeBar, ChargeBarFill, Enemy, EnemyBullet, FlashTimer, GridPosition, Health,
+    HealthText, Lifetime, MoveTimer, MuzzleFlash, Player, ProjectileHit, ProjectileImmobile,
+    ProjectileTrail, RenderConfig, TargetsTiles, Untargetable,
 };
 use crate::constants::*;
+use crate::enemies::{EnemyAttack, EnemyGuard, EnemyTraitContainer};
+use crate::systems::combat::{counter_hit, kill_enemy};
+use crate::systems::rumble::Rumble;
+use crate::systems::shake::{ScreenShake, TRAUMA_CHARGED_HIT};
+
+/// Debug overlay tinting the tiles ahead of the player by the weapon's damage falloff
+#[derive(Resource, Debug, Default)]
+pub struct FalloffOverlay {
+    pub enabled: bool,
+    /// Tiles ahead of the player with their falloff multiplier
+    pub tiles: Vec<((i32, i32), f32)>,
+}
+
+impl FalloffOverlay {
+    pub fn multiplier_at(&self, x: i32, y: i32) -> Option<f32> {
+        self.tiles
+            .iter()
+            .find(|(tile, _)| *tile == (x, y))
+            .map(|(_, multiplier)| *multiplier)
+    }
+}
 
-/// Handle weapon input (fire button press/hold/release)
+/// Toggle the falloff overlay (F4) and rebuild it from the player's row and weapon
+pub fn update_falloff_overlay(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    layout: Res<ArenaLayout>,
+    mut overlay: ResMut<FalloffOverlay>,
+    player_query: Query<(&GridPosition, &EquippedWeapon), With<Player>>,
+) {
+    if keyboard.just_pressed(KeyCode::F4) {
+        overlay.enabled = !overlay.enabled;
+    }
+
+    overlay.tiles.clear();
+    if !overlay.enabled {
+        return;
+    }
+    let Ok((pos, weapon)) = player_query.single() else {
+        return;
+    };
+    let multipliers = weapon.stats.falloff.multipliers(weapon.stats.range);
+    overlay.tiles.extend(
+        (pos.x + 1..layout.grid.width)
+            .zip(multipliers)
+            .map(|(x, multiplier)| ((x, pos.y), multiplier)),
+    );
+}
+
+/// Handle weapon input (weapon switching and fire button press/hold/release)
 pub fn weapon_input_system(
     mut commands: Commands,
     keyboard: Res<ButtonInput<KeyCode>>,
     gamepads: Query<&Gamepad>,
     time: Res<Time>,
     projectiles: Res<ProjectileSprites>,
-    mut query: Query<(&GridPosition, &EquippedWeapon, &mut WeaponState), With<Player>>,
+    upgrades: Res<PlayerUpgrades>,
+    bindings: Res<KeyBindings>,
+    charge_rule: Res<ChargeMoveRule>,
+    mut rng: ResMut<GameRng>,
+    mut player_weapons: ResMut<PlayerWeapons>,
+    mut stats: ResMut<BattleStats>,
+    mut query: Query<(&GridPosition, &mut EquippedWeapon, &mut WeaponState), With<Player>>,
 ) {
-    for (player_pos, weapon, mut state) in &mut query {
-        let mut fire_pressed = keyboard.just_pressed(KeyCode::Space);
-        let mut fire_held = keyboard.pressed(KeyCode::Space);
-        let mut fire_released = keyboard.just_released(KeyCode::Space);
-
-        // Gamepad Input
-        for gamepad in gamepads.iter() {
-            if gamepad.just_pressed(GamepadButton::South)
-                || gamepad.just_pressed(GamepadButton::RightTrigger2)
-            {
-                fire_pressed = true;
-            }
-            if gamepad.pressed(GamepadButton::South)
-                || gamepad.pressed(GamepadButton::RightTrigger2)
-            {
-                fire_held = true;
-            }
-            if gamepad.just_released(GamepadButton::South)
-                || gamepad.just_released(GamepadButton::RightTrigger2)
-            {
-                fire_released = true;
+    for (player_pos, mut weapon, mut state) in &mut query {
+        // Weapon switching: Tab / RB (by default) cycles through unlocked weapons
+        if bindings.just_pressed(InputAction::SwitchWeapon, &keyboard, &gamepads) {
+            let next = player_weapons.next_after(weapon.weapon_type);
+            if next != weapon.weapon_type {
+                *weapon = EquippedWeapon::with_upgrades(next, &upgrades);
+                *state = WeaponState::new(weapon.stats.fire_cooldown);
+                player_weapons.equipped = next;
             }
         }
+        let weapon = &*weapon;
+
+        let fire_pressed = bindings.just_pressed(InputAction::Fire, &keyboard, &gamepads);
+        let fire_held = bindings.pressed(InputAction::Fire, &keyboard, &gamepads);
+        let fire_released = bindings.just_released(InputAction::Fire, &keyboard, &gamepads);
 
         state.fire_held = fire_held;
 
@@ -479,33 +736,59 @@ pub fn weapon_input_system(
             }
         }
 
+        // Moving while charging costs charge, depending on the rule
+        let tile = (player_pos.x, player_pos.y);
+        let moved = state.last_tile.is_some_and(|last| last != tile);
+        state.last_tile = Some(tile);
+        if moved && state.firing_state == WeaponFiringState::Charging {
+            state.lose_charge(*charge_rule);
+        }
+
+        // A press during cooldown is buffered and fires the moment it ends
+        if fire_pressed && !state.is_ready() {
+            state.buffer_press(time.elapsed_secs());
+        }
+        let buffered = state.is_ready() && state.take_buffered(time.elapsed_secs());
+
         // Handle fire button press - immediate shot for blaster
-        if fire_pressed && state.is_ready() {
+        if (fire_pressed || buffered) && state.is_ready() {
             // Fire normal shot immediately
-            spawn_projectile(&mut commands, player_pos, weapon, false, &projectiles);
-
-            // Start charging if weapon supports it
-            if weapon.stats.charge_time > 0.0 {
+            spawn_projectile(
+                &mut commands,
+                player_pos,
+                weapon,
+                false,
+                &projectiles,
+                &mut rng.0,
+            );
+            stats.record_shot();
+
+            // Start charging if weapon supports it (and the button is still held)
+            if weapon.stats.charge_time > 0.0 && fire_held {
                 state.start_charging(weapon.stats.charge_time);
             } else {
                 state.start_cooldown(weapon.stats.fire_cooldown);
             }
         }
 
-        // Handle fire button release - charged shot if ready
+        // Handle fire button release - charged shot if ready, otherwise cancel
         if fire_released && state.firing_state == WeaponFiringState::Charging {
             if state.charge_ready {
                 // Fire charged shot
-                spawn_projectile(&mut commands, player_pos, weapon, true, &projectiles);
+                spawn_projectile(
+                    &mut commands,
+                    player_pos,
+                    weapon,
+                    true,
+                    &projectiles,
+                    &mut rng.0,
+                );
+                stats.record_shot();
+                state.start_cooldown(weapon.stats.fire_cooldown);
+            } else {
+                let cooldown = cancel_cooldown(state.charge_progress(), weapon.stats.fire_cooldown);
+                state.start_cooldown(cooldown);
             }
-            // Start cooldown regardless
-            state.start_cooldown(weapon.stats.fire_cooldown);
-        }
-
-        // Handle holding without charging complete - cancel on release
-        if fire_released && state.firing_state == WeaponFiringState::Charging && !state.charge_ready
-        {
-            state.start_cooldown(weapon.stats.fire_cooldown * 0.5); // Shorter cooldown for cancelled charge
         }
     }
 }
@@ -517,6 +800,7 @@ fn spawn_projectile(
     weapon: &EquippedWeapon,
     is_charged: bool,
     projectiles: &ProjectileSprites,
+    rng: &mut impl Rng,
 ) {
     let stats = &weapon.stats;
 
@@ -528,7 +812,7 @@ fn spawn_projectile(
     };
 
     // Roll for crit
-    let crit_result = stats.critical.roll();
+    let crit_result = stats.critical.roll(rng);
     let crit_multiplier = stats.critical.get_multiplier(crit_result);
 
     // Spawn projectile entity with sprite animation
@@ -546,6 +830,21 @@ fn spawn_projectile(
         )
     };
 
+    // Charged shots fly bigger, faster and through every enemy in the row,
+    // so they never read as a chip shot
+    let (tint, draw_size, move_time) = if is_charged {
+        (
+            stats.charged_projectile_color,
+            BULLET_DRAW_SIZE * stats.charged_projectile_size / stats.projectile_size,
+            1.0 / stats.charged_projectile_speed,
+        )
+    } else {
+        (
+            stats.damage.damage_type.tint(),
+            BULLET_DRAW_SIZE,
+            BULLET_MOVE_TIMER,
+        )
+    };
     commands.spawn((
         Sprite {
             image: sprite_image,
@@ -553,7 +852,8 @@ fn spawn_projectile(
                 layout: sprite_layout,
                 index: 1, // Start at travel frame
             }),
-            custom_size: Some(BULLET_DRAW_SIZE),
+            color: tint,
+            custom_size: Some(draw_size),
             ..default()
         },
         Transform::default(),
@@ -575,10 +875,13 @@ fn spawn_projectile(
             crit_multiplier,
             falloff: stats.falloff,
             max_range: stats.range,
+            piercing: stats.piercing || is_charged,
+            hit_enemies: Vec::new(),
         },
         ProjectileAnimation::blaster(is_charged),
-        MoveTimer(Timer::from_seconds(BULLET_MOVE_TIMER, TimerMode::Repeating)),
+        MoveTimer(Timer::from_seconds(move_time, TimerMode::Repeating)),
         TargetsTiles::single(), // Highlight tile at bullet's position
+        ProjectileTrail::new(tint),
     ));
 
     // Muzzle flash
@@ -602,6 +905,45 @@ fn spawn_projectile(
     ));
 }
 
+/// Fill the charge bar above the player while charging; it flashes white once
+/// the charged shot is ready (steady white when flashing is reduced) and hides
+/// when the fire button is released
+pub fn update_charge_bar(
+    time: Res<Time>,
+    layout: Res<ArenaLayout>,
+    reduce_flashing: Res<ReduceFlashing>,
+    player_query: Query<&WeaponState, With<Player>>,
+    mut bar_query: Query<&mut Visibility, With<ChargeBar>>,
+    mut fill_query: Query<&mut Sprite, With<ChargeBarFill>>,
+) {
+    let Ok(state) = player_query.single() else {
+        return;
+    };
+    let fill = state.charge_bar_fill();
+
+    for mut visibility in &mut bar_query {
+        *visibility = if fill.is_some() {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+    }
+
+    let Some(fill) = fill else {
+        return;
+    };
+    let bar_size = layout.scale_vec2(CHARGE_BAR_SIZE);
+    for mut sprite in &mut fill_query {
+        sprite.custom_size = Some(Vec2::new(bar_size.x * fill, bar_size.y));
+        let flash_on = reduce_flashing.0 || (time.elapsed_secs() * 12.0).sin() > 0.0;
+        sprite.color = if state.charge_ready && flash_on {
+            Color::WHITE
+        } else {
+            COLOR_ACTION_CHARGE
+        };
+    }
+}
+
 /// Update weapon cooldowns
 pub fn weapon_cooldown_system(time: Res<Time>, mut query: Query<&mut WeaponState>) {
     for mut state in &mut query {
@@ -617,38 +959,77 @@ pub fn weapon_cooldown_system(time: Res<Time>, mut query: Query<&mut WeaponState
 /// Handle projectiles hitting enemies (with proper damage calculation)
 pub fn projectile_hit_system(
     mut commands: Commands,
-    projectile_query: Query<
+    mut projectile_query: Query<
         (
             Entity,
             &GridPosition,
-            &Projectile,
+            &mut Projectile,
             &crate::assets::ProjectileAnimation,
         ),
         (With<Bullet>, Without<EnemyBullet>, Without<ProjectileHit>),
     >,
-    mut enemy_query: Query<(Entity, &GridPosition, &mut Health, &Children), With<Enemy>>,
+    mut enemy_query: Query<
+        (
+            Entity,
+            &GridPosition,
+            &mut Health,
+            &Children,
+            Option<&EnemyTraitContainer>,
+            Option<&mut EnemyGuard>,
+            Option<&mut EnemyAttack>,
+        ),
+        (With<Enemy>, Without<Untargetable>),
+    >,
     mut text_query: Query<&mut Text2d, With<HealthText>>,
+    mut rumble: Rumble,
+    mut shake: ResMut<ScreenShake>,
 ) {
-    for (bullet_entity, bullet_pos, projectile, anim) in &projectile_query {
-        for (enemy_entity, enemy_pos, mut health, children) in &mut enemy_query {
+    for (bullet_entity, bullet_pos, mut projectile, anim) in &mut projectile_query {
+        for (enemy_entity, enemy_pos, mut health, children, traits, guard, attack) in
+            &mut enemy_query
+        {
             if bullet_pos == enemy_pos {
+                // Piercing shots damage each enemy only once as they pass through
+                if projectile.piercing {
+                    if projectile.hit_enemies.contains(&enemy_entity) {
+                        continue;
+                    }
+                    projectile.hit_enemies.push(enemy_entity);
+                }
+
                 // Calculate damage with falloff and crit
-                let final_damage = projectile.calculate_damage(bullet_pos.x);
+                let mut final_damage = projectile.calculate_damage(bullet_pos.x);
+
+                // Blaster shots never guard-break, so armor always applies
+                if let Some(traits) = traits {
+                    final_damage = traits.traits.reduce_damage(final_damage, false);
+                }
+                if let Some(mut guard) = guard {
+                    final_damage = guard.absorb(final_damage, false);
+                }
+                final_damage = counter_hit(
+                    &mut commands,
+                    enemy_entity,
+                    *enemy_pos,
+                    attack.map(Mut::into_inner),
+                    final_damage,
+                );
 
                 health.current -= final_damage;
+                rumble.enemy_hit(final_damage);
+                if anim.is_charged {
+                    shake.add_trauma(TRAUMA_CHARGED_HIT);
+                }
 
                 // Transition projectile to impact state instead of despawning immediately
                 // Preserve the is_charged flag from the original animation
-                commands.entity(bullet_entity).insert((
-                    crate::assets::ProjectileAnimation {
-                        frame_indices: [0, 1, 2, 3],
-                        state: crate::assets::ProjectileAnimationState::Impact,
-                        timer: Timer::from_seconds(0.1, TimerMode::Once), // Short duration for impact
-                        is_charged: anim.is_charged,
-                    },
-                    ProjectileHit, // Mark as hit so it will despawn after finish state
-                    ProjectileImmobile, // Stop moving during animation
-                ));
+                if !projectile.piercing {
+                    commands.entity(bullet_entity).insert((
+                        ProjectileAnimation::impact(anim.is_charged),
+                        ProjectileHit, // Mark as hit so it will despawn after finish state
+                        ProjectileImmobile, // Stop moving during animation
+                    ));
+                }
 
                 // Update HP text
                 for child in children.iter() {
@@ -658,15 +1039,460 @@ pub fn projectile_hit_system(
                 }
 
                 if health.current <= 0 {
-                    commands.entity(enemy_entity).despawn();
+                    kill_enemy(&mut commands, enemy_entity);
                 } else {
                     commands
                         .entity(enemy_entity)
                         .insert(FlashTimer(Timer::from_seconds(FLASH_TIME, TimerMode::Once)));
                 }
 
-                break; // Bullet hit one enemy, stop checking
+                if !projectile.piercing {
+                    break; // Bullet hit one enemy, stop checking
+                }
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::actions::{ActionId, PendingAction, execute_pending_actions, init_chip_resources};
+    use crate::resources::{ColorScheme, GridSize};
+    use crate::systems::combat::falloff_tint;
+    use crate::systems::rumble::init_hit_feedback;
+
+    /// World with the resources `weapon_input_system` reads and one player
+    fn weapon_world(weapons: PlayerWeapons) -> (World, Entity) {
+        let mut world = World::new();
+        world.init_resource::<Time>();
+        world.init_resource::<ButtonInput<KeyCode>>();
+        world.init_resource::<PlayerUpgrades>();
+        world.init_resource::<KeyBindings>();
+        world.init_resource::<ChargeMoveRule>();
+        world.insert_resource(GameRng::from_seed(1));
+        world.init_resource::<BattleStats>();
+        world.insert_resource(ProjectileSprites {
+            blaster_image: Handle::default(),
+            blaster_layout: Handle::default(),
+            blaster_charged_image: Handle::default(),
+            blaster_charged_layout: Handle::default(),
+        });
+        let equipped = EquippedWeapon::new(weapons.equipped);
+        let state = WeaponState::new(equipped.stats.fire_cooldown);
+        world.insert_resource(weapons);
+        let player = world
+            .spawn((Player, GridPosition { x: 1, y: 1 }, equipped, state))
+            .id();
+        (world, player)
+    }
+
+    /// Run one 1/60 s frame with `key` pressed this frame
+    fn press(world: &mut World, schedule: &mut Schedule, key: KeyCode) {
+        let mut keyboard = world.resource_mut::<ButtonInput<KeyCode>>();
+        keyboard.reset_all();
+        keyboard.press(key);
+        world
+            .resource_mut::<Time>()
+            .advance_by(Duration::from_secs_f32(1.0 / 60.0));
+        schedule.run(world);
+    }
+
+    /// Run one 1/60 s frame with nothing pressed
+    fn idle(world: &mut World, schedule: &mut Schedule) {
+        world.resource_mut::<ButtonInput<KeyCode>>().reset_all();
+        world
+            .resource_mut::<Time>()
+            .advance_by(Duration::from_secs_f32(1.0 / 60.0));
+        schedule.run(world);
+    }
+
+    fn equipped(world: &World, player: Entity) -> WeaponType {
+        world.get::<EquippedWeapon>(player).unwrap().weapon_type
+    }
+
+    #[test]
+    fn switching_cycles_equipped_weapon_and_stats() {
+        let (mut world, player) = weapon_world(PlayerWeapons {
+            unlocked: vec![WeaponType::Blaster, WeaponType::Cannon],
+            equipped: WeaponType::Blaster,
+        });
+        let mut schedule = Schedule::default();
+        schedule.add_systems(weapon_input_system);
+
+        press(&mut world, &mut schedule, KeyCode::Tab);
+        assert_eq!(equipped(&world, player), WeaponType::Cannon);
+        assert_eq!(
+            world.get::<EquippedWeapon>(player).unwrap().stats.name,
+            WeaponType::Cannon.stats().name
+        );
+        assert_eq!(
+            world.resource::<PlayerWeapons>().equipped,
+            WeaponType::Cannon
+        );
+
+        press(&mut world, &mut schedule, KeyCode::Tab);
+        assert_eq!(equipped(&world, player), WeaponType::Blaster);
+    }
+
+    #[test]
+    fn switching_skips_weapons_that_are_not_owned() {
+        let (mut world, player) = weapon_world(PlayerWeapons::default());
+        let mut schedule = Schedule::default();
+        schedule.add_systems(weapon_input_system);
+
+        press(&mut world, &mut schedule, KeyCode::Tab);
+        assert_eq!(equipped(&world, player), WeaponType::Blaster);
+        assert_eq!(
+            PlayerWeapons::default().next_after(WeaponType::Blaster),
+            WeaponType::Blaster
+        );
+    }
+
+    #[test]
+    fn battle_stats_count_chip_uses_and_shots_fired() {
+        let (mut world, player) = weapon_world(PlayerWeapons::default());
+        init_chip_resources(&mut world);
+        for _ in 0..2 {
+            world.spawn(PendingAction {
+                action_id: ActionId::Cannon,
+                source_entity: player,
+                source_position: (1, 1),
+                source_slot: Some(0),
+                charged: false,
+            });
+        }
+        let mut schedule = Schedule::default();
+        schedule.add_systems((execute_pending_actions, weapon_input_system));
+
+        press(&mut world, &mut schedule, KeyCode::Space);
+
+        let stats = world.resource::<BattleStats>();
+        assert_eq!(stats.chip_uses(ActionId::Cannon), 2);
+        assert_eq!(stats.chip_uses(ActionId::Sword), 0);
+        assert_eq!(stats.shots_fired, 1);
+    }
+
+    #[test]
+    fn fire_press_just_before_cooldown_end_fires_when_it_ends() {
+        let (mut world, player) = weapon_world(PlayerWeapons {
+            unlocked: vec![WeaponType::Cannon],
+            equipped: WeaponType::Cannon,
+        });
+        let mut schedule = Schedule::default();
+        schedule.add_systems(weapon_input_system);
+        let shots = |world: &World| world.resource::<BattleStats>().shots_fired;
+
+        press(&mut world, &mut schedule, KeyCode::Space);
+        assert_eq!(shots(&world), 1);
+        while world
+            .get::<WeaponState>(player)
+            .unwrap()
+            .cooldown_timer
+            .remaining_secs()
+            > 0.1
+        {
+            idle(&mut world, &mut schedule);
+        }
+
+        // Still cooling down: the press is buffered instead of dropped
+        press(&mut world, &mut schedule, KeyCode::Space);
+        assert_eq!(shots(&world), 1);
+
+        let mut frames = 0;
+        while shots(&world) == 1 && frames < 60 {
+            idle(&mut world, &mut schedule);
+            frames += 1;
+        }
+        assert_eq!(shots(&world), 2);
+        assert!(frames <= 7, "fired {frames} frames after the press");
+    }
+
+    fn spawn_enemy(world: &mut World, x: i32, y: i32) -> Entity {
+        world
+            .spawn((
+                Enemy,
+                GridPosition { x, y },
+                Health {
+                    current: 100,
+                    max: 100,
+                },
+            ))
+            .with_children(|parent| {
+                parent.spawn((HealthText, Text2d::new("100")));
+            })
+            .id()
+    }
+
+    fn spawn_shot(world: &mut World, x: i32, y: i32, piercing: bool) -> Entity {
+        world
+            .spawn((
+                Bullet,
+                GridPosition { x, y },
+                Projectile {
+                    piercing,
+                    ..Projectile::test_shot(10, 6)
+                },
+                ProjectileAnimation::blaster(false),
+            ))
+            .id()
+    }
+
+    fn hit_world() -> World {
+        let mut world = World::new();
+        init_hit_feedback(&mut world);
+        world
+    }
+
+    fn hp(world: &World, enemy: Entity) -> i32 {
+        world.get::<Health>(enemy).unwrap().current
+    }
+
+    #[test]
+    fn piercing_shot_damages_every_enemy_in_its_row_once() {
+        let mut world = hit_world();
+        let front = spawn_enemy(&mut world, 3, 1);
+        let back = spawn_enemy(&mut world, 4, 1);
+        let shot = spawn_shot(&mut world, 3, 1, true);
+        let mut schedule = Schedule::default();
+        schedule.add_systems(projectile_hit_system);
+
+        schedule.run(&mut world);
+        world.get_mut::<GridPosition>(shot).unwrap().x = 4;
+        schedule.run(&mut world);
+        schedule.run(&mut world);
+
+        assert_eq!(hp(&world, front), 90);
+        assert_eq!(hp(&world, back), 90);
+        assert!(world.get::<ProjectileHit>(shot).is_none());
+    }
+
+    #[test]
+    fn normal_shot_stops_at_the_first_enemy() {
+        let mut world = hit_world();
+        let front = spawn_enemy(&mut world, 3, 1);
+        let shot = spawn_shot(&mut world, 3, 1, false);
+        let mut schedule = Schedule::default();
+        schedule.add_systems(projectile_hit_system);
+
+        schedule.run(&mut world);
+
+        assert_eq!(hp(&world, front), 90);
+        assert!(world.get::<ProjectileHit>(shot).is_some());
+    }
+
+    #[test]
+    fn charged_blaster_shot_is_bigger_recolored_and_pierces() {
+        let (mut world, _) = weapon_world(PlayerWeapons::default());
+        let mut schedule = Schedule::default();
+        schedule.add_systems(weapon_input_system);
+
+        // Tap fires the normal shot; keep holding through the charge, then let go
+        press(&mut world, &mut schedule, KeyCode::Space);
+        for _ in 0..60 {
+            world.resource_mut::<ButtonInput<KeyCode>>().clear();
+            world
+                .resource_mut::<Time>()
+                .advance_by(Duration::from_secs_f32(1.0 / 60.0));
+            schedule.run(&mut world);
+        }
+        world
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .release(KeyCode::Space);
+        schedule.run(&mut world);
+
+        let stats = blaster::blaster_stats();
+        let mut shots: Vec<(bool, bool, Vec2, Color)> = world
+            .query::<(&Projectile, &Sprite)>()
+            .iter(&world)
+            .map(|(shot, sprite)| {
+                (
+                    shot.is_charged,
+                    shot.piercing,
+                    sprite.custom_size.unwrap(),
+                    sprite.color,
+                )
+            })
+            .collect();
+        shots.sort_by_key(|(charged, ..)| *charged);
+        assert_eq!(shots.len(), 2);
+
+        let (_, normal_pierces, normal_size, normal_color) = shots[0];
+        assert!(!normal_pierces);
+        assert_eq!(normal_size, BULLET_DRAW_SIZE);
+        assert_eq!(normal_color, DamageType::Electric.tint());
+
+        let (charged, charged_pierces, charged_size, charged_color) = shots[1];
+        assert!(charged);
+        assert!(charged_pierces);
+        assert!(charged_size.x > normal_size.x);
+        assert_eq!(charged_color, stats.charged_projectile_color);
+    }
+
+    #[test]
+    fn rebound_fire_key_replaces_space() {
+        let (mut world, _) = weapon_world(PlayerWeapons::default());
+        world
+            .resource_mut::<KeyBindings>()
+            .rebind_key(InputAction::Fire, KeyCode::KeyF);
+        let mut schedule = Schedule::default();
+        schedule.add_systems(weapon_input_system);
+        let shots = |world: &mut World| {
+            world
+                .query_filtered::<(), With<Bullet>>()
+                .iter(world)
+                .count()
+        };
+
+        press(&mut world, &mut schedule, KeyCode::Space);
+        assert_eq!(shots(&mut world), 0);
+
+        press(&mut world, &mut schedule, KeyCode::KeyF);
+        assert_eq!(shots(&mut world), 1);
+    }
+
+    #[test]
+    fn same_seed_rolls_the_same_crits() {
+        let config = CriticalConfig {
+            chance: 0.5,
+            ..default()
+        };
+        let rolls = |seed| {
+            let mut rng = GameRng::from_seed(seed);
+            (0..32).map(|_| config.roll(&mut rng.0)).collect::<Vec<_>>()
+        };
+
+        let first = rolls(42);
+        assert_eq!(first, rolls(42));
+        assert!(first.contains(&CritResult::Normal));
+        assert!(first.contains(&CritResult::Critical));
+    }
+
+    #[test]
+    fn charge_bar_fill_tracks_charge_progress() {
+        let mut state = WeaponState::new(0.5);
+        assert_eq!(state.charge_bar_fill(), None);
+
+        state.start_charging(2.0);
+        if let Some(timer) = state.charge_timer.as_mut() {
+            timer.tick(Duration::from_secs_f32(0.5));
+        }
+        assert_eq!(state.charge_progress(), 0.25);
+        assert_eq!(state.charge_bar_fill(), Some(state.charge_progress()));
+        assert!(!state.charge_ready);
+
+        state.start_cooldown(0.5);
+        assert_eq!(state.charge_bar_fill(), None);
+    }
+
+    /// Charge progress after holding fire for 0.4 s, stepping up a row on
+    /// the last frame if `step` is set
+    fn charge_after_step(rule: ChargeMoveRule, step: bool) -> f32 {
+        let (mut world, player) = weapon_world(PlayerWeapons::default());
+        world.insert_resource(rule);
+        let mut schedule = Schedule::default();
+        schedule.add_systems(weapon_input_system);
+
+        press(&mut world, &mut schedule, KeyCode::Space);
+        for frame in 0..24 {
+            if step && frame == 23 {
+                world.get_mut::<GridPosition>(player).unwrap().y -= 1;
+            }
+            // Keep fire held without a new press
+            world.resource_mut::<ButtonInput<KeyCode>>().clear();
+            world
+                .resource_mut::<Time>()
+                .advance_by(Duration::from_secs_f32(1.0 / 60.0));
+            schedule.run(&mut world);
+        }
+        let state = world.get::<WeaponState>(player).unwrap();
+        assert_eq!(state.firing_state, WeaponFiringState::Charging);
+        state.charge_progress()
+    }
+
+    #[test]
+    fn moving_while_charging_costs_charge_under_the_penalty_rules() {
+        let standing = charge_after_step(ChargeMoveRule::Slow, false);
+        assert!(standing > 0.6);
+
+        // Free movement keeps the classic behavior
+        assert_eq!(charge_after_step(ChargeMoveRule::Free, true), standing);
+
+        let slowed = charge_after_step(ChargeMoveRule::Slow, true);
+        assert!(slowed < standing);
+        assert!((standing - slowed - CHARGE_MOVE_PENALTY).abs() < 1e-4);
+
+        assert_eq!(charge_after_step(ChargeMoveRule::Reset, true), 0.0);
+    }
+
+    #[test]
+    fn cancelled_charge_cooldown_scales_with_charge_progress() {
+        // A quick tap refunds almost the whole cooldown...
+        assert!((cancel_cooldown(0.0, 1.0) - MIN_CANCEL_COOLDOWN).abs() < 1e-6);
+        // ...half a charge refunds half...
+        assert!((cancel_cooldown(0.5, 1.0) - 0.5).abs() < 1e-6);
+        // ...and a nearly finished charge refunds little
+        assert!((cancel_cooldown(0.95, 0.4) - 0.38).abs() < 1e-6);
+    }
+
+    #[test]
+    fn falloff_multipliers_follow_get_multiplier() {
+        let falloff = FalloffConfig::default();
+        let multipliers = falloff.multipliers(6);
+        assert_eq!(multipliers.len(), 6);
+        for (distance, multiplier) in (1..=6).zip(&multipliers) {
+            assert_eq!(
+                *multiplier,
+                falloff.get_multiplier(distance),
+                "{distance} tiles"
+            );
+        }
+        assert_eq!(multipliers[0], 1.0);
+        assert_eq!(multipliers[5], falloff.min_multiplier);
+    }
+
+    #[test]
+    fn overlay_tints_the_row_ahead_of_the_player() {
+        let (mut world, _) = weapon_world(PlayerWeapons {
+            unlocked: vec![WeaponType::Cannon],
+            equipped: WeaponType::Cannon,
+        });
+        world.init_resource::<FalloffOverlay>();
+        world.init_resource::<ArenaLayout>();
+        let mut schedule = Schedule::default();
+        schedule.add_systems(update_falloff_overlay);
+
+        press(&mut world, &mut schedule, KeyCode::F4);
+        let overlay = world.resource::<FalloffOverlay>();
+        assert!(overlay.enabled);
+        let falloff = WeaponType::Cannon.stats().falloff;
+        for x in 2..GRID_WIDTH {
+            assert_eq!(
+                overlay.multiplier_at(x, 1),
+                Some(falloff.get_multiplier(x - 1)),
+                "column {x}"
+            );
+        }
+        assert_eq!(overlay.multiplier_at(1, 1), None);
+        assert_eq!(overlay.multiplier_at(3, 0), None);
+
+        // Nearer tiles are tinted brighter than the weakened far ones
+        let brightness: Vec<f32> = (2..GRID_WIDTH)
+            .map(|x| {
+                let multiplier = overlay.multiplier_at(x, 1).unwrap();
+                falloff_tint(multiplier, ColorScheme::Default).luminance()
+            })
+            .collect();
+        assert!(brightness.windows(2).all(|pair| pair[0] >= pair[1]));
+        assert!(brightness[0] > brightness[brightness.len() - 1]);
+
+        // A narrower arena ends the tint at its own edge
+        world.insert_resource(ArenaLayout::for_grid(1280.0, 800.0, GridSize::new(4, 3, 2)));
+        idle(&mut world, &mut schedule);
+        let overlay = world.resource::<FalloffOverlay>();
+        assert!(overlay.multiplier_at(3, 1).is_some());
+        assert_eq!(overlay.multiplier_at(4, 1), None);
+    }
+}